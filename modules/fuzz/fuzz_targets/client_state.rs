@@ -0,0 +1,19 @@
+#![no_main]
+
+use ibc::core::ics02_client::client_state::{AnyClientState, TENDERMINT_CLIENT_STATE_TYPE_URL};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let any = Any {
+        type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(),
+        value: data.to_vec(),
+    };
+
+    // Whatever is accepted must survive a roundtrip through its encoding.
+    if let Ok(client_state) = AnyClientState::try_from(any) {
+        let decoded = AnyClientState::try_from(Any::from(client_state.clone()))
+            .expect("re-encoded client state must decode");
+        assert_eq!(decoded, client_state);
+    }
+});
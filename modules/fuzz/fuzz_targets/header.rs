@@ -0,0 +1,22 @@
+#![no_main]
+
+use ibc::core::ics02_client::header::{AnyHeader, Header, TENDERMINT_HEADER_TYPE_URL};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let any = Any {
+        type_url: TENDERMINT_HEADER_TYPE_URL.to_string(),
+        value: data.to_vec(),
+    };
+
+    if let Ok(header) = AnyHeader::try_from(any) {
+        let _ = header.height();
+        let _ = header.timestamp();
+    }
+
+    // Headers are also read out of the attributes of update client events.
+    if let Ok(s) = core::str::from_utf8(data) {
+        let _ = AnyHeader::decode_from_string(s);
+    }
+});
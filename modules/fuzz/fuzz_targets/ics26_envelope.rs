@@ -0,0 +1,12 @@
+#![no_main]
+
+use ibc::applications::transfer::msgs::transfer::MsgTransfer;
+use ibc::core::ics26_routing::msgs::Ics26Envelope;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(msg) = ibc_fuzz::any_msg(data) {
+        let _ = MsgTransfer::try_from(msg.clone());
+        let _ = Ics26Envelope::try_from(msg);
+    }
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+use core::str::FromStr;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::ics24_host::path::Path;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let s = match core::str::from_utf8(data) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    if let Ok(client_id) = ClientId::from_str(s) {
+        assert_eq!(client_id.as_str(), s);
+    }
+    if let Ok(connection_id) = ConnectionId::from_str(s) {
+        assert_eq!(connection_id.as_str(), s);
+    }
+    if let Ok(channel_id) = ChannelId::from_str(s) {
+        assert_eq!(channel_id.as_str(), s);
+    }
+    if let Ok(port_id) = PortId::from_str(s) {
+        assert_eq!(port_id.as_str(), s);
+    }
+
+    let _ = ChainId::from_string(s).version();
+    let _ = Path::from_str(s);
+});
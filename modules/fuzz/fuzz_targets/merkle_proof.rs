@@ -0,0 +1,26 @@
+#![no_main]
+
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::core::ics23_commitment::specs::ProofSpecs;
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof, MerkleRoot};
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let raw = match RawMerkleProof::decode(data) {
+        Ok(raw) => raw,
+        Err(_) => return,
+    };
+    let proof = MerkleProof::from(raw);
+
+    let specs = ProofSpecs::cosmos();
+    let root = MerkleRoot {
+        hash: b"root".to_vec(),
+    };
+    let keys = MerklePath {
+        key_path: vec!["ibc".to_string(), "key".to_string()],
+    };
+
+    let _ = proof.verify_membership(&specs, root.clone(), keys.clone(), b"value".to_vec(), 0);
+    let _ = proof.verify_non_membership(&specs, root, keys);
+});
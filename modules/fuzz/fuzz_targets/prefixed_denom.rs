@@ -0,0 +1,21 @@
+#![no_main]
+
+use core::str::FromStr;
+
+use ibc::applications::transfer::denom::PrefixedDenom;
+use ibc::applications::transfer::forward::Forward;
+use ibc::applications::transfer::packet::PacketData;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = core::str::from_utf8(data) {
+        if let Ok(denom) = PrefixedDenom::from_str(s) {
+            assert_eq!(denom.to_string(), s);
+        }
+    }
+
+    // Denominations mostly reach a host chain within ICS-20 packet data.
+    if let Ok(data) = serde_json::from_slice::<PacketData>(data) {
+        let _ = Forward::from_packet_data(&data);
+    }
+});
@@ -0,0 +1,21 @@
+//! Helpers shared by the fuzz targets of the `ibc` crate.
+
+use ibc::core::ics26_routing::msgs::MSG_TYPE_URLS;
+use ibc_proto::google::protobuf::Any;
+
+/// Builds a message out of fuzzer input, using its first byte to pick the type URL.
+///
+/// Letting the fuzzer discover the type URLs by itself would waste most of its time on
+/// messages which are rejected before their value is even looked at.
+pub fn any_msg(data: &[u8]) -> Option<Any> {
+    let (selector, value) = data.split_first()?;
+    let type_url = MSG_TYPE_URLS.get(*selector as usize).map_or_else(
+        || String::from_utf8_lossy(value).into_owned(),
+        |url| url.to_string(),
+    );
+
+    Some(Any {
+        type_url,
+        value: value.to_vec(),
+    })
+}
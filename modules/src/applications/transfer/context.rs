@@ -4,6 +4,7 @@ use subtle_encoding::hex;
 use super::error::Error as Ics20Error;
 use crate::applications::transfer::acknowledgement::Acknowledgement;
 use crate::applications::transfer::events::{AckEvent, AckStatusEvent, RecvEvent, TimeoutEvent};
+use crate::applications::transfer::forward::{ForwardConfig, InFlightForward};
 use crate::applications::transfer::packet::PacketData;
 use crate::applications::transfer::relay::on_ack_packet::process_ack_packet;
 use crate::applications::transfer::relay::on_recv_packet::process_recv_packet;
@@ -12,7 +13,7 @@ use crate::applications::transfer::{PrefixedCoin, PrefixedDenom, VERSION};
 use crate::core::ics04_channel::channel::{Counterparty, Order};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
-use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
 use crate::core::ics04_channel::Version;
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::core::ics26_routing::context::{ModuleOutputBuilder, OnRecvPacketAck};
@@ -23,6 +24,25 @@ pub trait Ics20Keeper:
     ChannelKeeper + BankKeeper<AccountId = <Self as Ics20Keeper>::AccountId>
 {
     type AccountId;
+
+    /// Stores a forward sent in the packet with the given port, channel and sequence.
+    /// Implement only if the host chain forwards packets (see [`super::forward`]).
+    fn store_in_flight_forward(
+        &mut self,
+        _key: (PortId, ChannelId, Sequence),
+        _forward: InFlightForward,
+    ) -> Result<(), Ics20Error> {
+        Err(Ics20Error::forwarding_not_supported())
+    }
+
+    /// Deletes the forward sent in the packet with the given port, channel and sequence.
+    /// Implement only if the host chain forwards packets (see [`super::forward`]).
+    fn delete_in_flight_forward(
+        &mut self,
+        _key: &(PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics20Error> {
+        Ok(())
+    }
 }
 
 pub trait Ics20Reader: ChannelReader {
@@ -57,6 +77,22 @@ pub trait Ics20Reader: ChannelReader {
     fn denom_hash_string(&self, _denom: &PrefixedDenom) -> Option<String> {
         None
     }
+
+    /// Returns the configuration of packet forwarding, or `None` if the host chain does not
+    /// forward packets, in which case forwarding instructions are ignored.
+    /// Implement only if the host chain forwards packets (see [`super::forward`]).
+    fn forward_config(&self) -> Option<ForwardConfig> {
+        None
+    }
+
+    /// Returns the forward sent in the packet with the given port, channel and sequence, if any.
+    /// Implement only if the host chain forwards packets (see [`super::forward`]).
+    fn get_in_flight_forward(
+        &self,
+        _key: &(PortId, ChannelId, Sequence),
+    ) -> Option<InFlightForward> {
+        None
+    }
 }
 
 // https://github.com/cosmos/cosmos-sdk/blob/master/docs/architecture/adr-028-public-key-addresses.md
@@ -210,6 +246,38 @@ pub fn on_chan_close_confirm(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_upgrade_init(
+    ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    _counterparty: &Counterparty,
+    version: &Version,
+) -> Result<Version, Ics20Error> {
+    validate_transfer_channel_params(ctx, order, port_id, channel_id, version)?;
+    Ok(Version::ics20())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_upgrade_try(
+    ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    _counterparty: &Counterparty,
+    version: &Version,
+    counterparty_version: &Version,
+) -> Result<Version, Ics20Error> {
+    validate_transfer_channel_params(ctx, order, port_id, channel_id, version)?;
+    validate_counterparty_version(counterparty_version)?;
+    Ok(Version::ics20())
+}
+
 pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
     ctx: &Ctx,
     output: &mut ModuleOutputBuilder,
@@ -254,7 +322,7 @@ pub fn on_acknowledgement_packet(
     let acknowledgement = serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
         .map_err(|_| Ics20Error::ack_deserialization())?;
 
-    process_ack_packet(ctx, packet, &data, &acknowledgement)?;
+    process_ack_packet(ctx, output, packet, &data, &acknowledgement)?;
 
     let ack_event = AckEvent {
         receiver: data.receiver,
@@ -277,7 +345,7 @@ pub fn on_timeout_packet(
     let data = serde_json::from_slice::<PacketData>(&packet.data)
         .map_err(|_| Ics20Error::packet_data_deserialization())?;
 
-    process_timeout_packet(ctx, packet, &data)?;
+    process_timeout_packet(ctx, output, packet, &data)?;
 
     let timeout_event = TimeoutEvent {
         refund_receiver: data.sender,
@@ -12,6 +12,7 @@ use crate::core::ics24_host::error::ValidationError;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::prelude::*;
 use crate::signer::SignerError;
+use crate::timestamp::TimestampOverflowError;
 
 define_error! {
     #[derive(Debug, PartialEq, Eq)]
@@ -132,5 +133,16 @@ define_error! {
         UnknownMsgType
             { msg_type: String }
             | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+
+        InvalidForwardMetadata
+            { reason: String }
+            | e | { format_args!("invalid packet forward metadata: {0}", e.reason) },
+
+        ForwardTimeoutOverflow
+            [ TimestampOverflowError ]
+            | _ | { "timeout of the forwarded packet overflows" },
+
+        ForwardingNotSupported
+            | _ | { "packet forwarding is not supported by the host chain" },
     }
 }
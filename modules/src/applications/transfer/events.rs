@@ -1,5 +1,7 @@
 use crate::applications::transfer::acknowledgement::Acknowledgement;
 use crate::applications::transfer::{Amount, PrefixedDenom, MODULE_ID_STR};
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::ModuleEvent;
 use crate::prelude::*;
 use crate::signer::Signer;
@@ -8,6 +10,7 @@ const EVENT_TYPE_PACKET: &str = "fungible_token_packet";
 const EVENT_TYPE_TIMEOUT: &str = "timeout";
 const EVENT_TYPE_DENOM_TRACE: &str = "denomination_trace";
 const EVENT_TYPE_TRANSFER: &str = "ibc_transfer";
+const EVENT_TYPE_FORWARD: &str = "fungible_token_forward";
 
 pub enum Event {
     Recv(RecvEvent),
@@ -16,6 +19,7 @@ pub enum Event {
     Timeout(TimeoutEvent),
     DenomTrace(DenomTraceEvent),
     Transfer(TransferEvent),
+    Forward(ForwardEvent),
 }
 
 pub struct RecvEvent {
@@ -158,6 +162,42 @@ impl From<TransferEvent> for ModuleEvent {
     }
 }
 
+/// Emitted when received tokens are forwarded to the next chain, along with the identifiers
+/// of the packet in which they are sent.
+pub struct ForwardEvent {
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+    pub retries: u8,
+}
+
+impl From<ForwardEvent> for ModuleEvent {
+    fn from(ev: ForwardEvent) -> Self {
+        let ForwardEvent {
+            sender,
+            receiver,
+            port_id,
+            channel_id,
+            sequence,
+            retries,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_FORWARD.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("sender", sender).into(),
+                ("receiver", receiver).into(),
+                ("packet_src_port", port_id).into(),
+                ("packet_src_channel", channel_id).into(),
+                ("packet_sequence", sequence).into(),
+                ("retries", retries).into(),
+            ],
+        }
+    }
+}
+
 impl From<Event> for ModuleEvent {
     fn from(ev: Event) -> Self {
         match ev {
@@ -167,6 +207,7 @@ impl From<Event> for ModuleEvent {
             Event::Timeout(ev) => ev.into(),
             Event::DenomTrace(ev) => ev.into(),
             Event::Transfer(ev) => ev.into(),
+            Event::Forward(ev) => ev.into(),
         }
     }
 }
@@ -0,0 +1,655 @@
+//! Packet forwarding for ICS20, which lets a chain route the tokens it receives on to another
+//! chain, e.g. for a hub relaying user transfers between the chains connected to it.
+//!
+//! A chain which forwards packets (see [`Ics20Reader::forward_config`]) does not simply credit
+//! the tokens of a received packet carrying forwarding instructions, but sends them on through
+//! another channel right away. The instructions are either given in the memo of the packet, as
+//! a JSON object under the `forward` key:
+//!
+//! ```json
+//! {"forward": {"receiver": "cosmos1...", "port": "transfer", "channel": "channel-1"}}
+//! ```
+//!
+//! or in its receiver, as `{local receiver}|{port}/{channel}:{receiver}`.
+//!
+//! The tokens are first credited to the local receiver, i.e. the receiver of the packet in the
+//! first case, which then sends them to the receiver on the next chain. If the forward cannot be
+//! sent, the packet is acknowledged with an error, so that the tokens are refunded on the chain
+//! they came from. If the forwarded packet times out or is acknowledged with an error, the
+//! forward is retried, until it runs out of retries, after which the tokens are sent back to the
+//! sender of the received packet.
+//!
+//! [`Ics20Reader::forward_config`]: crate::applications::transfer::context::Ics20Reader::forward_config
+
+use core::str::FromStr;
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::applications::transfer::context::Ics20Context;
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::events::ForwardEvent;
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::relay::send_transfer::{
+    commit_transfer, prepare_transfer_with_memo, send_transfer, PreparedTransfer,
+};
+use crate::applications::transfer::PrefixedCoin;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics26_routing::context::ModuleOutputBuilder;
+use crate::events::ModuleEvent;
+use crate::handler::HandlerOutputBuilder;
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+
+/// The key of the forwarding instructions in the memo of a packet.
+pub const FORWARD_MEMO_KEY: &str = "forward";
+
+/// Configuration of packet forwarding on a chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForwardConfig {
+    /// Timeout of the forwarded packets, from the time at which they are sent.
+    pub timeout: Duration,
+    /// Number of times a forward is retried after its packet timed out or failed.
+    pub retries: u8,
+}
+
+impl Default for ForwardConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10 * 60),
+            retries: 1,
+        }
+    }
+}
+
+/// Forwarding instructions carried by the memo of a packet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForwardMetadata {
+    /// The receiver of the tokens on the next chain.
+    pub receiver: Signer,
+    /// The port through which the tokens are forwarded.
+    pub port: PortId,
+    /// The channel through which the tokens are forwarded.
+    pub channel: ChannelId,
+    /// Timeout of the forwarded packet in nanoseconds, instead of the one of the chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Number of retries of the forward, instead of the one of the chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u8>,
+    /// Memo of the forwarded packet, e.g. to forward it again from the next chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<serde_json::Value>,
+}
+
+/// A forward of the tokens of a received packet to the next chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Forward {
+    /// The account on this chain which receives the tokens and sends them to the next chain.
+    pub local_receiver: Signer,
+    pub metadata: ForwardMetadata,
+}
+
+impl Forward {
+    /// Returns the forward requested by the given packet data, if any.
+    pub fn from_packet_data(data: &PacketData) -> Result<Option<Self>, Ics20Error> {
+        if let Some(metadata) = data.memo.as_deref().map(parse_memo).transpose()? {
+            return Ok(metadata.map(|metadata| Forward {
+                local_receiver: data.receiver.clone(),
+                metadata,
+            }));
+        }
+
+        parse_receiver(data.receiver.as_ref())
+    }
+
+    /// Builds the transfer of the given tokens, as received by the local receiver, to the next
+    /// chain.
+    pub fn into_in_flight(
+        self,
+        config: &ForwardConfig,
+        token: PrefixedCoin,
+        refund: ForwardRefund,
+    ) -> InFlightForward {
+        let ForwardMetadata {
+            receiver,
+            port,
+            channel,
+            timeout,
+            retries,
+            next,
+        } = self.metadata;
+
+        InFlightForward {
+            msg: MsgTransfer {
+                source_port: port,
+                source_channel: channel,
+                token,
+                sender: self.local_receiver,
+                receiver,
+                timeout_height: TimeoutHeight::no_timeout(),
+                timeout_timestamp: Timestamp::none(),
+            },
+            memo: next.map(|next| next.to_string()),
+            timeout: timeout.map_or(config.timeout, Duration::from_nanos),
+            retries: retries.unwrap_or(config.retries),
+            refund,
+        }
+    }
+}
+
+/// Where the tokens of a forward go back to once it has run out of retries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForwardRefund {
+    /// The port on which the forwarded tokens were received.
+    pub port_id: PortId,
+    /// The channel on which the forwarded tokens were received.
+    pub channel_id: ChannelId,
+    /// The sender of the received packet.
+    pub receiver: Signer,
+}
+
+impl ForwardRefund {
+    /// The refund of a forward of the tokens received in the given packet.
+    pub fn for_packet(packet: &Packet, data: &PacketData) -> Self {
+        Self {
+            port_id: packet.destination_port.clone(),
+            channel_id: packet.destination_channel.clone(),
+            receiver: data.sender.clone(),
+        }
+    }
+}
+
+/// A forward whose packet has been sent to the next chain but has not been acknowledged yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InFlightForward {
+    /// The transfer to the next chain. Its timeout timestamp is set when it is sent.
+    pub msg: MsgTransfer<PrefixedCoin>,
+    /// The memo of the forwarded packet.
+    pub memo: Option<String>,
+    /// Timeout of the forwarded packet, from the time at which it is sent.
+    pub timeout: Duration,
+    /// Number of times the forward can still be retried.
+    pub retries: u8,
+    /// Where the tokens go back to once the forward has run out of retries.
+    pub refund: ForwardRefund,
+}
+
+impl InFlightForward {
+    /// The event announcing that this forward is sent in a packet with the given sequence.
+    pub fn event(&self, sequence: Sequence) -> ForwardEvent {
+        ForwardEvent {
+            sender: self.msg.sender.clone(),
+            receiver: self.msg.receiver.clone(),
+            port_id: self.msg.source_port.clone(),
+            channel_id: self.msg.source_channel.clone(),
+            sequence,
+            retries: self.retries,
+        }
+    }
+}
+
+fn parse_memo(memo: &str) -> Result<Option<ForwardMetadata>, Ics20Error> {
+    // Memos which are not JSON objects are not meant for this module.
+    let forward = match serde_json::from_str::<serde_json::Value>(memo) {
+        Ok(serde_json::Value::Object(mut memo)) => memo.remove(FORWARD_MEMO_KEY),
+        _ => None,
+    };
+
+    forward
+        .map(|forward| {
+            serde_json::from_value(forward)
+                .map_err(|e| Ics20Error::invalid_forward_metadata(e.to_string()))
+        })
+        .transpose()
+}
+
+fn parse_receiver(receiver: &str) -> Result<Option<Forward>, Ics20Error> {
+    let (local_receiver, next_hop) = match receiver.split_once('|') {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+
+    let invalid = || {
+        Ics20Error::invalid_forward_metadata(format!(
+            "expected a receiver of the form '{{receiver}}|{{port}}/{{channel}}:{{receiver}}', got '{}'",
+            receiver
+        ))
+    };
+
+    let (path, next_receiver) = next_hop.split_once(':').ok_or_else(invalid)?;
+    let (port, channel) = path.split_once('/').ok_or_else(invalid)?;
+
+    Ok(Some(Forward {
+        local_receiver: local_receiver.parse().map_err(Ics20Error::signer)?,
+        metadata: ForwardMetadata {
+            receiver: next_receiver.parse().map_err(Ics20Error::signer)?,
+            port: PortId::from_str(port)
+                .map_err(|e| Ics20Error::invalid_port_id(port.to_string(), e))?,
+            channel: ChannelId::from_str(channel)
+                .map_err(|e| Ics20Error::invalid_channel_id(channel.to_string(), e))?,
+            timeout: None,
+            retries: None,
+            next: None,
+        },
+    }))
+}
+
+/// A forward whose packet has been validated, but not sent yet.
+#[derive(Clone, Debug)]
+pub struct PreparedForward {
+    forward: InFlightForward,
+    transfer: PreparedTransfer,
+}
+
+impl PreparedForward {
+    /// The sequence of the packet in which the forward is sent.
+    pub fn sequence(&self) -> Sequence {
+        self.transfer.sequence()
+    }
+
+    /// The output of sending the forward: the events of its packet and transfer, and the
+    /// `ForwardEvent` announcing it.
+    pub fn output(&self) -> HandlerOutputBuilder<()> {
+        let mut output = self.transfer.output().clone();
+        output.emit(ModuleEvent::from(self.forward.event(self.sequence())).into());
+        output
+    }
+}
+
+/// Validates the packet of the given forward to the next chain, without modifying the state of
+/// the chain. The forward is sent once it is passed to [`commit_forward`].
+pub fn prepare_forward<Ctx: Ics20Context>(
+    ctx: &Ctx,
+    mut forward: InFlightForward,
+) -> Result<PreparedForward, Ics20Error> {
+    forward.msg.timeout_timestamp =
+        (ctx.host_timestamp() + forward.timeout).map_err(Ics20Error::forward_timeout_overflow)?;
+
+    let transfer = prepare_transfer_with_memo(ctx, forward.msg.clone(), forward.memo.clone())?;
+
+    Ok(PreparedForward { forward, transfer })
+}
+
+/// Sends the packet of a prepared forward and keeps track of the forward until the packet is
+/// acknowledged or times out. Returns the output of sending the forward.
+pub fn commit_forward<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    prepared: PreparedForward,
+) -> Result<HandlerOutputBuilder<()>, Ics20Error> {
+    let output = prepared.output();
+    let PreparedForward { forward, transfer } = prepared;
+
+    let key = (
+        forward.msg.source_port.clone(),
+        forward.msg.source_channel.clone(),
+        transfer.sequence(),
+    );
+
+    ctx.store_in_flight_forward(key.clone(), forward)?;
+
+    if let Err(e) = commit_transfer(ctx, transfer) {
+        ctx.delete_in_flight_forward(&key)?;
+        return Err(e);
+    }
+
+    Ok(output)
+}
+
+/// Sends the packet of the given forward to the next chain and keeps track of the forward until
+/// the packet is acknowledged or times out. Returns the output of sending the forward.
+pub fn send_forward<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    forward: InFlightForward,
+) -> Result<HandlerOutputBuilder<()>, Ics20Error> {
+    let prepared = prepare_forward(ctx, forward)?;
+    commit_forward(ctx, prepared)
+}
+
+/// Settles the forward sent in the given packet, if any, once the packet has been acknowledged
+/// or has timed out. The tokens of a failed forward have been refunded to the local receiver by
+/// then. The forward is retried if it has retries left, otherwise the tokens are sent back to
+/// the sender of the packet which was forwarded.
+pub fn settle_forward<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    failed: bool,
+) -> Result<(), Ics20Error> {
+    let key = (
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    );
+
+    let forward = match ctx.get_in_flight_forward(&key) {
+        Some(forward) => forward,
+        None => return Ok(()),
+    };
+
+    ctx.delete_in_flight_forward(&key)?;
+
+    if !failed {
+        return Ok(());
+    }
+
+    if forward.retries > 0 {
+        let retry = InFlightForward {
+            retries: forward.retries - 1,
+            ..forward.clone()
+        };
+
+        match send_forward(ctx, retry) {
+            Ok(forward_output) => {
+                output.merge_ibc_output(forward_output);
+                return Ok(());
+            }
+            Err(e) => output.log(format!(
+                "failed to retry the forward of packet {} on {}/{}: {}",
+                packet.sequence, packet.source_port, packet.source_channel, e
+            )),
+        }
+    }
+
+    // A forward which can neither be retried nor refunded leaves the tokens with the local
+    // receiver, rather than failing the acknowledgement or timeout of the packet.
+    let mut refund_output = HandlerOutputBuilder::new();
+    match refund_forward(ctx, &mut refund_output, forward) {
+        Ok(()) => output.merge_ibc_output(refund_output),
+        Err(e) => output.log(format!(
+            "failed to refund the forward of packet {} on {}/{}: {}",
+            packet.sequence, packet.source_port, packet.source_channel, e
+        )),
+    }
+
+    Ok(())
+}
+
+/// Sends the tokens of a forward which ran out of retries back to the sender of the packet
+/// which was forwarded, through the channel on which that packet was received.
+fn refund_forward<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    forward: InFlightForward,
+) -> Result<(), Ics20Error> {
+    let InFlightForward {
+        msg,
+        timeout,
+        refund,
+        ..
+    } = forward;
+
+    let timeout_timestamp =
+        (ctx.host_timestamp() + timeout).map_err(Ics20Error::forward_timeout_overflow)?;
+
+    let msg = MsgTransfer {
+        source_port: refund.port_id,
+        source_channel: refund.channel_id,
+        token: msg.token,
+        sender: msg.sender,
+        receiver: refund.receiver,
+        timeout_height: TimeoutHeight::no_timeout(),
+        timeout_timestamp,
+    };
+
+    send_transfer(ctx, output, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::{Forward, ForwardConfig, ForwardRefund};
+    use crate::applications::transfer::context::{on_recv_packet, on_timeout_packet, Ics20Reader};
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::{BaseCoin, PrefixedCoin};
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::packet::{Packet, Sequence};
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{ModuleOutputBuilder, OnRecvPacketAck};
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    fn packet_data(receiver: &str, memo: Option<&str>) -> PacketData {
+        PacketData {
+            token: BaseCoin {
+                denom: "uatom".parse().unwrap(),
+                amount: 10u64.into(),
+            }
+            .into(),
+            sender: get_dummy_account_id(),
+            receiver: receiver.parse().unwrap(),
+            memo: memo.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn forward_from_memo() {
+        let data = packet_data(
+            "hub1local",
+            Some(
+                r#"{"forward":{"receiver":"osmo1final","port":"transfer","channel":"channel-7","retries":3,"next":{"forward":{"receiver":"juno1final","port":"transfer","channel":"channel-2"}}}}"#,
+            ),
+        );
+
+        let forward = Forward::from_packet_data(&data).unwrap().unwrap();
+        assert_eq!(forward.local_receiver.as_ref(), "hub1local");
+        assert_eq!(forward.metadata.receiver.as_ref(), "osmo1final");
+        assert_eq!(forward.metadata.channel.as_str(), "channel-7");
+
+        let token: PrefixedCoin = data.token.clone();
+        let refund = ForwardRefund {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            receiver: data.sender.clone(),
+        };
+        let in_flight = forward.into_in_flight(&ForwardConfig::default(), token, refund);
+        assert_eq!(in_flight.retries, 3);
+        assert_eq!(in_flight.timeout, ForwardConfig::default().timeout);
+
+        // The instructions for the next hop are passed on in the memo of the forwarded packet.
+        let next = packet_data("juno1local", in_flight.memo.as_deref());
+        assert!(Forward::from_packet_data(&next).unwrap().is_some());
+    }
+
+    #[test]
+    fn forward_from_receiver() {
+        let data = packet_data("hub1local|transfer/channel-7:osmo1final", None);
+
+        let forward = Forward::from_packet_data(&data).unwrap().unwrap();
+        assert_eq!(forward.local_receiver.as_ref(), "hub1local");
+        assert_eq!(forward.metadata.port.as_str(), "transfer");
+        assert_eq!(forward.metadata.receiver.as_ref(), "osmo1final");
+
+        let malformed = packet_data("hub1local|transfer:osmo1final", None);
+        assert!(Forward::from_packet_data(&malformed).is_err());
+    }
+
+    #[test]
+    fn no_forward() {
+        assert!(Forward::from_packet_data(&packet_data("osmo1final", None))
+            .unwrap()
+            .is_none());
+
+        assert!(
+            Forward::from_packet_data(&packet_data("osmo1final", Some("thanks for the fish")))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    /// A hub whose `channel-0` leads to the chain the tokens come from, and whose `channel-1`
+    /// leads to the chain they are forwarded to.
+    fn hub(config: ForwardConfig) -> DummyTransferModule {
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let channel_end = |counterparty_channel| {
+            ChannelEnd::new(
+                State::Open,
+                Order::Unordered,
+                Counterparty::new(
+                    PortId::transfer(),
+                    Some(ChannelId::new(counterparty_channel)),
+                ),
+                vec![ConnectionId::default()],
+                Version::ics20(),
+            )
+        };
+
+        let ctx = MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 10).unwrap())
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_channel(PortId::transfer(), ChannelId::new(0), channel_end(5))
+            .with_channel(PortId::transfer(), ChannelId::new(1), channel_end(7))
+            .with_send_sequence(PortId::transfer(), ChannelId::new(0), 1.into())
+            .with_send_sequence(PortId::transfer(), ChannelId::new(1), 1.into());
+
+        DummyTransferModule::new(ctx.ibc_store_share()).with_forward_config(config)
+    }
+
+    /// A packet received by the hub on `channel-0`, with the given receiver.
+    fn received_packet(receiver: &str) -> Packet {
+        let data = PacketData {
+            sender: "cosmos1sender".parse().unwrap(),
+            ..packet_data(receiver, None)
+        };
+
+        Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(5),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: serde_json::to_vec(&data).unwrap(),
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    /// The packets sent by the hub, and the kinds of the module events, in the given output.
+    fn sent_packets(output: ModuleOutputBuilder) -> (Vec<Packet>, Vec<String>) {
+        let mut handler_output = HandlerOutputBuilder::<()>::new();
+        handler_output.merge_module_output(output);
+
+        let mut packets = Vec::new();
+        let mut kinds = Vec::new();
+        for event in handler_output.with_result(()).events {
+            match event {
+                IbcEvent::SendPacket(e) => packets.push(e.packet),
+                IbcEvent::AppModule(e) => kinds.push(e.kind),
+                _ => {}
+            }
+        }
+        (packets, kinds)
+    }
+
+    fn data_of(packet: &Packet) -> PacketData {
+        serde_json::from_slice(&packet.data).unwrap()
+    }
+
+    #[test]
+    fn forwarded_packet_is_retried_then_refunded() {
+        let mut module = hub(ForwardConfig {
+            retries: 1,
+            ..Default::default()
+        });
+        let relayer = get_dummy_account_id();
+
+        // The received tokens are forwarded on `channel-1` along with the packet.
+        let received = received_packet("hub1local|transfer/channel-1:osmo1final");
+        let mut output = ModuleOutputBuilder::new();
+        let write_fn = match on_recv_packet(&module, &mut output, &received, &relayer) {
+            OnRecvPacketAck::Successful(_, write_fn) => write_fn,
+            _ => panic!("the packet to forward should be received"),
+        };
+        write_fn(&mut module, &mut output).unwrap();
+
+        let (packets, kinds) = sent_packets(output);
+        assert_eq!(packets.len(), 1);
+        assert!(kinds.iter().any(|kind| kind == "fungible_token_forward"));
+
+        let forwarded = packets[0].clone();
+        assert_eq!(forwarded.source_channel, ChannelId::new(1));
+        assert_eq!(data_of(&forwarded).sender.as_ref(), "hub1local");
+        assert_eq!(data_of(&forwarded).receiver.as_ref(), "osmo1final");
+        assert_eq!(
+            data_of(&forwarded).token.denom.to_string(),
+            "transfer/channel-0/uatom"
+        );
+
+        let key = |sequence: u64| {
+            (
+                PortId::transfer(),
+                ChannelId::new(1),
+                Sequence::from(sequence),
+            )
+        };
+        assert!(module.get_in_flight_forward(&key(1)).is_some());
+
+        // The forwarded packet times out, so the forward is retried in the next packet.
+        let mut output = ModuleOutputBuilder::new();
+        on_timeout_packet(&mut module, &mut output, &forwarded, &relayer).unwrap();
+
+        let (packets, _) = sent_packets(output);
+        assert_eq!(packets.len(), 1);
+        let retried = packets[0].clone();
+        assert_eq!(retried.source_channel, ChannelId::new(1));
+        assert_eq!(retried.sequence, 2.into());
+        assert!(module.get_in_flight_forward(&key(1)).is_none());
+        assert_eq!(module.get_in_flight_forward(&key(2)).unwrap().retries, 0);
+
+        // The retry times out as well, so the tokens go back to the sender of the received
+        // packet, through the channel they came from.
+        let mut output = ModuleOutputBuilder::new();
+        on_timeout_packet(&mut module, &mut output, &retried, &relayer).unwrap();
+
+        let (packets, _) = sent_packets(output);
+        assert_eq!(packets.len(), 1);
+        let refund = packets[0].clone();
+        assert_eq!(refund.source_channel, ChannelId::new(0));
+        assert_eq!(data_of(&refund).sender.as_ref(), "hub1local");
+        assert_eq!(data_of(&refund).receiver.as_ref(), "cosmos1sender");
+        assert!(module.get_in_flight_forward(&key(2)).is_none());
+    }
+
+    #[test]
+    fn unsendable_forward_is_not_received() {
+        let module = hub(ForwardConfig::default());
+
+        // There is no `channel-9` to forward the tokens on, so the packet is acknowledged with
+        // an error before any tokens are credited.
+        let received = received_packet("hub1local|transfer/channel-9:osmo1final");
+        let mut output = ModuleOutputBuilder::new();
+        let ack = on_recv_packet(&module, &mut output, &received, &get_dummy_account_id());
+        assert!(matches!(ack, OnRecvPacketAck::Failed(_)));
+
+        let (packets, _) = sent_packets(output);
+        assert!(packets.is_empty());
+    }
+}
@@ -6,6 +6,7 @@ pub mod context;
 pub mod denom;
 pub mod error;
 pub mod events;
+pub mod forward;
 pub mod msgs;
 pub mod packet;
 pub mod relay;
@@ -1,4 +1,5 @@
-use alloc::string::ToString;
+use crate::prelude::*;
+
 use core::convert::TryFrom;
 use core::str::FromStr;
 
@@ -14,6 +15,10 @@ pub struct PacketData {
     pub token: PrefixedCoin,
     pub sender: Signer,
     pub receiver: Signer,
+    /// Optional memo, which may carry instructions to forward the tokens to another chain
+    /// (see [`super::forward`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 impl TryFrom<RawPacketData> for PacketData {
@@ -27,6 +32,7 @@ impl TryFrom<RawPacketData> for PacketData {
             token: PrefixedCoin { denom, amount },
             sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
             receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo: None,
         })
     }
 }
@@ -1,19 +1,24 @@
 use crate::applications::transfer::acknowledgement::Acknowledgement;
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::forward::settle_forward;
 use crate::applications::transfer::packet::PacketData;
 use crate::applications::transfer::relay::refund_packet_token;
 use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics26_routing::context::ModuleOutputBuilder;
 
 pub fn process_ack_packet(
     ctx: &mut impl Ics20Context,
+    output: &mut ModuleOutputBuilder,
     packet: &Packet,
     data: &PacketData,
     ack: &Acknowledgement,
 ) -> Result<(), Ics20Error> {
-    if matches!(ack, Acknowledgement::Error(_)) {
+    let failed = matches!(ack, Acknowledgement::Error(_));
+
+    if failed {
         refund_packet_token(ctx, packet, data)?;
     }
 
-    Ok(())
+    settle_forward(ctx, output, packet, failed)
 }
@@ -1,6 +1,9 @@
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
 use crate::applications::transfer::events::DenomTraceEvent;
+use crate::applications::transfer::forward::{
+    commit_forward, prepare_forward, Forward, ForwardRefund, PreparedForward,
+};
 use crate::applications::transfer::packet::PacketData;
 use crate::applications::transfer::{is_receiver_chain_source, TracePrefix};
 use crate::core::ics04_channel::packet::Packet;
@@ -17,8 +20,18 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         return Err(Ics20Error::receive_disabled());
     }
 
-    let receiver_account = data
-        .receiver
+    // Tokens to be forwarded are received by the local receiver of the forward. The forward is
+    // validated before the packet is received, so that the packet is acknowledged with an
+    // error, and its tokens refunded, if it cannot be forwarded.
+    let forward = match ctx.forward_config() {
+        Some(config) => Forward::from_packet_data(&data)?.map(|forward| (config, forward)),
+        None => None,
+    };
+    let refund = ForwardRefund::for_packet(packet, &data);
+
+    let receiver_account = forward
+        .as_ref()
+        .map_or(&data.receiver, |(_, forward)| &forward.local_receiver)
         .clone()
         .try_into()
         .map_err(|_| Ics20Error::parse_account_failure())?;
@@ -39,10 +52,22 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         let escrow_address =
             ctx.get_channel_escrow_address(&packet.destination_port, &packet.destination_channel)?;
 
-        Ok(Box::new(move |ctx| {
+        let forward = forward
+            .map(|(config, forward)| forward.into_in_flight(&config, coin.clone(), refund))
+            .map(|forward| prepare_forward(ctx, forward))
+            .transpose()?;
+
+        Ok(Box::new(move |ctx, output| {
             let ctx = ctx.downcast_mut::<Ctx>().unwrap();
             ctx.send_coins(&escrow_address, &receiver_account, &coin)
-                .map_err(|e| e.to_string())
+                .map_err(|e| e.to_string())?;
+            if let Err(e) = forward_received_coins(ctx, output, forward) {
+                // The packet is not received, so the tokens go back to escrow.
+                ctx.send_coins(&receiver_account, &escrow_address, &coin)
+                    .map_err(|e| e.to_string())?;
+                return Err(e);
+            }
+            Ok(())
         }))
     } else {
         // sender chain is the source, mint vouchers
@@ -62,10 +87,41 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         };
         output.emit(denom_trace_event.into());
 
-        Ok(Box::new(move |ctx| {
+        let forward = forward
+            .map(|(config, forward)| forward.into_in_flight(&config, coin.clone(), refund))
+            .map(|forward| prepare_forward(ctx, forward))
+            .transpose()?;
+
+        Ok(Box::new(move |ctx, output| {
             let ctx = ctx.downcast_mut::<Ctx>().unwrap();
             ctx.mint_coins(&receiver_account, &coin)
-                .map_err(|e| e.to_string())
+                .map_err(|e| e.to_string())?;
+            if let Err(e) = forward_received_coins(ctx, output, forward) {
+                // The packet is not received, so the minted vouchers are burnt again.
+                ctx.burn_coins(&receiver_account, &coin)
+                    .map_err(|e| e.to_string())?;
+                return Err(e);
+            }
+            Ok(())
         }))
     }
 }
+
+/// Sends the received tokens on to the next chain, and emits the events of sending them once
+/// the forward is stored. The forward has been validated by then, but if it still cannot be
+/// sent, the packet is not received, so that it eventually times out and the tokens are
+/// refunded to their sender.
+fn forward_received_coins<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    forward: Option<PreparedForward>,
+) -> Result<(), String> {
+    match forward {
+        Some(forward) => {
+            let forward_output = commit_forward(ctx, forward).map_err(|e| e.to_string())?;
+            output.merge_ibc_output(forward_output);
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
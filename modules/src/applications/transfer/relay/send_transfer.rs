@@ -3,12 +3,14 @@ use crate::applications::transfer::error::Error;
 use crate::applications::transfer::events::TransferEvent;
 use crate::applications::transfer::msgs::transfer::MsgTransfer;
 use crate::applications::transfer::packet::PacketData;
-use crate::applications::transfer::{is_sender_chain_source, Coin, PrefixedCoin};
+use crate::applications::transfer::{is_sender_chain_source, PrefixedCoin};
 use crate::core::ics04_channel::handler::send_packet::send_packet;
-use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::packet::{Packet, PacketResult, Sequence};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::ModuleEvent;
 use crate::handler::{HandlerOutput, HandlerOutputBuilder};
 use crate::prelude::*;
+use crate::signer::Signer;
 
 /// This function handles the transfer sending logic.
 /// If this method returns an error, the runtime is expected to rollback all state modifications to
@@ -18,6 +20,60 @@ pub fn send_transfer<Ctx, C>(
     output: &mut HandlerOutputBuilder<()>,
     msg: MsgTransfer<C>,
 ) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+    C: TryInto<PrefixedCoin>,
+{
+    send_transfer_with_memo(ctx, output, msg, None)
+}
+
+/// Same as [`send_transfer`], with the given memo in the data of the packet.
+pub fn send_transfer_with_memo<Ctx, C>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgTransfer<C>,
+    memo: Option<String>,
+) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+    C: TryInto<PrefixedCoin>,
+{
+    let transfer = prepare_transfer_with_memo(ctx, msg, memo)?;
+    output.merge(commit_transfer(ctx, transfer)?);
+    Ok(())
+}
+
+/// A transfer which has been validated, but whose tokens have not been moved yet.
+#[derive(Clone, Debug)]
+pub struct PreparedTransfer {
+    sender: Signer,
+    source_port: PortId,
+    source_channel: ChannelId,
+    coin: PrefixedCoin,
+    sequence: Sequence,
+    result: PacketResult,
+    output: HandlerOutputBuilder<()>,
+}
+
+impl PreparedTransfer {
+    /// The sequence of the packet of the transfer.
+    pub fn sequence(&self) -> Sequence {
+        self.sequence
+    }
+
+    /// The output of the transfer once it is committed.
+    pub fn output(&self) -> &HandlerOutputBuilder<()> {
+        &self.output
+    }
+}
+
+/// Validates the given transfer and builds its packet, without modifying the state of the
+/// chain. The transfer takes place once it is passed to [`commit_transfer`].
+pub fn prepare_transfer_with_memo<Ctx, C>(
+    ctx: &Ctx,
+    msg: MsgTransfer<C>,
+    memo: Option<String>,
+) -> Result<PreparedTransfer, Error>
 where
     Ctx: Ics20Context,
     C: TryInto<PrefixedCoin>,
@@ -48,40 +104,29 @@ where
         .get_next_sequence_send(&source_channel_key)
         .map_err(Error::ics04_channel)?;
 
-    let token = msg.token.try_into().map_err(|_| Error::invalid_token())?;
-    let denom = token.denom.clone();
-    let coin = Coin {
-        denom: denom.clone(),
-        amount: token.amount,
-    };
+    let coin: PrefixedCoin = msg.token.try_into().map_err(|_| Error::invalid_token())?;
 
-    let sender = msg
+    // The sender must be a valid account, even though its tokens are only moved on commit.
+    let _: <Ctx as Ics20Context>::AccountId = msg
         .sender
         .clone()
         .try_into()
         .map_err(|_| Error::parse_account_failure())?;
 
-    if is_sender_chain_source(msg.source_port.clone(), msg.source_channel.clone(), &denom) {
-        let escrow_address =
-            ctx.get_channel_escrow_address(&msg.source_port, &msg.source_channel)?;
-        ctx.send_coins(&sender, &escrow_address, &coin)?;
-    } else {
-        ctx.burn_coins(&sender, &coin)?;
-    }
-
     let data = {
         let data = PacketData {
-            token: coin,
+            token: coin.clone(),
             sender: msg.sender.clone(),
             receiver: msg.receiver.clone(),
+            memo,
         };
         serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
     };
 
     let packet = Packet {
         sequence,
-        source_port: msg.source_port,
-        source_channel: msg.source_channel,
+        source_port: msg.source_port.clone(),
+        source_channel: msg.source_channel.clone(),
         destination_port,
         destination_channel,
         data,
@@ -95,26 +140,70 @@ where
         events,
     } = send_packet(ctx, packet).map_err(Error::ics04_channel)?;
 
-    ctx.store_packet_result(result)
-        .map_err(Error::ics04_channel)?;
-
-    output.merge_output(
-        HandlerOutput::builder()
-            .with_log(log)
-            .with_events(events)
-            .with_result(()),
-    );
+    let mut output = HandlerOutputBuilder::new()
+        .with_log(log)
+        .with_events(events);
 
     output.log(format!(
         "IBC fungible token transfer: {} --({})--> {}",
-        msg.sender, token, msg.receiver
+        msg.sender, coin, msg.receiver
     ));
 
     let transfer_event = TransferEvent {
-        sender: msg.sender,
+        sender: msg.sender.clone(),
         receiver: msg.receiver,
     };
     output.emit(ModuleEvent::from(transfer_event).into());
 
-    Ok(())
+    Ok(PreparedTransfer {
+        sender: msg.sender,
+        source_port: msg.source_port,
+        source_channel: msg.source_channel,
+        coin,
+        sequence,
+        result,
+        output,
+    })
+}
+
+/// Moves the tokens of a prepared transfer and stores its packet, and returns the output of the
+/// transfer. If this fails, the tokens are left with the sender.
+pub fn commit_transfer<Ctx>(
+    ctx: &mut Ctx,
+    transfer: PreparedTransfer,
+) -> Result<HandlerOutputBuilder<()>, Error>
+where
+    Ctx: Ics20Context,
+{
+    let PreparedTransfer {
+        sender,
+        source_port,
+        source_channel,
+        coin,
+        result,
+        output,
+        ..
+    } = transfer;
+
+    let sender_account = sender
+        .try_into()
+        .map_err(|_| Error::parse_account_failure())?;
+
+    // The tokens are given back to the sender if the packet cannot be stored.
+    if is_sender_chain_source(source_port.clone(), source_channel.clone(), &coin.denom) {
+        let escrow_address = ctx.get_channel_escrow_address(&source_port, &source_channel)?;
+        ctx.send_coins(&sender_account, &escrow_address, &coin)?;
+        if let Err(e) = ctx.store_packet_result(result) {
+            ctx.send_coins(&escrow_address, &sender_account, &coin)?;
+            return Err(Error::ics04_channel(e));
+        }
+    } else {
+        ctx.burn_coins(&sender_account, &coin)?;
+        if let Err(e) = ctx.store_packet_result(result) {
+            ctx.mint_coins(&sender_account, &coin)?;
+            return Err(Error::ics04_channel(e));
+        }
+    }
+
+    Ok(output)
 }
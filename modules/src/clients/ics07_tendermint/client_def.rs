@@ -28,8 +28,8 @@ use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
 use crate::core::ics24_host::identifier::ConnectionId;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradePath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
 };
 use crate::core::ics24_host::Path;
 use crate::downcast;
@@ -253,6 +253,28 @@ impl ClientDef for TendermintClient {
         verify_membership(client_state, prefix, proof, root, path, value)
     }
 
+    fn verify_channel_upgrade_state(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: &ChannelUpgradePath,
+        expected_value: Vec<u8>,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+
+        verify_membership(
+            client_state,
+            prefix,
+            proof,
+            root,
+            path.clone(),
+            expected_value,
+        )
+    }
+
     fn verify_client_full_state(
         &self,
         client_state: &Self::ClientState,
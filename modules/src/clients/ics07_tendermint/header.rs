@@ -114,6 +114,11 @@ impl TryFrom<RawHeader> for Header {
                 .map_err(Error::invalid_raw_header)?,
         };
 
+        // `Header::height` assumes a non-zero height, so reject it here rather than panic later.
+        if header.signed_header.header.height.value() == 0 {
+            return Err(Error::invalid_header_height(0));
+        }
+
         if header.height().revision_number() != header.trusted_height.revision_number() {
             return Err(Error::mismatched_revisions(
                 header.trusted_height.revision_number(),
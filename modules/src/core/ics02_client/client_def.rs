@@ -16,6 +16,7 @@ use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::ChannelUpgradePath;
 use crate::downcast;
 use crate::prelude::*;
 use crate::Height;
@@ -92,6 +93,20 @@ pub trait ClientDef: Clone {
         expected_channel_end: &ChannelEnd,
     ) -> Result<(), Error>;
 
+    /// Verify a `proof` that the channel upgrade state stored under `path`
+    /// (upgrade sequence, upgrade timeout or error receipt) matches `expected_value`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_channel_upgrade_state(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: &ChannelUpgradePath,
+        expected_value: Vec<u8>,
+    ) -> Result<(), Error>;
+
     /// Verify the client state for this chain that it is stored on the counterparty chain.
     #[allow(clippy::too_many_arguments)]
     fn verify_client_full_state(
@@ -379,6 +394,50 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn verify_channel_upgrade_state(
+        &self,
+        client_state: &AnyClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: &ChannelUpgradePath,
+        expected_value: Vec<u8>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Tendermint)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_channel_upgrade_state(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    path,
+                    expected_value,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Mock)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_channel_upgrade_state(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    path,
+                    expected_value,
+                )
+            }
+        }
+    }
+
     fn verify_client_full_state(
         &self,
         client_state: &Self::ClientState,
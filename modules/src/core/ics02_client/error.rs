@@ -142,6 +142,14 @@ define_error! {
         MissingRawHeader
             | _ | { "missing raw header" },
 
+        InvalidStringAsHeader
+            { header: String }
+            [ TraceError<subtle_encoding::Error> ]
+            | e | {
+                format_args!("invalid string as header: {0}",
+                    e.header)
+            },
+
         DecodeRawMisbehaviour
             [ TraceError<TendermintProtoError> ]
             | _ | { "invalid raw misbehaviour" },
@@ -81,7 +81,8 @@ impl AnyHeader {
     }
 
     pub fn decode_from_string(s: &str) -> Result<Self, Error> {
-        let header_bytes = hex::decode(s).unwrap();
+        let header_bytes =
+            hex::decode(s).map_err(|e| Error::invalid_string_as_header(s.to_string(), e))?;
         Protobuf::decode(header_bytes.as_ref()).map_err(Error::invalid_raw_header)
     }
 }
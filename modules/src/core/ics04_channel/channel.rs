@@ -206,11 +206,8 @@ impl ChannelEnd {
     }
 
     pub fn validate_basic(&self) -> Result<(), Error> {
-        if self.connection_hops.len() != 1 {
-            return Err(Error::invalid_connection_hops_length(
-                1,
-                self.connection_hops.len(),
-            ));
+        if self.connection_hops.is_empty() {
+            return Err(Error::empty_connection_hops());
         }
         self.counterparty().validate_basic()
     }
@@ -355,6 +352,8 @@ pub enum State {
     TryOpen = 2,
     Open = 3,
     Closed = 4,
+    InitUpgrade = 5,
+    TryUpgrade = 6,
 }
 
 impl State {
@@ -366,6 +365,8 @@ impl State {
             Self::TryOpen => "TRYOPEN",
             Self::Open => "OPEN",
             Self::Closed => "CLOSED",
+            Self::InitUpgrade => "INITUPGRADE",
+            Self::TryUpgrade => "TRYUPGRADE",
         }
     }
 
@@ -377,6 +378,8 @@ impl State {
             2 => Ok(Self::TryOpen),
             3 => Ok(Self::Open),
             4 => Ok(Self::Closed),
+            5 => Ok(Self::InitUpgrade),
+            6 => Ok(Self::TryUpgrade),
             _ => Err(Error::unknown_state(s)),
         }
     }
@@ -386,6 +389,12 @@ impl State {
         self == State::Open
     }
 
+    /// Returns whether or not this channel state is part of an
+    /// in-progress channel upgrade handshake.
+    pub fn is_upgrading(self) -> bool {
+        matches!(self, State::InitUpgrade | State::TryUpgrade)
+    }
+
     /// Returns whether or not the channel with this state
     /// has progressed less or the same than the argument.
     ///
@@ -395,8 +404,18 @@ impl State {
     /// assert!(State::TryOpen.less_or_equal_progress(State::TryOpen));
     /// assert!(!State::Closed.less_or_equal_progress(State::Open));
     /// ```
+    ///
+    /// The upgrade states are considered to have progressed as far as `Open`,
+    /// since a channel only enters them after having been opened.
     pub fn less_or_equal_progress(self, other: Self) -> bool {
-        self as u32 <= other as u32
+        self.progress() <= other.progress()
+    }
+
+    fn progress(self) -> u32 {
+        match self {
+            State::InitUpgrade | State::TryUpgrade => State::Open as u32,
+            _ => self as u32,
+        }
     }
 }
 
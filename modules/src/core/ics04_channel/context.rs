@@ -10,8 +10,9 @@ use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::handler::recv_packet::RecvPacketResult;
-use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use crate::core::ics04_channel::{error::Error, packet::Receipt};
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::prelude::*;
@@ -68,6 +69,34 @@ pub trait ChannelReader {
         key: &(PortId, ChannelId, Sequence),
     ) -> Result<AcknowledgementCommitment, Error>;
 
+    /// Returns the sequence of the latest upgrade attempt of the given channel,
+    /// or `0` if no upgrade was ever attempted.
+    ///
+    /// Hosts that do not support channel upgrades can rely on the default implementations
+    /// of the upgrade methods of this trait and of [`ChannelKeeper`].
+    fn get_upgrade_sequence(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<u64, Error> {
+        Ok(0)
+    }
+
+    /// Returns the timeout of the upgrade in progress on the given channel.
+    fn get_upgrade_timeout(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<UpgradeTimeout, Error> {
+        Err(Error::missing_upgrade_timeout(port_channel_id.clone()))
+    }
+
+    /// Returns the channel end that was stored before the upgrade in progress on the
+    /// given channel started.
+    fn get_upgrade_restore_channel(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ChannelEnd, Error> {
+        Err(Error::missing_upgrade_restore_channel(
+            port_channel_id.clone(),
+        ))
+    }
+
     /// Compute the commitment for a packet.
     /// Note that the absence of `timeout_height` is treated as
     /// `{revision_number: 0, revision_height: 0}` to be consistent with ibc-go,
@@ -168,7 +197,37 @@ pub trait ChannelKeeper {
                 (result.port_id.clone(), result.channel_id.clone()),
                 1.into(),
             )?;
-            self.store_next_sequence_ack((result.port_id, result.channel_id), 1.into())?;
+            self.store_next_sequence_ack(
+                (result.port_id.clone(), result.channel_id.clone()),
+                1.into(),
+            )?;
+        }
+
+        if let Some(upgrade) = result.upgrade {
+            let port_channel_id = (result.port_id, result.channel_id);
+
+            match upgrade {
+                UpgradeResult::Started {
+                    sequence,
+                    timeout,
+                    restore_channel,
+                } => {
+                    self.store_upgrade_sequence(port_channel_id.clone(), sequence)?;
+                    if let Some(timeout) = timeout {
+                        self.store_upgrade_timeout(port_channel_id.clone(), timeout)?;
+                    }
+                    self.store_upgrade_restore_channel(port_channel_id, &restore_channel)?;
+                }
+                UpgradeResult::Completed { .. } => {
+                    self.delete_upgrade_timeout(port_channel_id.clone())?;
+                    self.delete_upgrade_restore_channel(port_channel_id)?;
+                }
+                UpgradeResult::Aborted { error_receipt } => {
+                    self.store_upgrade_error_receipt(port_channel_id.clone(), error_receipt)?;
+                    self.delete_upgrade_timeout(port_channel_id.clone())?;
+                    self.delete_upgrade_restore_channel(port_channel_id)?;
+                }
+            }
         }
 
         Ok(())
@@ -291,6 +350,56 @@ pub trait ChannelKeeper {
         seq: Sequence,
     ) -> Result<(), Error>;
 
+    /// The default implementations of the upgrade methods reject the upgrade, so that
+    /// hosts that do not support channel upgrades never end up with a partially stored one.
+    fn store_upgrade_sequence(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _sequence: u64,
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
+    fn store_upgrade_timeout(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _timeout: UpgradeTimeout,
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
+    fn delete_upgrade_timeout(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
+    /// Stores the channel end to restore if the upgrade in progress on the channel is aborted.
+    /// This is not part of the provable store.
+    fn store_upgrade_restore_channel(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
+    fn delete_upgrade_restore_channel(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _error_receipt: ErrorReceipt,
+    ) -> Result<(), Error> {
+        Err(Error::upgrade_not_supported())
+    }
+
     /// Called upon channel identifier creation (Init or Try message processing).
     /// Increases the counter which keeps track of how many channels have been created.
     /// Should never fail.
@@ -345,6 +345,102 @@ define_error! {
                     "application module error: {0}",
                     e.description)
             },
+
+        InvalidUpgradeTimeout
+            | _ | { "invalid channel upgrade timeout: neither timeout height nor timeout timestamp is set" },
+
+        UpgradeTimeoutElapsed
+            {
+                timeout_height: TimeoutHeight,
+                timeout_timestamp: Timestamp,
+            }
+            | e | {
+                format_args!(
+                    "channel upgrade timeout (height: {0}, timestamp: {1}) has already elapsed",
+                    e.timeout_height, e.timeout_timestamp)
+            },
+
+        UpgradeTimeoutNotReached
+            {
+                timeout_height: TimeoutHeight,
+                timeout_timestamp: Timestamp,
+            }
+            | e | {
+                format_args!(
+                    "channel upgrade timeout (height: {0}, timestamp: {1}) has not been reached yet",
+                    e.timeout_height, e.timeout_timestamp)
+            },
+
+        UpgradeSequenceMismatch
+            {
+                expected: u64,
+                actual: u64,
+            }
+            | e | {
+                format_args!(
+                    "channel upgrade sequence mismatch: expected {0}, got {1}",
+                    e.expected, e.actual)
+            },
+
+        MissingUpgradeTimeout
+            { port_channel_id: (PortId, ChannelId) }
+            | e | {
+                format_args!(
+                    "missing channel upgrade timeout for port {0} and channel {1}",
+                    e.port_channel_id.0,
+                    e.port_channel_id.1)
+            },
+
+        MissingUpgradeRestoreChannel
+            { port_channel_id: (PortId, ChannelId) }
+            | e | {
+                format_args!(
+                    "missing channel to restore after upgrade for port {0} and channel {1}",
+                    e.port_channel_id.0,
+                    e.port_channel_id.1)
+            },
+
+        MissingErrorReceipt
+            | _ | { "missing channel upgrade error receipt" },
+
+        UpgradeNotSupported
+            | _ | { "channel upgrades are not supported by this host" },
+
+        ModuleUpgradeNotSupported
+            { port_id: PortId }
+            | e | {
+                format_args!(
+                    "the application module bound to port {0} does not support channel upgrades",
+                    e.port_id)
+            },
+
+        InvalidUpgradeChannel
+            { reason: String }
+            | e | {
+                format_args!(
+                    "invalid proposed upgrade channel: {0}",
+                    e.reason)
+            },
+
+        VerifyChannelUpgradeFailed
+            [ client_error::Error ]
+            | _ | { "channel upgrade state verification failed" },
+
+        EmptyConnectionHops
+            | _ | { "a channel must have at least one connection hop" },
+
+        InvalidMultihopProof
+            { reason: String }
+            | e | { format_args!("invalid multi-hop proof: {}", e.reason) },
+
+        VerifyMultihopProofFailed
+            { hop: usize }
+            [ client_error::Error ]
+            | e | {
+                format_args!(
+                    "verification of the multi-hop proof failed at hop {}",
+                    e.hop)
+            },
     }
 }
 
@@ -67,6 +67,36 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
             .map(|res| res.ok().map(IbcEvent::CloseConfirmChannel))
             .ok()
             .flatten(),
+        Ok(IbcEventType::UpgradeInitChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeInit::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeInitChannel))
+            .ok()
+            .flatten(),
+        Ok(IbcEventType::UpgradeTryChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeTry::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeTryChannel))
+            .ok()
+            .flatten(),
+        Ok(IbcEventType::UpgradeAckChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeAck::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeAckChannel))
+            .ok()
+            .flatten(),
+        Ok(IbcEventType::UpgradeConfirmChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeConfirm::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeConfirmChannel))
+            .ok()
+            .flatten(),
+        Ok(IbcEventType::UpgradeTimeoutChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeTimeout::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeTimeoutChannel))
+            .ok()
+            .flatten(),
+        Ok(IbcEventType::UpgradeCancelChannel) => extract_attributes_from_tx(event)
+            .map(UpgradeCancel::try_from)
+            .map(|res| res.ok().map(IbcEvent::UpgradeCancelChannel))
+            .ok()
+            .flatten(),
         Ok(IbcEventType::SendPacket) => {
             extract_packet_and_write_ack_from_tx(event)
                 .map(|(packet, write_ack)| {
@@ -79,6 +109,18 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
                 })
                 .ok()
         }
+        Ok(IbcEventType::ReceivePacket) => {
+            extract_packet_and_write_ack_from_tx(event)
+                .map(|(packet, write_ack)| {
+                    // This event should not have a write ack.
+                    debug_assert_eq!(write_ack.len(), 0);
+                    IbcEvent::ReceivePacket(ReceivePacket {
+                        height: Height::new(0, 1).unwrap(),
+                        packet,
+                    })
+                })
+                .ok()
+        }
         Ok(IbcEventType::WriteAck) => extract_packet_and_write_ack_from_tx(event)
             .map(|(packet, write_ack)| {
                 IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
@@ -112,6 +154,18 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
                 })
                 .ok()
         }
+        Ok(IbcEventType::TimeoutOnClose) => {
+            extract_packet_and_write_ack_from_tx(event)
+                .map(|(packet, write_ack)| {
+                    // This event should not have a write ack.
+                    debug_assert_eq!(write_ack.len(), 0);
+                    IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket {
+                        height: Height::new(0, 1).unwrap(),
+                        packet,
+                    })
+                })
+                .ok()
+        }
         _ => None,
     }
 }
@@ -708,6 +762,306 @@ impl EventType for CloseConfirm {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeInit {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeInit> for Attributes {
+    fn from(ev: UpgradeInit) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeInit {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeInit> for IbcEvent {
+    fn from(v: UpgradeInit) -> Self {
+        IbcEvent::UpgradeInitChannel(v)
+    }
+}
+
+impl EventType for UpgradeInit {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeInitChannel
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeTry {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeTry> for Attributes {
+    fn from(ev: UpgradeTry) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeTry {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeTry> for IbcEvent {
+    fn from(v: UpgradeTry) -> Self {
+        IbcEvent::UpgradeTryChannel(v)
+    }
+}
+
+impl EventType for UpgradeTry {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeTryChannel
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeAck {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeAck> for Attributes {
+    fn from(ev: UpgradeAck) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeAck {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeAck> for IbcEvent {
+    fn from(v: UpgradeAck) -> Self {
+        IbcEvent::UpgradeAckChannel(v)
+    }
+}
+
+impl EventType for UpgradeAck {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeAckChannel
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeConfirm {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeConfirm> for Attributes {
+    fn from(ev: UpgradeConfirm) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeConfirm {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeConfirm> for IbcEvent {
+    fn from(v: UpgradeConfirm) -> Self {
+        IbcEvent::UpgradeConfirmChannel(v)
+    }
+}
+
+impl EventType for UpgradeConfirm {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeConfirmChannel
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeTimeout {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeTimeout> for Attributes {
+    fn from(ev: UpgradeTimeout) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeTimeout {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeTimeout> for IbcEvent {
+    fn from(v: UpgradeTimeout) -> Self {
+        IbcEvent::UpgradeTimeoutChannel(v)
+    }
+}
+
+impl EventType for UpgradeTimeout {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeTimeoutChannel
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeCancel {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+impl From<UpgradeCancel> for Attributes {
+    fn from(ev: UpgradeCancel) -> Self {
+        Self {
+            height: ev.height,
+            port_id: ev.port_id,
+            channel_id: ev.channel_id,
+            connection_id: ev.connection_id,
+            counterparty_port_id: ev.counterparty_port_id,
+            counterparty_channel_id: ev.counterparty_channel_id,
+        }
+    }
+}
+
+impl UpgradeCancel {
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        self.channel_id.as_ref()
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<UpgradeCancel> for IbcEvent {
+    fn from(v: UpgradeCancel) -> Self {
+        IbcEvent::UpgradeCancelChannel(v)
+    }
+}
+
+impl EventType for UpgradeCancel {
+    fn event_type() -> IbcEventType {
+        IbcEventType::UpgradeCancelChannel
+    }
+}
+
 macro_rules! impl_try_from_attribute_for_event {
     ($($event:ty),+) => {
         $(impl TryFrom<Attributes> for $event {
@@ -727,7 +1081,19 @@ macro_rules! impl_try_from_attribute_for_event {
     };
 }
 
-impl_try_from_attribute_for_event!(OpenInit, OpenTry, OpenAck, OpenConfirm, CloseConfirm);
+impl_try_from_attribute_for_event!(
+    OpenInit,
+    OpenTry,
+    OpenAck,
+    OpenConfirm,
+    CloseConfirm,
+    UpgradeInit,
+    UpgradeTry,
+    UpgradeAck,
+    UpgradeConfirm,
+    UpgradeTimeout,
+    UpgradeCancel
+);
 
 macro_rules! impl_from_ibc_to_abci_event {
     ($($event:ty),+) => {
@@ -750,7 +1116,13 @@ impl_from_ibc_to_abci_event!(
     OpenAck,
     OpenConfirm,
     CloseInit,
-    CloseConfirm
+    CloseConfirm,
+    UpgradeInit,
+    UpgradeTry,
+    UpgradeAck,
+    UpgradeConfirm,
+    UpgradeTimeout,
+    UpgradeCancel
 );
 
 macro_rules! impl_try_from_raw_obj_for_event {
@@ -771,7 +1143,13 @@ impl_try_from_raw_obj_for_event!(
     OpenAck,
     OpenConfirm,
     CloseInit,
-    CloseConfirm
+    CloseConfirm,
+    UpgradeInit,
+    UpgradeTry,
+    UpgradeAck,
+    UpgradeConfirm,
+    UpgradeTimeout,
+    UpgradeCancel
 );
 
 #[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -1188,8 +1566,20 @@ mod tests {
         abci_events.push(AbciEvent::from(open_confirm.clone()));
         let close_init = CloseInit::try_from(attributes.clone()).unwrap();
         abci_events.push(AbciEvent::from(close_init.clone()));
-        let close_confirm = CloseConfirm::try_from(attributes).unwrap();
+        let close_confirm = CloseConfirm::try_from(attributes.clone()).unwrap();
         abci_events.push(AbciEvent::from(close_confirm.clone()));
+        let upgrade_init = UpgradeInit::try_from(attributes.clone()).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_init.clone()));
+        let upgrade_try = UpgradeTry::try_from(attributes.clone()).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_try.clone()));
+        let upgrade_ack = UpgradeAck::try_from(attributes.clone()).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_ack.clone()));
+        let upgrade_confirm = UpgradeConfirm::try_from(attributes.clone()).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_confirm.clone()));
+        let upgrade_timeout = UpgradeTimeout::try_from(attributes.clone()).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_timeout.clone()));
+        let upgrade_cancel = UpgradeCancel::try_from(attributes).unwrap();
+        abci_events.push(AbciEvent::from(upgrade_cancel.clone()));
 
         for event in abci_events {
             match try_from_tx(&event) {
@@ -1212,6 +1602,24 @@ mod tests {
                     IbcEvent::CloseConfirmChannel(e) => {
                         assert_eq!(Attributes::from(e), close_confirm.clone().into())
                     }
+                    IbcEvent::UpgradeInitChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_init.clone().into())
+                    }
+                    IbcEvent::UpgradeTryChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_try.clone().into())
+                    }
+                    IbcEvent::UpgradeAckChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_ack.clone().into())
+                    }
+                    IbcEvent::UpgradeConfirmChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_confirm.clone().into())
+                    }
+                    IbcEvent::UpgradeTimeoutChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_timeout.clone().into())
+                    }
+                    IbcEvent::UpgradeCancelChannel(e) => {
+                        assert_eq!(Attributes::from(e), upgrade_cancel.clone().into())
+                    }
                     _ => panic!("unexpected event type"),
                 },
                 None => panic!("converted event was wrong"),
@@ -1237,6 +1645,11 @@ mod tests {
             packet: packet.clone(),
         };
         abci_events.push(AbciEvent::try_from(send_packet.clone()).unwrap());
+        let recv_packet = ReceivePacket {
+            height: Height::new(0, 1).unwrap(),
+            packet: packet.clone(),
+        };
+        abci_events.push(AbciEvent::try_from(recv_packet.clone()).unwrap());
         let write_ack = WriteAcknowledgement {
             height: Height::new(0, 1).unwrap(),
             packet: packet.clone(),
@@ -1250,20 +1663,29 @@ mod tests {
         abci_events.push(AbciEvent::try_from(ack_packet.clone()).unwrap());
         let timeout_packet = TimeoutPacket {
             height: Height::new(0, 1).unwrap(),
-            packet,
+            packet: packet.clone(),
         };
         abci_events.push(AbciEvent::try_from(timeout_packet.clone()).unwrap());
+        let timeout_on_close_packet = TimeoutOnClosePacket {
+            height: Height::new(0, 1).unwrap(),
+            packet,
+        };
+        abci_events.push(AbciEvent::try_from(timeout_on_close_packet.clone()).unwrap());
 
         for event in abci_events {
             match try_from_tx(&event) {
                 Some(e) => match e {
                     IbcEvent::SendPacket(e) => assert_eq!(e.packet, send_packet.packet),
+                    IbcEvent::ReceivePacket(e) => assert_eq!(e.packet, recv_packet.packet),
                     IbcEvent::WriteAcknowledgement(e) => {
                         assert_eq!(e.packet, write_ack.packet);
                         assert_eq!(e.ack, write_ack.ack);
                     }
                     IbcEvent::AcknowledgePacket(e) => assert_eq!(e.packet, ack_packet.packet),
                     IbcEvent::TimeoutPacket(e) => assert_eq!(e.packet, timeout_packet.packet),
+                    IbcEvent::TimeoutOnClosePacket(e) => {
+                        assert_eq!(e.packet, timeout_on_close_packet.packet)
+                    }
                     _ => panic!("unexpected event type"),
                 },
                 None => panic!("converted event was wrong"),
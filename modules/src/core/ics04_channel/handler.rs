@@ -1,9 +1,12 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
+use crate::prelude::*;
+
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::ChannelMsg;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use crate::core::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::core::ics26_routing::context::{
@@ -18,6 +21,12 @@ pub mod chan_open_ack;
 pub mod chan_open_confirm;
 pub mod chan_open_init;
 pub mod chan_open_try;
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
 pub mod recv_packet;
 pub mod send_packet;
 pub mod timeout;
@@ -36,12 +45,38 @@ pub enum ChannelIdState {
     Reused,
 }
 
+/// Defines the changes to the channel upgrade state that a `ChannelResult` carries.
+#[derive(Clone, Debug)]
+pub enum UpgradeResult {
+    /// An upgrade handshake was started on this end (`ChanUpgradeInit` or `ChanUpgradeTry`).
+    /// The channel end that was replaced is kept, so that it can be restored if the upgrade
+    /// is aborted. Only the chain which initiated the upgrade stores an upgrade timeout.
+    Started {
+        sequence: u64,
+        timeout: Option<UpgradeTimeout>,
+        restore_channel: ChannelEnd,
+    },
+
+    /// The upgrade handshake completed on this end (`ChanUpgradeAck` or `ChanUpgradeConfirm`).
+    /// The upgrade sequence and the channel end to restore are carried along in case the
+    /// application rejects the upgrade in its callback.
+    Completed {
+        sequence: u64,
+        restore_channel: ChannelEnd,
+    },
+
+    /// The upgrade was aborted and the channel end was restored. The error receipt is written
+    /// so that the counterparty can prove the abort and cancel the upgrade on its end as well.
+    Aborted { error_receipt: ErrorReceipt },
+}
+
 #[derive(Clone, Debug)]
 pub struct ChannelResult {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub channel_id_state: ChannelIdState,
     pub channel_end: ChannelEnd,
+    pub upgrade: Option<UpgradeResult>,
 }
 
 pub fn channel_validate<Ctx>(ctx: &Ctx, msg: &ChannelMsg) -> Result<ModuleId, Error>
@@ -72,6 +107,12 @@ where
         ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process(ctx, msg),
         ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, msg),
         ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeInit(msg) => chan_upgrade_init::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeTry(msg) => chan_upgrade_try::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeAck(msg) => chan_upgrade_ack::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeConfirm(msg) => chan_upgrade_confirm::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeTimeout(msg) => chan_upgrade_timeout::process(ctx, msg),
+        ChannelMsg::ChannelUpgradeCancel(msg) => chan_upgrade_cancel::process(ctx, msg),
     }?;
     let HandlerOutput {
         result,
@@ -135,10 +176,103 @@ where
         ChannelMsg::ChannelCloseConfirm(msg) => {
             cb.on_chan_close_confirm(module_output, &msg.port_id, &result.channel_id)?
         }
+        ChannelMsg::ChannelUpgradeInit(msg) => {
+            let version = cb.on_chan_upgrade_init(
+                module_output,
+                result.channel_end.ordering,
+                &result.channel_end.connection_hops,
+                &msg.port_id,
+                &result.channel_id,
+                result.channel_end.counterparty(),
+                result.channel_end.version(),
+            )?;
+            result.channel_end.version = version;
+        }
+        ChannelMsg::ChannelUpgradeTry(msg) => {
+            let cb_result = cb.on_chan_upgrade_try(
+                module_output,
+                result.channel_end.ordering,
+                &result.channel_end.connection_hops,
+                &msg.port_id,
+                &result.channel_id,
+                result.channel_end.counterparty(),
+                result.channel_end.version(),
+                msg.counterparty_channel.version(),
+            );
+            match cb_result {
+                Ok(version) => result.channel_end.version = version,
+                Err(e) => {
+                    cb.on_chan_upgrade_restore(module_output, &msg.port_id, &result.channel_id);
+                    result = abort_upgrade(result, e)?;
+                }
+            }
+        }
+        ChannelMsg::ChannelUpgradeAck(msg) => {
+            let cb_result = cb.on_chan_upgrade_ack(
+                module_output,
+                &msg.port_id,
+                &result.channel_id,
+                msg.counterparty_channel.version(),
+            );
+            if let Err(e) = cb_result {
+                cb.on_chan_upgrade_restore(module_output, &msg.port_id, &result.channel_id);
+                result = abort_upgrade(result, e)?;
+            }
+        }
+        ChannelMsg::ChannelUpgradeConfirm(msg) => {
+            cb.on_chan_upgrade_confirm(module_output, &msg.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelUpgradeTimeout(msg) => {
+            cb.on_chan_upgrade_restore(module_output, &msg.port_id, &result.channel_id)
+        }
+        ChannelMsg::ChannelUpgradeCancel(msg) => {
+            cb.on_chan_upgrade_restore(module_output, &msg.port_id, &result.channel_id)
+        }
     }
     Ok(result)
 }
 
+/// Whether the application rejected a `ChanUpgradeTry` or `ChanUpgradeAck` step that the
+/// channel handler had accepted, so that the upgrade was aborted by [`channel_callback`]. In
+/// that case, the event emitted by the handler for the step must not be emitted.
+pub fn upgrade_aborted_by_callback(msg: &ChannelMsg, result: &ChannelResult) -> bool {
+    matches!(
+        msg,
+        ChannelMsg::ChannelUpgradeTry(_) | ChannelMsg::ChannelUpgradeAck(_)
+    ) && matches!(result.upgrade, Some(UpgradeResult::Aborted { .. }))
+}
+
+/// Turns the result of an upgrade step into an aborted upgrade, after the application
+/// rejected it: the channel end that was stored before the upgrade started is restored, and an
+/// error receipt is written for the current upgrade sequence.
+fn abort_upgrade(result: ChannelResult, e: Error) -> Result<ChannelResult, Error> {
+    let (sequence, restore_channel) = match result.upgrade {
+        Some(UpgradeResult::Started {
+            sequence,
+            restore_channel,
+            ..
+        })
+        | Some(UpgradeResult::Completed {
+            sequence,
+            restore_channel,
+        }) => (sequence, restore_channel),
+        _ => {
+            return Err(Error::missing_upgrade_restore_channel((
+                result.port_id,
+                result.channel_id,
+            )))
+        }
+    };
+
+    Ok(ChannelResult {
+        channel_end: restore_channel,
+        upgrade: Some(UpgradeResult::Aborted {
+            error_receipt: ErrorReceipt::new(sequence, e.to_string()),
+        }),
+        ..result
+    })
+}
+
 pub fn get_module_for_packet_msg<Ctx>(ctx: &Ctx, msg: &PacketMsg) -> Result<ModuleId, Error>
 where
     Ctx: Ics26Context,
@@ -207,7 +341,7 @@ where
             let result = cb.on_recv_packet(module_output, &msg.packet, &msg.signer);
             match result {
                 OnRecvPacketAck::Nil(write_fn) | OnRecvPacketAck::Successful(_, write_fn) => {
-                    write_fn(cb.as_any_mut()).map_err(Error::app_module)?;
+                    write_fn(cb.as_any_mut(), module_output).map_err(Error::app_module)?;
                 }
                 OnRecvPacketAck::Failed(_) => {}
             }
@@ -78,6 +78,7 @@ pub fn process(
         packet,
         msg.acknowledgement.clone(),
         &connection_end,
+        source_channel_end.connection_hops(),
         &msg.proofs,
     )?;
 
@@ -27,11 +27,8 @@ pub(crate) fn process(
     }
 
     // An OPEN IBC connection running on the local (host) chain should exist.
-    if channel_end.connection_hops().len() != 1 {
-        return Err(Error::invalid_connection_hops_length(
-            1,
-            channel_end.connection_hops().len(),
-        ));
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
     }
 
     let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
@@ -52,6 +49,7 @@ pub(crate) fn process(
         channel_id: msg.channel_id.clone(),
         channel_id_state: ChannelIdState::Reused,
         channel_end,
+        upgrade: None,
     };
 
     let event_attributes = Attributes {
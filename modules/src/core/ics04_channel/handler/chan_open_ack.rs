@@ -7,6 +7,7 @@ use crate::core::ics04_channel::events::Attributes;
 use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
@@ -30,11 +31,8 @@ pub(crate) fn process(
 
     // An OPEN IBC connection running on the local (host) chain should exist.
 
-    if channel_end.connection_hops().len() != 1 {
-        return Err(Error::invalid_connection_hops_length(
-            1,
-            channel_end.connection_hops().len(),
-        ));
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
     }
 
     let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
@@ -51,12 +49,8 @@ pub(crate) fn process(
     let expected_counterparty =
         Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
 
-    let counterparty = conn.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
-    })?;
-
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, channel_end.connection_hops(), &msg.proofs)?;
 
     let expected_channel_end = ChannelEnd::new(
         State::TryOpen,
@@ -90,6 +84,7 @@ pub(crate) fn process(
         channel_id: msg.channel_id.clone(),
         channel_id_state: ChannelIdState::Reused,
         channel_end,
+        upgrade: None,
     };
 
     let event_attributes = Attributes {
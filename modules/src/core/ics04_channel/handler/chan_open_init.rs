@@ -17,11 +17,8 @@ pub(crate) fn process(
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
 
-    if msg.channel.connection_hops().len() != 1 {
-        return Err(Error::invalid_connection_hops_length(
-            1,
-            msg.channel.connection_hops().len(),
-        ));
+    if msg.channel.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
     }
 
     // An IBC connection running on the local (host) chain should exist.
@@ -61,6 +58,7 @@ pub(crate) fn process(
         channel_id: chan_id.clone(),
         channel_end: new_channel_end,
         channel_id_state: ChannelIdState::Generated,
+        upgrade: None,
     };
 
     let event_attributes = Attributes {
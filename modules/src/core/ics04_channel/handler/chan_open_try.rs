@@ -8,6 +8,7 @@ use crate::core::ics04_channel::events::Attributes;
 use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
 use crate::core::ics24_host::identifier::ChannelId;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
@@ -62,11 +63,8 @@ pub(crate) fn process(
     }?;
 
     // An IBC connection running on the local (host) chain should exist.
-    if msg.channel.connection_hops().len() != 1 {
-        return Err(Error::invalid_connection_hops_length(
-            1,
-            msg.channel.connection_hops().len(),
-        ));
+    if msg.channel.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
     }
 
     let conn = ctx.connection_end(&msg.channel.connection_hops()[0])?;
@@ -92,11 +90,8 @@ pub(crate) fn process(
     //      the port should be identical with the port we're using; the channel id should not be set
     //      since the counterparty cannot know yet which ID did we choose.
     let expected_counterparty = Counterparty::new(msg.port_id.clone(), None);
-    let counterparty = conn.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Error::undefined_connection_counterparty(msg.channel.connection_hops()[0].clone())
-    })?;
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, msg.channel.connection_hops(), &msg.proofs)?;
 
     // The other party should be storing a channel end in this configuration.
     let expected_channel_end = ChannelEnd::new(
@@ -131,6 +126,7 @@ pub(crate) fn process(
         },
         channel_id: channel_id.clone(),
         channel_end: new_channel_end,
+        upgrade: None,
     };
 
     let event_attributes = Attributes {
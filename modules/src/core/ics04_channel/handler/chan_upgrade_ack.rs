@@ -0,0 +1,290 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeAck`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::channel::{Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::verify::{
+    verify_channel_proofs, verify_channel_upgrade_proof,
+};
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
+use crate::core::ics04_channel::upgrade::upgrade_sequence_store_bytes;
+use crate::core::ics24_host::path::ChannelUpgradePath;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeAck,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the channel end being upgraded. It is in state `TryUpgrade` in case of
+    // crossing hellos.
+    let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::InitUpgrade)
+        && !channel_end.state_matches(&State::TryUpgrade)
+    {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            channel_end.connection_hops()[0].clone(),
+        ));
+    }
+
+    // Proof verification in two steps:
+    // 1. Setup: check the counterparty channel as found in the message is the one we expect.
+
+    let expected_counterparty =
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
+
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, channel_end.connection_hops(), &msg.proofs)?;
+
+    if !msg.counterparty_channel.state_matches(&State::TryUpgrade)
+        || !msg
+            .counterparty_channel
+            .counterparty_matches(&expected_counterparty)
+        || !msg
+            .counterparty_channel
+            .connection_hops_matches(&expected_connection_hops)
+        || !msg
+            .counterparty_channel
+            .order_matches(channel_end.ordering())
+    {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty channel does not match the channel being upgraded".to_string(),
+        ));
+    }
+
+    // 2. Verify proofs of the counterparty channel end and upgrade sequence.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &msg.counterparty_channel,
+        &msg.proofs,
+    )?;
+
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)?;
+
+    let counterparty_channel_id = channel_end
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .ok_or_else(Error::missing_channel_id)?;
+
+    verify_channel_upgrade_proof(
+        ctx,
+        msg.proofs.height(),
+        &conn,
+        channel_end.connection_hops(),
+        &msg.proof_upgrade_sequence,
+        ChannelUpgradePath::Sequence(
+            channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id,
+        ),
+        upgrade_sequence_store_bytes(sequence),
+    )?;
+
+    let restore_channel = ctx.get_upgrade_restore_channel(&port_channel_id)?;
+
+    output.log("success: channel upgrade ack ");
+
+    // Transition the channel end to the new state & pick a version.
+    channel_end.set_state(State::Open);
+    channel_end.set_version(msg.counterparty_channel.version().clone());
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end,
+        upgrade: Some(UpgradeResult::Completed {
+            sequence,
+            restore_channel,
+        }),
+    };
+
+    output.emit(IbcEvent::UpgradeAckChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::test_util::get_dummy_raw_msg_chan_upgrade_ack;
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::ZERO_DURATION;
+
+    #[test]
+    fn chan_upgrade_ack_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg = MsgChannelUpgradeAck::try_from(get_dummy_raw_msg_chan_upgrade_ack(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let mut init_upgrade_chan_end = msg.counterparty_channel.clone();
+        init_upgrade_chan_end.set_state(State::InitUpgrade);
+
+        let mut open_chan_end = msg.counterparty_channel.clone();
+        open_chan_end.set_state(State::Open);
+        open_chan_end.set_version(Default::default());
+
+        let context = context
+            .with_client(&client_id, proof_height)
+            .with_connection(conn_id, conn_end);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel is not upgrading".to_string(),
+                ctx: context.clone().with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    open_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeAck(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because no channel is stored for restoring".to_string(),
+                ctx: context.clone().with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    init_upgrade_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeAck(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        init_upgrade_chan_end,
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        1,
+                        None,
+                        open_chan_end,
+                    ),
+                msg: ChannelMsg::ChannelUpgradeAck(msg),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = channel_dispatch(&test.ctx, &test.msg);
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok((proto_output, res)) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_ack: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let proto_output = proto_output.with_result(());
+                    assert!(!proto_output.events.is_empty()); // Some events must exist.
+
+                    assert_eq!(res.channel_end.state().clone(), State::Open);
+                    assert!(matches!(
+                        res.upgrade,
+                        Some(UpgradeResult::Completed { sequence: 1, .. })
+                    ));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeAckChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_ack: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
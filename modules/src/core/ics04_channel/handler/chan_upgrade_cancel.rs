@@ -0,0 +1,267 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeCancel`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::verify::verify_channel_upgrade_proof;
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::core::ics24_host::path::ChannelUpgradePath;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeCancel,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the channel end being upgraded and validate it against the message.
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state().is_upgrading() {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    // The error receipt must not be about an upgrade attempt older than the current one.
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)?;
+
+    if msg.error_receipt.sequence < sequence {
+        return Err(Error::upgrade_sequence_mismatch(
+            sequence,
+            msg.error_receipt.sequence,
+        ));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            channel_end.connection_hops()[0].clone(),
+        ));
+    }
+
+    // Verify the proof of the error receipt written by the counterparty.
+    let counterparty_channel_id = channel_end
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .ok_or_else(Error::missing_channel_id)?;
+
+    verify_channel_upgrade_proof(
+        ctx,
+        msg.proofs.height(),
+        &conn,
+        channel_end.connection_hops(),
+        msg.proofs.object_proof(),
+        ChannelUpgradePath::Error(
+            channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id,
+        ),
+        msg.error_receipt.to_store_bytes(),
+    )?;
+
+    let restore_channel = ctx.get_upgrade_restore_channel(&port_channel_id)?;
+
+    output.log("success: channel upgrade cancel ");
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: restore_channel.connection_hops()[0].clone(),
+        counterparty_port_id: restore_channel.counterparty().port_id().clone(),
+        counterparty_channel_id: restore_channel.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+
+    // The channel end that was replaced by the upgrade is restored.
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end: restore_channel,
+        upgrade: Some(UpgradeResult::Aborted {
+            error_receipt: ErrorReceipt::new(sequence, msg.error_receipt.error.clone()),
+        }),
+    };
+
+    output.emit(IbcEvent::UpgradeCancelChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
+    use crate::core::ics04_channel::channel::{ChannelEnd, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::ZERO_DURATION;
+
+    #[test]
+    fn chan_upgrade_cancel_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg = MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel(
+            proof_height.revision_height(),
+            2,
+        ))
+        .unwrap();
+
+        let mut stale_msg = msg.clone();
+        stale_msg.error_receipt.sequence = 1;
+
+        // The channel being upgraded, and the channel end it replaced.
+        let restore_chan_end = ChannelEnd::try_from(get_dummy_raw_channel_end()).unwrap();
+        let mut try_upgrade_chan_end = restore_chan_end.clone();
+        try_upgrade_chan_end.set_state(State::TryUpgrade);
+
+        let context = context
+            .with_client(&client_id, proof_height)
+            .with_connection(conn_id, conn_end);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel is not upgrading".to_string(),
+                ctx: context.clone().with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    restore_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeCancel(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the error receipt is for an older upgrade"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        try_upgrade_chan_end.clone(),
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        2,
+                        None,
+                        restore_chan_end.clone(),
+                    ),
+                msg: ChannelMsg::ChannelUpgradeCancel(stale_msg),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        try_upgrade_chan_end,
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        2,
+                        None,
+                        restore_chan_end.clone(),
+                    ),
+                msg: ChannelMsg::ChannelUpgradeCancel(msg),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = channel_dispatch(&test.ctx, &test.msg);
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok((proto_output, res)) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_cancel: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let proto_output = proto_output.with_result(());
+                    assert!(!proto_output.events.is_empty()); // Some events must exist.
+
+                    // The channel end from before the upgrade is restored.
+                    assert_eq!(res.channel_end, restore_chan_end);
+                    assert!(matches!(
+                        res.upgrade,
+                        Some(UpgradeResult::Aborted { ref error_receipt }) if error_receipt.sequence == 2
+                    ));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeCancelChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_cancel: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
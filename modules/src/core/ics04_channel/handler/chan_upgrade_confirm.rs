@@ -0,0 +1,199 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeConfirm`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeConfirm,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the channel end being upgraded and validate it against the message.
+    let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::TryUpgrade) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            channel_end.connection_hops()[0].clone(),
+        ));
+    }
+
+    // Proof verification in two steps:
+    // 1. Setup: build the Channel as we expect to find it on the other party.
+
+    let expected_counterparty =
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
+
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, channel_end.connection_hops(), &msg.proofs)?;
+
+    let expected_channel_end = ChannelEnd::new(
+        State::Open,
+        *channel_end.ordering(),
+        expected_counterparty,
+        expected_connection_hops,
+        channel_end.version().clone(),
+    );
+
+    if msg.counterparty_channel != expected_channel_end {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty channel does not match the channel being upgraded".to_string(),
+        ));
+    }
+
+    //2. Verify proofs
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &expected_channel_end,
+        &msg.proofs,
+    )?;
+
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)?;
+    let restore_channel = ctx.get_upgrade_restore_channel(&port_channel_id)?;
+
+    output.log("success: channel upgrade confirm ");
+
+    // Transition the channel end to the new state.
+    channel_end.set_state(State::Open);
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end,
+        upgrade: Some(UpgradeResult::Completed {
+            sequence,
+            restore_channel,
+        }),
+    };
+
+    output.emit(IbcEvent::UpgradeConfirmChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::test_util::get_dummy_raw_msg_chan_upgrade_confirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::ZERO_DURATION;
+
+    #[test]
+    fn chan_upgrade_confirm_event_height() {
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let default_context = MockContext::default();
+        let client_consensus_state_height = default_context.host_height();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg = MsgChannelUpgradeConfirm::try_from(get_dummy_raw_msg_chan_upgrade_confirm(
+            client_consensus_state_height.revision_height(),
+        ))
+        .unwrap();
+
+        let mut try_upgrade_chan_end = msg.counterparty_channel.clone();
+        try_upgrade_chan_end.set_state(State::TryUpgrade);
+
+        let mut restore_chan_end = msg.counterparty_channel.clone();
+        restore_chan_end.set_version(Default::default());
+
+        let context = default_context
+            .with_client(&client_id, client_consensus_state_height)
+            .with_connection(conn_id, conn_end)
+            .with_channel(
+                msg.port_id.clone(),
+                msg.channel_id.clone(),
+                try_upgrade_chan_end,
+            )
+            .with_channel_upgrade(
+                msg.port_id.clone(),
+                msg.channel_id.clone(),
+                1,
+                None,
+                restore_chan_end,
+            );
+
+        let (handler_output_builder, res) =
+            channel_dispatch(&context, &ChannelMsg::ChannelUpgradeConfirm(msg)).unwrap();
+
+        assert_eq!(res.channel_end.state().clone(), State::Open);
+        assert!(matches!(
+            res.upgrade,
+            Some(UpgradeResult::Completed { sequence: 1, .. })
+        ));
+
+        let handler_output = handler_output_builder.with_result(());
+
+        assert!(!handler_output.events.is_empty()); // Some events must exist.
+
+        for event in handler_output.events.iter() {
+            assert!(matches!(event, &IbcEvent::UpgradeConfirmChannel(_)));
+            assert_eq!(event.height(), context.host_height());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeInit`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeInit,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the current channel end, which must be open to be upgraded.
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::Open) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    let proposed = &msg.proposed_upgrade_channel;
+
+    if !proposed.state_matches(&State::InitUpgrade) {
+        return Err(Error::invalid_upgrade_channel(format!(
+            "expected state {}, got {}",
+            State::InitUpgrade,
+            proposed.state()
+        )));
+    }
+
+    // The upgrade may change the ordering, the connection or the version of the channel,
+    // but not the channel end it is connected to.
+    if !proposed.counterparty_matches(channel_end.counterparty()) {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty of the channel cannot be changed".to_string(),
+        ));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if proposed.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&proposed.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            proposed.connection_hops()[0].clone(),
+        ));
+    }
+
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)? + 1;
+
+    output.log("success: channel upgrade init ");
+
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end: proposed.clone(),
+        upgrade: Some(UpgradeResult::Started {
+            sequence,
+            timeout: Some(msg.timeout),
+            restore_channel: channel_end,
+        }),
+    };
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: proposed.connection_hops()[0].clone(),
+        counterparty_port_id: proposed.counterparty().port_id().clone(),
+        counterparty_channel_id: proposed.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+    output.emit(IbcEvent::UpgradeInitChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::ZERO_DURATION;
+
+    #[test]
+    fn chan_upgrade_init_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let context = MockContext::default();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg =
+            MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init(100)).unwrap();
+
+        let mut open_chan_end = msg.proposed_upgrade_channel.clone();
+        open_chan_end.set_state(State::Open);
+        open_chan_end.set_version(Default::default());
+
+        let mut closed_chan_end = open_chan_end.clone();
+        closed_chan_end.set_state(State::Closed);
+
+        let mut bad_msg = msg.clone();
+        bad_msg.proposed_upgrade_channel = ChannelEnd::new(
+            State::InitUpgrade,
+            *open_chan_end.ordering(),
+            Default::default(),
+            open_chan_end.connection_hops().clone(),
+            open_chan_end.version().clone(),
+        );
+
+        let host_height = context.host_height();
+        let context = context.with_client(&client_id, host_height);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel does not exist".to_string(),
+                ctx: context
+                    .clone()
+                    .with_connection(conn_id.clone(), conn_end.clone()),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel is not open".to_string(),
+                ctx: context
+                    .clone()
+                    .with_connection(conn_id.clone(), conn_end.clone())
+                    .with_channel(msg.port_id.clone(), msg.channel_id.clone(), closed_chan_end),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the counterparty would change".to_string(),
+                ctx: context
+                    .clone()
+                    .with_connection(conn_id.clone(), conn_end.clone())
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        open_chan_end.clone(),
+                    ),
+                msg: ChannelMsg::ChannelUpgradeInit(bad_msg),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context.with_connection(conn_id, conn_end).with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    open_chan_end,
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(msg),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = channel_dispatch(&test.ctx, &test.msg);
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok((proto_output, res)) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_init: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let proto_output = proto_output.with_result(());
+                    assert!(!proto_output.events.is_empty()); // Some events must exist.
+
+                    // The proposed channel end replaces the current one, which is kept for restoring.
+                    assert_eq!(res.channel_end.state().clone(), State::InitUpgrade);
+                    assert!(matches!(
+                        res.upgrade,
+                        Some(UpgradeResult::Started {
+                            sequence: 1,
+                            timeout: Some(_),
+                            ..
+                        })
+                    ));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeInitChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_init: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
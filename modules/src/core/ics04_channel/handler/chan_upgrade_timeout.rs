@@ -0,0 +1,301 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTimeout`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::channel::{Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::ics04_channel::multihop::{counterparty_connection_hops, MultihopProofs};
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeTimeout,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the channel end being upgraded and validate it against the message.
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::InitUpgrade)
+        && !channel_end.state_matches(&State::TryUpgrade)
+    {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if channel_end.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&channel_end.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            channel_end.connection_hops()[0].clone(),
+        ));
+    }
+
+    // The upgrade timeout must have passed on the counterparty chain, as seen by the
+    // client at the proof height. For multi-hop channels, the counterparty is seen through
+    // the consensus state proven on the last intermediate chain.
+    let timeout = ctx.get_upgrade_timeout(&port_channel_id)?;
+
+    let client_id = conn.client_id().clone();
+    let proof_height = msg.proofs.height();
+    let (counterparty_height, consensus_state) = if channel_end.connection_hops().len() > 1 {
+        MultihopProofs::try_from(msg.proofs.object_proof())?.counterparty_consensus_state()?
+    } else {
+        (
+            proof_height,
+            ctx.client_consensus_state(&client_id, proof_height)?,
+        )
+    };
+
+    if !timeout.has_expired(&consensus_state.timestamp(), counterparty_height) {
+        return Err(Error::upgrade_timeout_not_reached(
+            timeout.timeout_height,
+            timeout.timeout_timestamp,
+        ));
+    }
+
+    // Proof verification in two steps:
+    // 1. Setup: check the counterparty channel as found in the message is the one we expect.
+    // The counterparty has not moved past the point where it could still accept this upgrade.
+
+    let expected_counterparty =
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
+
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, channel_end.connection_hops(), &msg.proofs)?;
+
+    if !(msg.counterparty_channel.state_matches(&State::Open)
+        || msg.counterparty_channel.state_matches(&State::InitUpgrade))
+        || !msg
+            .counterparty_channel
+            .counterparty_matches(&expected_counterparty)
+        || !msg
+            .counterparty_channel
+            .connection_hops_matches(&expected_connection_hops)
+    {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty channel does not match the channel being upgraded".to_string(),
+        ));
+    }
+
+    //2. Verify proofs
+    verify_channel_proofs(
+        ctx,
+        proof_height,
+        &channel_end,
+        &conn,
+        &msg.counterparty_channel,
+        &msg.proofs,
+    )?;
+
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)?;
+    let restore_channel = ctx.get_upgrade_restore_channel(&port_channel_id)?;
+
+    output.log("success: channel upgrade timeout ");
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: restore_channel.connection_hops()[0].clone(),
+        counterparty_port_id: restore_channel.counterparty().port_id().clone(),
+        counterparty_channel_id: restore_channel.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+
+    // The channel end that was replaced by the upgrade is restored.
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end: restore_channel,
+        upgrade: Some(UpgradeResult::Aborted {
+            error_receipt: ErrorReceipt::new(sequence, "upgrade timed out".to_string()),
+        }),
+    };
+
+    output.emit(IbcEvent::UpgradeTimeoutChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::test_util::get_dummy_raw_msg_chan_upgrade_timeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics04_channel::upgrade::UpgradeTimeout;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    #[test]
+    fn chan_upgrade_timeout_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg = MsgChannelUpgradeTimeout::try_from(get_dummy_raw_msg_chan_upgrade_timeout(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        // The channel being upgraded, and the channel end it replaced.
+        let restore_chan_end = msg.counterparty_channel.clone();
+        let mut init_upgrade_chan_end = msg.counterparty_channel.clone();
+        init_upgrade_chan_end.set_state(State::InitUpgrade);
+        init_upgrade_chan_end.set_version("ics20-2".to_string().into());
+
+        let expired_timeout = UpgradeTimeout::new(
+            TimeoutHeight::At(Height::new(0, 1).unwrap()),
+            Timestamp::none(),
+        )
+        .unwrap();
+
+        let pending_timeout =
+            UpgradeTimeout::new(TimeoutHeight::At(proof_height.add(100)), Timestamp::none())
+                .unwrap();
+
+        let context = context
+            .with_client(&client_id, proof_height)
+            .with_connection(conn_id, conn_end)
+            .with_channel(
+                msg.port_id.clone(),
+                msg.channel_id.clone(),
+                init_upgrade_chan_end,
+            );
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because no upgrade timeout is stored".to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    1,
+                    None,
+                    restore_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeTimeout(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the upgrade timeout has not been reached"
+                    .to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    1,
+                    Some(pending_timeout),
+                    restore_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeTimeout(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context.with_channel_upgrade(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    1,
+                    Some(expired_timeout),
+                    restore_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeTimeout(msg),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = channel_dispatch(&test.ctx, &test.msg);
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok((proto_output, res)) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_timeout: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let proto_output = proto_output.with_result(());
+                    assert!(!proto_output.events.is_empty()); // Some events must exist.
+
+                    // The channel end from before the upgrade is restored.
+                    assert_eq!(res.channel_end, restore_chan_end);
+                    assert!(matches!(
+                        res.upgrade,
+                        Some(UpgradeResult::Aborted { ref error_receipt }) if error_receipt.sequence == 1
+                    ));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeTimeoutChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_timeout: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
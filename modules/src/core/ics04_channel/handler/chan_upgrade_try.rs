@@ -0,0 +1,382 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTry`.
+use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics04_channel::channel::{Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::Attributes;
+use crate::core::ics04_channel::handler::verify::{
+    verify_channel_proofs, verify_channel_upgrade_proof,
+};
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, UpgradeResult};
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
+use crate::core::ics04_channel::upgrade::upgrade_sequence_store_bytes;
+use crate::core::ics24_host::path::ChannelUpgradePath;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeTry,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+
+    // Retrieve the current channel end. It is either open, or in state `InitUpgrade` if
+    // both chains initiated an upgrade at the same time (crossing hellos).
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::Open) && !channel_end.state_matches(&State::InitUpgrade) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            *channel_end.state(),
+        ));
+    }
+
+    let proposed = &msg.proposed_upgrade_channel;
+
+    if !proposed.state_matches(&State::TryUpgrade) {
+        return Err(Error::invalid_upgrade_channel(format!(
+            "expected state {}, got {}",
+            State::TryUpgrade,
+            proposed.state()
+        )));
+    }
+
+    if !proposed.counterparty_matches(channel_end.counterparty()) {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty of the channel cannot be changed".to_string(),
+        ));
+    }
+
+    // Both ends must agree on the ordering of the upgraded channel.
+    if !proposed.order_matches(msg.counterparty_channel.ordering()) {
+        return Err(Error::invalid_upgrade_channel(format!(
+            "ordering {} does not match the counterparty ordering {}",
+            proposed.ordering(),
+            msg.counterparty_channel.ordering()
+        )));
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if proposed.connection_hops().is_empty() {
+        return Err(Error::empty_connection_hops());
+    }
+
+    let conn = ctx.connection_end(&proposed.connection_hops()[0])?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(
+            proposed.connection_hops()[0].clone(),
+        ));
+    }
+
+    // The upgrade timeout is checked against the host chain.
+    if msg
+        .timeout
+        .has_expired(&ctx.host_timestamp(), ctx.host_height())
+    {
+        return Err(Error::upgrade_timeout_elapsed(
+            msg.timeout.timeout_height,
+            msg.timeout.timeout_timestamp,
+        ));
+    }
+
+    // In case of crossing hellos both ends must be upgrading with the same sequence,
+    // otherwise the counterparty must be attempting a newer upgrade than the last one seen here.
+    let sequence = ctx.get_upgrade_sequence(&port_channel_id)?;
+    let restore_channel = if channel_end.state_matches(&State::InitUpgrade) {
+        if msg.counterparty_sequence != sequence {
+            return Err(Error::upgrade_sequence_mismatch(
+                sequence,
+                msg.counterparty_sequence,
+            ));
+        }
+        ctx.get_upgrade_restore_channel(&port_channel_id)?
+    } else {
+        if msg.counterparty_sequence <= sequence {
+            return Err(Error::upgrade_sequence_mismatch(
+                sequence + 1,
+                msg.counterparty_sequence,
+            ));
+        }
+        channel_end.clone()
+    };
+
+    // Proof verification in two steps:
+    // 1. Setup: check the counterparty channel as found in the message is the one we expect.
+
+    let expected_counterparty =
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
+
+    let expected_connection_hops =
+        counterparty_connection_hops(&conn, proposed.connection_hops(), &msg.proofs)?;
+
+    if !msg.counterparty_channel.state_matches(&State::InitUpgrade)
+        || !msg
+            .counterparty_channel
+            .counterparty_matches(&expected_counterparty)
+        || !msg
+            .counterparty_channel
+            .connection_hops_matches(&expected_connection_hops)
+    {
+        return Err(Error::invalid_upgrade_channel(
+            "the counterparty channel does not match the channel being upgraded".to_string(),
+        ));
+    }
+
+    // 2. Verify proofs of the counterparty channel end, upgrade timeout and upgrade sequence.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        proposed,
+        &conn,
+        &msg.counterparty_channel,
+        &msg.proofs,
+    )?;
+
+    let counterparty_port_channel_id = (
+        proposed.counterparty().port_id().clone(),
+        proposed
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or_else(Error::missing_channel_id)?,
+    );
+
+    verify_channel_upgrade_proof(
+        ctx,
+        msg.proofs.height(),
+        &conn,
+        proposed.connection_hops(),
+        &msg.proof_upgrade_timeout,
+        ChannelUpgradePath::Timeout(
+            counterparty_port_channel_id.0.clone(),
+            counterparty_port_channel_id.1.clone(),
+        ),
+        msg.timeout.to_store_bytes(),
+    )?;
+
+    verify_channel_upgrade_proof(
+        ctx,
+        msg.proofs.height(),
+        &conn,
+        proposed.connection_hops(),
+        &msg.proof_upgrade_sequence,
+        ChannelUpgradePath::Sequence(
+            counterparty_port_channel_id.0,
+            counterparty_port_channel_id.1,
+        ),
+        upgrade_sequence_store_bytes(msg.counterparty_sequence),
+    )?;
+
+    output.log("success: channel upgrade try ");
+
+    let result = ChannelResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end: proposed.clone(),
+        upgrade: Some(UpgradeResult::Started {
+            sequence: msg.counterparty_sequence,
+            timeout: None,
+            restore_channel,
+        }),
+    };
+
+    let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
+        channel_id: Some(msg.channel_id.clone()),
+        connection_id: proposed.connection_hops()[0].clone(),
+        counterparty_port_id: proposed.counterparty().port_id().clone(),
+        counterparty_channel_id: proposed.counterparty().channel_id().cloned(),
+        height: ctx.host_height(),
+    };
+    output.emit(IbcEvent::UpgradeTryChannel(
+        event_attributes
+            .try_into()
+            .map_err(|_| Error::missing_channel_id())?,
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::{channel_dispatch, UpgradeResult};
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::test_util::get_dummy_raw_msg_chan_upgrade_try;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::ZERO_DURATION;
+
+    #[test]
+    fn chan_upgrade_try_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::default();
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let msg = MsgChannelUpgradeTry::try_from(get_dummy_raw_msg_chan_upgrade_try(
+            proof_height.revision_height(),
+            proof_height.revision_height() + 100,
+        ))
+        .unwrap();
+
+        let mut open_chan_end = msg.proposed_upgrade_channel.clone();
+        open_chan_end.set_state(State::Open);
+        open_chan_end.set_version(Default::default());
+
+        let mut init_upgrade_chan_end = msg.proposed_upgrade_channel.clone();
+        init_upgrade_chan_end.set_state(State::InitUpgrade);
+
+        let mut stale_msg = msg.clone();
+        stale_msg.counterparty_sequence = 1;
+
+        let mut expired_msg = msg.clone();
+        expired_msg.timeout.timeout_height = proof_height.decrement().unwrap().into();
+
+        let context = context
+            .with_client(&client_id, proof_height)
+            .with_connection(conn_id, conn_end);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel does not exist".to_string(),
+                ctx: context.clone(),
+                msg: ChannelMsg::ChannelUpgradeTry(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the upgrade sequence is stale".to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        open_chan_end.clone(),
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        1,
+                        None,
+                        open_chan_end.clone(),
+                    ),
+                msg: ChannelMsg::ChannelUpgradeTry(stale_msg),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the upgrade timed out".to_string(),
+                ctx: context.clone().with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    open_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeTry(expired_msg),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context.clone().with_channel(
+                    msg.port_id.clone(),
+                    msg.channel_id.clone(),
+                    open_chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeTry(msg.clone()),
+                want_pass: true,
+            },
+            Test {
+                name: "Good parameters, crossing hellos".to_string(),
+                ctx: context
+                    .with_channel(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        init_upgrade_chan_end,
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id.clone(),
+                        msg.channel_id.clone(),
+                        msg.counterparty_sequence,
+                        Some(msg.timeout),
+                        open_chan_end,
+                    ),
+                msg: ChannelMsg::ChannelUpgradeTry(msg),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = channel_dispatch(&test.ctx, &test.msg);
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok((proto_output, res)) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_try: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let proto_output = proto_output.with_result(());
+                    assert!(!proto_output.events.is_empty()); // Some events must exist.
+
+                    assert_eq!(res.channel_end.state().clone(), State::TryUpgrade);
+                    assert!(matches!(
+                        res.upgrade,
+                        Some(UpgradeResult::Started { timeout: None, .. })
+                    ));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeTryChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_try: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -82,6 +82,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
         msg.proofs.height(),
         packet,
         &connection_end,
+        dest_channel_end.connection_hops(),
         &msg.proofs,
     )?;
 
@@ -57,7 +57,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
     // check that timeout height or timeout timestamp has passed on the other end
     let proof_height = msg.proofs.height();
 
-    if packet.timeout_height.has_expired(proof_height) {
+    if !packet.timeout_height.has_expired(proof_height) {
         return Err(Error::packet_timeout_height_not_reached(
             packet.timeout_height,
             proof_height,
@@ -103,6 +103,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             ctx,
             msg.proofs.height(),
             &connection_end,
+            source_channel_end.connection_hops(),
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs,
@@ -120,6 +121,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             ctx,
             msg.proofs.height(),
             &connection_end,
+            source_channel_end.connection_hops(),
             packet.clone(),
             &msg.proofs,
         )?;
@@ -175,7 +177,7 @@ mod tests {
         let context = MockContext::default();
 
         let msg_proof_height = 2;
-        let msg_timeout_height = 5;
+        let msg_timeout_height = 1;
         let timeout_timestamp = 5;
 
         let client_height = Height::new(0, 2).unwrap();
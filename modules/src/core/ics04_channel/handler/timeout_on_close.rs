@@ -6,6 +6,7 @@ use crate::core::ics04_channel::handler::verify::{
     verify_next_sequence_recv, verify_packet_receipt_absence,
 };
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+use crate::core::ics04_channel::multihop::counterparty_connection_hops;
 use crate::core::ics04_channel::packet::PacketResult;
 use crate::core::ics04_channel::{
     context::ChannelReader, error::Error, handler::timeout::TimeoutPacketResult,
@@ -60,12 +61,11 @@ pub fn process(
         Some(packet.source_channel.clone()),
     );
 
-    let counterparty = connection_end.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Error::undefined_connection_counterparty(source_channel_end.connection_hops()[0].clone())
-    })?;
-
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops = counterparty_connection_hops(
+        &connection_end,
+        source_channel_end.connection_hops(),
+        &msg.proofs,
+    )?;
 
     let expected_channel_end = ChannelEnd::new(
         State::Closed,
@@ -95,6 +95,7 @@ pub fn process(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            source_channel_end.connection_hops(),
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs,
@@ -111,6 +112,7 @@ pub fn process(
             ctx,
             msg.proofs.height(),
             &connection_end,
+            source_channel_end.connection_hops(),
             packet.clone(),
             &msg.proofs,
         )?;
@@ -1,14 +1,23 @@
-use crate::core::ics02_client::client_consensus::ConsensusState;
-use crate::core::ics02_client::client_state::ClientState;
+use core::time::Duration;
+
+use crate::core::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
+use crate::core::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::core::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
-use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::multihop::MultihopProofs;
+use crate::core::ics04_channel::packet::{Packet, Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::UpgradeTimeout;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::ChannelUpgradePath;
 use crate::prelude::*;
 use crate::proofs::Proofs;
+use crate::timestamp::Timestamp;
 use crate::Height;
 
 /// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
@@ -20,29 +29,24 @@ pub fn verify_channel_proofs(
     expected_chan: &ChannelEnd,
     proofs: &Proofs,
 ) -> Result<(), Error> {
-    // This is the client which will perform proof verification.
-    let client_id = connection_end.client_id().clone();
-
-    let client_state = ctx.client_state(&client_id)?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Error::frozen_client(client_id));
-    }
-
-    let consensus_state = ctx.client_consensus_state(&client_id, proofs.height())?;
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+    let counterparty = resolve_counterparty_proof(
+        ctx,
+        height,
+        connection_end,
+        channel_end.connection_hops(),
+        proofs.object_proof(),
+    )?;
 
     // Verify the proof for the channel state against the expected channel end.
     // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-    client_def
+    counterparty
+        .client_def
         .verify_channel_state(
-            &client_state,
-            height,
-            connection_end.counterparty().prefix(),
-            proofs.object_proof(),
-            consensus_state.root(),
+            &counterparty.client_state,
+            counterparty.height,
+            counterparty.connection_end.counterparty().prefix(),
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
             channel_end.counterparty().port_id(),
             channel_end.counterparty().channel_id().unwrap(),
             expected_chan,
@@ -56,19 +60,16 @@ pub fn verify_packet_recv_proofs(
     height: Height,
     packet: &Packet,
     connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
     proofs: &Proofs,
 ) -> Result<(), Error> {
-    let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Error::frozen_client(client_id.clone()));
-    }
-
-    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+    let counterparty = resolve_counterparty_proof(
+        ctx,
+        height,
+        connection_end,
+        connection_hops,
+        proofs.object_proof(),
+    )?;
 
     let commitment = ctx.packet_commitment(
         packet.data.clone(),
@@ -77,14 +78,15 @@ pub fn verify_packet_recv_proofs(
     );
 
     // Verify the proof for the packet against the chain store.
-    client_def
+    counterparty
+        .client_def
         .verify_packet_data(
-            ctx,
-            &client_state,
-            height,
-            connection_end,
-            proofs.object_proof(),
-            consensus_state.root(),
+            &DelayPeriodReader::new(ctx, height),
+            &counterparty.client_state,
+            counterparty.height,
+            &counterparty.connection_end,
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
             &packet.source_port,
             &packet.source_channel,
             packet.sequence,
@@ -102,31 +104,29 @@ pub fn verify_packet_acknowledgement_proofs(
     packet: &Packet,
     acknowledgement: Acknowledgement,
     connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
     proofs: &Proofs,
 ) -> Result<(), Error> {
-    let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Error::frozen_client(client_id.clone()));
-    }
-
-    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
+    let counterparty = resolve_counterparty_proof(
+        ctx,
+        height,
+        connection_end,
+        connection_hops,
+        proofs.object_proof(),
+    )?;
 
     let ack_commitment = ctx.ack_commitment(acknowledgement);
 
-    let client_def = AnyClient::from_client_type(client_state.client_type());
-
     // Verify the proof for the packet against the chain store.
-    client_def
+    counterparty
+        .client_def
         .verify_packet_acknowledgement(
-            ctx,
-            &client_state,
-            height,
-            connection_end,
-            proofs.object_proof(),
-            consensus_state.root(),
+            &DelayPeriodReader::new(ctx, height),
+            &counterparty.client_state,
+            counterparty.height,
+            &counterparty.connection_end,
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             packet.sequence,
@@ -142,31 +142,29 @@ pub fn verify_next_sequence_recv(
     ctx: &dyn ChannelReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
     packet: Packet,
     seq: Sequence,
     proofs: &Proofs,
 ) -> Result<(), Error> {
-    let client_id = connection_end.client_id();
-    let client_state = ctx.client_state(client_id)?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Error::frozen_client(client_id.clone()));
-    }
-
-    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+    let counterparty = resolve_counterparty_proof(
+        ctx,
+        height,
+        connection_end,
+        connection_hops,
+        proofs.object_proof(),
+    )?;
 
     // Verify the proof for the packet against the chain store.
-    client_def
+    counterparty
+        .client_def
         .verify_next_sequence_recv(
-            ctx,
-            &client_state,
-            height,
-            connection_end,
-            proofs.object_proof(),
-            consensus_state.root(),
+            &DelayPeriodReader::new(ctx, height),
+            &counterparty.client_state,
+            counterparty.height,
+            &counterparty.connection_end,
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             packet.sequence,
@@ -180,9 +178,99 @@ pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
     height: Height,
     connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
     packet: Packet,
     proofs: &Proofs,
 ) -> Result<(), Error> {
+    let counterparty = resolve_counterparty_proof(
+        ctx,
+        height,
+        connection_end,
+        connection_hops,
+        proofs.object_proof(),
+    )?;
+
+    // Verify the proof for the packet against the chain store.
+    counterparty
+        .client_def
+        .verify_packet_receipt_absence(
+            &DelayPeriodReader::new(ctx, height),
+            &counterparty.client_state,
+            counterparty.height,
+            &counterparty.connection_end,
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+        )
+        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
+
+    Ok(())
+}
+
+/// Entry point for verifying a proof of the channel upgrade state (upgrade sequence,
+/// upgrade timeout or error receipt) stored on the counterparty chain under `path`.
+pub fn verify_channel_upgrade_proof(
+    ctx: &dyn ChannelReader,
+    height: Height,
+    connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
+    proof: &CommitmentProofBytes,
+    path: ChannelUpgradePath,
+    expected_value: Vec<u8>,
+) -> Result<(), Error> {
+    let counterparty =
+        resolve_counterparty_proof(ctx, height, connection_end, connection_hops, proof)?;
+
+    counterparty
+        .client_def
+        .verify_channel_upgrade_state(
+            &counterparty.client_state,
+            counterparty.height,
+            counterparty.connection_end.counterparty().prefix(),
+            &counterparty.proof,
+            counterparty.consensus_state.root(),
+            &path,
+            expected_value,
+        )
+        .map_err(Error::verify_channel_upgrade_failed)
+}
+
+/// The client with which a value stored on the counterparty chain of a channel is verified,
+/// and the consensus state of the counterparty chain, at `height`, against which it is verified.
+struct CounterpartyProof {
+    client_def: AnyClient,
+    client_state: AnyClientState,
+    height: Height,
+    /// The connection end whose counterparty prefix the value is stored under. It is the
+    /// connection end of the local chain, with the delay period of the channel.
+    connection_end: ConnectionEnd,
+    consensus_state: AnyConsensusState,
+    proof: CommitmentProofBytes,
+}
+
+/// Resolves the client, consensus state and proof with which a value stored on the
+/// counterparty chain of a channel with the given `connection_hops` is verified.
+///
+/// For a single-hop channel, these are the client of `connection_end` and its consensus state
+/// at `height`, and `proof` itself. For a multi-hop channel, `proof` carries
+/// [`MultihopProofs`], which are verified hop by hop: the connection end of each hop is proven
+/// to be open on its intermediate chain, along with the client state and consensus state of the
+/// next chain held by the client of that connection. The proofs stored on an intermediate chain
+/// are verified with the client state and the consensus state proven on the previous chain, at
+/// the height of that consensus state. The value is then verified with the client state and
+/// consensus state proven on the last intermediate chain, under the commitment prefix of the
+/// counterparty of the last hop. The delay period of such a channel is the longest delay period
+/// of its connections, and is enforced by the local client.
+fn resolve_counterparty_proof(
+    ctx: &dyn ChannelReader,
+    height: Height,
+    connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
+    proof: &CommitmentProofBytes,
+) -> Result<CounterpartyProof, Error> {
+    // This is the client which will perform proof verification.
     let client_id = connection_end.client_id();
     let client_state = ctx.client_state(client_id)?;
 
@@ -191,24 +279,412 @@ pub fn verify_packet_receipt_absence(
         return Err(Error::frozen_client(client_id.clone()));
     }
 
-    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
+    let consensus_state = ctx.client_consensus_state(client_id, height)?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    // Verify the proof for the packet against the chain store.
-    client_def
-        .verify_packet_receipt_absence(
-            ctx,
-            &client_state,
+    if connection_hops.len() <= 1 {
+        return Ok(CounterpartyProof {
+            client_def,
+            client_state,
             height,
-            connection_end,
-            proofs.object_proof(),
-            consensus_state.root(),
-            &packet.destination_port,
-            &packet.destination_channel,
-            packet.sequence,
+            connection_end: connection_end.clone(),
+            consensus_state,
+            proof: proof.clone(),
+        });
+    }
+
+    let multihop_proofs = MultihopProofs::try_from(proof)?;
+    multihop_proofs.validate_hops(connection_hops)?;
+
+    let mut hop_client = CounterpartyProof {
+        client_def,
+        client_state,
+        height,
+        connection_end: connection_end.clone(),
+        consensus_state,
+        proof: multihop_proofs.key_proof.clone(),
+    };
+    let mut delay_period = connection_end.delay_period();
+
+    for (hop, ((connection_proof, client_state_proof), consensus_proof)) in multihop_proofs
+        .connection_proofs
+        .iter()
+        .zip(multihop_proofs.client_state_proofs.iter())
+        .zip(multihop_proofs.consensus_proofs.iter())
+        .enumerate()
+    {
+        let hop_number = hop + 1;
+        let hop_connection_end = &connection_proof.connection_end;
+
+        if !hop_connection_end.is_open() {
+            return Err(Error::connection_not_open(
+                connection_proof.connection_id.clone(),
+            ));
+        }
+
+        if hop_connection_end.client_id() != &client_state_proof.client_id
+            || hop_connection_end.client_id() != &consensus_proof.client_id
+        {
+            return Err(Error::invalid_multihop_proof(format!(
+                "client and consensus states of hop {} are not held by the client {} of connection {}",
+                hop_number,
+                hop_connection_end.client_id(),
+                connection_proof.connection_id
+            )));
+        }
+
+        // The proofs are stored on the intermediate chain, under the prefix of the counterparty
+        // of the previous hop, and verified with the client and consensus state of that chain.
+        let prefix = hop_client.connection_end.counterparty().prefix().clone();
+        let root = hop_client.consensus_state.root();
+
+        hop_client
+            .client_def
+            .verify_connection_state(
+                &hop_client.client_state,
+                hop_client.height,
+                &prefix,
+                &connection_proof.proof,
+                root,
+                &connection_proof.connection_id,
+                hop_connection_end,
+            )
+            .map_err(|e| Error::verify_multihop_proof_failed(hop_number, e))?;
+
+        hop_client
+            .client_def
+            .verify_client_full_state(
+                &hop_client.client_state,
+                hop_client.height,
+                &prefix,
+                &client_state_proof.proof,
+                root,
+                &client_state_proof.client_id,
+                &client_state_proof.client_state,
+            )
+            .map_err(|e| Error::verify_multihop_proof_failed(hop_number, e))?;
+
+        hop_client
+            .client_def
+            .verify_client_consensus_state(
+                &hop_client.client_state,
+                hop_client.height,
+                &prefix,
+                &consensus_proof.proof,
+                root,
+                &consensus_proof.client_id,
+                consensus_proof.consensus_height,
+                &consensus_proof.consensus_state,
+            )
+            .map_err(|e| Error::verify_multihop_proof_failed(hop_number, e))?;
+
+        // The next chain is tracked by the client proven on this one, which must hold the
+        // consensus state proven above.
+        let next_client_state = client_state_proof.client_state.clone();
+
+        if next_client_state.is_frozen() {
+            return Err(Error::frozen_client(client_state_proof.client_id.clone()));
+        }
+
+        if consensus_proof.consensus_height > next_client_state.latest_height() {
+            return Err(Error::invalid_multihop_proof(format!(
+                "consensus height {} of hop {} is higher than the latest height {} of client {}",
+                consensus_proof.consensus_height,
+                hop_number,
+                next_client_state.latest_height(),
+                client_state_proof.client_id
+            )));
+        }
+
+        hop_client.client_def = AnyClient::from_client_type(next_client_state.client_type());
+        hop_client.client_state = next_client_state;
+        hop_client.height = consensus_proof.consensus_height;
+        hop_client.consensus_state = consensus_proof.consensus_state.clone();
+        hop_client.connection_end = hop_connection_end.clone();
+        delay_period = delay_period.max(hop_connection_end.delay_period());
+    }
+
+    // The delay period is enforced by the local client, so the resulting connection end keeps
+    // the local client id.
+    hop_client.connection_end = ConnectionEnd::new(
+        *connection_end.state(),
+        connection_end.client_id().clone(),
+        hop_client.connection_end.counterparty().clone(),
+        connection_end.versions().to_vec(),
+        delay_period,
+    );
+
+    Ok(hop_client)
+}
+
+/// The context with which the proofs of packets are verified.
+///
+/// The delay period of a channel is enforced by the local client, from the time and height at
+/// which it processed the consensus state of the adjacent chain at the proof height. For a
+/// multi-hop channel, the packet proofs are verified against a consensus state of the
+/// counterparty chain at another height, so the processed time and height of the local client
+/// are looked up at the proof height instead.
+struct DelayPeriodReader<'a> {
+    ctx: &'a dyn ChannelReader,
+    proof_height: Height,
+}
+
+impl<'a> DelayPeriodReader<'a> {
+    fn new(ctx: &'a dyn ChannelReader, proof_height: Height) -> Self {
+        Self { ctx, proof_height }
+    }
+}
+
+impl ChannelReader for DelayPeriodReader<'_> {
+    fn channel_end(&self, port_channel_id: &(PortId, ChannelId)) -> Result<ChannelEnd, Error> {
+        self.ctx.channel_end(port_channel_id)
+    }
+
+    fn connection_end(&self, connection_id: &ConnectionId) -> Result<ConnectionEnd, Error> {
+        self.ctx.connection_end(connection_id)
+    }
+
+    fn connection_channels(&self, cid: &ConnectionId) -> Result<Vec<(PortId, ChannelId)>, Error> {
+        self.ctx.connection_channels(cid)
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, Error> {
+        self.ctx.client_state(client_id)
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Error> {
+        self.ctx.client_consensus_state(client_id, height)
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Error> {
+        self.ctx.get_next_sequence_send(port_channel_id)
+    }
+
+    fn get_next_sequence_recv(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Error> {
+        self.ctx.get_next_sequence_recv(port_channel_id)
+    }
+
+    fn get_next_sequence_ack(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Error> {
+        self.ctx.get_next_sequence_ack(port_channel_id)
+    }
+
+    fn get_packet_commitment(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<PacketCommitment, Error> {
+        self.ctx.get_packet_commitment(key)
+    }
+
+    fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Result<Receipt, Error> {
+        self.ctx.get_packet_receipt(key)
+    }
+
+    fn get_packet_acknowledgement(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<AcknowledgementCommitment, Error> {
+        self.ctx.get_packet_acknowledgement(key)
+    }
+
+    fn get_upgrade_sequence(&self, port_channel_id: &(PortId, ChannelId)) -> Result<u64, Error> {
+        self.ctx.get_upgrade_sequence(port_channel_id)
+    }
+
+    fn get_upgrade_timeout(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<UpgradeTimeout, Error> {
+        self.ctx.get_upgrade_timeout(port_channel_id)
+    }
+
+    fn get_upgrade_restore_channel(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ChannelEnd, Error> {
+        self.ctx.get_upgrade_restore_channel(port_channel_id)
+    }
+
+    fn hash(&self, value: Vec<u8>) -> Vec<u8> {
+        self.ctx.hash(value)
+    }
+
+    fn host_height(&self) -> Height {
+        self.ctx.host_height()
+    }
+
+    fn host_timestamp(&self) -> Timestamp {
+        self.ctx.host_timestamp()
+    }
+
+    fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Error> {
+        self.ctx.host_consensus_state(height)
+    }
+
+    fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Error> {
+        self.ctx.pending_host_consensus_state()
+    }
+
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        _height: Height,
+    ) -> Result<Timestamp, Error> {
+        self.ctx.client_update_time(client_id, self.proof_height)
+    }
+
+    fn client_update_height(&self, client_id: &ClientId, _height: Height) -> Result<Height, Error> {
+        self.ctx.client_update_height(client_id, self.proof_height)
+    }
+
+    fn channel_counter(&self) -> Result<u64, Error> {
+        self.ctx.channel_counter()
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        self.ctx.max_expected_time_per_block()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::verify_packet_recv_proofs;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::multihop::test_util::get_dummy_raw_msg_multihop_proofs;
+    use crate::core::ics04_channel::multihop::MultihopProofs;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::proofs::Proofs;
+    use crate::timestamp::ZERO_DURATION;
+    use crate::Height;
+
+    fn connection_end(client_id: &str, counterparty_connection_id: &str) -> ConnectionEnd {
+        ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.parse().unwrap(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(counterparty_connection_id.parse().unwrap()),
+                b"ibc".to_vec().try_into().unwrap(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
         )
-        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
+    }
 
-    Ok(())
+    /// A 2-hop channel, from the local chain through an intermediate chain to the
+    /// counterparty chain, and the multi-hop proofs of a value stored on the counterparty.
+    struct TwoHopChannel {
+        ctx: MockContext,
+        proof_height: Height,
+        connection_end: ConnectionEnd,
+        connection_hops: Vec<ConnectionId>,
+        proofs: MultihopProofs,
+    }
+
+    impl TwoHopChannel {
+        fn new() -> Self {
+            let proof_height = Height::new(0, 10).unwrap();
+            let counterparty_height = Height::new(0, 25).unwrap();
+
+            let local_client_id: ClientId = "07-tendermint-0".parse().unwrap();
+            let ctx = MockContext::default().with_client(&local_client_id, proof_height);
+
+            let connection_hops: Vec<ConnectionId> = vec![
+                "connection-0".parse().unwrap(),
+                "connection-1".parse().unwrap(),
+            ];
+
+            let proofs = MultihopProofs::try_from(get_dummy_raw_msg_multihop_proofs(vec![(
+                connection_hops[1].clone(),
+                connection_end("07-tendermint-1", "connection-2"),
+                MockClientState::new(MockHeader::new(counterparty_height)).into(),
+                MockHeader::new(counterparty_height).into(),
+            )]))
+            .unwrap();
+
+            Self {
+                ctx,
+                proof_height,
+                connection_end: connection_end("07-tendermint-0", "connection-5"),
+                connection_hops,
+                proofs,
+            }
+        }
+
+        fn verify_packet(&self) -> Result<(), crate::core::ics04_channel::error::Error> {
+            let proofs = Proofs::new(
+                CommitmentProofBytes::try_from(self.proofs.clone()).unwrap(),
+                None,
+                None,
+                None,
+                self.proof_height,
+            )
+            .unwrap();
+
+            verify_packet_recv_proofs(
+                &self.ctx,
+                self.proof_height,
+                &Packet::default(),
+                &self.connection_end,
+                &self.connection_hops,
+                &proofs,
+            )
+        }
+    }
+
+    #[test]
+    fn two_hop_packet_proof_verifies() {
+        let channel = TwoHopChannel::new();
+
+        assert!(channel.verify_packet().is_ok());
+    }
+
+    #[test]
+    fn two_hop_proof_requires_proofs_for_the_intermediate_chain() {
+        let mut channel = TwoHopChannel::new();
+        channel.proofs.client_state_proofs.clear();
+
+        assert!(channel.verify_packet().is_err());
+    }
+
+    #[test]
+    fn two_hop_proof_rejects_client_state_of_another_client() {
+        let mut channel = TwoHopChannel::new();
+        channel.proofs.client_state_proofs[0].client_id = "07-tendermint-9".parse().unwrap();
+
+        assert!(channel.verify_packet().is_err());
+    }
+
+    #[test]
+    fn two_hop_proof_rejects_consensus_height_above_client_height() {
+        let mut channel = TwoHopChannel::new();
+        channel.proofs.consensus_proofs[0].consensus_height = Height::new(0, 26).unwrap();
+
+        assert!(channel.verify_packet().is_err());
+    }
 }
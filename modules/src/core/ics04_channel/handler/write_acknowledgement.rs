@@ -32,7 +32,7 @@ pub fn process(
 
     if !dest_channel_end.state_matches(&State::Open) {
         return Err(Error::invalid_channel_state(
-            packet.source_channel,
+            packet.destination_channel,
             dest_channel_end.state,
         ));
     }
@@ -56,8 +56,8 @@ pub fn process(
     }
 
     let result = PacketResult::WriteAck(WriteAckPacketResult {
-        port_id: packet.source_port.clone(),
-        channel_id: packet.source_channel.clone(),
+        port_id: packet.destination_port.clone(),
+        channel_id: packet.destination_channel.clone(),
         seq: packet.sequence,
         ack_commitment: ctx.ack_commitment(ack.clone().into()),
     });
@@ -88,6 +88,7 @@ mod tests {
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::core::ics04_channel::handler::write_acknowledgement::process;
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::packet::PacketResult;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
@@ -208,4 +209,57 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn write_ack_result_is_keyed_by_destination() {
+        let client_height = Height::new(0, 1).unwrap();
+
+        let mut packet: Packet = get_dummy_raw_packet(1, 6).try_into().unwrap();
+        packet.source_port = PortId::transfer();
+        packet.source_channel = ChannelId::new(7);
+
+        let dest_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(
+                packet.source_port.clone(),
+                Some(packet.source_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let ctx = MockContext::default()
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_channel(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+                dest_channel_end,
+            );
+
+        let output = process(&ctx, packet.clone(), vec![1]).unwrap();
+
+        match output.result {
+            PacketResult::WriteAck(result) => {
+                // The acknowledgement is written on the receiving end of the channel.
+                assert_eq!(result.port_id, packet.destination_port);
+                assert_eq!(result.channel_id, packet.destination_channel);
+                assert_eq!(result.seq, packet.sequence);
+            }
+            _ => panic!("expected a WriteAck result"),
+        }
+    }
 }
@@ -8,8 +8,10 @@ pub mod events;
 
 pub mod handler;
 pub mod msgs;
+pub mod multihop;
 pub mod packet;
 pub mod timeout;
+pub mod upgrade;
 
 pub mod commitment;
 mod version;
@@ -1,5 +1,5 @@
-//! Message definitions for all ICS4 domain types: channel open & close handshake datagrams, as well
-//! as packets.
+//! Message definitions for all ICS4 domain types: channel open, close & upgrade handshake datagrams,
+//! as well as packets.
 
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
@@ -9,6 +9,12 @@ use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
@@ -24,6 +30,14 @@ pub mod chan_open_try;
 pub mod chan_close_confirm;
 pub mod chan_close_init;
 
+// Upgrade handshake messages.
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
+
 // Packet specific messages.
 pub mod acknowledgement;
 pub mod recv_packet;
@@ -39,6 +53,12 @@ pub enum ChannelMsg {
     ChannelOpenConfirm(MsgChannelOpenConfirm),
     ChannelCloseInit(MsgChannelCloseInit),
     ChannelCloseConfirm(MsgChannelCloseConfirm),
+    ChannelUpgradeInit(MsgChannelUpgradeInit),
+    ChannelUpgradeTry(MsgChannelUpgradeTry),
+    ChannelUpgradeAck(MsgChannelUpgradeAck),
+    ChannelUpgradeConfirm(MsgChannelUpgradeConfirm),
+    ChannelUpgradeTimeout(MsgChannelUpgradeTimeout),
+    ChannelUpgradeCancel(MsgChannelUpgradeCancel),
 }
 
 impl ChannelMsg {
@@ -62,6 +82,24 @@ impl ChannelMsg {
             ChannelMsg::ChannelCloseConfirm(msg) => ctx
                 .lookup_module_by_port(&msg.port_id)
                 .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeInit(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeTry(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeAck(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeConfirm(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeTimeout(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
+            ChannelMsg::ChannelUpgradeCancel(msg) => ctx
+                .lookup_module_by_port(&msg.port_id)
+                .map_err(Error::ics05_port)?,
         };
         Ok(module_id)
     }
@@ -0,0 +1,546 @@
+//! Types for the proofs of values stored on the counterparty of a multi-hop channel, i.e., a
+//! channel whose connection hops go through one or more intermediate chains.
+//!
+//! For a channel with connection hops `[c_0, ..., c_n]` on chain `A`, connection `c_i` is
+//! stored on the `i`-th chain of the path (chain `A` being the `0`-th one). A value stored on the
+//! counterparty chain is proven by chaining the proofs of the intermediate chains: each of them
+//! proves the connection end of its hop, and the client state and consensus state of the next
+//! chain held by the client of that connection, starting with the consensus state that the
+//! client of `c_0` holds for the first intermediate chain. The proofs stored on each chain are
+//! verified with the client state and consensus state proven on the previous chain.
+
+use crate::prelude::*;
+
+use core::str::FromStr;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::{
+    MsgMultihopProofs as RawMsgMultihopProofs, MultihopProof as RawMultihopProof,
+};
+use ibc_proto::ibc::core::commitment::v1::MerklePath as RawMerklePath;
+
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics24_host::path::{
+    ClientConsensusStatePath, ClientStatePath, ConnectionsPath, Path,
+};
+use crate::proofs::Proofs;
+use crate::Height;
+
+/// Proof that the connection end of a hop is stored on an intermediate chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionHopProof {
+    pub connection_id: ConnectionId,
+    pub connection_end: ConnectionEnd,
+    pub proof: CommitmentProofBytes,
+}
+
+/// Proof that an intermediate chain stores the consensus state of the next chain on the path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsensusStateHopProof {
+    pub client_id: ClientId,
+    pub consensus_height: Height,
+    pub consensus_state: AnyConsensusState,
+    pub proof: CommitmentProofBytes,
+}
+
+/// Proof that an intermediate chain stores the client state of the next chain on the path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientStateHopProof {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
+    pub proof: CommitmentProofBytes,
+}
+
+/// The proofs carried, in place of a single proof, by messages for multi-hop channels.
+///
+/// The connection, client state and consensus state proofs are ordered starting from the
+/// intermediate chain adjacent to the chain verifying them, and `key_proof` proves the value
+/// itself against the root of the last consensus state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultihopProofs {
+    pub key_proof: CommitmentProofBytes,
+    pub connection_proofs: Vec<ConnectionHopProof>,
+    pub consensus_proofs: Vec<ConsensusStateHopProof>,
+    pub client_state_proofs: Vec<ClientStateHopProof>,
+}
+
+impl MultihopProofs {
+    /// Checks that there is one connection proof, one client state proof and one consensus
+    /// state proof for each intermediate chain of a channel with the given connection hops,
+    /// and that the connection proofs are for these hops.
+    pub fn validate_hops(&self, connection_hops: &[ConnectionId]) -> Result<(), Error> {
+        let intermediate_hops = connection_hops.len().saturating_sub(1);
+
+        if self.connection_proofs.len() != intermediate_hops
+            || self.client_state_proofs.len() != intermediate_hops
+            || self.consensus_proofs.len() != intermediate_hops
+        {
+            return Err(Error::invalid_multihop_proof(format!(
+                "expected {} connection, client state and consensus state proofs, got {}, {} and {}",
+                intermediate_hops,
+                self.connection_proofs.len(),
+                self.client_state_proofs.len(),
+                self.consensus_proofs.len()
+            )));
+        }
+
+        for (hop, connection_proof) in self.connection_proofs.iter().enumerate() {
+            if connection_proof.connection_id != connection_hops[hop + 1] {
+                return Err(Error::invalid_multihop_proof(format!(
+                    "proof of hop {} is for connection {}, expected {}",
+                    hop + 1,
+                    connection_proof.connection_id,
+                    connection_hops[hop + 1]
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The height and consensus state of the counterparty chain, as stored on the last
+    /// intermediate chain of the path.
+    pub fn counterparty_consensus_state(&self) -> Result<(Height, AnyConsensusState), Error> {
+        self.consensus_proofs
+            .last()
+            .map(|proof| (proof.consensus_height, proof.consensus_state.clone()))
+            .ok_or_else(|| {
+                Error::invalid_multihop_proof("missing consensus state proofs".to_string())
+            })
+    }
+
+    /// The connection hops of the channel end on the counterparty chain, i.e., the hops
+    /// of this end in reverse order, identified by their counterparty connection ids.
+    pub fn counterparty_connection_hops(
+        &self,
+        connection_end: &ConnectionEnd,
+        connection_id: &ConnectionId,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        core::iter::once((connection_id, connection_end))
+            .chain(
+                self.connection_proofs
+                    .iter()
+                    .map(|proof| (&proof.connection_id, &proof.connection_end)),
+            )
+            .rev()
+            .map(|(connection_id, connection_end)| {
+                connection_end
+                    .counterparty()
+                    .connection_id()
+                    .cloned()
+                    .ok_or_else(|| Error::undefined_connection_counterparty(connection_id.clone()))
+            })
+            .collect()
+    }
+}
+
+/// The connection hops that the counterparty channel end of a channel with the given
+/// `connection_hops` is expected to have. `connection_end` is the end of the first hop,
+/// and for multi-hop channels the others are taken from the multi-hop proofs in `proofs`.
+pub fn counterparty_connection_hops(
+    connection_end: &ConnectionEnd,
+    connection_hops: &[ConnectionId],
+    proofs: &Proofs,
+) -> Result<Vec<ConnectionId>, Error> {
+    let connection_id = connection_hops
+        .first()
+        .ok_or_else(Error::empty_connection_hops)?;
+
+    if connection_hops.len() == 1 {
+        let ccid = connection_end
+            .counterparty()
+            .connection_id()
+            .ok_or_else(|| Error::undefined_connection_counterparty(connection_id.clone()))?;
+
+        return Ok(vec![ccid.clone()]);
+    }
+
+    let multihop_proofs = MultihopProofs::try_from(proofs.object_proof())?;
+    multihop_proofs.validate_hops(connection_hops)?;
+    multihop_proofs.counterparty_connection_hops(connection_end, connection_id)
+}
+
+fn parse_key_path(key_path: Option<RawMerklePath>) -> Result<Path, Error> {
+    let key = key_path
+        .and_then(|key_path| key_path.key_path.last().cloned())
+        .ok_or_else(|| Error::invalid_multihop_proof("missing key path".to_string()))?;
+
+    Path::from_str(&key).map_err(|_| Error::invalid_multihop_proof(format!("invalid key {}", key)))
+}
+
+fn key_path(path: impl Into<Path>) -> Option<RawMerklePath> {
+    Some(RawMerklePath {
+        key_path: vec![path.into().to_string()],
+    })
+}
+
+fn proof_bytes(proof: Vec<u8>) -> Result<CommitmentProofBytes, Error> {
+    CommitmentProofBytes::try_from(proof).map_err(Error::invalid_proof)
+}
+
+impl TryFrom<RawMultihopProof> for ConnectionHopProof {
+    type Error = Error;
+
+    fn try_from(raw: RawMultihopProof) -> Result<Self, Self::Error> {
+        let connection_id = match parse_key_path(raw.key_path)? {
+            Path::Connections(ConnectionsPath(connection_id)) => connection_id,
+            path => {
+                return Err(Error::invalid_multihop_proof(format!(
+                    "expected a connection path, got {}",
+                    path
+                )))
+            }
+        };
+
+        let connection_end = ConnectionEnd::decode_vec(&raw.value)
+            .map_err(|e| Error::invalid_multihop_proof(format!("invalid connection end: {}", e)))?;
+
+        Ok(ConnectionHopProof {
+            connection_id,
+            connection_end,
+            proof: proof_bytes(raw.proof)?,
+        })
+    }
+}
+
+impl From<ConnectionHopProof> for RawMultihopProof {
+    fn from(value: ConnectionHopProof) -> Self {
+        RawMultihopProof {
+            proof: value.proof.into(),
+            value: value.connection_end.encode_vec().unwrap(),
+            key_path: key_path(ConnectionsPath(value.connection_id)),
+        }
+    }
+}
+
+impl TryFrom<RawMultihopProof> for ConsensusStateHopProof {
+    type Error = Error;
+
+    fn try_from(raw: RawMultihopProof) -> Result<Self, Self::Error> {
+        let (client_id, consensus_height) = match parse_key_path(raw.key_path)? {
+            Path::ClientConsensusState(ClientConsensusStatePath {
+                client_id,
+                epoch,
+                height,
+            }) => (
+                client_id,
+                Height::new(epoch, height).map_err(|e| {
+                    Error::invalid_multihop_proof(format!("invalid consensus height: {}", e))
+                })?,
+            ),
+            path => {
+                return Err(Error::invalid_multihop_proof(format!(
+                    "expected a consensus state path, got {}",
+                    path
+                )))
+            }
+        };
+
+        let consensus_state = AnyConsensusState::decode_vec(&raw.value).map_err(|e| {
+            Error::invalid_multihop_proof(format!("invalid consensus state: {}", e))
+        })?;
+
+        Ok(ConsensusStateHopProof {
+            client_id,
+            consensus_height,
+            consensus_state,
+            proof: proof_bytes(raw.proof)?,
+        })
+    }
+}
+
+impl From<ConsensusStateHopProof> for RawMultihopProof {
+    fn from(value: ConsensusStateHopProof) -> Self {
+        RawMultihopProof {
+            proof: value.proof.into(),
+            value: value.consensus_state.encode_vec().unwrap(),
+            key_path: key_path(ClientConsensusStatePath {
+                client_id: value.client_id,
+                epoch: value.consensus_height.revision_number(),
+                height: value.consensus_height.revision_height(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<RawMultihopProof> for ClientStateHopProof {
+    type Error = Error;
+
+    fn try_from(raw: RawMultihopProof) -> Result<Self, Self::Error> {
+        let client_id = match parse_key_path(raw.key_path)? {
+            Path::ClientState(ClientStatePath(client_id)) => client_id,
+            path => {
+                return Err(Error::invalid_multihop_proof(format!(
+                    "expected a client state path, got {}",
+                    path
+                )))
+            }
+        };
+
+        let client_state = AnyClientState::decode_vec(&raw.value)
+            .map_err(|e| Error::invalid_multihop_proof(format!("invalid client state: {}", e)))?;
+
+        Ok(ClientStateHopProof {
+            client_id,
+            client_state,
+            proof: proof_bytes(raw.proof)?,
+        })
+    }
+}
+
+impl From<ClientStateHopProof> for RawMultihopProof {
+    fn from(value: ClientStateHopProof) -> Self {
+        RawMultihopProof {
+            proof: value.proof.into(),
+            value: value.client_state.encode_vec().unwrap(),
+            key_path: key_path(ClientStatePath(value.client_id)),
+        }
+    }
+}
+
+impl Protobuf<RawMsgMultihopProofs> for MultihopProofs {}
+
+impl TryFrom<RawMsgMultihopProofs> for MultihopProofs {
+    type Error = Error;
+
+    fn try_from(raw: RawMsgMultihopProofs) -> Result<Self, Self::Error> {
+        let key_proof = raw
+            .key_proof
+            .ok_or_else(|| Error::invalid_multihop_proof("missing key proof".to_string()))?;
+
+        Ok(MultihopProofs {
+            key_proof: proof_bytes(key_proof.proof)?,
+            connection_proofs: raw
+                .connection_proofs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            consensus_proofs: raw
+                .consensus_proofs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            client_state_proofs: raw
+                .client_state_proofs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<MultihopProofs> for RawMsgMultihopProofs {
+    fn from(value: MultihopProofs) -> Self {
+        RawMsgMultihopProofs {
+            key_proof: Some(RawMultihopProof {
+                proof: value.key_proof.into(),
+                value: vec![],
+                key_path: None,
+            }),
+            connection_proofs: value
+                .connection_proofs
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            consensus_proofs: value.consensus_proofs.into_iter().map(Into::into).collect(),
+            client_state_proofs: value
+                .client_state_proofs
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+/// Multi-hop proofs are carried in the proof fields of the channel and packet messages.
+impl TryFrom<&CommitmentProofBytes> for MultihopProofs {
+    type Error = Error;
+
+    fn try_from(proof: &CommitmentProofBytes) -> Result<Self, Self::Error> {
+        let bytes: Vec<u8> = proof.clone().into();
+
+        MultihopProofs::decode_vec(&bytes)
+            .map_err(|e| Error::invalid_multihop_proof(format!("cannot decode proofs: {}", e)))
+    }
+}
+
+impl TryFrom<MultihopProofs> for CommitmentProofBytes {
+    type Error = Error;
+
+    fn try_from(proofs: MultihopProofs) -> Result<Self, Self::Error> {
+        proof_bytes(proofs.encode_vec().unwrap())
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::channel::v1::{
+        MsgMultihopProofs as RawMsgMultihopProofs, MultihopProof as RawMultihopProof,
+    };
+
+    use super::{key_path, ClientStateHopProof, ConnectionHopProof, ConsensusStateHopProof};
+    use crate::core::ics02_client::client_consensus::AnyConsensusState;
+    use crate::core::ics02_client::client_state::AnyClientState;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics24_host::identifier::ConnectionId;
+    use crate::core::ics24_host::path::ConnectionsPath;
+    use crate::test_utils::get_dummy_proof;
+
+    /// Returns dummy multi-hop proofs through the given intermediate hops, each given by the
+    /// connection end of the hop, and the client state and consensus state of the next chain
+    /// held by the client of that connection. The consensus state is the one at the latest
+    /// height of the client state.
+    pub fn get_dummy_raw_msg_multihop_proofs(
+        hops: Vec<(
+            ConnectionId,
+            ConnectionEnd,
+            AnyClientState,
+            AnyConsensusState,
+        )>,
+    ) -> RawMsgMultihopProofs {
+        let mut connection_proofs = Vec::new();
+        let mut client_state_proofs = Vec::new();
+        let mut consensus_proofs = Vec::new();
+
+        for (connection_id, connection_end, client_state, consensus_state) in hops {
+            let client_id = connection_end.client_id().clone();
+            let consensus_height = client_state.latest_height();
+
+            connection_proofs.push(RawMultihopProof::from(ConnectionHopProof {
+                connection_id,
+                connection_end,
+                proof: get_dummy_proof().try_into().unwrap(),
+            }));
+            client_state_proofs.push(RawMultihopProof::from(ClientStateHopProof {
+                client_id: client_id.clone(),
+                client_state,
+                proof: get_dummy_proof().try_into().unwrap(),
+            }));
+            consensus_proofs.push(RawMultihopProof::from(ConsensusStateHopProof {
+                client_id,
+                consensus_height,
+                consensus_state,
+                proof: get_dummy_proof().try_into().unwrap(),
+            }));
+        }
+
+        RawMsgMultihopProofs {
+            key_proof: Some(RawMultihopProof {
+                proof: get_dummy_proof(),
+                value: vec![],
+                key_path: key_path(ConnectionsPath(ConnectionId::default())),
+            }),
+            connection_proofs,
+            consensus_proofs,
+            client_state_proofs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::test_util::get_dummy_raw_msg_multihop_proofs;
+    use super::MultihopProofs;
+    use crate::core::ics02_client::client_consensus::AnyConsensusState;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::header::MockHeader;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    fn connection_end(client_id: &str, counterparty_connection_id: &str) -> ConnectionEnd {
+        ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.parse().unwrap(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(counterparty_connection_id.parse().unwrap()),
+                b"ibc".to_vec().try_into().unwrap(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+    }
+
+    #[test]
+    fn multihop_proofs_roundtrip() {
+        let height = Height::new(0, 10).unwrap();
+        let consensus_state: AnyConsensusState = MockHeader::new(height)
+            .with_timestamp(Timestamp::none())
+            .into();
+
+        let raw = get_dummy_raw_msg_multihop_proofs(vec![(
+            "connection-1".parse().unwrap(),
+            connection_end("07-tendermint-1", "connection-2"),
+            MockClientState::new(MockHeader::new(height)).into(),
+            consensus_state,
+        )]);
+
+        let proofs = MultihopProofs::try_from(raw.clone()).unwrap();
+        let proof_bytes = CommitmentProofBytes::try_from(proofs.clone()).unwrap();
+
+        assert_eq!(MultihopProofs::try_from(&proof_bytes).unwrap(), proofs);
+        assert_eq!(proofs.consensus_proofs[0].consensus_height, height);
+        assert_eq!(
+            proofs.client_state_proofs[0].client_id,
+            "07-tendermint-1".parse::<ClientId>().unwrap()
+        );
+    }
+
+    #[test]
+    fn multihop_counterparty_connection_hops() {
+        let height = Height::new(0, 10).unwrap();
+        let consensus_state: AnyConsensusState = MockHeader::new(height)
+            .with_timestamp(Timestamp::none())
+            .into();
+
+        let connection_hops: Vec<ConnectionId> = vec![
+            "connection-0".parse().unwrap(),
+            "connection-1".parse().unwrap(),
+        ];
+
+        let proofs = MultihopProofs::try_from(get_dummy_raw_msg_multihop_proofs(vec![(
+            connection_hops[1].clone(),
+            connection_end("07-tendermint-1", "connection-7"),
+            MockClientState::new(MockHeader::new(height)).into(),
+            consensus_state,
+        )]))
+        .unwrap();
+
+        assert!(proofs.validate_hops(&connection_hops).is_ok());
+        assert!(proofs.validate_hops(&connection_hops[..1]).is_err());
+
+        // The counterparty channel end goes back through the same hops, in reverse order.
+        let hops = proofs
+            .counterparty_connection_hops(
+                &connection_end("07-tendermint-0", "connection-3"),
+                &connection_hops[0],
+            )
+            .unwrap();
+
+        assert_eq!(
+            hops,
+            vec![
+                "connection-7".parse::<ConnectionId>().unwrap(),
+                "connection-3".parse::<ConnectionId>().unwrap()
+            ]
+        );
+    }
+}
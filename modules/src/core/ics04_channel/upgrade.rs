@@ -0,0 +1,193 @@
+//! Types used by the channel upgrade handshake (`ChanUpgradeInit`, `ChanUpgradeTry`,
+//! `ChanUpgradeAck`, `ChanUpgradeConfirm`, and the `ChanUpgradeTimeout`/`ChanUpgradeCancel`
+//! messages used to abort an upgrade).
+
+use crate::prelude::*;
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::{
+    ErrorReceipt as RawErrorReceipt, UpgradeTimeout as RawUpgradeTimeout,
+};
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::timestamp::{Expiry::Expired, Timestamp};
+use crate::Height;
+
+/// The timeout after which a channel upgrade handshake is aborted on the
+/// counterparty chain. At least one of the timeout height or timestamp must be set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeTimeout {
+    pub timeout_height: TimeoutHeight,
+    pub timeout_timestamp: Timestamp,
+}
+
+impl UpgradeTimeout {
+    pub fn new(timeout_height: TimeoutHeight, timeout_timestamp: Timestamp) -> Result<Self, Error> {
+        if timeout_height == TimeoutHeight::Never && timeout_timestamp == Timestamp::none() {
+            return Err(Error::invalid_upgrade_timeout());
+        }
+
+        Ok(Self {
+            timeout_height,
+            timeout_timestamp,
+        })
+    }
+
+    /// Checks whether the upgrade timeout has passed, given the latest
+    /// height and timestamp of the chain it is checked against.
+    pub fn has_expired(&self, chain_ts: &Timestamp, chain_height: Height) -> bool {
+        let height_timed_out = self.timeout_height.has_expired(chain_height);
+
+        let timestamp_timed_out = self.timeout_timestamp != Timestamp::none()
+            && chain_ts.check_expiry(&self.timeout_timestamp) == Expired;
+
+        height_timed_out || timestamp_timed_out
+    }
+
+    /// The bytes stored under the upgrade timeout path, against which proofs are verified.
+    pub fn to_store_bytes(self) -> Vec<u8> {
+        RawUpgradeTimeout::from(self).encode_to_vec()
+    }
+}
+
+impl Protobuf<RawUpgradeTimeout> for UpgradeTimeout {}
+
+impl TryFrom<RawUpgradeTimeout> for UpgradeTimeout {
+    type Error = Error;
+
+    fn try_from(raw: RawUpgradeTimeout) -> Result<Self, Self::Error> {
+        let timeout_height: TimeoutHeight = raw
+            .timeout_height
+            .try_into()
+            .map_err(|_| Error::invalid_timeout_height())?;
+
+        let timeout_timestamp = Timestamp::from_nanoseconds(raw.timeout_timestamp)
+            .map_err(Error::invalid_packet_timestamp)?;
+
+        Self::new(timeout_height, timeout_timestamp)
+    }
+}
+
+impl From<UpgradeTimeout> for RawUpgradeTimeout {
+    fn from(timeout: UpgradeTimeout) -> Self {
+        RawUpgradeTimeout {
+            timeout_height: timeout.timeout_height.into(),
+            timeout_timestamp: timeout.timeout_timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// Written by a chain when it aborts an upgrade, so that the counterparty can
+/// prove that the upgrade attempt with the given sequence failed and cancel its side.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReceipt {
+    /// The upgrade sequence that failed.
+    pub sequence: u64,
+    /// A description of the reason why the upgrade was aborted.
+    pub error: String,
+}
+
+impl ErrorReceipt {
+    pub fn new(sequence: u64, error: String) -> Self {
+        Self { sequence, error }
+    }
+
+    /// The bytes stored under the upgrade error path, against which proofs are verified.
+    pub fn to_store_bytes(&self) -> Vec<u8> {
+        RawErrorReceipt::from(self.clone()).encode_to_vec()
+    }
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+    type Error = Error;
+
+    fn try_from(raw: RawErrorReceipt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: raw.sequence,
+            error: raw.error,
+        })
+    }
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+    fn from(receipt: ErrorReceipt) -> Self {
+        RawErrorReceipt {
+            sequence: receipt.sequence,
+            error: receipt.error,
+        }
+    }
+}
+
+/// The bytes stored under the upgrade sequence path, against which proofs are verified.
+pub fn upgrade_sequence_store_bytes(sequence: u64) -> Vec<u8> {
+    sequence.to_be_bytes().to_vec()
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::channel::v1::UpgradeTimeout as RawUpgradeTimeout;
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    /// Returns a dummy `RawUpgradeTimeout`, for testing only!
+    pub fn get_dummy_raw_upgrade_timeout() -> RawUpgradeTimeout {
+        RawUpgradeTimeout {
+            timeout_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: 100,
+            }),
+            timeout_timestamp: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use ibc_proto::ibc::core::channel::v1::UpgradeTimeout as RawUpgradeTimeout;
+
+    use super::test_util::get_dummy_raw_upgrade_timeout;
+    use super::UpgradeTimeout;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    #[test]
+    fn upgrade_timeout_try_from_raw() {
+        let raw = get_dummy_raw_upgrade_timeout();
+
+        assert!(UpgradeTimeout::try_from(raw.clone()).is_ok());
+
+        // Neither height nor timestamp set
+        let no_timeout = RawUpgradeTimeout {
+            timeout_height: None,
+            timeout_timestamp: 0,
+        };
+        assert!(UpgradeTimeout::try_from(no_timeout).is_err());
+
+        // Timestamp only
+        let ts_only = RawUpgradeTimeout {
+            timeout_height: None,
+            timeout_timestamp: 10,
+        };
+        assert!(UpgradeTimeout::try_from(ts_only).is_ok());
+
+        let timeout = UpgradeTimeout::try_from(raw.clone()).unwrap();
+        assert_eq!(RawUpgradeTimeout::from(timeout), raw);
+    }
+
+    #[test]
+    fn upgrade_timeout_expiry() {
+        let timeout = UpgradeTimeout::try_from(get_dummy_raw_upgrade_timeout()).unwrap();
+
+        assert!(!timeout.has_expired(&Timestamp::none(), Height::new(0, 100).unwrap()));
+        assert!(timeout.has_expired(&Timestamp::none(), Height::new(0, 101).unwrap()));
+    }
+}
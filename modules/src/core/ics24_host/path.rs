@@ -27,6 +27,9 @@ const UPGRADED_CLIENT_STATE: &str = "upgradedClient";
 /// - The key identifying the upgraded consensus state
 const UPGRADED_CLIENT_CONSENSUS_STATE: &str = "upgradedConsState";
 
+/// The prefix of all the keys related to channel upgrades
+const CHANNEL_UPGRADES: &str = "channelUpgrades";
+
 /// The Path enum abstracts out the different sub-paths.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, From, Display)]
 pub enum Path {
@@ -44,6 +47,7 @@ pub enum Path {
     Acks(AcksPath),
     Receipts(ReceiptsPath),
     Upgrade(ClientUpgradePath),
+    ChannelUpgrade(ChannelUpgradePath),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
@@ -148,6 +152,31 @@ pub enum ClientUpgradePath {
     UpgradedClientConsensusState(u64),
 }
 
+/// Paths that are specific for channel upgrades.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum ChannelUpgradePath {
+    #[display(
+        fmt = "{}/upgradeSequence/ports/{}/channels/{}",
+        CHANNEL_UPGRADES,
+        _0,
+        _1
+    )]
+    Sequence(PortId, ChannelId),
+    #[display(
+        fmt = "{}/upgradeTimeout/ports/{}/channels/{}",
+        CHANNEL_UPGRADES,
+        _0,
+        _1
+    )]
+    Timeout(PortId, ChannelId),
+    #[display(fmt = "{}/upgradeError/ports/{}/channels/{}", CHANNEL_UPGRADES, _0, _1)]
+    Error(PortId, ChannelId),
+    /// The channel end stored before an upgrade, used to restore the channel
+    /// when the upgrade is aborted. This is kept in the private store.
+    #[display(fmt = "{}/restore/ports/{}/channels/{}", CHANNEL_UPGRADES, _0, _1)]
+    Restore(PortId, ChannelId),
+}
+
 /// Sub-paths which are not part of the specification, but are still
 /// useful to represent for parsing purposes.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -159,7 +188,12 @@ enum SubPath {
 impl Path {
     /// Indication if the path is provable.
     pub fn is_provable(&self) -> bool {
-        !matches!(&self, Path::ClientConnections(_) | Path::Ports(_))
+        !matches!(
+            &self,
+            Path::ClientConnections(_)
+                | Path::Ports(_)
+                | Path::ChannelUpgrade(ChannelUpgradePath::Restore(_, _))
+        )
     }
 
     /// into_bytes implementation
@@ -193,6 +227,7 @@ impl FromStr for Path {
             .or_else(|| parse_acks(&components))
             .or_else(|| parse_receipts(&components))
             .or_else(|| parse_upgrades(&components))
+            .or_else(|| parse_channel_upgrades(&components))
             .ok_or_else(|| PathError::parse_failure(s.to_string()))
     }
 }
@@ -207,9 +242,9 @@ fn parse_client_paths(components: &[&str]) -> Option<Path> {
         return None;
     }
 
-    let client_id = match ClientId::from_str(components[1]) {
-        Ok(s) => s,
-        Err(_) => return None,
+    let client_id = match components.get(1).map(|id| ClientId::from_str(id)) {
+        Some(Ok(s)) => s,
+        _ => return None,
     };
 
     if components.len() == 3 {
@@ -603,6 +638,44 @@ fn parse_upgrades(components: &[&str]) -> Option<Path> {
     }
 }
 
+fn parse_channel_upgrades(components: &[&str]) -> Option<Path> {
+    if components.len() != 6 {
+        return None;
+    }
+
+    let first = match components.first() {
+        Some(f) => *f,
+        None => return None,
+    };
+
+    if first != CHANNEL_UPGRADES {
+        return None;
+    }
+
+    let port = parse_ports(&components[2..=3]);
+    let channel = parse_channels(&components[4..=5]);
+
+    let port_id = if let Some(Path::Ports(PortsPath(port_id))) = port {
+        port_id
+    } else {
+        return None;
+    };
+
+    let channel_id = if let Some(SubPath::Channels(channel_id)) = channel {
+        channel_id
+    } else {
+        return None;
+    };
+
+    match components[1] {
+        "upgradeSequence" => Some(ChannelUpgradePath::Sequence(port_id, channel_id).into()),
+        "upgradeTimeout" => Some(ChannelUpgradePath::Timeout(port_id, channel_id).into()),
+        "upgradeError" => Some(ChannelUpgradePath::Error(port_id, channel_id).into()),
+        "restore" => Some(ChannelUpgradePath::Restore(port_id, channel_id).into()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -615,6 +688,13 @@ mod tests {
         assert!(invalid_path.is_err());
     }
 
+    #[test]
+    fn truncated_client_path_doesnt_parse() {
+        let invalid_path = Path::from_str("clients");
+
+        assert!(invalid_path.is_err());
+    }
+
     #[test]
     fn test_parse_client_paths_fn() {
         let path = "clients/07-tendermint-0/clientType";
@@ -1014,4 +1094,63 @@ mod tests {
             Path::Upgrade(ClientUpgradePath::UpgradedClientConsensusState(0)),
         );
     }
+
+    #[test]
+    fn test_parse_channel_upgrades_fn() {
+        let path = "channelUpgrades/upgradeSequence/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgrade(ChannelUpgradePath::Sequence(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/upgradeTimeout/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgrade(ChannelUpgradePath::Timeout(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgrade(ChannelUpgradePath::Error(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/unknown/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(parse_channel_upgrades(&components), None);
+    }
+
+    #[test]
+    fn channel_upgrade_restore_path_parses() {
+        let path = "channelUpgrades/restore/ports/defaultPort/channels/channel-0";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+
+        let path = path.unwrap();
+        assert_eq!(
+            path,
+            Path::ChannelUpgrade(ChannelUpgradePath::Restore(
+                PortId::default(),
+                ChannelId::default()
+            )),
+        );
+        assert!(!path.is_provable());
+    }
 }
@@ -29,12 +29,12 @@ pub fn validate_identifier(id: &str, min: usize, max: usize) -> Result<(), Error
     }
 
     // Check that the identifier comprises only valid characters:
-    // - Alphanumeric
+    // - ASCII alphanumeric
     // - `.`, `_`, `+`, `-`, `#`
     // - `[`, `]`, `<`, `>`
     if !id
         .chars()
-        .all(|c| c.is_alphanumeric() || VALID_SPECIAL_CHARS.contains(c))
+        .all(|c| c.is_ascii_alphanumeric() || VALID_SPECIAL_CHARS.contains(c))
     {
         return Err(Error::invalid_character(id.to_string()));
     }
@@ -167,4 +167,11 @@ mod tests {
         let id = validate_identifier("id/1", 1, 10);
         assert!(id.is_err())
     }
+
+    #[test]
+    fn parse_invalid_id_non_ascii_chars() {
+        // invalid id with non-ASCII alphanumeric chars
+        let id = validate_identifier("chännel-0", 1, 20);
+        assert!(id.is_err())
+    }
 }
@@ -79,7 +79,7 @@ impl Borrow<str> for ModuleId {
 /// Types implementing this trait are expected to implement `From<GenericAcknowledgement>`
 pub trait Acknowledgement: AsRef<[u8]> {}
 
-pub type WriteFn = dyn FnOnce(&mut dyn Any) -> Result<(), String>;
+pub type WriteFn = dyn FnOnce(&mut dyn Any, &mut ModuleOutputBuilder) -> Result<(), String>;
 
 pub enum OnRecvPacketAck {
     Nil(Box<WriteFn>),
@@ -160,13 +160,79 @@ pub trait Module: Send + Sync + AsAnyMut {
         Ok(())
     }
 
+    /// Called when this chain proposes an upgrade of the channel. Returns the version that
+    /// the application wants to use on the upgraded channel. Applications must opt in to
+    /// channel upgrades, so by default the upgrade is rejected.
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_upgrade_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<Version, Error> {
+        Err(Error::module_upgrade_not_supported(port_id.clone()))
+    }
+
+    /// Called when the counterparty proposed an upgrade of the channel. Returns the version
+    /// that the application agrees to use on the upgraded channel; an error aborts the upgrade.
+    /// By default the upgrade is rejected.
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_upgrade_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+        _counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Err(Error::module_upgrade_not_supported(port_id.clone()))
+    }
+
+    /// Called when the counterparty accepted the upgrade proposed by this chain. An error
+    /// aborts the upgrade.
+    fn on_chan_upgrade_ack(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_upgrade_confirm(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when an upgrade is aborted and the channel is restored to its state before
+    /// the upgrade, so that the application can restore its own state as well.
+    fn on_chan_upgrade_restore(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) {
+    }
+
     fn on_recv_packet(
         &self,
         _output: &mut ModuleOutputBuilder,
         _packet: &Packet,
         _relayer: &Signer,
     ) -> OnRecvPacketAck {
-        OnRecvPacketAck::Nil(Box::new(|_| Ok(())))
+        OnRecvPacketAck::Nil(Box::new(|_, _| Ok(())))
     }
 
     fn on_acknowledgement_packet(
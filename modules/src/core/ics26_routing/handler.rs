@@ -6,7 +6,7 @@ use crate::core::ics02_client::handler::dispatch as ics2_msg_dispatcher;
 use crate::core::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
 use crate::core::ics04_channel::handler::{
     channel_callback as ics4_callback, channel_dispatch as ics4_msg_dispatcher,
-    channel_validate as ics4_validate, recv_packet::RecvPacketResult,
+    channel_validate as ics4_validate, recv_packet::RecvPacketResult, upgrade_aborted_by_callback,
 };
 use crate::core::ics04_channel::handler::{
     get_module_for_packet_msg, packet_callback as ics4_packet_callback,
@@ -18,7 +18,8 @@ use crate::core::ics26_routing::error::Error;
 use crate::core::ics26_routing::msgs::Ics26Envelope::{
     self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
 };
-use crate::{events::IbcEvent, handler::HandlerOutput};
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerOutputBuilder};
 
 /// Result of message execution - comprises of events emitted and logs entries created during the
 /// execution of a transaction message.
@@ -92,9 +93,16 @@ where
             let mut module_output = ModuleOutputBuilder::new();
             let cb_result =
                 ics4_callback(ctx, &module_id, &msg, channel_result, &mut module_output);
-            handler_builder.merge(module_output);
             let channel_result = cb_result.map_err(Error::ics04_channel)?;
 
+            // The upgrade step only succeeded if the application accepted it.
+            if upgrade_aborted_by_callback(&msg, &channel_result) {
+                handler_builder = HandlerOutputBuilder::new().with_log(vec![
+                    "channel upgrade aborted by the application".to_string(),
+                ]);
+            }
+            handler_builder.merge_module_output(module_output);
+
             // Apply any results to the host chain store.
             ctx.store_channel_result(channel_result)
                 .map_err(Error::ics04_channel)?;
@@ -113,7 +121,7 @@ where
 
             let mut module_output = ModuleOutputBuilder::new();
             let cb_result = ics4_packet_callback(ctx, &module_id, &msg, &mut module_output);
-            handler_builder.merge(module_output);
+            handler_builder.merge_module_output(module_output);
             cb_result.map_err(Error::ics04_channel)?;
 
             // Apply any results to the host chain store.
@@ -315,6 +323,7 @@ mod tests {
                 },
                 sender: msg_transfer_two.sender.clone(),
                 receiver: msg_transfer_two.receiver.clone(),
+                memo: None,
             };
             serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
         };
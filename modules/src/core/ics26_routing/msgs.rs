@@ -2,17 +2,51 @@ use crate::prelude::*;
 
 use ibc_proto::google::protobuf::Any;
 
-use crate::core::ics02_client::msgs::{create_client, update_client, upgrade_client, ClientMsg};
+use crate::applications::transfer::msgs::transfer;
+use crate::core::ics02_client::msgs::{
+    create_client, misbehavior, update_client, upgrade_client, ClientMsg,
+};
 use crate::core::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
 };
 use crate::core::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
-    chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
+    chan_open_init, chan_open_try, chan_upgrade_ack, chan_upgrade_cancel, chan_upgrade_confirm,
+    chan_upgrade_init, chan_upgrade_timeout, chan_upgrade_try, recv_packet, timeout,
+    timeout_on_close, ChannelMsg, PacketMsg,
 };
 use crate::core::ics26_routing::error::Error;
 use tendermint_proto::Protobuf;
 
+/// Type URLs of the messages which can be submitted to a host chain.
+pub const MSG_TYPE_URLS: &[&str] = &[
+    create_client::TYPE_URL,
+    update_client::TYPE_URL,
+    upgrade_client::TYPE_URL,
+    misbehavior::TYPE_URL,
+    conn_open_init::TYPE_URL,
+    conn_open_try::TYPE_URL,
+    conn_open_ack::TYPE_URL,
+    conn_open_confirm::TYPE_URL,
+    chan_open_init::TYPE_URL,
+    chan_open_try::TYPE_URL,
+    chan_open_ack::TYPE_URL,
+    chan_open_confirm::TYPE_URL,
+    chan_close_init::TYPE_URL,
+    chan_close_confirm::TYPE_URL,
+    chan_upgrade_init::TYPE_URL,
+    chan_upgrade_try::TYPE_URL,
+    chan_upgrade_ack::TYPE_URL,
+    chan_upgrade_confirm::TYPE_URL,
+    chan_upgrade_timeout::TYPE_URL,
+    chan_upgrade_cancel::TYPE_URL,
+    recv_packet::TYPE_URL,
+    acknowledgement::TYPE_URL,
+    timeout::TYPE_URL,
+    timeout_on_close::TYPE_URL,
+    transfer::TYPE_URL,
+];
+
 /// Enumeration of all messages that the local ICS26 module is capable of routing.
 #[derive(Clone, Debug)]
 pub enum Ics26Envelope {
@@ -121,6 +155,52 @@ impl TryFrom<Any> for Ics26Envelope {
                     ChannelMsg::ChannelCloseConfirm(domain_msg),
                 ))
             }
+            chan_upgrade_init::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_init::MsgChannelUpgradeInit::decode_vec(&any_msg.value)
+                        .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeInit(domain_msg),
+                ))
+            }
+            chan_upgrade_try::TYPE_URL => {
+                let domain_msg = chan_upgrade_try::MsgChannelUpgradeTry::decode_vec(&any_msg.value)
+                    .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeTry(domain_msg),
+                ))
+            }
+            chan_upgrade_ack::TYPE_URL => {
+                let domain_msg = chan_upgrade_ack::MsgChannelUpgradeAck::decode_vec(&any_msg.value)
+                    .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeAck(domain_msg),
+                ))
+            }
+            chan_upgrade_confirm::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_confirm::MsgChannelUpgradeConfirm::decode_vec(&any_msg.value)
+                        .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeConfirm(domain_msg),
+                ))
+            }
+            chan_upgrade_timeout::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_timeout::MsgChannelUpgradeTimeout::decode_vec(&any_msg.value)
+                        .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeTimeout(domain_msg),
+                ))
+            }
+            chan_upgrade_cancel::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_cancel::MsgChannelUpgradeCancel::decode_vec(&any_msg.value)
+                        .map_err(Error::malformed_message_bytes)?;
+                Ok(Ics26Envelope::Ics4ChannelMsg(
+                    ChannelMsg::ChannelUpgradeCancel(domain_msg),
+                ))
+            }
             // ICS04 packet messages
             recv_packet::TYPE_URL => {
                 let domain_msg = recv_packet::MsgRecvPacket::decode_vec(&any_msg.value)
@@ -76,7 +76,11 @@ pub enum WithBlockDataType {
     CreateClient,
     UpdateClient,
     SendPacket,
+    RecvPacket,
     WriteAck,
+    AckPacket,
+    Timeout,
+    TimeoutOnClose,
 }
 
 impl WithBlockDataType {
@@ -85,7 +89,11 @@ impl WithBlockDataType {
             WithBlockDataType::CreateClient => "create_client",
             WithBlockDataType::UpdateClient => "update_client",
             WithBlockDataType::SendPacket => "send_packet",
+            WithBlockDataType::RecvPacket => "receive_packet",
             WithBlockDataType::WriteAck => "write_acknowledgement",
+            WithBlockDataType::AckPacket => "acknowledge_packet",
+            WithBlockDataType::Timeout => "timeout_packet",
+            WithBlockDataType::TimeoutOnClose => "timeout_packet_on_close",
         }
     }
 }
@@ -111,6 +119,12 @@ const CHANNEL_OPEN_ACK_EVENT: &str = "channel_open_ack";
 const CHANNEL_OPEN_CONFIRM_EVENT: &str = "channel_open_confirm";
 const CHANNEL_CLOSE_INIT_EVENT: &str = "channel_close_init";
 const CHANNEL_CLOSE_CONFIRM_EVENT: &str = "channel_close_confirm";
+const CHANNEL_UPGRADE_INIT_EVENT: &str = "channel_upgrade_init";
+const CHANNEL_UPGRADE_TRY_EVENT: &str = "channel_upgrade_try";
+const CHANNEL_UPGRADE_ACK_EVENT: &str = "channel_upgrade_ack";
+const CHANNEL_UPGRADE_CONFIRM_EVENT: &str = "channel_upgrade_confirm";
+const CHANNEL_UPGRADE_TIMEOUT_EVENT: &str = "channel_upgrade_timeout";
+const CHANNEL_UPGRADE_CANCEL_EVENT: &str = "channel_upgrade_cancel";
 /// Packet event types
 const SEND_PACKET_EVENT: &str = "send_packet";
 const RECEIVE_PACKET_EVENT: &str = "receive_packet";
@@ -120,7 +134,7 @@ const TIMEOUT_EVENT: &str = "timeout_packet";
 const TIMEOUT_ON_CLOSE_EVENT: &str = "timeout_packet_on_close";
 
 /// Events types
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum IbcEventType {
     NewBlock,
     CreateClient,
@@ -137,6 +151,12 @@ pub enum IbcEventType {
     OpenConfirmChannel,
     CloseInitChannel,
     CloseConfirmChannel,
+    UpgradeInitChannel,
+    UpgradeTryChannel,
+    UpgradeAckChannel,
+    UpgradeConfirmChannel,
+    UpgradeTimeoutChannel,
+    UpgradeCancelChannel,
     SendPacket,
     ReceivePacket,
     WriteAck,
@@ -166,6 +186,12 @@ impl IbcEventType {
             IbcEventType::OpenConfirmChannel => CHANNEL_OPEN_CONFIRM_EVENT,
             IbcEventType::CloseInitChannel => CHANNEL_CLOSE_INIT_EVENT,
             IbcEventType::CloseConfirmChannel => CHANNEL_CLOSE_CONFIRM_EVENT,
+            IbcEventType::UpgradeInitChannel => CHANNEL_UPGRADE_INIT_EVENT,
+            IbcEventType::UpgradeTryChannel => CHANNEL_UPGRADE_TRY_EVENT,
+            IbcEventType::UpgradeAckChannel => CHANNEL_UPGRADE_ACK_EVENT,
+            IbcEventType::UpgradeConfirmChannel => CHANNEL_UPGRADE_CONFIRM_EVENT,
+            IbcEventType::UpgradeTimeoutChannel => CHANNEL_UPGRADE_TIMEOUT_EVENT,
+            IbcEventType::UpgradeCancelChannel => CHANNEL_UPGRADE_CANCEL_EVENT,
             IbcEventType::SendPacket => SEND_PACKET_EVENT,
             IbcEventType::ReceivePacket => RECEIVE_PACKET_EVENT,
             IbcEventType::WriteAck => WRITE_ACK_EVENT,
@@ -199,6 +225,12 @@ impl FromStr for IbcEventType {
             CHANNEL_OPEN_CONFIRM_EVENT => Ok(IbcEventType::OpenConfirmChannel),
             CHANNEL_CLOSE_INIT_EVENT => Ok(IbcEventType::CloseInitChannel),
             CHANNEL_CLOSE_CONFIRM_EVENT => Ok(IbcEventType::CloseConfirmChannel),
+            CHANNEL_UPGRADE_INIT_EVENT => Ok(IbcEventType::UpgradeInitChannel),
+            CHANNEL_UPGRADE_TRY_EVENT => Ok(IbcEventType::UpgradeTryChannel),
+            CHANNEL_UPGRADE_ACK_EVENT => Ok(IbcEventType::UpgradeAckChannel),
+            CHANNEL_UPGRADE_CONFIRM_EVENT => Ok(IbcEventType::UpgradeConfirmChannel),
+            CHANNEL_UPGRADE_TIMEOUT_EVENT => Ok(IbcEventType::UpgradeTimeoutChannel),
+            CHANNEL_UPGRADE_CANCEL_EVENT => Ok(IbcEventType::UpgradeCancelChannel),
             SEND_PACKET_EVENT => Ok(IbcEventType::SendPacket),
             RECEIVE_PACKET_EVENT => Ok(IbcEventType::ReceivePacket),
             WRITE_ACK_EVENT => Ok(IbcEventType::WriteAck),
@@ -234,6 +266,12 @@ pub enum IbcEvent {
     OpenConfirmChannel(ChannelEvents::OpenConfirm),
     CloseInitChannel(ChannelEvents::CloseInit),
     CloseConfirmChannel(ChannelEvents::CloseConfirm),
+    UpgradeInitChannel(ChannelEvents::UpgradeInit),
+    UpgradeTryChannel(ChannelEvents::UpgradeTry),
+    UpgradeAckChannel(ChannelEvents::UpgradeAck),
+    UpgradeConfirmChannel(ChannelEvents::UpgradeConfirm),
+    UpgradeTimeoutChannel(ChannelEvents::UpgradeTimeout),
+    UpgradeCancelChannel(ChannelEvents::UpgradeCancel),
 
     SendPacket(ChannelEvents::SendPacket),
     ReceivePacket(ChannelEvents::ReceivePacket),
@@ -280,6 +318,12 @@ impl fmt::Display for IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => write!(f, "OpenConfirmChannelEv({:?})", ev),
             IbcEvent::CloseInitChannel(ev) => write!(f, "CloseInitChannelEv({})", ev),
             IbcEvent::CloseConfirmChannel(ev) => write!(f, "CloseConfirmChannelEv({:?})", ev),
+            IbcEvent::UpgradeInitChannel(ev) => write!(f, "UpgradeInitChannelEv({:?})", ev),
+            IbcEvent::UpgradeTryChannel(ev) => write!(f, "UpgradeTryChannelEv({:?})", ev),
+            IbcEvent::UpgradeAckChannel(ev) => write!(f, "UpgradeAckChannelEv({:?})", ev),
+            IbcEvent::UpgradeConfirmChannel(ev) => write!(f, "UpgradeConfirmChannelEv({:?})", ev),
+            IbcEvent::UpgradeTimeoutChannel(ev) => write!(f, "UpgradeTimeoutChannelEv({:?})", ev),
+            IbcEvent::UpgradeCancelChannel(ev) => write!(f, "UpgradeCancelChannelEv({:?})", ev),
 
             IbcEvent::SendPacket(ev) => write!(f, "SendPacketEv({})", ev),
             IbcEvent::ReceivePacket(ev) => write!(f, "ReceivePacketEv({})", ev),
@@ -314,6 +358,12 @@ impl TryFrom<IbcEvent> for AbciEvent {
             IbcEvent::OpenConfirmChannel(event) => event.into(),
             IbcEvent::CloseInitChannel(event) => event.into(),
             IbcEvent::CloseConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeInitChannel(event) => event.into(),
+            IbcEvent::UpgradeTryChannel(event) => event.into(),
+            IbcEvent::UpgradeAckChannel(event) => event.into(),
+            IbcEvent::UpgradeConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeTimeoutChannel(event) => event.into(),
+            IbcEvent::UpgradeCancelChannel(event) => event.into(),
             IbcEvent::SendPacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::ReceivePacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::WriteAcknowledgement(event) => event.try_into().map_err(Error::channel)?,
@@ -370,6 +420,12 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.height(),
             IbcEvent::CloseInitChannel(ev) => ev.height(),
             IbcEvent::CloseConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeInitChannel(ev) => ev.height(),
+            IbcEvent::UpgradeTryChannel(ev) => ev.height(),
+            IbcEvent::UpgradeAckChannel(ev) => ev.height(),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeTimeoutChannel(ev) => ev.height(),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.height(),
             IbcEvent::SendPacket(ev) => ev.height(),
             IbcEvent::ReceivePacket(ev) => ev.height(),
             IbcEvent::WriteAcknowledgement(ev) => ev.height(),
@@ -397,11 +453,18 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.set_height(height),
             IbcEvent::CloseInitChannel(ev) => ev.set_height(height),
             IbcEvent::CloseConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeInitChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeTryChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeAckChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeTimeoutChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.set_height(height),
             IbcEvent::SendPacket(ev) => ev.set_height(height),
             IbcEvent::ReceivePacket(ev) => ev.set_height(height),
             IbcEvent::WriteAcknowledgement(ev) => ev.set_height(height),
             IbcEvent::AcknowledgePacket(ev) => ev.set_height(height),
             IbcEvent::TimeoutPacket(ev) => ev.set_height(height),
+            IbcEvent::TimeoutOnClosePacket(ev) => ev.set_height(height),
             _ => unimplemented!(),
         }
     }
@@ -423,6 +486,12 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(_) => IbcEventType::OpenConfirmChannel,
             IbcEvent::CloseInitChannel(_) => IbcEventType::CloseInitChannel,
             IbcEvent::CloseConfirmChannel(_) => IbcEventType::CloseConfirmChannel,
+            IbcEvent::UpgradeInitChannel(_) => IbcEventType::UpgradeInitChannel,
+            IbcEvent::UpgradeTryChannel(_) => IbcEventType::UpgradeTryChannel,
+            IbcEvent::UpgradeAckChannel(_) => IbcEventType::UpgradeAckChannel,
+            IbcEvent::UpgradeConfirmChannel(_) => IbcEventType::UpgradeConfirmChannel,
+            IbcEvent::UpgradeTimeoutChannel(_) => IbcEventType::UpgradeTimeoutChannel,
+            IbcEvent::UpgradeCancelChannel(_) => IbcEventType::UpgradeCancelChannel,
             IbcEvent::SendPacket(_) => IbcEventType::SendPacket,
             IbcEvent::ReceivePacket(_) => IbcEventType::ReceivePacket,
             IbcEvent::WriteAcknowledgement(_) => IbcEventType::WriteAck,
@@ -440,6 +509,12 @@ impl IbcEvent {
             IbcEvent::OpenTryChannel(ev) => Some(ev.into()),
             IbcEvent::OpenAckChannel(ev) => Some(ev.into()),
             IbcEvent::OpenConfirmChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeInitChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeTryChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeAckChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeConfirmChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeTimeoutChannel(ev) => Some(ev.into()),
+            IbcEvent::UpgradeCancelChannel(ev) => Some(ev.into()),
             _ => None,
         }
     }
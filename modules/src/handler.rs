@@ -1,4 +1,4 @@
-use crate::events::IbcEvent;
+use crate::events::{IbcEvent, ModuleEvent};
 use crate::prelude::*;
 use core::marker::PhantomData;
 
@@ -21,6 +21,9 @@ impl<T, E> HandlerOutput<T, E> {
 pub struct HandlerOutputBuilder<T, E = IbcEvent> {
     log: Vec<String>,
     events: Vec<E>,
+    /// Events of core IBC handlers which ran on behalf of a module, e.g. the `SendPacket` event
+    /// of a packet sent from a module callback. See [`Self::merge_ibc_output`].
+    ibc_events: Vec<IbcEvent>,
     marker: PhantomData<T>,
 }
 
@@ -29,6 +32,7 @@ impl<T, E> HandlerOutputBuilder<T, E> {
         Self {
             log: Vec::new(),
             events: Vec::new(),
+            ibc_events: Vec::new(),
             marker: PhantomData,
         }
     }
@@ -61,11 +65,15 @@ impl<T, E> HandlerOutputBuilder<T, E> {
 
     pub fn merge<Event: Into<E>>(&mut self, other: HandlerOutputBuilder<(), Event>) {
         let HandlerOutputBuilder {
-            mut log, events, ..
+            mut log,
+            events,
+            mut ibc_events,
+            ..
         } = other;
         self.log.append(&mut log);
         self.events
             .append(&mut events.into_iter().map(Into::into).collect());
+        self.ibc_events.append(&mut ibc_events);
     }
 
     pub fn merge_output<Event: Into<E>>(&mut self, other: HandlerOutput<(), Event>) {
@@ -77,3 +85,36 @@ impl<T, E> HandlerOutputBuilder<T, E> {
             .append(&mut events.into_iter().map(Into::into).collect());
     }
 }
+
+impl<T> HandlerOutputBuilder<T, IbcEvent> {
+    /// Merges the output of a module callback, along with the events of the core IBC handlers
+    /// which ran on its behalf.
+    pub fn merge_module_output(&mut self, other: HandlerOutputBuilder<(), ModuleEvent>) {
+        let HandlerOutputBuilder {
+            mut log,
+            events,
+            mut ibc_events,
+            ..
+        } = other;
+        self.log.append(&mut log);
+        self.events
+            .append(&mut events.into_iter().map(IbcEvent::AppModule).collect());
+        self.events.append(&mut ibc_events);
+    }
+}
+
+impl HandlerOutputBuilder<(), ModuleEvent> {
+    /// Merges the output of a core IBC handler which ran on behalf of a module, e.g. to send a
+    /// packet from a module callback.
+    pub fn merge_ibc_output(&mut self, other: HandlerOutputBuilder<()>) {
+        let HandlerOutputBuilder {
+            mut log,
+            mut events,
+            mut ibc_events,
+            ..
+        } = other;
+        self.log.append(&mut log);
+        self.ibc_events.append(&mut events);
+        self.ibc_events.append(&mut ibc_events);
+    }
+}
@@ -15,7 +15,7 @@ use crate::core::ics23_commitment::commitment::{
 };
 use crate::core::ics23_commitment::merkle::apply_prefix;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
-use crate::core::ics24_host::path::ClientConsensusStatePath;
+use crate::core::ics24_host::path::{ChannelUpgradePath, ClientConsensusStatePath};
 use crate::core::ics24_host::Path;
 use crate::mock::client_state::{MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
@@ -32,20 +32,36 @@ impl ClientDef for MockClient {
 
     fn check_header_and_update_state(
         &self,
-        _ctx: &dyn ClientReader,
-        _client_id: ClientId,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error> {
+        let consensus_state = MockConsensusState::new(header);
+
+        // Like a Tendermint client, the client may be updated to a past height for which it
+        // has no consensus state yet, and updating it again with its latest header is a no-op.
         if client_state.latest_height() >= header.height() {
-            return Err(Error::low_header_height(
-                header.height(),
-                client_state.latest_height(),
-            ));
+            match ctx.maybe_consensus_state(&client_id, header.height())? {
+                Some(AnyConsensusState::Mock(existing))
+                    if existing == consensus_state
+                        && client_state.latest_height() == header.height() =>
+                {
+                    return Ok((client_state, consensus_state));
+                }
+                Some(_) => {
+                    return Err(Error::low_header_height(
+                        header.height(),
+                        client_state.latest_height(),
+                    ));
+                }
+                None => {}
+            }
         }
+
         Ok((
-            MockClientState::new(header),
-            MockConsensusState::new(header),
+            MockClientState::new(header).with_chain_id(client_state.chain_id),
+            consensus_state,
         ))
     }
 
@@ -99,6 +115,19 @@ impl ClientDef for MockClient {
         Ok(())
     }
 
+    fn verify_channel_upgrade_state(
+        &self,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: &ChannelUpgradePath,
+        _expected_value: Vec<u8>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn verify_client_full_state(
         &self,
         _client_state: &Self::ClientState,
@@ -181,6 +210,6 @@ impl ClientDef for MockClient {
         _proof_upgrade_client: MerkleProof,
         _proof_upgrade_consensus_state: MerkleProof,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Error> {
-        Ok((*client_state, consensus_state.clone()))
+        Ok((client_state.clone(), consensus_state.clone()))
     }
 }
@@ -37,10 +37,13 @@ pub struct MockClientRecord {
 
 /// A mock of a client state. For an example of a real structure that this mocks, you can see
 /// `ClientState` of ics07_tendermint/client_state.rs.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MockClientState {
     pub header: MockHeader,
     pub frozen_height: Option<Height>,
+    /// The identifier of the chain tracked by the client, which defaults to
+    /// `ChainId::default()` for the clients of the mock contexts.
+    pub chain_id: ChainId,
 }
 
 impl Protobuf<RawMockClientState> for MockClientState {}
@@ -50,9 +53,14 @@ impl MockClientState {
         Self {
             header,
             frozen_height: None,
+            chain_id: ChainId::default(),
         }
     }
 
+    pub fn with_chain_id(self, chain_id: ChainId) -> Self {
+        Self { chain_id, ..self }
+    }
+
     pub fn latest_height(&self) -> Height {
         self.header.height()
     }
@@ -76,7 +84,7 @@ impl TryFrom<RawMockClientState> for MockClientState {
     type Error = Error;
 
     fn try_from(raw: RawMockClientState) -> Result<Self, Self::Error> {
-        Ok(Self::new(raw.header.unwrap().try_into()?))
+        Ok(Self::new(raw.header.unwrap().try_into()?).with_chain_id(ChainId::from(raw.chain_id)))
     }
 }
 
@@ -87,6 +95,7 @@ impl From<MockClientState> for RawMockClientState {
                 height: Some(value.header.height().into()),
                 timestamp: value.header.timestamp.nanoseconds(),
             }),
+            chain_id: value.chain_id.to_string(),
         }
     }
 }
@@ -95,7 +104,7 @@ impl ClientState for MockClientState {
     type UpgradeOptions = ();
 
     fn chain_id(&self) -> ChainId {
-        todo!()
+        self.chain_id.clone()
     }
 
     fn client_type(&self) -> ClientType {
@@ -30,6 +30,7 @@ use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCo
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error as Ics04Error;
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as Ics05Error;
 use crate::core::ics05_port::error::Error;
@@ -368,6 +369,35 @@ impl MockContext {
         self
     }
 
+    /// Associates an upgrade in progress with a channel of this context: the upgrade sequence,
+    /// the upgrade timeout (if this end initiated the upgrade), and the channel end to restore
+    /// if the upgrade is aborted.
+    pub fn with_channel_upgrade(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        sequence: u64,
+        timeout: Option<UpgradeTimeout>,
+        restore_channel: ChannelEnd,
+    ) -> Self {
+        let port_channel_id = (port_id, chan_id);
+        {
+            let mut ibc_store = self.ibc_store.lock().unwrap();
+            ibc_store
+                .upgrade_sequence
+                .insert(port_channel_id.clone(), sequence);
+            if let Some(timeout) = timeout {
+                ibc_store
+                    .upgrade_timeout
+                    .insert(port_channel_id.clone(), timeout);
+            }
+            ibc_store
+                .upgrade_restore_channel
+                .insert(port_channel_id, restore_channel);
+        }
+        self
+    }
+
     pub fn with_height(self, target_height: Height) -> Self {
         let latest_height = self.latest_height();
         if target_height.revision_number() > latest_height.revision_number() {
@@ -593,6 +623,18 @@ pub struct MockIbcStore {
 
     // Used by unordered channel
     pub packet_receipt: BTreeMap<(PortId, ChannelId, Sequence), Receipt>,
+
+    /// Tracks the sequence of the latest upgrade attempt of each channel.
+    pub upgrade_sequence: BTreeMap<(PortId, ChannelId), u64>,
+
+    /// The timeouts of the upgrades in progress that were initiated on this chain.
+    pub upgrade_timeout: BTreeMap<(PortId, ChannelId), UpgradeTimeout>,
+
+    /// The channel ends to restore if the upgrades in progress are aborted.
+    pub upgrade_restore_channel: BTreeMap<(PortId, ChannelId), ChannelEnd>,
+
+    /// The error receipts of the aborted upgrades.
+    pub upgrade_error_receipt: BTreeMap<(PortId, ChannelId), ErrorReceipt>,
 }
 
 #[derive(Default)]
@@ -776,6 +818,54 @@ impl ChannelReader for MockContext {
         }
     }
 
+    fn get_upgrade_sequence(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<u64, Ics04Error> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_sequence
+            .get(port_channel_id)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    fn get_upgrade_timeout(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<UpgradeTimeout, Ics04Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_timeout
+            .get(port_channel_id)
+        {
+            Some(timeout) => Ok(*timeout),
+            None => Err(Ics04Error::missing_upgrade_timeout(port_channel_id.clone())),
+        }
+    }
+
+    fn get_upgrade_restore_channel(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ChannelEnd, Ics04Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_restore_channel
+            .get(port_channel_id)
+        {
+            Some(channel_end) => Ok(channel_end.clone()),
+            None => Err(Ics04Error::missing_upgrade_restore_channel(
+                port_channel_id.clone(),
+            )),
+        }
+    }
+
     fn hash(&self, value: Vec<u8>) -> Vec<u8> {
         sha2::Sha256::digest(value).to_vec()
     }
@@ -952,6 +1042,82 @@ impl ChannelKeeper for MockContext {
         Ok(())
     }
 
+    fn store_upgrade_sequence(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        sequence: u64,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_sequence
+            .insert(port_channel_id, sequence);
+        Ok(())
+    }
+
+    fn store_upgrade_timeout(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        timeout: UpgradeTimeout,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_timeout
+            .insert(port_channel_id, timeout);
+        Ok(())
+    }
+
+    fn delete_upgrade_timeout(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_timeout
+            .remove(&port_channel_id);
+        Ok(())
+    }
+
+    fn store_upgrade_restore_channel(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_restore_channel
+            .insert(port_channel_id, channel_end.clone());
+        Ok(())
+    }
+
+    fn delete_upgrade_restore_channel(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_restore_channel
+            .remove(&port_channel_id);
+        Ok(())
+    }
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_error_receipt
+            .insert(port_channel_id, error_receipt);
+        Ok(())
+    }
+
     fn increase_channel_counter(&mut self) {
         self.ibc_store.lock().unwrap().channel_ids_counter += 1;
     }
@@ -1514,7 +1680,7 @@ mod tests {
             ) -> OnRecvPacketAck {
                 OnRecvPacketAck::Successful(
                     Box::new(MockAck::default()),
-                    Box::new(|module| {
+                    Box::new(|module, _| {
                         let module = module.downcast_mut::<FooModule>().unwrap();
                         module.counter += 1;
                         Ok(())
@@ -1581,7 +1747,11 @@ mod tests {
                 _ => None,
             })
             .for_each(|(mid, write_fn)| {
-                write_fn(ctx.router.get_route_mut(&mid).unwrap().as_any_mut()).unwrap()
+                write_fn(
+                    ctx.router.get_route_mut(&mid).unwrap().as_any_mut(),
+                    &mut ModuleOutputBuilder::new(),
+                )
+                .unwrap()
             });
     }
 }
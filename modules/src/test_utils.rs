@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use tendermint::{block, consensus, evidence, public_key::Algorithm};
 
 use crate::applications::transfer::context::{BankKeeper, Ics20Context, Ics20Keeper, Ics20Reader};
+use crate::applications::transfer::forward::{ForwardConfig, InFlightForward};
 use crate::applications::transfer::{error::Error as Ics20Error, PrefixedCoin};
 use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_state::AnyClientState;
@@ -63,11 +65,24 @@ pub fn get_dummy_bech32_account() -> String {
 #[derive(Debug)]
 pub struct DummyTransferModule {
     ibc_store: Arc<Mutex<MockIbcStore>>,
+    forward_config: Option<ForwardConfig>,
+    in_flight_forwards: BTreeMap<(PortId, ChannelId, Sequence), InFlightForward>,
 }
 
 impl DummyTransferModule {
     pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
-        Self { ibc_store }
+        Self {
+            ibc_store,
+            forward_config: None,
+            in_flight_forwards: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_forward_config(self, forward_config: ForwardConfig) -> Self {
+        Self {
+            forward_config: Some(forward_config),
+            ..self
+        }
     }
 }
 
@@ -89,6 +104,23 @@ impl Module for DummyTransferModule {
 
 impl Ics20Keeper for DummyTransferModule {
     type AccountId = Signer;
+
+    fn store_in_flight_forward(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        forward: InFlightForward,
+    ) -> Result<(), Ics20Error> {
+        self.in_flight_forwards.insert(key, forward);
+        Ok(())
+    }
+
+    fn delete_in_flight_forward(
+        &mut self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics20Error> {
+        self.in_flight_forwards.remove(key);
+        Ok(())
+    }
 }
 
 impl ChannelKeeper for DummyTransferModule {
@@ -234,6 +266,17 @@ impl Ics20Reader for DummyTransferModule {
     fn is_receive_enabled(&self) -> bool {
         true
     }
+
+    fn forward_config(&self) -> Option<ForwardConfig> {
+        self.forward_config
+    }
+
+    fn get_in_flight_forward(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Option<InFlightForward> {
+        self.in_flight_forwards.get(key).cloned()
+    }
 }
 
 impl ChannelReader for DummyTransferModule {
@@ -346,6 +389,10 @@ impl ChannelReader for DummyTransferModule {
         Height::new(0, 1).unwrap()
     }
 
+    fn host_timestamp(&self) -> Timestamp {
+        Timestamp::now()
+    }
+
     fn host_consensus_state(&self, _height: Height) -> Result<AnyConsensusState, Error> {
         unimplemented!()
     }
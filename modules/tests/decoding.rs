@@ -0,0 +1,339 @@
+//! Property-based tests of the decoding of untrusted input.
+//!
+//! A host chain embedding `ibc` decodes messages, client states, headers, denominations,
+//! identifiers and proofs out of bytes supplied by anyone able to submit a transaction,
+//! so none of these entry points may panic, whatever the input. The same entry points
+//! are exercised for longer by the `cargo-fuzz` targets in `modules/fuzz`.
+
+use core::str::FromStr;
+
+use ibc::applications::transfer::acknowledgement::Acknowledgement;
+use ibc::applications::transfer::denom::PrefixedDenom;
+use ibc::applications::transfer::forward::Forward;
+use ibc::applications::transfer::msgs::transfer::{self, MsgTransfer};
+use ibc::applications::transfer::packet::PacketData;
+use ibc::core::ics02_client::client_state::{AnyClientState, TENDERMINT_CLIENT_STATE_TYPE_URL};
+use ibc::core::ics02_client::header::{AnyHeader, Header, TENDERMINT_HEADER_TYPE_URL};
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::core::ics23_commitment::specs::ProofSpecs;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::ics24_host::path::Path;
+use ibc::core::ics24_host::validate::validate_identifier;
+use ibc::core::ics26_routing::msgs::{Ics26Envelope, MSG_TYPE_URLS};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof, MerkleRoot};
+use ibc_proto::ics23::commitment_proof::Proof;
+use ibc_proto::ics23::{CommitmentProof, ExistenceProof, InnerOp, LeafOp, NonExistenceProof};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use prost::encoding::{encode_key, encode_varint, WireType};
+
+/// Segments of the paths of the IBC store.
+const PATH_SEGMENTS: &[&str] = &[
+    "clients",
+    "clientType",
+    "clientState",
+    "connections",
+    "consensusStates",
+    "ports",
+    "channelEnds",
+    "channels",
+    "sequences",
+    "nextSequenceSend",
+    "nextSequenceRecv",
+    "nextSequenceAck",
+    "commitments",
+    "acks",
+    "receipts",
+    "upgradedIBCState",
+    "upgradedClient",
+    "upgradedConsState",
+    "channelUpgrades",
+    "upgradeSequence",
+    "upgradeTimeout",
+    "upgradeError",
+    "restore",
+];
+
+const VALID_SPECIAL_CHARS: &str = "._+-#[]<>";
+
+fn bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..max_len)
+}
+
+/// A field of a protobuf message, as found on the wire.
+#[derive(Clone, Debug)]
+enum Field {
+    Varint(u64),
+    LengthDelimited(Vec<u8>),
+}
+
+fn encode_fields(fields: Vec<(u32, Field)>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (tag, field) in fields {
+        match field {
+            Field::Varint(value) => {
+                encode_key(tag, WireType::Varint, &mut buf);
+                encode_varint(value, &mut buf);
+            }
+            Field::LengthDelimited(bytes) => {
+                encode_key(tag, WireType::LengthDelimited, &mut buf);
+                encode_varint(bytes.len() as u64, &mut buf);
+                buf.extend(bytes);
+            }
+        }
+    }
+    buf
+}
+
+/// Well-formed protobuf encodings of nested messages with arbitrary contents.
+///
+/// Uniformly random bytes are almost always rejected by the protobuf decoder, so these
+/// reach much deeper into the domain type conversions.
+fn protobuf_bytes() -> impl Strategy<Value = Vec<u8>> {
+    let leaf = prop_oneof![bytes(64), identifier().prop_map(String::into_bytes)];
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        let field = prop_oneof![
+            any::<u64>().prop_map(Field::Varint),
+            inner.prop_map(Field::LengthDelimited),
+        ];
+        vec((1u32..16, field), 0..8).prop_map(encode_fields)
+    })
+}
+
+fn message_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![bytes(512), protobuf_bytes()]
+}
+
+fn any_msg() -> impl Strategy<Value = Any> {
+    let type_url = prop_oneof![
+        4 => proptest::sample::select(MSG_TYPE_URLS).prop_map(String::from),
+        1 => any::<String>(),
+    ];
+    (type_url, message_bytes()).prop_map(|(type_url, value)| Any { type_url, value })
+}
+
+/// Strings which are often, but not always, valid identifiers.
+fn identifier() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "(client|connection|channel|07-tendermint|9999-mock)-[0-9]{1,6}",
+        "[a-zA-Z0-9._+#<>\\[\\]-]{0,70}",
+        ".{0,70}",
+    ]
+}
+
+/// Strings which are often, but not always, valid paths of the IBC store.
+fn path() -> impl Strategy<Value = String> {
+    let segment = prop_oneof![
+        proptest::sample::select(PATH_SEGMENTS).prop_map(String::from),
+        identifier(),
+        "[0-9]{1,3}(-[0-9]{1,3})?",
+    ];
+    vec(segment, 0..8).prop_map(|segments| segments.join("/"))
+}
+
+/// Strings which are often, but not always, valid (prefixed) denominations.
+fn denom() -> impl Strategy<Value = String> {
+    let hop = (identifier(), identifier()).prop_map(|(port, channel)| format!("{port}/{channel}"));
+    let prefixed = (vec(hop, 0..4), "[a-zA-Z0-9/:._-]{0,20}").prop_map(|(hops, base_denom)| {
+        hops.into_iter()
+            .chain(Some(base_denom))
+            .collect::<Vec<_>>()
+            .join("/")
+    });
+    prop_oneof![prefixed, ".{0,70}"]
+}
+
+/// Contents of JSON strings, which may not contain quotes or backslashes.
+fn json_string() -> impl Strategy<Value = String> {
+    "[^\"\\\\]{0,40}"
+}
+
+/// JSON-encoded ICS-20 packet data, possibly carrying forwarding instructions.
+fn packet_data_json() -> impl Strategy<Value = Vec<u8>> {
+    let forward = (json_string(), identifier(), identifier()).prop_map(
+        |(receiver, port, channel)| {
+            format!(
+                r#"{{\"forward\":{{\"receiver\":\"{receiver}\",\"port\":\"{port}\",\"channel\":\"{channel}\"}}}}"#
+            )
+        },
+    );
+    let memo = prop_oneof![json_string(), forward];
+    let packet_data = (
+        identifier(),
+        json_string(),
+        "[0-9]{1,80}|.{0,10}",
+        json_string(),
+        json_string(),
+        memo,
+    )
+        .prop_map(|(trace_path, base_denom, amount, sender, receiver, memo)| {
+            format!(
+                r#"{{"token":{{"denom":{{"trace_path":"{trace_path}","base_denom":"{base_denom}"}},"amount":"{amount}"}},"sender":"{sender}","receiver":"{receiver}","memo":"{memo}"}}"#
+            )
+            .into_bytes()
+        });
+    prop_oneof![packet_data, bytes(256)]
+}
+
+fn leaf_op() -> impl Strategy<Value = LeafOp> {
+    (-1i32..8, -1i32..8, -1i32..8, -1i32..10, bytes(8)).prop_map(
+        |(hash, prehash_key, prehash_value, length, prefix)| LeafOp {
+            hash,
+            prehash_key,
+            prehash_value,
+            length,
+            prefix,
+        },
+    )
+}
+
+fn inner_op() -> impl Strategy<Value = InnerOp> {
+    (-1i32..8, bytes(40), bytes(40)).prop_map(|(hash, prefix, suffix)| InnerOp {
+        hash,
+        prefix,
+        suffix,
+    })
+}
+
+fn existence_proof() -> impl Strategy<Value = ExistenceProof> {
+    (
+        bytes(32),
+        bytes(32),
+        option::of(leaf_op()),
+        vec(inner_op(), 0..4),
+    )
+        .prop_map(|(key, value, leaf, path)| ExistenceProof {
+            key,
+            value,
+            leaf,
+            path,
+        })
+}
+
+fn commitment_proof() -> impl Strategy<Value = CommitmentProof> {
+    let non_existence_proof = (
+        bytes(32),
+        option::of(existence_proof()),
+        option::of(existence_proof()),
+    )
+        .prop_map(|(key, left, right)| NonExistenceProof { key, left, right });
+
+    prop_oneof![
+        existence_proof().prop_map(|proof| Some(Proof::Exist(proof))),
+        non_existence_proof.prop_map(|proof| Some(Proof::Nonexist(proof))),
+        Just(None),
+    ]
+    .prop_map(|proof| CommitmentProof { proof })
+}
+
+proptest! {
+    #[test]
+    fn envelope_decoding_never_panics(msg in any_msg()) {
+        let _ = Ics26Envelope::try_from(msg);
+    }
+
+    #[test]
+    fn transfer_decoding_never_panics(value in message_bytes()) {
+        let _ = MsgTransfer::try_from(Any { type_url: transfer::TYPE_URL.to_string(), value });
+    }
+
+    #[test]
+    fn packet_data_decoding_never_panics(data in packet_data_json()) {
+        if let Ok(data) = serde_json::from_slice::<PacketData>(&data) {
+            let _ = Forward::from_packet_data(&data);
+        }
+        let _ = serde_json::from_slice::<Acknowledgement>(&data);
+    }
+
+    #[test]
+    fn client_state_decoding_roundtrips(value in message_bytes()) {
+        let any = Any { type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(), value };
+        if let Ok(client_state) = AnyClientState::try_from(any) {
+            let decoded = AnyClientState::try_from(Any::from(client_state.clone()));
+            prop_assert_eq!(decoded.ok(), Some(client_state));
+        }
+    }
+
+    #[test]
+    fn header_decoding_never_panics(value in message_bytes(), s in ".{0,256}") {
+        let any = Any { type_url: TENDERMINT_HEADER_TYPE_URL.to_string(), value };
+        if let Ok(header) = AnyHeader::try_from(any) {
+            let _ = header.height();
+            let _ = header.timestamp();
+        }
+        let _ = AnyHeader::decode_from_string(&s);
+    }
+
+    #[test]
+    fn prefixed_denom_parsing_roundtrips(s in denom()) {
+        if let Ok(denom) = PrefixedDenom::from_str(&s) {
+            prop_assert_eq!(denom.to_string(), s.clone());
+            prop_assert_eq!(PrefixedDenom::from_str(&denom.to_string()).ok(), Some(denom));
+        }
+    }
+
+    #[test]
+    fn identifier_validation_is_sound(id in identifier(), min in 0usize..20, extra in 0usize..80) {
+        let max = min + extra;
+        if validate_identifier(&id, min, max).is_ok() {
+            prop_assert!(!id.is_empty());
+            prop_assert!((min..=max).contains(&id.len()));
+            prop_assert!(id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || VALID_SPECIAL_CHARS.contains(c)));
+        }
+    }
+
+    #[test]
+    fn identifiers_roundtrip(id in identifier()) {
+        if let Ok(client_id) = ClientId::from_str(&id) {
+            prop_assert_eq!(client_id.to_string(), id.clone());
+        }
+        if let Ok(connection_id) = ConnectionId::from_str(&id) {
+            prop_assert_eq!(connection_id.to_string(), id.clone());
+        }
+        if let Ok(channel_id) = ChannelId::from_str(&id) {
+            prop_assert_eq!(channel_id.to_string(), id.clone());
+        }
+        if let Ok(port_id) = PortId::from_str(&id) {
+            prop_assert_eq!(port_id.to_string(), id.clone());
+        }
+        let chain_id = ChainId::from_string(&id);
+        prop_assert_eq!(chain_id.as_str(), id.as_str());
+    }
+
+    #[test]
+    fn path_parsing_never_panics(s in path()) {
+        let _ = Path::from_str(&s);
+    }
+
+    #[test]
+    fn merkle_proof_verification_never_panics(
+        proofs in vec(commitment_proof(), 0..3),
+        root in bytes(32),
+        keys in vec(".{0,16}", 0..3),
+        value in bytes(32),
+    ) {
+        let proof = MerkleProof::from(RawMerkleProof { proofs });
+        let specs = ProofSpecs::cosmos();
+        let root = MerkleRoot { hash: root };
+        let keys = MerklePath { key_path: keys };
+
+        let _ = proof.verify_membership(&specs, root.clone(), keys.clone(), value, 0);
+        let _ = proof.verify_non_membership(&specs, root, keys);
+    }
+
+    #[test]
+    fn merkle_proof_decoding_never_panics(value in message_bytes()) {
+        let raw = CommitmentProofBytes::try_from(value)
+            .ok()
+            .and_then(|bytes| RawMerkleProof::try_from(bytes).ok());
+        if let Some(raw) = raw {
+            let proof = MerkleProof::from(raw);
+            let _ = CommitmentProofBytes::try_from(proof);
+        }
+    }
+}
@@ -50,15 +50,20 @@ impl CompileCmd {
             out_dir.display()
         );
 
+        let root = env!("CARGO_MANIFEST_DIR");
+
         // Paths
         let proto_paths = [
             // ibc-go proto files
             format!("{}/proto/ibc", ibc_dir.display()),
+            // multi-hop channel proofs, not defined by ibc-go
+            format!("{}/../proto/definitions/multihop", root),
         ];
 
         let proto_includes_paths = [
             format!("{}/proto", ibc_dir.display()),
             format!("{}/third_party/proto", ibc_dir.display()),
+            format!("{}/../proto/definitions/multihop", root),
         ];
 
         // List available proto files
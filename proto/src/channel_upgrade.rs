@@ -0,0 +1,178 @@
+// Channel upgrade handshake types.
+//
+// The IBC Go commit this library is generated from does not define the channel upgrade
+// handshake yet, so these types are maintained by hand, following the layout of the
+// generated code. Remove them once the commit is bumped to one that defines them.
+
+/// UpgradeTimeout defines a type which encapsulates the upgrade timeout values at which the
+/// counterparty must no longer proceed with the upgrade handshake.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpgradeTimeout {
+    /// block height after which the upgrade times out
+    #[prost(message, optional, tag="1")]
+    pub timeout_height: ::core::option::Option<super::super::client::v1::Height>,
+    /// block timestamp (in nanoseconds) after which the upgrade times out
+    #[prost(uint64, tag="2")]
+    pub timeout_timestamp: u64,
+}
+/// ErrorReceipt defines a type which encapsulates the upgrade sequence and
+/// error associated with the upgrade handshake failure. When a channel upgrade
+/// handshake is aborted both chains are expected to increment to the next
+/// sequence.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ErrorReceipt {
+    /// the channel upgrade sequence
+    #[prost(uint64, tag="1")]
+    pub sequence: u64,
+    /// the error message detailing the cause of failure
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeInit defines the request type for the ChannelUpgradeInit rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInit {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub proposed_upgrade_channel: ::core::option::Option<Channel>,
+    #[prost(message, optional, tag="4")]
+    pub timeout_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(uint64, tag="5")]
+    pub timeout_timestamp: u64,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeInitResponse defines the MsgChannelUpgradeInit response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInitResponse {
+}
+/// MsgChannelUpgradeTry defines the request type for the ChannelUpgradeTry rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTry {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_channel: ::core::option::Option<Channel>,
+    #[prost(uint64, tag="4")]
+    pub counterparty_sequence: u64,
+    #[prost(message, optional, tag="5")]
+    pub proposed_upgrade_channel: ::core::option::Option<Channel>,
+    #[prost(message, optional, tag="6")]
+    pub timeout_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(uint64, tag="7")]
+    pub timeout_timestamp: u64,
+    #[prost(bytes="vec", tag="8")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub proof_upgrade_timeout: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="10")]
+    pub proof_upgrade_sequence: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="11")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="12")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeTryResponse defines the MsgChannelUpgradeTry response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTryResponse {
+}
+/// MsgChannelUpgradeAck defines the request type for the ChannelUpgradeAck rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAck {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_channel: ::core::option::Option<Channel>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub proof_upgrade_sequence: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="6")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="7")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeAckResponse defines the MsgChannelUpgradeAck response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAckResponse {
+}
+/// MsgChannelUpgradeConfirm defines the request type for the ChannelUpgradeConfirm rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirm {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_channel: ::core::option::Option<Channel>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeConfirmResponse defines the MsgChannelUpgradeConfirm response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirmResponse {
+}
+/// MsgChannelUpgradeTimeout defines the request type for the ChannelUpgradeTimeout rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTimeout {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_channel: ::core::option::Option<Channel>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeTimeoutResponse defines the MsgChannelUpgradeTimeout response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTimeoutResponse {
+}
+/// MsgChannelUpgradeCancel defines the request type for the ChannelUpgradeCancel rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancel {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub error_receipt: ::core::option::Option<ErrorReceipt>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_error_receipt: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeCancelResponse defines the MsgChannelUpgradeCancel response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancelResponse {
+}
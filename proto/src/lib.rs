@@ -157,6 +157,7 @@ pub mod ibc {
         pub mod channel {
             pub mod v1 {
                 include_proto!("ibc.core.channel.v1.rs");
+                include!("channel_upgrade.rs");
             }
         }
         pub mod client {
@@ -419,6 +419,35 @@ pub struct MsgAcknowledgement {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MsgAcknowledgementResponse {
 }
+/// MultihopProof holds the proof of a value stored on a chain along the
+/// connection hops of a multi-hop channel, together with the value and the
+/// path under which it is stored, relative to the chain's commitment prefix.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultihopProof {
+    #[prost(bytes="vec", tag="1")]
+    pub proof: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub value: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="3")]
+    pub key_path: ::core::option::Option<super::super::commitment::v1::MerklePath>,
+}
+/// MsgMultihopProofs holds the proofs needed to verify a value stored on the
+/// counterparty of a multi-hop channel: the proof of the value itself, and the
+/// proofs of the connection ends, client states and consensus states stored on
+/// each of the intermediate chains.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgMultihopProofs {
+    #[prost(message, optional, tag="1")]
+    pub key_proof: ::core::option::Option<MultihopProof>,
+    #[prost(message, repeated, tag="2")]
+    pub connection_proofs: ::prost::alloc::vec::Vec<MultihopProof>,
+    #[prost(message, repeated, tag="3")]
+    pub consensus_proofs: ::prost::alloc::vec::Vec<MultihopProof>,
+    #[prost(message, repeated, tag="4")]
+    pub client_state_proofs: ::prost::alloc::vec::Vec<MultihopProof>,
+}
 /// Generated client implementations.
 #[cfg(feature = "client")]
 pub mod msg_client {
@@ -9,6 +9,8 @@ pub struct Header {
 pub struct ClientState {
     #[prost(message, optional, tag="1")]
     pub header: ::core::option::Option<Header>,
+    #[prost(string, tag="2")]
+    pub chain_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConsensusState {
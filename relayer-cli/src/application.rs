@@ -173,11 +173,11 @@ impl Application for CliApp {
 
         if command.json {
             // Enable JSON by using the crate-level `Tracing`
-            let tracing = JsonTracing::new(config.global)?;
+            let tracing = JsonTracing::new(config.global, &config.tracing)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
         } else {
             // Use abscissa's tracing, which pretty-prints to the terminal obeying log levels
-            let tracing = PrettyTracing::new(config.global)?;
+            let tracing = PrettyTracing::new(config.global, &config.tracing)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
         }
     }
@@ -0,0 +1,219 @@
+use core::str::FromStr;
+use core::time::Duration;
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use http::uri::Uri;
+use tendermint_rpc::HttpClient;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc::core::ics04_channel::channel::State;
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::chain::cosmos::query::staking::{query_staking_params, unbonding_period};
+use ibc_relayer::chain::cosmos::query::status::query_max_block_interval;
+use ibc_relayer::chain::counterparty::counterparty_chain_from_channel;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{PageRequest, QueryChannelsRequest};
+use ibc_relayer::config::chain_registry::ChainMetadata;
+use ibc_relayer::config::filter::{ChannelFilters, FilterPattern};
+use ibc_relayer::config::{self as relayer_config, ChainConfig, Config, PacketFilter};
+use ibc_relayer::registry::Registry;
+
+use crate::conclude::Output;
+use crate::config::{validate_config, Diagnostic};
+use crate::prelude::*;
+
+/// Number of recent blocks inspected to estimate the `max_block_time` of a chain.
+const BLOCK_TIME_SAMPLE_SIZE: u32 = 100;
+
+/// Generate a configuration for the given chains from chain-registry style metadata,
+/// deriving the remaining settings by querying the chains themselves:
+///
+/// - `trusting_period` is set to 2/3 of the unbonding period found in the staking params,
+/// - `max_block_time` is set to twice the longest interval between recent blocks,
+/// - `packet_filter` allows the open channels between the given chains.
+#[derive(Command, Debug, Parser, PartialEq)]
+pub struct AutoCmd {
+    #[clap(
+        long = "chains",
+        required = true,
+        multiple_values = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifiers of the chains to generate a configuration for"
+    )]
+    chain_ids: Vec<ChainId>,
+
+    #[clap(
+        long = "registry",
+        required = true,
+        value_name = "REGISTRY_DIR",
+        help_heading = "REQUIRED",
+        help = "Path to a local directory of chain-registry style metadata, \
+                with one `chain.json` file per chain"
+    )]
+    registry: PathBuf,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        default_value = "testkey",
+        help = "Name of the key to use for all the chains"
+    )]
+    key_name: String,
+
+    #[clap(
+        long = "output",
+        value_name = "OUTPUT_FILE",
+        help = "Path of the file to write the configuration to; printed to stdout if omitted"
+    )]
+    output: Option<PathBuf>,
+}
+
+impl Runnable for AutoCmd {
+    fn run(&self) {
+        let config = match generate_config(self) {
+            Ok(config) => config,
+            Err(e) => Output::error(format!("failed to generate configuration: {}", e)).exit(),
+        };
+
+        match validate_config(&config) {
+            Ok(()) => {}
+            Err(Diagnostic::Warning(e)) => warn!("generated configuration: {}", e),
+            Err(Diagnostic::Error(e)) => {
+                Output::error(format!("generated configuration is invalid: {}", e)).exit()
+            }
+        }
+
+        match &self.output {
+            Some(path) => match relayer_config::store(&config, path) {
+                Ok(()) => Output::success_msg(format!(
+                    "configuration for {} chain(s) written to '{}'",
+                    config.chains.len(),
+                    path.display()
+                ))
+                .exit(),
+                Err(e) => Output::error(format!("failed to write configuration: {}", e)).exit(),
+            },
+            None => match toml::to_string_pretty(&config) {
+                Ok(toml) => Output::success_msg(toml).exit(),
+                Err(e) => Output::error(format!("failed to encode configuration: {}", e)).exit(),
+            },
+        }
+    }
+}
+
+fn generate_config(cmd: &AutoCmd) -> Result<Config, Box<dyn std::error::Error>> {
+    let rt = TokioRuntime::new()?;
+
+    let mut chains = Vec::with_capacity(cmd.chain_ids.len());
+
+    for chain_id in &cmd.chain_ids {
+        let metadata = ChainMetadata::find(&cmd.registry, chain_id)?;
+        let mut chain_config = metadata.to_chain_config(&cmd.key_name)?;
+
+        apply_chain_params(&rt, &mut chain_config)?;
+
+        chains.push(chain_config);
+    }
+
+    let mut config = Config {
+        chains,
+        ..Config::default()
+    };
+
+    let mut registry = <Registry<BaseChainHandle>>::new(config.clone());
+
+    let packet_filters = cmd
+        .chain_ids
+        .iter()
+        .map(|chain_id| channels_filter(&mut registry, chain_id, &cmd.chain_ids))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (chain_config, filter) in config.chains.iter_mut().zip(packet_filters) {
+        chain_config.packet_filter = filter;
+    }
+
+    Ok(config)
+}
+
+/// Set the `trusting_period` and `max_block_time` of the chain from its staking
+/// parameters and from the timestamps of its latest blocks.
+fn apply_chain_params(
+    rt: &TokioRuntime,
+    chain_config: &mut ChainConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let grpc_addr = Uri::from_str(&chain_config.grpc_addr.to_string())?;
+    let staking_params = rt.block_on(query_staking_params(&grpc_addr))?;
+    let unbonding_period = unbonding_period(&staking_params)?;
+
+    chain_config.trusting_period = Some(2 * unbonding_period / 3);
+
+    let rpc_client = HttpClient::new(chain_config.rpc_addr.clone())?;
+    let max_block_interval = rt.block_on(query_max_block_interval(
+        &rpc_client,
+        &chain_config.rpc_addr,
+        BLOCK_TIME_SAMPLE_SIZE,
+    ))?;
+
+    match max_block_interval {
+        Some(interval) => {
+            chain_config.max_block_time = (2 * interval).max(Duration::from_secs(1));
+        }
+        None => warn!(
+            "chain '{}': not enough blocks to estimate the block time, using the default max_block_time of {:?}",
+            chain_config.id, chain_config.max_block_time
+        ),
+    }
+
+    Ok(())
+}
+
+/// Build a packet filter allowing the open channels of the given chain whose
+/// counterparty is one of the given chains.
+///
+/// If there is no such channel, the filter allows no channel at all.
+fn channels_filter(
+    registry: &mut Registry<BaseChainHandle>,
+    chain_id: &ChainId,
+    chain_ids: &[ChainId],
+) -> Result<PacketFilter, Box<dyn std::error::Error>> {
+    let chain = registry.get_or_spawn(chain_id)?;
+
+    let channels = chain.query_channels(QueryChannelsRequest {
+        pagination: Some(PageRequest::all()),
+    })?;
+
+    let mut filters = Vec::new();
+
+    for channel in channels {
+        if !channel.channel_end.state_matches(&State::Open) {
+            continue;
+        }
+
+        match counterparty_chain_from_channel(&chain, &channel.channel_id, &channel.port_id) {
+            Ok(counterparty_chain_id) if chain_ids.contains(&counterparty_chain_id) => {
+                filters.push((
+                    FilterPattern::Exact(channel.port_id),
+                    FilterPattern::Exact(channel.channel_id),
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => warn!(
+                "chain '{}': skipping channel {}/{}: {}",
+                chain_id, channel.port_id, channel.channel_id, e
+            ),
+        }
+    }
+
+    if filters.is_empty() {
+        warn!(
+            "chain '{}': no open channel found towards the other chains, \
+             add the channels to relay on to its packet filter",
+            chain_id
+        );
+    }
+
+    Ok(PacketFilter::Allow(ChannelFilters::new(filters)))
+}
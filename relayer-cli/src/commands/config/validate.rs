@@ -2,16 +2,28 @@ use std::fs;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
+use tokio::runtime::Runtime as TokioRuntime;
 
-use crate::conclude::Output;
+use ibc_relayer::chain::cosmos::validate::{
+    validate_chain_config, validate_chain_config_live, ChainConfigReport,
+};
+
+use crate::conclude::{json, Output};
 use crate::config;
 use crate::prelude::*;
 
 /// In order to validate the configuration file the command will check that the file exists,
 /// that it is readable and not empty. It will then check the validity of the fields inside
-/// the file.
-#[derive(Command, Debug, Parser)]
-pub struct ValidateCmd {}
+/// the file, and finally check the configuration of each chain, against its live node if
+/// `--live` is given.
+#[derive(Command, Debug, Parser, PartialEq)]
+pub struct ValidateCmd {
+    #[clap(
+        long = "live",
+        help = "Also check the configuration of each chain against its live node"
+    )]
+    live: bool,
+}
 
 impl Runnable for ValidateCmd {
     /// Validate the loaded configuration.
@@ -41,9 +53,60 @@ impl Runnable for ValidateCmd {
 
         // No need to output the underlying error, this is done already when the application boots.
         // See `application::CliApp::after_config`.
-        match config::validate_config(&config) {
-            Ok(_) => Output::success("configuration is valid").exit(),
-            Err(_) => Output::error("configuration is invalid").exit(),
+        if config::validate_config(&config).is_err() {
+            Output::error("configuration is invalid").exit();
+        }
+
+        let reports: Vec<ChainConfigReport> = if self.live {
+            let rt = match TokioRuntime::new() {
+                Ok(rt) => rt,
+                Err(e) => Output::error(format!("failed to start the Tokio runtime: {}", e)).exit(),
+            };
+
+            config
+                .chains
+                .iter()
+                .map(|chain_config| rt.block_on(validate_chain_config_live(chain_config)))
+                .collect()
+        } else {
+            config.chains.iter().map(validate_chain_config).collect()
+        };
+
+        let (output, status) = if reports.iter().all(ChainConfigReport::is_valid) {
+            (Output::with_success(), "configuration is valid")
+        } else {
+            (Output::with_error(), "configuration is invalid")
+        };
+
+        if json() {
+            output.with_result(reports).exit()
+        } else {
+            let summary: String = reports.iter().map(ToString::to_string).collect();
+            output.with_msg(format!("{}\n{}", status, summary)).exit()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::ValidateCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_validate_offline() {
+        assert_eq!(
+            ValidateCmd { live: false },
+            ValidateCmd::parse_from(&["test"])
+        )
+    }
+
+    #[test]
+    fn test_validate_live() {
+        assert_eq!(
+            ValidateCmd { live: true },
+            ValidateCmd::parse_from(&["test", "--live"])
+        )
+    }
+}
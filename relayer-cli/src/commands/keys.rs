@@ -5,7 +5,10 @@ use abscissa_core::{Command, Runnable};
 mod add;
 mod balance;
 mod delete;
+mod export;
+mod import;
 mod list;
+mod rotate;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
@@ -21,4 +24,13 @@ pub enum KeysCmd {
 
     /// Query balance for a key from a configured chain. If no key is given, the key is retrieved from the configuration file.
     Balance(balance::KeyBalanceCmd),
+
+    /// Export a key from a configured chain, in the keyring format or as a raw hex private key
+    Export(export::KeysExportCmd),
+
+    /// Import a key to a configured chain from a file holding a raw hex, armored or keyring-format private key
+    Import(import::KeysImportCmd),
+
+    /// Rotate the key of a configured chain: add a new key, move the balance to it, and switch `key_name` in the config
+    Rotate(rotate::KeysRotateCmd),
 }
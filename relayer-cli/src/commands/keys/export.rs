@@ -0,0 +1,278 @@
+use core::str::FromStr;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::keyring::{KeyEntry, KeyRing, Store};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The formats in which a key can be exported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The key entry as stored in the keyring, which can be imported back with `keys import`
+    Json,
+    /// The hex-encoded raw private key, only for keys not derived from a mnemonic
+    Hex,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "hex" => Ok(Self::Hex),
+            _ => Err(format!(
+                "unknown key format '{}', expected 'json' or 'hex'",
+                s
+            )),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `keys export` CLI command.
+///
+/// `keys export --chain <CHAIN_ID> [--key-name <KEY_NAME>] [--format <FORMAT>] [--output <FILE>]`
+///
+/// If no key name is given, it will be taken from the configuration file.
+/// If no output file is given, the key is printed to stdout.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct KeysExportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "json",
+        help = "Format of the exported key: `json` for a keyring entry, `hex` for the raw private key of a key not derived from a mnemonic"
+    )]
+    format: ExportFormat,
+
+    #[clap(
+        long = "output",
+        value_name = "OUTPUT_FILE",
+        help = "Path of the file to write the key to; printed to stdout if omitted"
+    )]
+    output: Option<PathBuf>,
+}
+
+impl Runnable for KeysExportCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let key_name = self
+            .key_name
+            .clone()
+            .unwrap_or_else(|| chain_config.key_name.clone());
+
+        let key = KeyRing::new(Store::Test, &chain_config.account_prefix, &chain_config.id)
+            .and_then(|keyring| keyring.get_key(&key_name));
+
+        let key = match key {
+            Ok(key) => key,
+            Err(e) => Output::error(format!(
+                "cannot load key '{}' on chain {}: {}",
+                key_name, self.chain_id, e
+            ))
+            .exit(),
+        };
+
+        let exported = match encode_key(&key, self.format) {
+            Ok(exported) => exported,
+            Err(e) => Output::error(format!("cannot encode key '{}': {}", key_name, e)).exit(),
+        };
+
+        match &self.output {
+            Some(path) => match write_key_file(path, &exported) {
+                Ok(()) => Output::success_msg(format!(
+                    "Exported key '{}' ({}) on chain {} to {}",
+                    key_name,
+                    key.account,
+                    self.chain_id,
+                    path.display()
+                ))
+                .exit(),
+                Err(e) => Output::error(format!(
+                    "cannot write key to file {}: {}",
+                    path.display(),
+                    e
+                ))
+                .exit(),
+            },
+            None => Output::success_msg(exported).exit(),
+        }
+    }
+}
+
+/// Writes an exported key to a file which only its owner can read and write.
+fn write_key_file(path: &Path, exported: &str) -> io::Result<()> {
+    let mut file = open_key_file(path)?;
+    file.write_all(exported.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn open_key_file(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+
+    // The mode only applies to new files, so restrict an existing file as well.
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn open_key_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+fn encode_key(key: &KeyEntry, format: ExportFormat) -> Result<String, Box<dyn std::error::Error>> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(key)?),
+        // A raw private key has no chain code, so a key derived from a mnemonic
+        // could not be imported back as the same key.
+        ExportFormat::Hex if key.private_key.depth != 0 => {
+            Err("the key is derived from a mnemonic, export it with `--format json` instead".into())
+        }
+        ExportFormat::Hex => Ok(hex::encode(key.private_key.to_priv().to_bytes())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{ExportFormat, KeysExportCmd};
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_export_defaults() {
+        assert_eq!(
+            KeysExportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: None,
+                format: ExportFormat::Json,
+                output: None,
+            },
+            KeysExportCmd::parse_from(&["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_keys_export_hex() {
+        assert_eq!(
+            KeysExportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: Some("kname".to_owned()),
+                format: ExportFormat::Hex,
+                output: None,
+            },
+            KeysExportCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "kname",
+                "--format",
+                "hex"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_export_hex_mnemonic_key() {
+        use core::str::FromStr;
+        use ibc_relayer::config::AddressType;
+        use ibc_relayer::keyring::{HDPath, KeyRing, Store};
+
+        let keyring =
+            KeyRing::new(Store::Memory, "cosmos", &ChainId::from_string("chain_id")).unwrap();
+
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let hd_key = keyring
+            .key_from_mnemonic(mnemonic, &hd_path, &AddressType::Cosmos)
+            .unwrap();
+
+        assert!(super::encode_key(&hd_key, ExportFormat::Hex).is_err());
+
+        // A raw private key exported as hex is imported back as the same key.
+        let raw_key = keyring
+            .key_from_private_key(&[1u8; 32], &AddressType::Cosmos)
+            .unwrap();
+        let exported = super::encode_key(&raw_key, ExportFormat::Hex).unwrap();
+        let imported = keyring
+            .key_from_private_key(&hex::decode(exported).unwrap(), &AddressType::Cosmos)
+            .unwrap();
+
+        assert_eq!(imported.account, raw_key.account);
+    }
+
+    #[test]
+    fn test_keys_export_no_chain() {
+        assert!(KeysExportCmd::try_parse_from(&["test"]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_keys_export_file_mode() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("hermes-export-{}.json", std::process::id()));
+
+        // An existing file readable by others is restricted as well.
+        fs::write(&path, "previous").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        super::write_key_file(&path, "exported").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(contents, "exported");
+    }
+}
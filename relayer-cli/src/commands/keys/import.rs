@@ -0,0 +1,237 @@
+use core::str::FromStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use dialoguer::Password;
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer::keyring::{KeyEntry, KeyRing, Store};
+
+use crate::application::app_config;
+use crate::commands::keys::add::check_key_exists;
+use crate::conclude::Output;
+
+/// The formats from which a key can be imported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// A key entry, as exported by `keys export --format json`
+    Json,
+    /// A hex-encoded raw private key, as exported by `keys export --format hex`
+    Hex,
+    /// An ASCII-armored private key, as exported by the Cosmos SDK `keys export` command
+    Armor,
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "hex" => Ok(Self::Hex),
+            "armor" => Ok(Self::Armor),
+            _ => Err(format!(
+                "unknown key format '{}', expected 'json', 'hex' or 'armor'",
+                s
+            )),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `keys import` CLI command.
+///
+/// `keys import --chain <CHAIN_ID> --file <FILE> [--format <FORMAT>] [--key-name <KEY_NAME>]`
+///
+/// An armored key is decrypted with the passphrase read from `--passphrase-file`,
+/// or prompted for if that flag is not given.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct KeysImportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "file",
+        required = true,
+        value_name = "FILE",
+        help_heading = "REQUIRED",
+        help = "Path to the file containing the key to import"
+    )]
+    file: PathBuf,
+
+    #[clap(
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "json",
+        help = "Format of the key to import: `json`, `hex` or `armor`"
+    )]
+    format: ImportFormat,
+
+    #[clap(
+        long = "passphrase-file",
+        value_name = "PASSPHRASE_FILE",
+        help = "Path to the file containing the passphrase of an armored key"
+    )]
+    passphrase_file: Option<PathBuf>,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "overwrite",
+        help = "Overwrite the key if there is already one with the same key name"
+    )]
+    overwrite: bool,
+}
+
+impl Runnable for KeysImportCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let key_name = self
+            .key_name
+            .clone()
+            .unwrap_or_else(|| chain_config.key_name.clone());
+
+        match self.import_key(chain_config, &key_name) {
+            Ok(key) => Output::success_msg(format!(
+                "Imported key '{}' ({}) on chain {}",
+                key_name, key.account, self.chain_id
+            ))
+            .exit(),
+            Err(e) => Output::error(format!(
+                "An error occurred importing the key on chain {} from file {:?}: {}",
+                self.chain_id, self.file, e
+            ))
+            .exit(),
+        }
+    }
+}
+
+impl KeysImportCmd {
+    fn import_key(
+        &self,
+        config: &ChainConfig,
+        key_name: &str,
+    ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut keyring = KeyRing::new(Store::Test, &config.account_prefix, &config.id)?;
+
+        check_key_exists(&keyring, key_name, self.overwrite);
+
+        let contents = fs::read_to_string(&self.file).map_err(|_| "error reading the key file")?;
+
+        let key = match self.format {
+            ImportFormat::Json => serde_json::from_str(&contents)?,
+            ImportFormat::Hex => {
+                let private_key = hex::decode(contents.trim())?;
+                keyring.key_from_private_key(&private_key, &config.address_type)?
+            }
+            ImportFormat::Armor => {
+                let passphrase = read_passphrase(self.passphrase_file.as_deref())?;
+                keyring.key_from_armor(&contents, &passphrase, &config.address_type)?
+            }
+        };
+
+        keyring.add_key(key_name, key.clone())?;
+
+        Ok(key)
+    }
+}
+
+fn read_passphrase(passphrase_file: Option<&Path>) -> Result<String, Box<dyn std::error::Error>> {
+    match passphrase_file {
+        Some(path) => {
+            let passphrase =
+                fs::read_to_string(path).map_err(|_| "error reading the passphrase file")?;
+
+            Ok(passphrase.trim_end_matches(&['\r', '\n'][..]).to_string())
+        }
+        None => Ok(Password::new()
+            .with_prompt("Passphrase of the armored key")
+            .interact()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{ImportFormat, KeysImportCmd};
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_import_defaults() {
+        assert_eq!(
+            KeysImportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                file: PathBuf::from("key.json"),
+                format: ImportFormat::Json,
+                passphrase_file: None,
+                key_name: None,
+                overwrite: false,
+            },
+            KeysImportCmd::parse_from(&["test", "--chain", "chain_id", "--file", "key.json"])
+        )
+    }
+
+    #[test]
+    fn test_keys_import_armor() {
+        assert_eq!(
+            KeysImportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                file: PathBuf::from("key.armor"),
+                format: ImportFormat::Armor,
+                passphrase_file: Some(PathBuf::from("passphrase")),
+                key_name: None,
+                overwrite: false,
+            },
+            KeysImportCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_id",
+                "--file",
+                "key.armor",
+                "--format",
+                "armor",
+                "--passphrase-file",
+                "passphrase"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_import_unknown_format() {
+        assert!(KeysImportCmd::try_parse_from(&[
+            "test", "--chain", "chain_id", "--file", "key", "--format", "pem"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_keys_import_no_file() {
+        assert!(KeysImportCmd::try_parse_from(&["test", "--chain", "chain_id"]).is_err());
+    }
+}
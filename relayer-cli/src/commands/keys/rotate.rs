@@ -0,0 +1,355 @@
+use core::str::FromStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::signer::Signer;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_relayer::bank::send_tokens;
+use ibc_relayer::chain::cosmos::gas::calculate_fee;
+use ibc_relayer::chain::cosmos::types::gas::max_gas_from_config;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::config::{ChainConfig, Config};
+use ibc_relayer::keyring::HDPath;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::commands::keys::add::restore_key;
+use crate::conclude::Output;
+use crate::config::config_path;
+use crate::prelude::*;
+
+/// The data structure that represents the arguments when invoking the `keys rotate` CLI command.
+///
+/// `keys rotate --chain <CHAIN_ID> --new-key-name <KEY_NAME> --mnemonic-file <MNEMONIC_FILE>`
+///
+/// Rotating the key of a chain:
+///   1. restores the new key from the given mnemonic,
+///   2. sends the balance of the current key to the new key through a bank transfer,
+///      keeping enough tokens on the current key to pay for the fees of that transfer,
+///   3. switches the `key_name` of the chain to the new key in the configuration file,
+///      editing that line in place so that the rest of the file, comments included, is kept.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct KeysRotateCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "new-key-name",
+        required = true,
+        value_name = "NEW_KEY_NAME",
+        help_heading = "REQUIRED",
+        help = "Name of the key to rotate to"
+    )]
+    new_key_name: String,
+
+    #[clap(
+        long = "mnemonic-file",
+        required = true,
+        value_name = "MNEMONIC_FILE",
+        help_heading = "REQUIRED",
+        help = "Path to file containing the mnemonic of the key to rotate to"
+    )]
+    mnemonic_file: PathBuf,
+
+    #[clap(
+        long = "hd-path",
+        value_name = "HD_PATH",
+        help = "Derivation path for the new key",
+        default_value = "m/44'/118'/0'/0/0"
+    )]
+    hd_path: String,
+}
+
+impl Runnable for KeysRotateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config.clone(),
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        if chain_config.key_name == self.new_key_name {
+            Output::error(format!(
+                "key '{}' is already the key of chain {}",
+                self.new_key_name, self.chain_id
+            ))
+            .exit();
+        }
+
+        let config_path = match config_path() {
+            Some(path) => path,
+            None => Output::error("no configuration file found").exit(),
+        };
+
+        if let Err(e) = self.rotate_key(&chain_config) {
+            Output::error(format!(
+                "An error occurred rotating the key on chain {}: {}",
+                self.chain_id, e
+            ))
+            .exit();
+        }
+
+        match set_key_name(&config_path, &self.chain_id, &self.new_key_name) {
+            Ok(()) => Output::success_msg(format!(
+                "Rotated the key of chain {} from '{}' to '{}'",
+                self.chain_id, chain_config.key_name, self.new_key_name
+            ))
+            .exit(),
+            Err(e) => Output::error(format!(
+                "the balance was moved to key '{}', but the configuration file {} could not be \
+                updated, set `key_name = '{}'` for chain {} manually: {}",
+                self.new_key_name,
+                config_path.display(),
+                self.new_key_name,
+                self.chain_id,
+                e
+            ))
+            .exit(),
+        }
+    }
+}
+
+impl KeysRotateCmd {
+    fn rotate_key(&self, chain_config: &ChainConfig) -> Result<(), Box<dyn std::error::Error>> {
+        let hd_path = HDPath::from_str(&self.hd_path)
+            .map_err(|_| format!("invalid derivation path: {}", self.hd_path))?;
+
+        let new_key = restore_key(
+            &self.mnemonic_file,
+            &self.new_key_name,
+            &hd_path,
+            chain_config,
+            false,
+        )?;
+
+        let receiver = Signer::from_str(&new_key.account)?;
+
+        let chain = spawn_chain_runtime(&app_config(), &self.chain_id)?;
+        let balance = chain.query_balance(None)?;
+        let balance: u128 = balance.amount.parse()?;
+
+        // Keep enough tokens to pay for the fees of the transfer itself.
+        let fee_reserve = calculate_fee(max_gas_from_config(chain_config), &chain_config.gas_price);
+        let fee_reserve: u128 = fee_reserve.amount.parse()?;
+
+        if balance <= fee_reserve {
+            warn!(
+                "balance of key '{}' ({}{}) does not cover the fees of a transfer, not moving it to key '{}'",
+                chain_config.key_name, balance, chain_config.gas_price.denom, self.new_key_name
+            );
+
+            return Ok(());
+        }
+
+        let amount = Coin {
+            denom: chain_config.gas_price.denom.clone(),
+            amount: (balance - fee_reserve).to_string(),
+        };
+
+        info!(
+            "moving {}{} from key '{}' to key '{}' ({})",
+            amount.amount, amount.denom, chain_config.key_name, self.new_key_name, new_key.account
+        );
+
+        send_tokens(&chain, &receiver, amount)?;
+
+        Ok(())
+    }
+}
+
+/// Sets the `key_name` of the given chain in the configuration file at `path`.
+fn set_key_name(
+    path: &Path,
+    chain_id: &ChainId,
+    key_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+
+    let new_contents = replace_key_name(&contents, chain_id, key_name)
+        .ok_or("no `key_name` entry found for the chain")?;
+
+    // Make sure that the edited file still holds the configuration we expect.
+    let new_config: Config = toml::from_str(&new_contents)?;
+    match new_config.find_chain(chain_id) {
+        Some(chain_config) if chain_config.key_name == key_name => {}
+        _ => return Err("the edited configuration does not hold the new key name".into()),
+    }
+
+    fs::write(path, new_contents)?;
+
+    Ok(())
+}
+
+/// Replaces the value of the `key_name` entry of the `[[chains]]` table with the given
+/// chain identifier, leaving every other line of the configuration untouched.
+///
+/// Returns `None` if no such entry is found.
+fn replace_key_name(contents: &str, chain_id: &ChainId, key_name: &str) -> Option<String> {
+    let parse_entry = |line: &str| -> Option<(String, String)> {
+        let table = toml::from_str::<toml::value::Table>(line).ok()?;
+        let (key, value) = table.into_iter().next()?;
+        Some((key, value.as_str()?.to_string()))
+    };
+
+    // The `id` and `key_name` line index of each `[[chains]]` table
+    let mut chains: Vec<(Option<String>, Option<usize>)> = Vec::new();
+    let mut in_chain_table = false;
+
+    let mut lines: Vec<&str> = contents.lines().collect();
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            in_chain_table = trimmed == "[[chains]]";
+            if in_chain_table {
+                chains.push((None, None));
+            }
+            continue;
+        }
+
+        if !in_chain_table {
+            continue;
+        }
+
+        if let (Some((key, value)), Some(chain)) = (parse_entry(trimmed), chains.last_mut()) {
+            match key.as_str() {
+                "id" => chain.0 = Some(value),
+                "key_name" => chain.1 = Some(index),
+                _ => {}
+            }
+        }
+    }
+
+    let index = chains
+        .into_iter()
+        .find(|(id, _)| id.as_deref() == Some(chain_id.as_str()))
+        .and_then(|(_, index)| index)?;
+
+    let line = lines[index];
+    let indent = &line[..line.len() - line.trim_start().len()];
+    // A trailing comment starts after the closing quote of the value.
+    let value = line[line.find('=')? + 1..].trim_start();
+    let quote = value.chars().next()?;
+    let after_value = &value[1 + value[1..].find(quote)? + 1..];
+    let comment = after_value.find('#').map(|start| &after_value[start..]);
+
+    let new_line = match comment {
+        Some(comment) => format!("{}key_name = '{}' {}", indent, key_name, comment),
+        None => format!("{}key_name = '{}'", indent, key_name),
+    };
+
+    lines[index] = &new_line;
+
+    let mut new_contents = lines.join("\n");
+    if contents.ends_with('\n') {
+        new_contents.push('\n');
+    }
+
+    Some(new_contents)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{replace_key_name, KeysRotateCmd};
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_rotate() {
+        assert_eq!(
+            KeysRotateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                new_key_name: "new_key".to_owned(),
+                mnemonic_file: PathBuf::from("mnemonic_file"),
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+            },
+            KeysRotateCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_id",
+                "--new-key-name",
+                "new_key",
+                "--mnemonic-file",
+                "mnemonic_file"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_rotate_no_mnemonic() {
+        assert!(KeysRotateCmd::try_parse_from(&[
+            "test",
+            "--chain",
+            "chain_id",
+            "--new-key-name",
+            "new_key"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_replace_key_name_keeps_comments() {
+        let contents = r#"# Global settings
+[global]
+log_level = 'info'
+
+[[chains]]
+id = 'ibc-0'
+# The key used to sign transactions
+key_name = 'testkey' # rotated regularly, don't remove
+
+[chains.packet_filter]
+policy = 'allow'
+
+[[chains]]
+id = 'ibc-1'
+key_name = 'testkey'
+"#;
+
+        let expected = r#"# Global settings
+[global]
+log_level = 'info'
+
+[[chains]]
+id = 'ibc-0'
+# The key used to sign transactions
+key_name = 'newkey' # rotated regularly, don't remove
+
+[chains.packet_filter]
+policy = 'allow'
+
+[[chains]]
+id = 'ibc-1'
+key_name = 'testkey'
+"#;
+
+        assert_eq!(
+            replace_key_name(contents, &ChainId::from_string("ibc-0"), "newkey").as_deref(),
+            Some(expected)
+        );
+
+        assert_eq!(
+            replace_key_name(contents, &ChainId::from_string("ibc-2"), "newkey"),
+            None
+        );
+    }
+}
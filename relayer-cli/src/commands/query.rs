@@ -17,6 +17,7 @@ mod clients;
 mod connection;
 mod connections;
 mod packet;
+mod paths;
 mod transfer;
 mod tx;
 
@@ -48,6 +49,9 @@ pub enum QueryCmd {
     #[clap(subcommand)]
     Packet(QueryPacketCmds),
 
+    /// Query the health of all paths between the configured chains
+    Paths(paths::QueryPathsCmd),
+
     /// Query information about transactions
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
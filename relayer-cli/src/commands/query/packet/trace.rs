@@ -0,0 +1,170 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::chain::counterparty::{packet_lifecycle, PacketLifecycle};
+use ibc_relayer::chain::handle::BaseChainHandle;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// This command reconstructs the lifecycle of a packet sent by the given chain:
+///
+/// 1. queries the chain to get its counterparty chain, channel and port identifiers,
+/// 2. queries the transactions of both chains for the `SendPacket`, `ReceivePacket`,
+///    `WriteAcknowledgement`, `AcknowledgePacket` and `TimeoutPacket` events of the packet,
+/// 3. queries the clients at both ends for the client updates used to verify the proofs
+///    of the receive, acknowledgement and timeout messages.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct QueryPacketTraceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain which sent the packet"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "sequence",
+        visible_alias = "seq",
+        required = true,
+        value_name = "SEQUENCE",
+        help_heading = "REQUIRED",
+        help = "Sequence of the packet to trace"
+    )]
+    sequence: Sequence,
+}
+
+impl QueryPacketTraceCmd {
+    fn execute(&self) -> Result<PacketLifecycle, Error> {
+        let config = app_config();
+
+        let (chains, chan_conn_cli) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        debug!(
+            chain=%self.chain_id,
+            "fetched channel from source chain: {:?}",
+            chan_conn_cli.channel
+        );
+
+        packet_lifecycle(
+            &chains.src,
+            &chains.dst,
+            &chan_conn_cli.channel,
+            &chan_conn_cli.connection,
+            self.sequence,
+        )
+        .map_err(Error::supervisor)
+    }
+}
+
+impl Runnable for QueryPacketTraceCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(lifecycle) => Output::success(lifecycle).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryPacketTraceCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::{
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChainId, ChannelId, PortId},
+    };
+
+    #[test]
+    fn test_query_packet_trace() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42),
+            },
+            QueryPacketTraceCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--sequence",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_aliases() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42),
+            },
+            QueryPacketTraceCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07",
+                "--seq",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_no_sequence() {
+        assert!(QueryPacketTraceCmd::try_parse_from(&[
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07"
+        ])
+        .is_err());
+    }
+}
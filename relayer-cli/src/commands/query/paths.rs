@@ -0,0 +1,410 @@
+use core::fmt;
+use core::time::Duration;
+use std::collections::BTreeSet;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::events::WithBlockDataType;
+use ibc_relayer::chain::counterparty::{pending_packet_summary, PendingPackets};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{
+    QueryHeight, QueryHostConsensusStateRequest, QueryPacketEventDataRequest, QueryTxRequest,
+};
+use ibc_relayer::config::Config;
+use ibc_relayer::error::Error as RelayerError;
+use ibc_relayer::foreign_client::ForeignClient;
+use ibc_relayer::registry::Registry;
+use ibc_relayer::supervisor::client_state_filter::FilterPolicy;
+use ibc_relayer::supervisor::scan::{ChainScanner, ScanMode};
+use ibc_relayer::util::duration::format_duration;
+
+use crate::conclude::{json, Output};
+use crate::prelude::*;
+
+/// The data structure that represents the arguments when invoking the `query paths` CLI command.
+///
+/// `query paths [--full-scan]`
+///
+/// Reports the health of every open channel allowed by the packet filters
+/// of the configured chains, together with its counterparty channel.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct QueryPathsCmd {
+    #[clap(
+        long = "full-scan",
+        help = "Scan all clients, connections and channels of the chains, \
+                even for chains whose packet filter only allows specific channels"
+    )]
+    full_scan: bool,
+}
+
+/// The health of a path, ie. of a channel and its counterparty channel.
+#[derive(Debug, Serialize)]
+struct PathHealth {
+    a: PathEndHealth,
+    b: PathEndHealth,
+    /// The delay period of the connection underlying the channel.
+    connection_delay: String,
+    has_connection_delay: bool,
+}
+
+/// The health of one end of a path.
+#[derive(Debug, Serialize)]
+struct PathEndHealth {
+    chain_id: ChainId,
+    port_id: PortId,
+    channel_id: ChannelId,
+    connection_id: Option<ConnectionId>,
+    /// The client of the counterparty chain hosted by this chain.
+    client_id: ClientId,
+    client_expiry: ClientExpiry,
+    /// The packets sent by this chain that are still pending,
+    /// or `None` if they could not be queried.
+    pending: Option<PendingPacketsHealth>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ClientExpiry {
+    /// The client expires after the given time, unless it is updated in the meantime.
+    ExpiresIn(String),
+    Expired,
+    /// The client does not expire.
+    Never,
+    /// The expiry of the client could not be determined.
+    Unknown(String),
+}
+
+#[derive(Debug, Serialize)]
+struct PendingPacketsHealth {
+    /// The number of packets not yet received on the counterparty chain.
+    unreceived_packets: usize,
+    /// The number of packets received on the counterparty chain,
+    /// whose acknowledgement is not yet received on this chain.
+    unreceived_acks: usize,
+    /// The time since the oldest of those packets was sent.
+    oldest_pending_packet_age: Option<String>,
+}
+
+impl Runnable for QueryPathsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let scan_mode = if self.full_scan {
+            ScanMode::Full
+        } else {
+            ScanMode::Auto
+        };
+
+        let paths = query_paths(&config, scan_mode);
+
+        if json() {
+            Output::success(paths).exit()
+        } else {
+            let report: String = paths.iter().map(ToString::to_string).collect();
+            Output::success_msg(format!("\n{}", report)).exit()
+        }
+    }
+}
+
+fn query_paths(config: &Config, scan_mode: ScanMode) -> Vec<PathHealth> {
+    let mut registry = Registry::<BaseChainHandle>::new(config.clone());
+    let mut client_state_filter = FilterPolicy::default();
+
+    let scans =
+        ChainScanner::new(config, &mut registry, &mut client_state_filter, scan_mode).scan_chains();
+
+    let mut paths = Vec::new();
+
+    // Each path may be found by scanning either of its ends
+    let mut visited = BTreeSet::new();
+
+    for scan in scans.chains {
+        let scan = match scan {
+            Ok(scan) => scan,
+            Err(e) => {
+                warn!("skipping chain, reason: failed to scan chain: {}", e);
+                continue;
+            }
+        };
+
+        for client in scan.clients.values() {
+            for connection in client.connections.values() {
+                for channel in connection.channels.values() {
+                    let counterparty_channel = match &channel.counterparty {
+                        Some(counterparty) if channel.channel.channel_end.is_open() => counterparty,
+                        _ => continue,
+                    };
+
+                    let counterparty_chain_id = client.counterparty_chain_id();
+
+                    if !visited.insert((
+                        scan.chain_id.clone(),
+                        channel.channel.port_id.clone(),
+                        channel.channel.channel_id.clone(),
+                    )) {
+                        continue;
+                    }
+
+                    visited.insert((
+                        counterparty_chain_id.clone(),
+                        counterparty_channel.port_id.clone(),
+                        counterparty_channel.channel_id.clone(),
+                    ));
+
+                    let chains = registry.get_or_spawn(&scan.chain_id).and_then(|chain| {
+                        let counterparty_chain = registry.get_or_spawn(&counterparty_chain_id)?;
+                        Ok((chain, counterparty_chain))
+                    });
+
+                    let (chain, counterparty_chain) = match chains {
+                        Ok(chains) => chains,
+                        Err(e) => {
+                            warn!(
+                                "skipping channel {}/{} on chain {}, reason: {}",
+                                channel.channel.port_id,
+                                channel.channel.channel_id,
+                                scan.chain_id,
+                                e
+                            );
+                            continue;
+                        }
+                    };
+
+                    let connection_end = &connection.connection.connection_end;
+                    let connection_delay = connection_end.delay_period();
+
+                    let a = path_end_health(
+                        &chain,
+                        &counterparty_chain,
+                        &channel.channel,
+                        Some(connection.connection.connection_id.clone()),
+                        client.client.client_id.clone(),
+                    );
+
+                    let b = path_end_health(
+                        &counterparty_chain,
+                        &chain,
+                        counterparty_channel,
+                        connection_end.counterparty().connection_id().cloned(),
+                        connection_end.counterparty().client_id().clone(),
+                    );
+
+                    paths.push(PathHealth {
+                        a,
+                        b,
+                        connection_delay: humantime::format_duration(connection_delay).to_string(),
+                        has_connection_delay: !connection_delay.is_zero(),
+                    });
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+fn path_end_health(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    channel: &IdentifiedChannelEnd,
+    connection_id: Option<ConnectionId>,
+    client_id: ClientId,
+) -> PathEndHealth {
+    let client =
+        ForeignClient::restore(client_id.clone(), chain.clone(), counterparty_chain.clone());
+
+    let client_expiry = match client.time_until_expiry() {
+        Ok(Some(expires_in)) if expires_in.is_zero() => ClientExpiry::Expired,
+        Ok(Some(expires_in)) => ClientExpiry::ExpiresIn(format_duration(expires_in)),
+        Ok(None) => ClientExpiry::Never,
+        Err(e) => ClientExpiry::Unknown(e.to_string()),
+    };
+
+    let pending = match pending_packet_summary(chain, counterparty_chain, channel) {
+        Ok(pending) => {
+            let oldest_pending_packet_age = oldest_pending_packet_age(chain, channel, &pending)
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "failed to query the age of the oldest pending packet on channel {}/{} of chain {}: {}",
+                        channel.port_id, channel.channel_id, chain.id(), e
+                    );
+                    None
+                });
+
+            Some(PendingPacketsHealth {
+                unreceived_packets: pending.unreceived_packets.len(),
+                unreceived_acks: pending.unreceived_acks.len(),
+                oldest_pending_packet_age: oldest_pending_packet_age.map(format_duration),
+            })
+        }
+        Err(e) => {
+            warn!(
+                "failed to query the pending packets on channel {}/{} of chain {}: {}",
+                channel.port_id,
+                channel.channel_id,
+                chain.id(),
+                e
+            );
+            None
+        }
+    };
+
+    PathEndHealth {
+        chain_id: chain.id(),
+        port_id: channel.port_id.clone(),
+        channel_id: channel.channel_id.clone(),
+        connection_id,
+        client_id,
+        client_expiry,
+        pending,
+    }
+}
+
+/// Returns the time elapsed since the oldest pending packet was sent, according
+/// to the latest block time of the chain, if there is any pending packet.
+fn oldest_pending_packet_age(
+    chain: &impl ChainHandle,
+    channel: &IdentifiedChannelEnd,
+    pending: &PendingPackets,
+) -> Result<Option<Duration>, RelayerError> {
+    let oldest_sequence: Sequence = match pending
+        .unreceived_packets
+        .iter()
+        .chain(&pending.unreceived_acks)
+        .min()
+    {
+        Some(sequence) => *sequence,
+        None => return Ok(None),
+    };
+
+    let counterparty = channel.channel_end.counterparty();
+
+    let counterparty_channel_id = match counterparty.channel_id() {
+        Some(channel_id) => channel_id.clone(),
+        None => return Ok(None),
+    };
+
+    let events = chain.query_txs(QueryTxRequest::Packet(QueryPacketEventDataRequest {
+        event_id: WithBlockDataType::SendPacket,
+        source_channel_id: channel.channel_id.clone(),
+        source_port_id: channel.port_id.clone(),
+        destination_channel_id: counterparty_channel_id,
+        destination_port_id: counterparty.port_id.clone(),
+        sequences: vec![oldest_sequence],
+        height: QueryHeight::Latest,
+    }))?;
+
+    let send_height = match events.first() {
+        Some(event) => event.height(),
+        None => return Ok(None),
+    };
+
+    let sent_at = chain
+        .query_host_consensus_state(QueryHostConsensusStateRequest {
+            height: QueryHeight::Specific(send_height),
+        })?
+        .timestamp();
+
+    let now = chain.query_application_status()?.timestamp;
+
+    Ok(now.duration_since(&sent_at))
+}
+
+/// Format a duration with a precision of one second.
+impl fmt::Display for PathHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Path: {}:{}/{} <=> {}:{}/{}",
+            self.a.chain_id,
+            self.a.port_id,
+            self.a.channel_id,
+            self.b.chain_id,
+            self.b.port_id,
+            self.b.channel_id
+        )?;
+
+        if self.has_connection_delay {
+            writeln!(f, "  | Connection delay: {}", self.connection_delay)?;
+        } else {
+            writeln!(f, "  | Connection delay: none")?;
+        }
+
+        write!(f, "{}", self.a)?;
+        write!(f, "{}", self.b)
+    }
+}
+
+impl fmt::Display for PathEndHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let connection_id = self
+            .connection_id
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "<none>".to_string());
+
+        writeln!(f, "  + Chain: {}", self.chain_id)?;
+        writeln!(f, "    | Connection: {}", connection_id)?;
+        writeln!(
+            f,
+            "    | Client: {} ({})",
+            self.client_id, self.client_expiry
+        )?;
+
+        match &self.pending {
+            Some(pending) => {
+                writeln!(
+                    f,
+                    "    | Pending: {} unreceived packet(s), {} unreceived ack(s)",
+                    pending.unreceived_packets, pending.unreceived_acks
+                )?;
+
+                if let Some(age) = &pending.oldest_pending_packet_age {
+                    writeln!(f, "    | Oldest pending packet sent: {} ago", age)?;
+                }
+
+                Ok(())
+            }
+            None => writeln!(f, "    | Pending: <unknown>"),
+        }
+    }
+}
+
+impl fmt::Display for ClientExpiry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientExpiry::ExpiresIn(expires_in) => write!(f, "expires in {}", expires_in),
+            ClientExpiry::Expired => write!(f, "expired"),
+            ClientExpiry::Never => write!(f, "does not expire"),
+            ClientExpiry::Unknown(reason) => write!(f, "expiry unknown: {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryPathsCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_query_paths() {
+        assert_eq!(
+            QueryPathsCmd { full_scan: false },
+            QueryPathsCmd::parse_from(&["test"])
+        )
+    }
+
+    #[test]
+    fn test_query_paths_full_scan() {
+        assert_eq!(
+            QueryPathsCmd { full_scan: true },
+            QueryPathsCmd::parse_from(&["test", "--full-scan"])
+        )
+    }
+}
@@ -11,6 +11,7 @@ mod channel;
 pub(crate) mod client;
 mod connection;
 mod packet;
+mod recover;
 mod transfer;
 mod upgrade;
 
@@ -78,6 +79,12 @@ pub enum TxRawCommands {
 
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
+
+    /// Recover an expired client through a substitute client and a governance proposal
+    RecoverClient(recover::TxRecoverClientCmd),
+
+    /// Verify that a client has been recovered once the recovery proposal has passed
+    VerifyClientRecovery(recover::TxVerifyClientRecoveryCmd),
 }
 
 impl Override<Config> for TxCmd {
@@ -0,0 +1,310 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+use tendermint::abci::transaction::Hash as TxHash;
+
+use ibc::core::ics02_client::client_state::ClientState;
+use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::client_recovery::{
+    build_and_send_client_update_proposal, create_substitute_client, verify_recovered_client,
+    ClientUpdateProposalOptions,
+};
+use ibc_relayer::config::Config;
+use ibc_relayer::foreign_client::ForeignClient;
+
+use crate::cli_utils::spawn_chain_runtime_generic;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Recover an expired client through a `ClientUpdateProposal` governance proposal:
+///
+/// 1. creates a substitute client on the host chain, tracking the same chain with the
+///    same parameters as the expired client (unless an existing substitute is given),
+/// 2. submits a proposal to replace the expired client with the substitute client.
+///
+/// Once the proposal has passed, use `tx raw verify-client-recovery` to check that
+/// the client has been recovered.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct TxRecoverClientCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain that hosts the expired client"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the expired client to recover"
+    )]
+    client_id: ClientId,
+
+    #[clap(
+        long = "amount",
+        required = true,
+        value_name = "AMOUNT",
+        help_heading = "REQUIRED",
+        help = "Amount of the deposit of the proposal"
+    )]
+    amount: u64,
+
+    #[clap(
+        long = "denom",
+        value_name = "DENOM",
+        default_value = "stake",
+        help = "Denomination of the deposit of the proposal"
+    )]
+    denom: String,
+
+    #[clap(
+        long = "substitute-client",
+        value_name = "SUBSTITUTE_CLIENT_ID",
+        help = "Identifier of an existing substitute client; a new one is created if omitted"
+    )]
+    substitute_client_id: Option<ClientId>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClientRecoveryProposal {
+    subject_client_id: ClientId,
+    substitute_client_id: ClientId,
+    proposal_tx_hash: TxHash,
+}
+
+impl TxRecoverClientCmd {
+    fn execute(&self) -> Result<ClientRecoveryProposal, Error> {
+        let config = app_config();
+
+        let client = spawn_client(&config, &self.chain_id, &self.client_id)?;
+
+        if !client.is_expired_or_frozen() {
+            warn!(
+                client = %self.client_id,
+                chain = %self.chain_id,
+                "client is neither expired nor frozen, the proposal will likely be rejected"
+            );
+        }
+
+        let substitute_client_id = match &self.substitute_client_id {
+            Some(substitute_client_id) => substitute_client_id.clone(),
+            None => create_substitute_client(&client)
+                .map_err(Error::client_recovery)?
+                .id()
+                .clone(),
+        };
+
+        let opts = ClientUpdateProposalOptions {
+            subject_client_id: self.client_id.clone(),
+            substitute_client_id: substitute_client_id.clone(),
+            amount: self.amount,
+            denom: self.denom.clone(),
+        };
+
+        let proposal_tx_hash = build_and_send_client_update_proposal(client.dst_chain(), &opts)
+            .map_err(Error::client_recovery)?;
+
+        Ok(ClientRecoveryProposal {
+            subject_client_id: self.client_id.clone(),
+            substitute_client_id,
+            proposal_tx_hash,
+        })
+    }
+}
+
+impl Runnable for TxRecoverClientCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(proposal) => Output::success(proposal).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Verify that an expired client has been recovered, once the `ClientUpdateProposal`
+/// submitted with `tx raw recover-client` has passed.
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct TxVerifyClientRecoveryCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain that hosts the recovered client"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the recovered client"
+    )]
+    client_id: ClientId,
+
+    #[clap(
+        long = "substitute-client",
+        required = true,
+        value_name = "SUBSTITUTE_CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the substitute client used in the proposal"
+    )]
+    substitute_client_id: ClientId,
+}
+
+impl Runnable for TxVerifyClientRecoveryCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let client = match spawn_client(&config, &self.chain_id, &self.client_id) {
+            Ok(client) => client,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        match verify_recovered_client(&client, &self.substitute_client_id) {
+            Ok(expires_in) => Output::success_msg(format!(
+                "client {} on chain {} has been recovered and expires in {}",
+                self.client_id,
+                self.chain_id,
+                humantime::format_duration(expires_in)
+            ))
+            .exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Spawns the runtimes of the host chain and of the chain targeted by the given client,
+/// and restores the client.
+fn spawn_client(
+    config: &Config,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> Result<ForeignClient<BaseChainHandle, BaseChainHandle>, Error> {
+    let chain = spawn_chain_runtime_generic::<BaseChainHandle>(config, chain_id)?;
+
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    let counterparty_chain =
+        spawn_chain_runtime_generic::<BaseChainHandle>(config, &client_state.chain_id())?;
+
+    ForeignClient::find(counterparty_chain, chain, client_id).map_err(Error::foreign_client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TxRecoverClientCmd, TxVerifyClientRecoveryCmd};
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+
+    #[test]
+    fn test_recover_client_required_only() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                amount: 10000000,
+                denom: "stake".to_owned(),
+                substitute_client_id: None,
+            },
+            TxRecoverClientCmd::parse_from(&[
+                "test",
+                "--host-chain",
+                "chain_id",
+                "--client",
+                "07-tendermint-0",
+                "--amount",
+                "10000000"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_substitute() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                amount: 10000000,
+                denom: "uatom".to_owned(),
+                substitute_client_id: Some(ClientId::from_str("07-tendermint-1").unwrap()),
+            },
+            TxRecoverClientCmd::parse_from(&[
+                "test",
+                "--host-chain",
+                "chain_id",
+                "--client",
+                "07-tendermint-0",
+                "--amount",
+                "10000000",
+                "--denom",
+                "uatom",
+                "--substitute-client",
+                "07-tendermint-1"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_no_amount() {
+        assert!(TxRecoverClientCmd::try_parse_from(&[
+            "test",
+            "--host-chain",
+            "chain_id",
+            "--client",
+            "07-tendermint-0"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_verify_client_recovery() {
+        assert_eq!(
+            TxVerifyClientRecoveryCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                substitute_client_id: ClientId::from_str("07-tendermint-1").unwrap(),
+            },
+            TxVerifyClientRecoveryCmd::parse_from(&[
+                "test",
+                "--host-chain",
+                "chain_id",
+                "--client",
+                "07-tendermint-0",
+                "--substitute-client",
+                "07-tendermint-1"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_verify_client_recovery_no_substitute() {
+        assert!(TxVerifyClientRecoveryCmd::try_parse_from(&[
+            "test",
+            "--host-chain",
+            "chain_id",
+            "--client",
+            "07-tendermint-0"
+        ])
+        .is_err())
+    }
+}
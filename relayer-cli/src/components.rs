@@ -1,9 +1,21 @@
 //! Various components for internal use by the Abscissa subsystem.
 
 use abscissa_core::{Component, FrameworkError, FrameworkErrorKind};
-use tracing_subscriber::{filter::EnvFilter, util::SubscriberInitExt, FmtSubscriber};
-
-use ibc_relayer::config::{GlobalConfig, LogLevel};
+use opentelemetry::sdk::{trace as sdktrace, Resource};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::{
+    filter::{EnvFilter, Targets},
+    fmt,
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+    Layer,
+};
+
+use ibc_relayer::config::{GlobalConfig, LogLevel, TracingConfig};
+use ibc_relayer::trace;
 
 use crate::config::Error;
 
@@ -17,27 +29,30 @@ const HERMES_LOG_VAR: &str = "RUST_LOG";
 /// - Customizing the log output level, for filtering the output produced via tracing macros
 ///   (`debug!`, `info!`, etc.) or abscissa macros (`status_err`, `status_info`, etc.).
 /// - Enabling JSON-formatted output without coloring
+/// - Exporting the spans tracing the event batches, if enabled in the `[tracing]` section
 #[derive(Component, Debug)]
 pub struct JsonTracing;
 
 impl JsonTracing {
     /// Creates a new [`JsonTracing`] component
-    pub fn new(cfg: GlobalConfig) -> Result<Self, FrameworkError> {
+    pub fn new(cfg: GlobalConfig, tracing_cfg: &TracingConfig) -> Result<Self, FrameworkError> {
         let filter = build_tracing_filter(cfg.log_level)?;
         // Note: JSON formatter is un-affected by ANSI 'color' option. Set to 'false'.
         let use_color = false;
 
-        // Construct a tracing subscriber with the supplied filter and enable reloading.
-        let builder = FmtSubscriber::builder()
+        // Construct a formatting layer with the supplied filter.
+        let fmt_layer = fmt::layer()
             .with_target(false)
-            .with_env_filter(filter)
             .with_writer(std::io::stderr)
             .with_ansi(use_color)
             .with_thread_ids(true)
-            .json();
+            .json()
+            .with_filter(filter);
 
-        let subscriber = builder.finish();
-        subscriber.init();
+        tracing_subscriber::registry()
+            .with(fmt_layer)
+            .with(build_otlp_layer(tracing_cfg)?)
+            .init();
 
         Ok(Self)
     }
@@ -51,28 +66,75 @@ impl JsonTracing {
 /// - Customizing the log output level, for filtering the output produced via tracing macros
 ///   (`debug!`, `info!`, etc.) or abscissa macros (`status_err`, `status_info`, etc.).
 /// - Enabling pretty output with coloring
+/// - Exporting the spans tracing the event batches, if enabled in the `[tracing]` section
 pub struct PrettyTracing;
 
 impl PrettyTracing {
     /// Creates a new [`PrettyTracing`] component
-    pub fn new(cfg: GlobalConfig) -> Result<Self, FrameworkError> {
+    pub fn new(cfg: GlobalConfig, tracing_cfg: &TracingConfig) -> Result<Self, FrameworkError> {
         let filter = build_tracing_filter(cfg.log_level)?;
 
-        // Construct a tracing subscriber with the supplied filter and enable reloading.
-        let builder = FmtSubscriber::builder()
+        // Construct a formatting layer with the supplied filter.
+        let fmt_layer = fmt::layer()
             .with_target(false)
-            .with_env_filter(filter)
             .with_writer(std::io::stderr)
             .with_ansi(enable_ansi())
-            .with_thread_ids(true);
+            .with_thread_ids(true)
+            .with_filter(filter);
 
-        let subscriber = builder.finish();
-        subscriber.init();
+        tracing_subscriber::registry()
+            .with(fmt_layer)
+            .with(build_otlp_layer(tracing_cfg)?)
+            .init();
 
         Ok(Self)
     }
 }
 
+/// Builds a layer exporting the spans tracing the event batches (see [`ibc_relayer::trace`])
+/// to an OpenTelemetry collector over OTLP, if enabled in the given configuration.
+///
+/// The layer only records the spans under the [`trace::TARGET`] target, regardless of the log level.
+/// Spans are exported in batches from a background thread, which are flushed when the CLI exits.
+fn build_otlp_layer<S>(cfg: &TracingConfig) -> Result<Option<impl Layer<S>>, FrameworkError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !cfg.enabled {
+        return Ok(None);
+    }
+
+    let exporter = opentelemetry_otlp::new_exporter()
+        .http()
+        .with_endpoint(&cfg.endpoint);
+
+    let trace_config = sdktrace::config().with_resource(Resource::new(vec![KeyValue::new(
+        "service.name",
+        cfg.service_name.clone(),
+    )]));
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(exporter)
+        .with_trace_config(trace_config)
+        .install_batch(opentelemetry::runtime::TokioCurrentThread)
+        .map_err(|e| {
+            eprintln!(
+                "ERROR: unable to export traces to the OpenTelemetry collector at {}: {}",
+                cfg.endpoint, e
+            );
+
+            FrameworkErrorKind::ComponentError
+                .context(Error::tracing_exporter(cfg.endpoint.clone(), e))
+        })?;
+
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(Targets::new().with_target(trace::TARGET, Level::TRACE));
+
+    Ok(Some(layer))
+}
+
 /// Check if both stdout and stderr are proper terminal (tty),
 /// so that we know whether or not to enable colored output,
 /// using ANSI escape codes. If either is not, eg. because
@@ -104,7 +166,12 @@ fn build_tracing_filter(default_level: LogLevel) -> Result<EnvFilter, FrameworkE
 
     // Build the filter directive
     match EnvFilter::try_new(&directive) {
-        Ok(out) => Ok(out),
+        // The spans tracing the event batches are only meant to be exported, not logged.
+        Ok(out) => Ok(out.add_directive(
+            format!("{}=off", trace::TARGET)
+                .parse()
+                .expect("invalid directive for the trace target"),
+        )),
         Err(e) => {
             eprintln!(
                 "ERROR: unable to initialize Hermes with log filtering directive {:?}: {}",
@@ -75,6 +75,9 @@ pub fn exit_with(out: Output) -> ! {
         println!("{}: {}", out.status, out.result);
     }
 
+    // Export the spans still buffered by the OpenTelemetry exporter, if any
+    opentelemetry::global::shutdown_tracer_provider();
+
     // The return code
     if status == Status::Error {
         std::process::exit(1);
@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use flex_error::{define_error, TraceError};
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use opentelemetry::trace::TraceError as OpenTelemetryError;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
 
@@ -35,6 +36,14 @@ define_error! {
                 format!("invalid log directive: {0:?}", e.directive)
             },
 
+        TracingExporter
+            { endpoint: String, }
+            [ TraceError<OpenTelemetryError> ]
+            |e| {
+                format!("failed to install the OpenTelemetry exporter for the collector at {0}",
+                    e.endpoint)
+            },
+
         InvalidMode
             { reason: String, }
             |e| {
@@ -86,6 +95,16 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        InvalidWalletRefill
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("config file specifies an invalid `wallet_refill` for the chain '{0}', caused by: {1}",
+                    e.chain_id, e.reason)
+            },
     }
 }
 
@@ -109,6 +128,9 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c)?;
+
+        // Validate the wallet refill policy
+        validate_wallet_refill(&c.id, c)?;
     }
 
     // Check for invalid mode config
@@ -193,3 +215,33 @@ fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagn
 
     Ok(())
 }
+
+fn validate_wallet_refill(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let refill = match &config.wallet_refill {
+        Some(refill) => refill,
+        None => return Ok(()),
+    };
+
+    if refill.treasury_key_name == config.key_name {
+        return Err(Diagnostic::Error(Error::invalid_wallet_refill(
+            id.clone(),
+            "the treasury key must be different from the relayer key".to_string(),
+        )));
+    }
+
+    if refill.target <= refill.threshold {
+        return Err(Diagnostic::Error(Error::invalid_wallet_refill(
+            id.clone(),
+            "`target` must be greater than `threshold`".to_string(),
+        )));
+    }
+
+    if refill.max_per_day == 0 {
+        return Err(Diagnostic::Warning(Error::invalid_wallet_refill(
+            id.clone(),
+            "`max_per_day` is zero, the wallet will never be refilled".to_string(),
+        )));
+    }
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::process;
 
 use abscissa_core::clap::Parser;
-use abscissa_core::{Command, Configurable, FrameworkError, Runnable};
+use abscissa_core::{Command, Configurable, FrameworkError, FrameworkErrorKind, Runnable};
 use clap::IntoApp;
 use ibc_relayer::config::Config;
 
@@ -22,6 +22,13 @@ pub struct EntryPoint {
     #[clap(long = "json", help = "Enable JSON output")]
     pub json: bool,
 
+    /// Simulate the transactions instead of submitting them
+    #[clap(
+        long = "dry-run",
+        help = "Simulate the transactions instead of submitting them, and report the messages, estimated fees and predicted events"
+    )]
+    pub dry_run: bool,
+
     /// Subcommand to execute.
     ///
     /// The `command` option will delegate option parsing to the command type,
@@ -65,7 +72,19 @@ impl Configurable<Config> for EntryPoint {
 
     /// Process the configuration after it has been loaded, potentially
     /// modifying it or returning an error if options are incompatible
-    fn process_config(&self, config: Config) -> Result<Config, FrameworkError> {
+    fn process_config(&self, mut config: Config) -> Result<Config, FrameworkError> {
+        if self.dry_run {
+            if let Some(CliCmd::Start(_)) = &self.command {
+                return Err(FrameworkErrorKind::ConfigError
+                    .context("the `start` command does not support `--dry-run`")
+                    .into());
+            }
+
+            for chain_config in config.chains.iter_mut() {
+                chain_config.dry_run = true;
+            }
+        }
+
         match &self.command {
             Some(cmd) => cmd.process_config(config),
             None => Ok(config),
@@ -8,6 +8,7 @@ use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics24_host::identifier::ChainId;
 
 use ibc_relayer::channel::ChannelError;
+use ibc_relayer::client_recovery::ClientRecoveryError;
 use ibc_relayer::connection::ConnectionError;
 use ibc_relayer::error::Error as RelayerError;
 use ibc_relayer::foreign_client::ForeignClientError;
@@ -102,5 +103,9 @@ define_error! {
         UpgradeChain
             [ UpgradeChainError ]
             |_| { "upgrade chain error" },
+
+        ClientRecovery
+            [ ClientRecoveryError ]
+            |_| { "client recovery error" },
     }
 }
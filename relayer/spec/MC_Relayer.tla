@@ -0,0 +1,35 @@
+---- MODULE MC_Relayer ----
+EXTENDS Relayer_typedefs
+
+CHAIN_IDS == {1, 2}
+MAX_PACKETS == 3
+
+VARIABLES
+    \* Interchain state
+    \* @type: CHAIN_ID -> CHAIN;
+    chains,
+    \* @type: Bool;
+    relayerRunning,
+    \* Action performed at current step
+    \* @type: ACTION;
+    action
+
+INSTANCE Relayer
+
+\* Trace where a packet is received after a packet with a higher sequence
+RelayRecvPacketOutOfOrderTest ==
+    /\ action.name = RelayRecvPacketAction
+    /\ \E sequence \in chains[Counterparty(action.chainId)].received:
+        sequence > action.sequence
+\* Trace with a RelayTimeoutPacket action
+RelayTimeoutPacketTest == action.name = RelayTimeoutPacketAction
+\* Trace where the relayer crashes after relaying a packet, before
+\* seeing the transaction confirmed
+RelayRecvPacketAndCrashTest == action.name = RelayRecvPacketAndCrashAction
+
+\* Negate the trace predicate to find counter-example
+RelayRecvPacketOutOfOrderInv == ~RelayRecvPacketOutOfOrderTest
+RelayTimeoutPacketInv == ~RelayTimeoutPacketTest
+RelayRecvPacketAndCrashInv == ~RelayRecvPacketAndCrashTest
+
+====
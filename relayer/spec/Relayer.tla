@@ -0,0 +1,248 @@
+---- MODULE Relayer ----
+EXTENDS Apalache, Integers, FiniteSets, Relayer_typedefs
+
+CONSTANTS
+    \* Set of blockchain names
+    \* @type: Set(CHAIN_ID);
+    CHAIN_IDS,
+    \* Maximum number of packets sent by each chain
+    \* @type: Int;
+    MAX_PACKETS
+
+VARIABLES
+    \* Interchain state
+    \* @type: CHAIN_ID -> CHAIN;
+    chains,
+    \* @type: Bool;
+    relayerRunning,
+    \* Action performed at current step
+    \* @type: ACTION;
+    action
+
+\* Actions
+NullAction == "Null"
+SendPacketAction == "SendPacket"
+ExpirePacketsAction == "ExpirePackets"
+RelayRecvPacketAction == "RelayRecvPacket"
+RelayRecvPacketAndCrashAction == "RelayRecvPacketAndCrash"
+RelayAckPacketAction == "RelayAckPacket"
+RelayTimeoutPacketAction == "RelayTimeoutPacket"
+ClearPacketsAction == "ClearPackets"
+ClearAcksAction == "ClearAcks"
+CrashRelayerAction == "CrashRelayer"
+RestartRelayerAction == "RestartRelayer"
+
+(*
+We model the relaying of packets on a channel between two chains,
+using the following actions.
+
+SendPacket              : a chain sends a packet, with a timeout which may expire
+ExpirePackets           : the counterparty of a chain advances past the timeout
+                          of the expiring packets sent by the chain
+
+RelayRecvPacket         : the relayer delivers the `SendPacket` event of a packet,
+                          in any order, and relays it to the counterparty
+RelayRecvPacketAndCrash : the relayer relays a packet to the counterparty, but
+                          crashes before seeing the transaction confirmed
+RelayAckPacket          : the relayer relays the acknowledgement of a packet
+RelayTimeoutPacket      : the relayer relays the timeout of an expired packet
+
+ClearPackets            : the relayer relays every pending packet, or its timeout
+ClearAcks               : the relayer relays every pending acknowledgement
+
+CrashRelayer            : the relayer crashes, losing its in-memory state
+RestartRelayer          : the relayer restarts
+*)
+
+\* @type: (Str, CHAIN_ID, SEQUENCE, Bool) => ACTION;
+MkAction(name, chainId, sequence, expiring) == [
+    name |-> name,
+    chainId |-> chainId,
+    sequence |-> sequence,
+    expiring |-> expiring
+]
+
+\* @type: (CHAIN_ID) => CHAIN;
+Genesis(chainId) == [
+    id |-> chainId,
+    nextSequence |-> 1,
+    expiring |-> {},
+    committed |-> {},
+    expired |-> {},
+    acknowledged |-> {},
+    timedOut |-> {},
+    received |-> {}
+]
+
+\* The channel connects the chains 1 and 2
+\* @type: (CHAIN_ID) => CHAIN_ID;
+Counterparty(chainId) == 3 - chainId
+
+\* Packets sent by a chain which its counterparty can receive
+\* @type: (CHAIN_ID) => Set(SEQUENCE);
+Receivable(chainId) ==
+    (chains[chainId].committed \ chains[Counterparty(chainId)].received)
+        \ chains[chainId].expired
+
+\* Packets sent by a chain which can be acknowledged
+\* @type: (CHAIN_ID) => Set(SEQUENCE);
+Acknowledgeable(chainId) ==
+    chains[chainId].committed \intersect chains[Counterparty(chainId)].received
+
+\* Packets sent by a chain which can be timed out
+\* @type: (CHAIN_ID) => Set(SEQUENCE);
+TimeoutAble(chainId) ==
+    chains[chainId].committed \intersect chains[chainId].expired
+
+\* @type: (CHAIN_ID, Set(SEQUENCE)) => (CHAIN_ID -> CHAIN);
+Receive(chainId, sequences) ==
+    [chains EXCEPT ![Counterparty(chainId)].received = @ \union sequences]
+
+\* @type: (CHAIN_ID, Set(SEQUENCE)) => (CHAIN_ID -> CHAIN);
+Acknowledge(chainId, sequences) ==
+    [chains EXCEPT ![chainId] = [@ EXCEPT
+        !.committed = @ \ sequences,
+        !.acknowledged = @ \union sequences
+    ]]
+
+\* @type: (CHAIN_ID, Set(SEQUENCE)) => (CHAIN_ID -> CHAIN);
+Timeout(chainId, sequences) ==
+    [chains EXCEPT ![chainId] = [@ EXCEPT
+        !.committed = @ \ sequences,
+        !.timedOut = @ \union sequences
+    ]]
+
+SendPacketNext ==
+    \E chainId \in CHAIN_IDS, expiring \in BOOLEAN:
+        LET sequence == chains[chainId].nextSequence IN
+        /\ sequence <= MAX_PACKETS
+        /\ chains' = [chains EXCEPT ![chainId] = [@ EXCEPT
+                !.nextSequence = sequence + 1,
+                !.committed = @ \union {sequence},
+                !.expiring = IF expiring THEN @ \union {sequence} ELSE @
+            ]]
+        /\ action' = MkAction(SendPacketAction, chainId, sequence, expiring)
+        /\ UNCHANGED relayerRunning
+
+ExpirePacketsNext ==
+    \E chainId \in CHAIN_IDS:
+        LET expiring == Receivable(chainId) \intersect chains[chainId].expiring IN
+        /\ expiring /= {}
+        /\ chains' = [chains EXCEPT ![chainId].expired = @ \union expiring]
+        /\ action' = MkAction(ExpirePacketsAction, chainId, 0, FALSE)
+        /\ UNCHANGED relayerRunning
+
+RelayRecvPacketNext ==
+    \E chainId \in CHAIN_IDS:
+        \E sequence \in Receivable(chainId):
+            /\ relayerRunning
+            /\ chains' = Receive(chainId, {sequence})
+            /\ action' = MkAction(RelayRecvPacketAction, chainId, sequence, FALSE)
+            /\ UNCHANGED relayerRunning
+
+RelayRecvPacketAndCrashNext ==
+    \E chainId \in CHAIN_IDS:
+        \E sequence \in Receivable(chainId):
+            /\ relayerRunning
+            /\ chains' = Receive(chainId, {sequence})
+            /\ action' = MkAction(RelayRecvPacketAndCrashAction, chainId, sequence, FALSE)
+            /\ relayerRunning' = FALSE
+
+RelayAckPacketNext ==
+    \E chainId \in CHAIN_IDS:
+        \E sequence \in Acknowledgeable(chainId):
+            /\ relayerRunning
+            /\ chains' = Acknowledge(chainId, {sequence})
+            /\ action' = MkAction(RelayAckPacketAction, chainId, sequence, FALSE)
+            /\ UNCHANGED relayerRunning
+
+RelayTimeoutPacketNext ==
+    \E chainId \in CHAIN_IDS:
+        \E sequence \in TimeoutAble(chainId):
+            /\ relayerRunning
+            /\ chains' = Timeout(chainId, {sequence})
+            /\ action' = MkAction(RelayTimeoutPacketAction, chainId, sequence, FALSE)
+            /\ UNCHANGED relayerRunning
+
+ClearPacketsNext ==
+    \E chainId \in CHAIN_IDS:
+        LET
+            receivable == Receivable(chainId)
+            timeoutAble == TimeoutAble(chainId)
+        IN
+        /\ relayerRunning
+        /\ receivable \union timeoutAble /= {}
+        /\ chains' = [Receive(chainId, receivable) EXCEPT ![chainId] = [@ EXCEPT
+                !.committed = @ \ timeoutAble,
+                !.timedOut = @ \union timeoutAble
+            ]]
+        /\ action' = MkAction(ClearPacketsAction, chainId, 0, FALSE)
+        /\ UNCHANGED relayerRunning
+
+ClearAcksNext ==
+    \E chainId \in CHAIN_IDS:
+        /\ relayerRunning
+        /\ Acknowledgeable(chainId) /= {}
+        /\ chains' = Acknowledge(chainId, Acknowledgeable(chainId))
+        /\ action' = MkAction(ClearAcksAction, chainId, 0, FALSE)
+        /\ UNCHANGED relayerRunning
+
+CrashRelayerNext ==
+    /\ relayerRunning
+    /\ relayerRunning' = FALSE
+    /\ action' = MkAction(CrashRelayerAction, 0, 0, FALSE)
+    /\ UNCHANGED chains
+
+RestartRelayerNext ==
+    /\ ~relayerRunning
+    /\ relayerRunning' = TRUE
+    /\ action' = MkAction(RestartRelayerAction, 0, 0, FALSE)
+    /\ UNCHANGED chains
+
+Init ==
+    /\ chains = [chainId \in CHAIN_IDS |-> Genesis(chainId)]
+    /\ relayerRunning = TRUE
+    /\ action = MkAction(NullAction, 0, 0, FALSE)
+
+Next ==
+    \/ SendPacketNext
+    \/ ExpirePacketsNext
+    \/ RelayRecvPacketNext
+    \/ RelayRecvPacketAndCrashNext
+    \/ RelayAckPacketNext
+    \/ RelayTimeoutPacketNext
+    \/ ClearPacketsNext
+    \/ ClearAcksNext
+    \/ CrashRelayerNext
+    \/ RestartRelayerNext
+
+\* A packet is never both received and timed out
+NoDoubleRelay ==
+    \A chainId \in CHAIN_IDS:
+        chains[chainId].timedOut \intersect chains[Counterparty(chainId)].received = {}
+
+\* Only the packets which expired are timed out
+TimeoutAfterExpiry ==
+    \A chainId \in CHAIN_IDS:
+        chains[chainId].timedOut \subseteq chains[chainId].expired
+
+\* Only the packets which were received are acknowledged
+AckAfterReceipt ==
+    \A chainId \in CHAIN_IDS:
+        chains[chainId].acknowledged \subseteq chains[Counterparty(chainId)].received
+
+\* Every packet sent is either pending, acknowledged or timed out
+NoPacketLost ==
+    \A chainId \in CHAIN_IDS:
+        \A sequence \in 1..(chains[chainId].nextSequence - 1):
+            sequence \in chains[chainId].committed
+                \union chains[chainId].acknowledged
+                \union chains[chainId].timedOut
+
+Invariant ==
+    /\ NoDoubleRelay
+    /\ TimeoutAfterExpiry
+    /\ AckAfterReceipt
+    /\ NoPacketLost
+
+====
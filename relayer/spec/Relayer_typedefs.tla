@@ -0,0 +1,37 @@
+---- MODULE Relayer_typedefs ----
+
+(*
+    A chain records the packets it sent on the channel:
+    - `expiring`: packets with a timeout close enough to expire,
+    - `committed`: packets whose commitment is still stored,
+    - `expired`: packets whose timeout elapsed on the counterparty before
+      they were received,
+    - `acknowledged`: packets which were acknowledged,
+    - `timedOut`: packets which were timed out,
+    as well as the packets it `received` from the counterparty, whose
+    acknowledgements are written right away.
+
+    @typeAlias: CHAIN_ID = Int;
+    @typeAlias: SEQUENCE = Int;
+
+    @typeAlias: CHAIN = [
+        id: CHAIN_ID,
+        nextSequence: SEQUENCE,
+        expiring: Set(SEQUENCE),
+        committed: Set(SEQUENCE),
+        expired: Set(SEQUENCE),
+        acknowledged: Set(SEQUENCE),
+        timedOut: Set(SEQUENCE),
+        received: Set(SEQUENCE)
+    ];
+
+    @typeAlias: ACTION = [
+        name: Str,
+        chainId: CHAIN_ID,
+        sequence: SEQUENCE,
+        expiring: Bool
+    ];
+*)
+typedefs == TRUE
+
+====
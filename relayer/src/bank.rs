@@ -0,0 +1,104 @@
+//! Transfers of tokens between accounts of the same chain, through the bank module.
+
+use flex_error::define_error;
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::IbcEvent;
+use ibc::signer::Signer;
+use ibc_proto::cosmos::bank::v1beta1::MsgSend;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::google::protobuf::Any;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::tracking::TrackedMsgs;
+use crate::error::Error;
+
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+define_error! {
+    BankError {
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed while submitting the bank send message to chain {0}",
+                    e.chain_id)
+            },
+
+        TxResponse
+            { event: String }
+            |e| {
+                format!("tx response event consists of an error: {}",
+                    e.event)
+            },
+    }
+}
+
+pub fn build_send_message(sender: &Signer, receiver: &Signer, amount: Coin) -> Any {
+    let msg = MsgSend {
+        from_address: sender.to_string(),
+        to_address: receiver.to_string(),
+        amount: vec![amount],
+    };
+
+    let mut buf_msg = Vec::new();
+    prost::Message::encode(&msg, &mut buf_msg).unwrap();
+
+    Any {
+        type_url: MSG_SEND_TYPE_URL.to_string(),
+        value: buf_msg,
+    }
+}
+
+/// Send the given amount from the account of the key configured for the chain
+/// to the given receiver, and wait for the transaction to be committed.
+pub fn send_tokens<Chain: ChainHandle>(
+    chain: &Chain,
+    receiver: &Signer,
+    amount: Coin,
+) -> Result<Vec<IbcEvent>, BankError> {
+    let sender = chain.get_signer().map_err(BankError::key)?;
+
+    let msg = build_send_message(&sender, receiver, amount);
+
+    let events = chain
+        .send_messages_and_wait_commit(TrackedMsgs::new_single(msg, "bank-send"))
+        .map_err(|e| BankError::submit(chain.id(), e))?;
+
+    check_events(events)
+}
+
+/// Send the given amount from the account of the key `key_name` in the keyring of the chain,
+/// whose address is `sender`, to the given receiver, and wait for the transaction to be committed.
+pub fn send_tokens_with_key<Chain: ChainHandle>(
+    chain: &Chain,
+    key_name: &str,
+    sender: &Signer,
+    receiver: &Signer,
+    amount: Coin,
+) -> Result<Vec<IbcEvent>, BankError> {
+    let msg = build_send_message(sender, receiver, amount);
+
+    let events = chain
+        .send_messages_with_key_and_wait_commit(
+            key_name.to_string(),
+            TrackedMsgs::new_single(msg, "bank-send"),
+        )
+        .map_err(|e| BankError::submit(chain.id(), e))?;
+
+    check_events(events)
+}
+
+/// Check if the chain rejected the transaction
+fn check_events(events: Vec<IbcEvent>) -> Result<Vec<IbcEvent>, BankError> {
+    match events.iter().find_map(|event| match event {
+        IbcEvent::ChainError(e) => Some(e.clone()),
+        _ => None,
+    }) {
+        None => Ok(events),
+        Some(e) => Err(BankError::tx_response(e)),
+    }
+}
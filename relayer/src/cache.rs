@@ -14,14 +14,18 @@ use ibc::core::ics03_connection::connection::ConnectionEnd;
 use ibc::core::ics04_channel::channel::ChannelEnd;
 use ibc::core::ics24_host::identifier::{ClientId, ConnectionId, PortChannelId};
 
+use crate::denom::DenomTrace;
+
 const CHANNEL_CACHE_TTL: Duration = Duration::from_secs(60);
 const CONNECTION_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
 const CLIENT_STATE_CACHE_TTL: Duration = Duration::from_millis(500);
 const LATEST_HEIGHT_CACHE_TTL: Duration = Duration::from_millis(200);
+const DENOM_TRACE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
 
 const CHANNEL_CACHE_CAPACITY: u64 = 10_000;
 const CONNECTION_CACHE_CAPACITY: u64 = 10_000;
 const CLIENT_STATE_CACHE_CAPACITY: u64 = 10_000;
+const DENOM_TRACE_CACHE_CAPACITY: u64 = 10_000;
 
 /// Whether or not a result was in cache (ie. a cache hit)
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -47,6 +51,8 @@ pub struct Cache {
     client_states: MokaCache<ClientId, AnyClientState>,
     /// The latest `Height` associated with the chain runtime this `Cache` is associated with.
     latest_height: MokaCache<(), Height>,
+    /// Cache storing [`DenomTrace`]s keyed by their hashes.
+    denom_traces: MokaCache<String, DenomTrace>,
 }
 
 impl Default for Cache {
@@ -78,11 +84,17 @@ impl Cache {
             .max_capacity(1)
             .build();
 
+        let denom_traces = MokaCache::builder()
+            .time_to_live(DENOM_TRACE_CACHE_TTL)
+            .max_capacity(DENOM_TRACE_CACHE_CAPACITY)
+            .build();
+
         Cache {
             channels,
             connections,
             client_states,
             latest_height,
+            denom_traces,
         }
     }
 
@@ -174,6 +186,31 @@ impl Cache {
             Ok((height, CacheStatus::Miss))
         }
     }
+
+    /// Return a cached [`DenomTrace`] via its hash if it exists in the cache.
+    /// Otherwise, attempts to fetch it via the supplied fetcher function `F`. If `F`
+    /// returns successfully with the denom trace, a copy of it is stored in the cache
+    /// before it is returned.
+    ///
+    /// The trace of a denom never changes once created, hence the large time-to-live.
+    pub fn get_or_try_insert_denom_trace_with<F, E>(
+        &self,
+        hash: &str,
+        f: F,
+    ) -> CacheResult<DenomTrace, E>
+    where
+        F: FnOnce() -> Result<DenomTrace, E>,
+    {
+        let hash = hash.to_string();
+
+        if let Some(trace) = self.denom_traces.get(&hash) {
+            Ok((trace, CacheStatus::Hit))
+        } else {
+            let trace = f()?;
+            self.denom_traces.insert(hash, trace.clone());
+            Ok((trace, CacheStatus::Miss))
+        }
+    }
 }
 
 impl fmt::Debug for Cache {
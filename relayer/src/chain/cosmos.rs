@@ -34,11 +34,12 @@ use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectio
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::events as ChannelEvents;
 use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
 use ibc::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradePath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
 };
 use ibc::core::ics24_host::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
 use ibc::events::IbcEvent;
@@ -55,13 +56,16 @@ use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
 };
+use crate::chain::cosmos::dry_run::{simulate_batched_messages, SimulatedTx};
 use crate::chain::cosmos::encode::encode_to_bech32;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::query::account::{get_or_fetch_account, query_account};
 use crate::chain::cosmos::query::balance::query_balance;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
+use crate::chain::cosmos::query::staking::{query_staking_params, unbonding_period};
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::query_txs;
+use crate::chain::cosmos::query::upgrade::{query_applied_upgrades, query_upgrade_plan};
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
@@ -75,23 +79,25 @@ use crate::event::monitor::{EventMonitor, EventReceiver, TxMonitorCmd};
 use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
+use crate::upgrade_chain::UpgradePlan;
 
 use super::requests::{
     IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-    QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-    QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHeight,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-    QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketEventDataRequest,
-    QueryPacketReceiptRequest, QueryTxRequest, QueryUnreceivedAcksRequest,
-    QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
-    QueryUpgradedConsensusStateRequest,
+    QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+    QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+    QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryHeight, QueryHostConsensusStateRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketEventDataRequest, QueryPacketReceiptRequest,
+    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
 };
 
 pub mod batch;
 pub mod client;
 pub mod compatibility;
+pub mod dry_run;
 pub mod encode;
 pub mod estimate;
 pub mod gas;
@@ -100,6 +106,7 @@ pub mod retry;
 pub mod simulate;
 pub mod tx;
 pub mod types;
+pub mod validate;
 pub mod version;
 pub mod wait;
 
@@ -228,41 +235,14 @@ impl CosmosSdkChain {
         crate::time!("query_staking_params");
         crate::telemetry!(query, self.id(), "query_staking_params");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(Error::grpc_transport)?;
-
-        let request =
-            tonic::Request::new(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {});
-
-        let response = self
-            .block_on(client.params(request))
-            .map_err(Error::grpc_status)?;
-
-        let params = response
-            .into_inner()
-            .params
-            .ok_or_else(|| Error::grpc_response_param("no staking params".to_string()))?;
-
-        Ok(params)
+        self.block_on(query_staking_params(&self.grpc_addr))
     }
 
     /// The unbonding period of this chain
     pub fn unbonding_period(&self) -> Result<Duration, Error> {
         crate::time!("unbonding_period");
 
-        let unbonding_time = self.query_staking_params()?.unbonding_time.ok_or_else(|| {
-            Error::grpc_response_param("no unbonding time in staking params".to_string())
-        })?;
-
-        Ok(Duration::new(
-            unbonding_time.seconds as u64,
-            unbonding_time.nanos as u32,
-        ))
+        unbonding_period(&self.query_staking_params()?)
     }
 
     /// The number of historical entries kept by this chain
@@ -417,6 +397,31 @@ impl CosmosSdkChain {
         let account =
             get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
 
+        if self.config.dry_run {
+            // The events are predicted for the next block
+            let height = query_status(&self.config.id, &self.rpc_client, &self.config.rpc_addr)
+                .await?
+                .height
+                .increment();
+
+            let simulated_txs = simulate_batched_messages(
+                &self.tx_config,
+                self.config.max_msg_num,
+                self.config.max_tx_size,
+                &key_entry,
+                account,
+                &self.config.memo_prefix,
+                proto_msgs,
+                height,
+            )
+            .await?;
+
+            return Ok(simulated_txs
+                .into_iter()
+                .flat_map(|simulated_tx| simulated_tx.ibc_events)
+                .collect());
+        }
+
         send_batched_messages_and_wait_commit(
             &self.tx_config,
             self.config.max_msg_num,
@@ -429,6 +434,70 @@ impl CosmosSdkChain {
         .await
     }
 
+    async fn do_send_messages_with_key_and_wait_commit(
+        &mut self,
+        key_name: &str,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        // The account of the relayer key is cached, so its transactions go through the usual path.
+        if key_name == self.config.key_name {
+            return self.do_send_messages_and_wait_commit(tracked_msgs).await;
+        }
+
+        crate::time!("send_messages_with_key_and_wait_commit");
+
+        let _span = span!(
+            Level::DEBUG,
+            "send_tx_commit",
+            id = %tracked_msgs.tracking_id(),
+            key = %key_name
+        )
+        .entered();
+
+        let key_entry = self.keybase().get_key(key_name).map_err(Error::key_base)?;
+
+        // The sequence of any other account is fetched anew for every call.
+        let mut account: Account = query_account(&self.grpc_addr, &key_entry.account)
+            .await?
+            .into();
+
+        if self.config.dry_run {
+            // The events are predicted for the next block
+            let height = query_status(&self.config.id, &self.rpc_client, &self.config.rpc_addr)
+                .await?
+                .height
+                .increment();
+
+            let simulated_txs = simulate_batched_messages(
+                &self.tx_config,
+                self.config.max_msg_num,
+                self.config.max_tx_size,
+                &key_entry,
+                &account,
+                &self.config.memo_prefix,
+                tracked_msgs.msgs,
+                height,
+            )
+            .await?;
+
+            return Ok(simulated_txs
+                .into_iter()
+                .flat_map(|simulated_tx| simulated_tx.ibc_events)
+                .collect());
+        }
+
+        send_batched_messages_and_wait_commit(
+            &self.tx_config,
+            self.config.max_msg_num,
+            self.config.max_tx_size,
+            &key_entry,
+            &mut account,
+            &self.config.memo_prefix,
+            tracked_msgs.msgs,
+        )
+        .await
+    }
+
     async fn do_send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
@@ -445,6 +514,31 @@ impl CosmosSdkChain {
         let account =
             get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
 
+        if self.config.dry_run {
+            // The events are predicted for the next block
+            let height = query_status(&self.config.id, &self.rpc_client, &self.config.rpc_addr)
+                .await?
+                .height
+                .increment();
+
+            let simulated_txs = simulate_batched_messages(
+                &self.tx_config,
+                self.config.max_msg_num,
+                self.config.max_tx_size,
+                &key_entry,
+                account,
+                &self.config.memo_prefix,
+                proto_msgs,
+                height,
+            )
+            .await?;
+
+            return Ok(simulated_txs
+                .iter()
+                .map(SimulatedTx::to_tx_sync_response)
+                .collect());
+        }
+
         send_batched_messages_and_wait_check_tx(
             &self.tx_config,
             self.config.max_msg_num,
@@ -593,6 +687,16 @@ impl ChainEndpoint for CosmosSdkChain {
         runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &mut self,
+        key_name: &str,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let runtime = self.rt.clone();
+
+        runtime.block_on(self.do_send_messages_with_key_and_wait_commit(key_name, tracked_msgs))
+    }
+
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
@@ -678,6 +782,23 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(denom_trace)
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        crate::time!("query_upgrade_plan");
+        crate::telemetry!(query, self.id(), "query_upgrade_plan");
+
+        self.block_on(query_upgrade_plan(&self.grpc_addr))
+    }
+
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        crate::time!("query_applied_upgrades");
+        crate::telemetry!(query, self.id(), "query_applied_upgrades");
+
+        self.block_on(query_applied_upgrades(
+            &self.rpc_client,
+            &self.config.rpc_addr,
+        ))
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!("query_commitment_prefix");
         crate::telemetry!(query, self.id(), "query_commitment_prefix");
@@ -1427,6 +1548,95 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        crate::time!("query_channel_upgrade_sequence");
+        crate::telemetry!(query, self.id(), "query_channel_upgrade_sequence");
+
+        let res = self.query(
+            ChannelUpgradePath::Sequence(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        // No upgrade was ever attempted on the channel if the sequence is not set,
+        // otherwise we expect a u64 encoded in big-endian.
+        let sequence = match res.value.len() {
+            0 => 0,
+            8 => Bytes::from(res.value).get_u64(),
+            _ => return Err(Error::query("channel_upgrade_sequence".into())),
+        };
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((sequence, Some(proof)))
+            }
+            IncludeProof::No => Ok((sequence, None)),
+        }
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        crate::time!("query_channel_upgrade_timeout");
+        crate::telemetry!(query, self.id(), "query_channel_upgrade_timeout");
+
+        let res = self.query(
+            ChannelUpgradePath::Timeout(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        if res.value.is_empty() {
+            return Err(Error::empty_response_value());
+        }
+
+        let timeout = UpgradeTimeout::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((timeout, Some(proof)))
+            }
+            IncludeProof::No => Ok((timeout, None)),
+        }
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        crate::time!("query_channel_upgrade_error");
+        crate::telemetry!(query, self.id(), "query_channel_upgrade_error");
+
+        let res = self.query(
+            ChannelUpgradePath::Error(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        if res.value.is_empty() {
+            return Err(Error::empty_response_value());
+        }
+
+        let error_receipt = ErrorReceipt::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((error_receipt, Some(proof)))
+            }
+            IncludeProof::No => Ok((error_receipt, None)),
+        }
+    }
+
     /// This function queries transactions for events matching certain criteria.
     /// 1. Client Update request - returns a vector with at most one update client event
     /// 2. Packet event request - returns at most one packet event for each sequence specified
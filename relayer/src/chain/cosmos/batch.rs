@@ -132,7 +132,7 @@ async fn send_messages_as_batches(
     Ok(tx_sync_results)
 }
 
-fn batch_messages(
+pub fn batch_messages(
     max_msg_num: MaxMsgNum,
     max_tx_size: MaxTxSize,
     messages: Vec<Any>,
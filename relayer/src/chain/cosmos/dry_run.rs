@@ -0,0 +1,297 @@
+use ibc::events::{from_tx_response_event, IbcEvent};
+use ibc::Height;
+use ibc_proto::cosmos::tx::v1beta1::{Tx, TxRaw};
+use ibc_proto::google::protobuf::Any;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use subtle_encoding::base64;
+use tendermint::abci::tag::Tag;
+use tendermint::abci::transaction::Hash as TxHash;
+use tendermint::abci::{Code, Data, Event as AbciEvent, Log};
+use tendermint_proto::abci::Event;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tracing::info;
+
+use crate::chain::cosmos::batch::batch_messages;
+use crate::chain::cosmos::encode::{encode_tx_raw, sign_tx};
+use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::simulate::send_tx_simulate;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::error::Error;
+use crate::keyring::KeyEntry;
+
+/// The outcome of the simulation of a transaction which was not submitted.
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulatedTx {
+    /// Hash of the simulated transaction. A transaction submitted for the same messages
+    /// has another hash, as its fee is then estimated from the gas used.
+    #[serde(skip)]
+    pub hash: TxHash,
+    pub messages: Vec<SimulatedMessage>,
+    /// Gas used by the simulation, `None` if the simulation failed
+    pub gas_used: Option<u64>,
+    /// Fee that would have been paid for the transaction, `None` if the simulation failed
+    pub estimated_fee: Option<String>,
+    /// Events that would have been emitted by the transaction
+    pub events: Vec<SimulatedEvent>,
+    /// Error returned by the simulation, if any
+    pub error: Option<String>,
+    /// The IBC events that would have been emitted by the transaction,
+    /// or a `ChainError` event per message if the simulation failed.
+    #[serde(skip)]
+    pub ibc_events: Vec<IbcEvent>,
+}
+
+/// A message of a simulated transaction, with its base64-encoded protobuf value.
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulatedMessage {
+    pub type_url: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulatedEvent {
+    pub kind: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl SimulatedTx {
+    /// The response the chain would have returned to the broadcast of the transaction,
+    /// carrying the outcome of the simulation in its log.
+    pub fn to_tx_sync_response(&self) -> Response {
+        let code = match self.error {
+            None => Code::Ok,
+            // The simulation does not report the ABCI code of the error
+            Some(_) => Code::Err(1),
+        };
+
+        Response {
+            code,
+            data: Data::from(Vec::new()),
+            log: Log::from(serde_json::to_string(self).unwrap_or_default().as_str()),
+            hash: self.hash,
+        }
+    }
+}
+
+/// Builds the transactions that would be submitted for the given messages, batched
+/// as they would be, and simulates them instead of submitting them.
+/// The predicted events are reported at the given height.
+#[allow(clippy::too_many_arguments)]
+pub async fn simulate_batched_messages(
+    config: &TxConfig,
+    max_msg_num: MaxMsgNum,
+    max_tx_size: MaxTxSize,
+    key_entry: &KeyEntry,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    height: Height,
+) -> Result<Vec<SimulatedTx>, Error> {
+    let batches = batch_messages(max_msg_num, max_tx_size, messages)?;
+
+    let mut simulated_txs = Vec::new();
+
+    for batch in batches {
+        let simulated_tx =
+            simulate_messages(config, key_entry, account, tx_memo, batch, height).await?;
+
+        info!(
+            chain = %config.chain_id,
+            "dry run, the following transaction was simulated and not submitted: {}",
+            serde_json::to_string_pretty(&simulated_tx).unwrap_or_default()
+        );
+
+        simulated_txs.push(simulated_tx);
+    }
+
+    Ok(simulated_txs)
+}
+
+async fn simulate_messages(
+    config: &TxConfig,
+    key_entry: &KeyEntry,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    height: Height,
+) -> Result<SimulatedTx, Error> {
+    let message_count = messages.len();
+
+    let simulated_messages = messages
+        .iter()
+        .map(|message| SimulatedMessage {
+            type_url: message.type_url.clone(),
+            value: String::from_utf8_lossy(&base64::encode(&message.value)).into_owned(),
+        })
+        .collect();
+
+    let signed_tx = sign_tx(
+        config,
+        key_entry,
+        account,
+        tx_memo,
+        messages,
+        &config.gas_config.max_fee,
+    )?;
+
+    let tx_bytes = encode_tx_raw(TxRaw {
+        body_bytes: signed_tx.body_bytes,
+        auth_info_bytes: signed_tx.auth_info_bytes,
+        signatures: signed_tx.signatures.clone(),
+    })?;
+
+    let hash = TxHash::new(Sha256::digest(&tx_bytes).into());
+
+    let tx = Tx {
+        body: Some(signed_tx.body),
+        auth_info: Some(signed_tx.auth_info),
+        signatures: signed_tx.signatures,
+    };
+
+    let simulated_tx = match send_tx_simulate(&config.grpc_address, tx).await {
+        Ok(response) => {
+            let gas_used = response.gas_info.map(|gas_info| gas_info.gas_used);
+
+            let estimated_fee = gas_used
+                .map(|gas_used| gas_amount_to_fee(&config.gas_config, gas_used))
+                .and_then(|fee| fee.amount.into_iter().next())
+                .map(|coin| format!("{}{}", coin.amount, coin.denom));
+
+            let events: Vec<SimulatedEvent> = response
+                .result
+                .map(|result| result.events.iter().map(simulated_event).collect())
+                .unwrap_or_default();
+
+            let ibc_events = events
+                .iter()
+                .filter_map(|event| from_tx_response_event(height, &abci_event(event)))
+                .collect();
+
+            SimulatedTx {
+                hash,
+                messages: simulated_messages,
+                gas_used,
+                estimated_fee,
+                events,
+                error: None,
+                ibc_events,
+            }
+        }
+        Err(e) => SimulatedTx {
+            hash,
+            messages: simulated_messages,
+            gas_used: None,
+            estimated_fee: None,
+            events: Vec::new(),
+            ibc_events: vec![
+                IbcEvent::ChainError(format!(
+                    "simulation on chain {} reports error: {}",
+                    config.chain_id, e
+                ));
+                message_count
+            ],
+            error: Some(e.to_string()),
+        },
+    };
+
+    Ok(simulated_tx)
+}
+
+fn simulated_event(event: &Event) -> SimulatedEvent {
+    SimulatedEvent {
+        kind: event.r#type.clone(),
+        attributes: event
+            .attributes
+            .iter()
+            .map(|attribute| {
+                (
+                    String::from_utf8_lossy(&attribute.key).into_owned(),
+                    String::from_utf8_lossy(&attribute.value).into_owned(),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn abci_event(event: &SimulatedEvent) -> AbciEvent {
+    AbciEvent {
+        type_str: event.kind.clone(),
+        attributes: event
+            .attributes
+            .iter()
+            .map(|(key, value)| Tag {
+                key: key.parse().expect("Key::from_str() impl is infallible"),
+                value: value.parse().expect("Value::from_str() impl is infallible"),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::ClientId;
+    use ibc::events::{from_tx_response_event, IbcEvent};
+    use ibc::Height;
+    use tendermint::abci::transaction::Hash as TxHash;
+    use tendermint::abci::Code;
+
+    use super::{abci_event, SimulatedEvent, SimulatedTx};
+
+    #[test]
+    fn predicted_ibc_events() {
+        let height = Height::new(0, 42).unwrap();
+
+        let event = SimulatedEvent {
+            kind: "create_client".to_string(),
+            attributes: vec![
+                ("client_id".to_string(), "07-tendermint-3".to_string()),
+                ("client_type".to_string(), "07-tendermint".to_string()),
+                ("consensus_height".to_string(), "1-10".to_string()),
+            ],
+        };
+
+        match from_tx_response_event(height, &abci_event(&event)) {
+            Some(IbcEvent::CreateClient(create_client)) => {
+                let client_id: ClientId = "07-tendermint-3".parse().unwrap();
+                assert_eq!(create_client.client_id(), &client_id);
+                assert_eq!(create_client.height(), height);
+            }
+            other => panic!("expected a CreateClient event, got {:?}", other),
+        }
+
+        // Events of other modules are not predicted as IBC events
+        let event = SimulatedEvent {
+            kind: "transfer".to_string(),
+            attributes: vec![("amount".to_string(), "100stake".to_string())],
+        };
+
+        assert!(from_tx_response_event(height, &abci_event(&event)).is_none());
+    }
+
+    #[test]
+    fn simulated_tx_sync_response() {
+        let mut simulated_tx = SimulatedTx {
+            hash: TxHash::new([1; 32]),
+            messages: Vec::new(),
+            gas_used: Some(100_000),
+            estimated_fee: Some("1000stake".to_string()),
+            events: Vec::new(),
+            error: None,
+            ibc_events: Vec::new(),
+        };
+
+        let response = simulated_tx.to_tx_sync_response();
+        assert_eq!(response.code, Code::Ok);
+        assert_eq!(response.hash, simulated_tx.hash);
+        assert!(response.log.to_string().contains("1000stake"));
+
+        simulated_tx.error = Some("insufficient funds".to_string());
+
+        let response = simulated_tx.to_tx_sync_response();
+        assert!(response.code.is_err());
+        assert!(response.log.to_string().contains("insufficient funds"));
+    }
+}
@@ -126,7 +126,7 @@ fn encode_signer_info(
     Ok(signer_info)
 }
 
-fn encode_tx_raw(tx_raw: TxRaw) -> Result<Vec<u8>, Error> {
+pub fn encode_tx_raw(tx_raw: TxRaw) -> Result<Vec<u8>, Error> {
     let mut tx_bytes = Vec::new();
     prost::Message::encode(&tx_raw, &mut tx_bytes)
         .map_err(|e| Error::protobuf_encode("Transaction".to_string(), e))?;
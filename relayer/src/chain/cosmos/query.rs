@@ -16,8 +16,11 @@ use crate::error::Error;
 pub mod account;
 pub mod balance;
 pub mod denom_trace;
+pub mod fee;
+pub mod staking;
 pub mod status;
 pub mod tx;
+pub mod upgrade;
 
 /// Generic query response type
 #[derive(Clone, Debug, PartialEq)]
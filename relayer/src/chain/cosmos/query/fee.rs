@@ -0,0 +1,110 @@
+use http::uri::{PathAndQuery, Uri};
+use tonic::codec::ProstCodec;
+use tonic::transport::Endpoint;
+
+use crate::config::GasPrice;
+use crate::error::Error;
+
+/// Path of the `Config` method of the node service, which reports the minimum gas prices
+/// configured on the node (Cosmos SDK v0.46 and later).
+const NODE_CONFIG_PATH: &str = "/cosmos.base.node.v1beta1.Service/Config";
+
+// The node service is not part of `ibc-proto` yet, hence these hand-written messages, which
+// follow `cosmos/base/node/v1beta1/query.proto`.
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigResponse {
+    #[prost(string, tag = "1")]
+    minimum_gas_price: String,
+}
+
+/// Uses the GRPC client to retrieve the minimum gas prices configured on the node, which are
+/// empty if the node accepts transactions without fees.
+pub async fn query_min_gas_prices(grpc_address: &Uri) -> Result<Vec<GasPrice>, Error> {
+    let channel = Endpoint::from(grpc_address.clone())
+        .connect()
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await.map_err(|e| {
+        Error::grpc_status(tonic::Status::unknown(format!(
+            "service was not ready: {}",
+            e
+        )))
+    })?;
+
+    let response: tonic::Response<ConfigResponse> = client
+        .unary(
+            tonic::Request::new(ConfigRequest {}),
+            PathAndQuery::from_static(NODE_CONFIG_PATH),
+            ProstCodec::default(),
+        )
+        .await
+        .map_err(Error::grpc_status)?;
+
+    parse_min_gas_prices(&response.into_inner().minimum_gas_price)
+}
+
+/// Parses minimum gas prices as configured on a node, eg. `0.0025uatom,0.01stake`.
+pub fn parse_min_gas_prices(min_gas_prices: &str) -> Result<Vec<GasPrice>, Error> {
+    min_gas_prices
+        .split(',')
+        .map(str::trim)
+        .filter(|price| !price.is_empty())
+        .map(|price| {
+            let invalid =
+                || Error::grpc_response_param(format!("invalid minimum gas price '{}'", price));
+
+            let split = price
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .ok_or_else(invalid)?;
+            let (amount, denom) = price.split_at(split);
+
+            Ok(GasPrice::new(
+                amount.parse().map_err(|_| invalid())?,
+                denom.to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_min_gas_prices;
+    use crate::config::GasPrice;
+
+    #[test]
+    fn parse_min_gas_prices_of_node() {
+        assert_eq!(
+            parse_min_gas_prices("0.0025uatom, 0.01stake").unwrap(),
+            vec![
+                GasPrice::new(0.0025, "uatom".to_string()),
+                GasPrice::new(0.01, "stake".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            parse_min_gas_prices(
+                "1ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            )
+            .unwrap(),
+            vec![GasPrice::new(
+                1.0,
+                "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2".to_string()
+            )]
+        );
+
+        assert!(parse_min_gas_prices("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid_min_gas_prices() {
+        assert!(parse_min_gas_prices("uatom").is_err());
+        assert!(parse_min_gas_prices("0.0025").is_err());
+        assert!(parse_min_gas_prices("0.0.25uatom").is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use core::time::Duration;
+
+use http::uri::Uri;
+
+use ibc_proto::cosmos::staking::v1beta1::{
+    query_client::QueryClient, Params as StakingParams, QueryParamsRequest,
+};
+
+use crate::error::Error;
+
+/// Uses the GRPC client to retrieve the staking parameters of the chain
+pub async fn query_staking_params(grpc_address: &Uri) -> Result<StakingParams, Error> {
+    let mut client = QueryClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QueryParamsRequest {});
+
+    let response = client.params(request).await.map_err(Error::grpc_status)?;
+
+    response
+        .into_inner()
+        .params
+        .ok_or_else(|| Error::grpc_response_param("no staking params".to_string()))
+}
+
+/// Extracts the unbonding period from the staking parameters of the chain
+pub fn unbonding_period(params: &StakingParams) -> Result<Duration, Error> {
+    let unbonding_time = params.unbonding_time.as_ref().ok_or_else(|| {
+        Error::grpc_response_param("no unbonding time in staking params".to_string())
+    })?;
+
+    Ok(Duration::new(
+        unbonding_time.seconds as u64,
+        unbonding_time.nanos as u32,
+    ))
+}
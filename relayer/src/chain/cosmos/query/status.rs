@@ -1,5 +1,8 @@
+use core::time::Duration;
+
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc::Height;
+use tendermint::block::Height as TmHeight;
 use tendermint_rpc::{Client, HttpClient, Url};
 
 use crate::chain::endpoint::ChainStatus;
@@ -39,3 +42,41 @@ pub async fn query_status(
         timestamp: time.into(),
     })
 }
+
+/// Query the headers of the latest `sample_size` blocks via an RPC query,
+/// and return the longest interval observed between two consecutive blocks.
+///
+/// Returns `None` if fewer than two blocks could be retrieved.
+pub async fn query_max_block_interval(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    sample_size: u32,
+) -> Result<Option<Duration>, Error> {
+    let latest_height = rpc_client
+        .abci_info()
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?
+        .last_block_height;
+
+    let min_height = latest_height
+        .value()
+        .saturating_sub(u64::from(sample_size))
+        .max(1);
+
+    let min_height = TmHeight::try_from(min_height).map_err(Error::invalid_height)?;
+
+    // The `/blockchain` endpoint returns the block metas in descending order of height,
+    // and caps the number of metas it returns, so the sample may be smaller than requested.
+    let block_metas = rpc_client
+        .blockchain(min_height, latest_height)
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?
+        .block_metas;
+
+    let max_interval = block_metas
+        .windows(2)
+        .filter_map(|pair| pair[0].header.time.duration_since(pair[1].header.time).ok())
+        .max();
+
+    Ok(max_interval)
+}
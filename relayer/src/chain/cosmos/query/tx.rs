@@ -201,7 +201,11 @@ fn packet_from_tx_search_response(
         .tx_result
         .events
         .into_iter()
-        .find_map(|ev| filter_matching_event(ev, request, seq)))
+        .find_map(|ev| filter_matching_event(ev, request, seq))
+        .map(|mut ev| {
+            ev.set_height(height);
+            ev
+        }))
 }
 
 fn filter_matching_event(
@@ -230,11 +234,27 @@ fn filter_matching_event(
         IbcEvent::SendPacket(ref send_ev) if matches_packet(request, seq, &send_ev.packet) => {
             Some(ibc_event)
         }
+        IbcEvent::ReceivePacket(ref recv_ev) if matches_packet(request, seq, &recv_ev.packet) => {
+            Some(ibc_event)
+        }
         IbcEvent::WriteAcknowledgement(ref ack_ev)
             if matches_packet(request, seq, &ack_ev.packet) =>
         {
             Some(ibc_event)
         }
+        IbcEvent::AcknowledgePacket(ref ack_ev) if matches_packet(request, seq, &ack_ev.packet) => {
+            Some(ibc_event)
+        }
+        IbcEvent::TimeoutPacket(ref timeout_ev)
+            if matches_packet(request, seq, &timeout_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
+        IbcEvent::TimeoutOnClosePacket(ref timeout_ev)
+            if matches_packet(request, seq, &timeout_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
         _ => None,
     }
 }
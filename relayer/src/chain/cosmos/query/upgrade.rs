@@ -0,0 +1,129 @@
+use core::str::FromStr;
+
+use http::uri::Uri;
+use prost::Message;
+use tendermint::abci::Path as TendermintABCIPath;
+use tendermint::block::Height;
+use tendermint_rpc::{HttpClient, Url};
+
+use ibc_proto::cosmos::base::kv::v1beta1::{Pair, Pairs};
+use ibc_proto::cosmos::upgrade::v1beta1::{query_client::QueryClient, QueryCurrentPlanRequest};
+
+use crate::chain::cosmos::query::abci_query;
+use crate::error::Error;
+use crate::upgrade_chain::UpgradePlan;
+
+/// Path to iterate over the keys of the upgrade module store sharing a given prefix.
+const SDK_UPGRADE_SUBSPACE_QUERY_PATH: &str = "store/upgrade/subspace";
+
+/// Prefix of the keys under which the upgrade module records the upgrades it applied.
+const DONE_PREFIX: u8 = 0x1;
+
+/// Uses the GRPC client to retrieve the upgrade plan currently scheduled, if any.
+pub async fn query_upgrade_plan(grpc_address: &Uri) -> Result<Option<UpgradePlan>, Error> {
+    let mut client = QueryClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QueryCurrentPlanRequest {});
+
+    let response = client
+        .current_plan(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(Error::grpc_status)?;
+
+    Ok(response.plan.map(|plan| UpgradePlan {
+        name: plan.name,
+        height: plan.height as u64,
+    }))
+}
+
+/// Uses the RPC client to retrieve the upgrades applied so far, as recorded
+/// in the store of the upgrade module.
+pub async fn query_applied_upgrades(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+) -> Result<Vec<UpgradePlan>, Error> {
+    // SAFETY: Creating a Path from a constant; this should never fail
+    let path = TendermintABCIPath::from_str(SDK_UPGRADE_SUBSPACE_QUERY_PATH)
+        .expect("Turning SDK upgrade subspace query path constant into a Tendermint ABCI path");
+
+    let response = abci_query(
+        rpc_client,
+        rpc_address,
+        path,
+        char::from(DONE_PREFIX).to_string(),
+        Height::from(0_u32),
+        false,
+    )
+    .await?;
+
+    let pairs = Pairs::decode(response.value.as_slice())
+        .map_err(|e| Error::protobuf_decode("Pairs".to_string(), e))?;
+
+    Ok(pairs
+        .pairs
+        .iter()
+        .filter_map(decode_applied_upgrade)
+        .collect())
+}
+
+/// Decodes an entry of the applied upgrades, which is keyed by
+/// `0x1 | name` with the height as value up to Cosmos SDK v0.45, and
+/// keyed by `0x1 | height | name` since Cosmos SDK v0.46.
+fn decode_applied_upgrade(pair: &Pair) -> Option<UpgradePlan> {
+    let key = pair.key.strip_prefix(&[DONE_PREFIX])?;
+
+    let (height, name) = match <[u8; 8]>::try_from(pair.value.as_slice()) {
+        Ok(height) => (height, key),
+        Err(_) => {
+            let height = <[u8; 8]>::try_from(key.get(..8)?).ok()?;
+            (height, &key[8..])
+        }
+    };
+
+    Some(UpgradePlan {
+        name: String::from_utf8(name.to_vec()).ok()?,
+        height: u64::from_be_bytes(height),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::kv::v1beta1::Pair;
+
+    use super::decode_applied_upgrade;
+    use crate::upgrade_chain::UpgradePlan;
+
+    fn plan(name: &str, height: u64) -> Option<UpgradePlan> {
+        Some(UpgradePlan {
+            name: name.to_string(),
+            height,
+        })
+    }
+
+    #[test]
+    fn decode_applied_upgrades() {
+        // Up to Cosmos SDK v0.45
+        let pair = Pair {
+            key: [&[0x1], b"v2".as_slice()].concat(),
+            value: 300_u64.to_be_bytes().to_vec(),
+        };
+        assert_eq!(decode_applied_upgrade(&pair), plan("v2", 300));
+
+        // Since Cosmos SDK v0.46
+        let pair = Pair {
+            key: [&[0x1], 300_u64.to_be_bytes().as_slice(), b"v2"].concat(),
+            value: vec![0x1],
+        };
+        assert_eq!(decode_applied_upgrade(&pair), plan("v2", 300));
+
+        // Not an applied upgrade
+        let pair = Pair {
+            key: [&[0x0], b"v2".as_slice()].concat(),
+            value: 300_u64.to_be_bytes().to_vec(),
+        };
+        assert_eq!(decode_applied_upgrade(&pair), None);
+    }
+}
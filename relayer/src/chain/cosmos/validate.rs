@@ -0,0 +1,348 @@
+//! Validation of the configuration of a chain, optionally against its live node.
+//!
+//! Unlike [`CosmosSdkChain::validate_params`](super::CosmosSdkChain::validate_params),
+//! which stops at the first error, every check is performed and the outcome of each
+//! is collected in a [`ChainConfigReport`].
+
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+
+use http::uri::Uri;
+use num_bigint::BigInt;
+use serde::Serialize;
+use tendermint::abci::Path as TendermintABCIPath;
+use tendermint_rpc::{Client, HttpClient};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::chain::cosmos::gas::mul_ceil;
+use crate::chain::cosmos::query::fee::query_min_gas_prices;
+use crate::chain::cosmos::query::staking::{query_staking_params, unbonding_period};
+use crate::chain::cosmos::GENESIS_MAX_BYTES_MAX_FRACTION;
+use crate::config::{ChainConfig, GasPrice};
+use crate::keyring::KeyRing;
+
+/// Key of the IBC store holding the sequence of the next client identifier,
+/// which is set by the IBC module at genesis.
+const NEXT_CLIENT_SEQUENCE_KEY: &str = "nextClientSequence";
+
+/// The outcome of a single configuration check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed(String),
+    /// The check could not be performed, eg. because the node could not be reached.
+    Skipped(String),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigCheck {
+    /// The configuration field being checked
+    pub field: &'static str,
+    pub status: CheckStatus,
+}
+
+/// The outcome of all the configuration checks performed for a chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainConfigReport {
+    pub chain_id: ChainId,
+    pub checks: Vec<ConfigCheck>,
+}
+
+impl ChainConfigReport {
+    /// Returns true if none of the checks failed.
+    pub fn is_valid(&self) -> bool {
+        !self
+            .checks
+            .iter()
+            .any(|check| matches!(check.status, CheckStatus::Failed(_)))
+    }
+
+    fn push(&mut self, field: &'static str, status: CheckStatus) {
+        self.checks.push(ConfigCheck { field, status });
+    }
+}
+
+impl fmt::Display for ChainConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "chain '{}':", self.chain_id)?;
+
+        for check in &self.checks {
+            match &check.status {
+                CheckStatus::Passed => writeln!(f, "  [ok]      {}", check.field)?,
+                CheckStatus::Failed(reason) => {
+                    writeln!(f, "  [failed]  {}: {}", check.field, reason)?
+                }
+                CheckStatus::Skipped(reason) => {
+                    writeln!(f, "  [skipped] {}: {}", check.field, reason)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check the parts of the configuration of a chain which do not require its node:
+///
+/// - `account_prefix` matches the address of the configured key.
+pub fn validate_chain_config(config: &ChainConfig) -> ChainConfigReport {
+    let mut report = ChainConfigReport {
+        chain_id: config.id.clone(),
+        checks: Vec::new(),
+    };
+
+    report.push("account_prefix", check_account_prefix(config));
+
+    report
+}
+
+/// Check the configuration of a chain against its live node, in addition to the checks of
+/// [`validate_chain_config`]:
+///
+/// - `id` matches the network reported by the node status,
+/// - `gas_price` is accepted by the minimum gas prices of the node,
+/// - `trusting_period` is shorter than the unbonding period,
+/// - `max_tx_size` is within the consensus parameters,
+/// - `store_prefix` is the prefix of the IBC commitment store.
+pub async fn validate_chain_config_live(config: &ChainConfig) -> ChainConfigReport {
+    let mut report = validate_chain_config(config);
+
+    match HttpClient::new(config.rpc_addr.clone()) {
+        Ok(rpc_client) => {
+            report.push("id", check_chain_id(config, &rpc_client).await);
+            report.push("max_tx_size", check_max_tx_size(config, &rpc_client).await);
+            report.push(
+                "store_prefix",
+                check_store_prefix(config, &rpc_client).await,
+            );
+        }
+        Err(e) => {
+            let reason = format!("cannot connect to RPC endpoint {}: {}", config.rpc_addr, e);
+
+            for field in ["id", "max_tx_size", "store_prefix"] {
+                report.push(field, CheckStatus::Skipped(reason.clone()));
+            }
+        }
+    }
+
+    match Uri::from_str(&config.grpc_addr.to_string()) {
+        Ok(grpc_addr) => {
+            report.push("gas_price", check_gas_price(config, &grpc_addr).await);
+            report.push(
+                "trusting_period",
+                check_trusting_period(config, &grpc_addr).await,
+            );
+        }
+        Err(e) => {
+            let reason = format!("invalid gRPC endpoint {}: {}", config.grpc_addr, e);
+
+            for field in ["gas_price", "trusting_period"] {
+                report.push(field, CheckStatus::Skipped(reason.clone()));
+            }
+        }
+    }
+
+    report
+}
+
+async fn check_chain_id(config: &ChainConfig, rpc_client: &HttpClient) -> CheckStatus {
+    match rpc_client.status().await {
+        Ok(status) if status.node_info.network.as_str() == config.id.as_str() => {
+            CheckStatus::Passed
+        }
+        Ok(status) => CheckStatus::Failed(format!(
+            "node at {} reports network '{}'",
+            config.rpc_addr, status.node_info.network
+        )),
+        Err(e) => CheckStatus::Skipped(format!("/status query failed: {}", e)),
+    }
+}
+
+fn check_account_prefix(config: &ChainConfig) -> CheckStatus {
+    let key = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
+        .and_then(|keyring| keyring.get_key(&config.key_name));
+
+    let key = match key {
+        Ok(key) => key,
+        Err(e) => {
+            return CheckStatus::Skipped(format!("cannot load key '{}': {}", config.key_name, e))
+        }
+    };
+
+    // The human-readable part of a bech32 address ends at the last separator.
+    match key.account.rsplit_once('1') {
+        Some((hrp, _)) if hrp == config.account_prefix => CheckStatus::Passed,
+        _ => CheckStatus::Failed(format!(
+            "address '{}' of key '{}' does not have prefix '{}'",
+            key.account, config.key_name, config.account_prefix
+        )),
+    }
+}
+
+async fn check_gas_price(config: &ChainConfig, grpc_addr: &Uri) -> CheckStatus {
+    match query_min_gas_prices(grpc_addr).await {
+        Ok(min_gas_prices) => check_min_gas_price(&config.gas_price, &min_gas_prices),
+        Err(e) => CheckStatus::Skipped(format!("node config query failed: {}", e)),
+    }
+}
+
+fn check_min_gas_price(gas_price: &GasPrice, min_gas_prices: &[GasPrice]) -> CheckStatus {
+    // A node without minimum gas prices accepts any fee.
+    if min_gas_prices.is_empty() {
+        return CheckStatus::Passed;
+    }
+
+    match min_gas_prices
+        .iter()
+        .find(|min| min.denom == gas_price.denom)
+    {
+        Some(min) if gas_price.price >= min.price => CheckStatus::Passed,
+        Some(min) => CheckStatus::Failed(format!(
+            "gas price {} is below the minimum gas price {} of the node",
+            gas_price, min
+        )),
+        None => CheckStatus::Failed(format!(
+            "denom '{}' is not accepted for fees by the node, which expects one of: {}",
+            gas_price.denom,
+            min_gas_prices
+                .iter()
+                .map(|min| min.denom.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+async fn check_trusting_period(config: &ChainConfig, grpc_addr: &Uri) -> CheckStatus {
+    let unbonding_period = match query_staking_params(grpc_addr)
+        .await
+        .and_then(|p| unbonding_period(&p))
+    {
+        Ok(unbonding_period) => unbonding_period,
+        Err(e) => return CheckStatus::Skipped(format!("staking params query failed: {}", e)),
+    };
+
+    let trusting_period = config.trusting_period.unwrap_or(2 * unbonding_period / 3);
+
+    if trusting_period <= Duration::ZERO {
+        CheckStatus::Failed("trusting period must be greater than zero".to_string())
+    } else if trusting_period >= unbonding_period {
+        CheckStatus::Failed(format!(
+            "trusting period ({:?}) must be smaller than the unbonding period ({:?})",
+            trusting_period, unbonding_period
+        ))
+    } else {
+        CheckStatus::Passed
+    }
+}
+
+async fn check_max_tx_size(config: &ChainConfig, rpc_client: &HttpClient) -> CheckStatus {
+    let latest_height = match rpc_client.abci_info().await {
+        Ok(info) => info.last_block_height,
+        Err(e) => return CheckStatus::Skipped(format!("/abci_info query failed: {}", e)),
+    };
+
+    let max_bytes = match rpc_client.consensus_params(latest_height).await {
+        Ok(response) => response.consensus_params.block.max_bytes,
+        Err(e) => return CheckStatus::Skipped(format!("/consensus_params query failed: {}", e)),
+    };
+
+    let max_tx_size: usize = config.max_tx_size.into();
+
+    if BigInt::from(max_tx_size) > mul_ceil(max_bytes, GENESIS_MAX_BYTES_MAX_FRACTION) {
+        CheckStatus::Failed(format!(
+            "max_tx_size ({}) exceeds {} of the block max_bytes ({})",
+            max_tx_size, GENESIS_MAX_BYTES_MAX_FRACTION, max_bytes
+        ))
+    } else {
+        CheckStatus::Passed
+    }
+}
+
+async fn check_store_prefix(config: &ChainConfig, rpc_client: &HttpClient) -> CheckStatus {
+    let path = match TendermintABCIPath::from_str(&format!("store/{}/key", config.store_prefix)) {
+        Ok(path) => path,
+        Err(e) => return CheckStatus::Failed(format!("invalid store prefix: {}", e)),
+    };
+
+    let response = rpc_client
+        .abci_query(
+            Some(path),
+            NEXT_CLIENT_SEQUENCE_KEY.as_bytes().to_vec(),
+            None,
+            false,
+        )
+        .await;
+
+    match response {
+        Ok(response) if response.code.is_ok() && !response.value.is_empty() => CheckStatus::Passed,
+        Ok(response) if response.code.is_ok() => CheckStatus::Failed(format!(
+            "store '{}' does not hold the IBC state",
+            config.store_prefix
+        )),
+        Ok(response) => CheckStatus::Failed(format!(
+            "store '{}' cannot be queried: {}",
+            config.store_prefix, response.log
+        )),
+        Err(e) => CheckStatus::Skipped(format!("/abci_query failed: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_min_gas_price, ChainConfigReport, CheckStatus};
+    use crate::config::GasPrice;
+
+    fn gas_price(price: f64, denom: &str) -> GasPrice {
+        GasPrice::new(price, denom.to_string())
+    }
+
+    #[test]
+    fn gas_price_against_min_gas_prices() {
+        let min_gas_prices = [gas_price(0.0025, "uatom"), gas_price(0.01, "stake")];
+
+        assert_eq!(
+            check_min_gas_price(&gas_price(0.0025, "uatom"), &min_gas_prices),
+            CheckStatus::Passed
+        );
+        assert_eq!(
+            check_min_gas_price(&gas_price(0.1, "stake"), &min_gas_prices),
+            CheckStatus::Passed
+        );
+        assert!(matches!(
+            check_min_gas_price(&gas_price(0.001, "uatom"), &min_gas_prices),
+            CheckStatus::Failed(_)
+        ));
+        assert!(matches!(
+            check_min_gas_price(&gas_price(0.1, "uosmo"), &min_gas_prices),
+            CheckStatus::Failed(_)
+        ));
+
+        // Any fee is accepted by a node without minimum gas prices.
+        assert_eq!(
+            check_min_gas_price(&gas_price(0.0, "uosmo"), &[]),
+            CheckStatus::Passed
+        );
+    }
+
+    #[test]
+    fn report_is_valid_unless_a_check_failed() {
+        let mut report = ChainConfigReport {
+            chain_id: "ibc-0".parse().unwrap(),
+            checks: Vec::new(),
+        };
+
+        report.push("id", CheckStatus::Passed);
+        report.push("gas_price", CheckStatus::Skipped("unreachable".to_string()));
+        assert!(report.is_valid());
+
+        report.push("max_tx_size", CheckStatus::Failed("too large".to_string()));
+        assert!(!report.is_valid());
+        assert!(report
+            .to_string()
+            .contains("[failed]  max_tx_size: too large"));
+    }
+}
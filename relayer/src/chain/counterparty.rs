@@ -6,8 +6,9 @@ use tracing::{error, trace};
 
 use super::requests::{
     IncludeProof, PageRequest, QueryChannelRequest, QueryClientConnectionsRequest,
-    QueryClientStateRequest, QueryConnectionRequest, QueryPacketAcknowledgementsRequest,
-    QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    QueryClientEventRequest, QueryClientStateRequest, QueryConnectionRequest,
+    QueryConsensusStatesRequest, QueryPacketAcknowledgementsRequest, QueryPacketEventDataRequest,
+    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
 };
 use super::{
     handle::ChainHandle,
@@ -19,15 +20,20 @@ use crate::path::PathIdentifiers;
 use crate::supervisor::Error;
 use ibc::{
     core::{
+        ics02_client::client_consensus::AnyConsensusStateWithHeight,
         ics02_client::client_state::{ClientState, IdentifiedAnyClientState},
         ics03_connection::connection::{
             ConnectionEnd, IdentifiedConnectionEnd, State as ConnectionState,
         },
         ics04_channel::channel::{IdentifiedChannelEnd, State},
+        ics04_channel::packet::Packet,
+        ics04_channel::timeout::TimeoutHeight,
         ics24_host::identifier::{
             ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId,
         },
     },
+    events::{IbcEvent, WithBlockDataType},
+    timestamp::Timestamp,
     Height,
 };
 
@@ -613,3 +619,220 @@ pub fn pending_packet_summary(
         unreceived_acks: pending_acks,
     })
 }
+
+/// The stage reached by a packet in its lifecycle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacketStatus {
+    /// No event was found for the packet on either chain.
+    NotFound,
+    /// Sent on the source chain, but not yet received on the destination chain.
+    Sent,
+    /// Received on the destination chain,
+    /// but the acknowledgement is not yet received on the source chain.
+    Received,
+    /// The acknowledgement was received on the source chain.
+    Acknowledged,
+    /// The timeout of the packet was processed on the source chain.
+    TimedOut,
+}
+
+/// An event of the lifecycle of a packet, along with the client update
+/// that installed the consensus state used to verify the proof carried
+/// by the message which emitted the event, if any.
+#[derive(Clone, Debug, Serialize)]
+pub struct PacketLifecycleEvent {
+    pub chain_id: ChainId,
+    pub event: IbcEvent,
+    pub client_update: Option<IbcEvent>,
+}
+
+/// The events emitted by both ends of a channel over the lifecycle of a packet.
+#[derive(Clone, Debug, Serialize)]
+pub struct PacketLifecycle {
+    pub sequence: Sequence,
+    pub status: PacketStatus,
+    /// The `SendPacket` event on the source chain.
+    pub send: Option<PacketLifecycleEvent>,
+    /// The `ReceivePacket` event on the destination chain.
+    pub recv: Option<PacketLifecycleEvent>,
+    /// The `WriteAcknowledgement` event on the destination chain.
+    pub write_ack: Option<PacketLifecycleEvent>,
+    /// The `AcknowledgePacket` event on the source chain.
+    pub ack: Option<PacketLifecycleEvent>,
+    /// The `TimeoutPacket` or `TimeoutOnClosePacket` event on the source chain.
+    pub timeout: Option<PacketLifecycleEvent>,
+}
+
+/// Reconstructs the lifecycle of the packet with the given sequence, sent on `channel`
+/// from `chain` to `counterparty_chain`, from the transactions of both chains.
+///
+/// The client update reported for an event is the one that installed the earliest consensus
+/// state of the counterparty chain which can verify the proof of the event, provided that
+/// the update happened no later than the event. This is a best-effort lookup: it finds
+/// nothing if that consensus state was pruned, and does not apply to `TimeoutOnClosePacket`.
+pub fn packet_lifecycle(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    channel: &IdentifiedChannelEnd,
+    connection: &IdentifiedConnectionEnd,
+    sequence: Sequence,
+) -> Result<PacketLifecycle, Error> {
+    let counterparty = channel.channel_end.counterparty();
+    let counterparty_channel_id = counterparty
+        .channel_id
+        .as_ref()
+        .ok_or_else(Error::missing_counterparty_channel_id)?;
+
+    // The client of the counterparty chain hosted by the chain, and vice versa
+    let client_id = connection.connection_end.client_id();
+    let counterparty_client_id = connection.connection_end.counterparty().client_id();
+
+    let request = |event_id| QueryPacketEventDataRequest {
+        event_id,
+        source_channel_id: channel.channel_id.clone(),
+        source_port_id: channel.port_id.clone(),
+        destination_channel_id: counterparty_channel_id.clone(),
+        destination_port_id: counterparty.port_id.clone(),
+        sequences: vec![sequence],
+        height: QueryHeight::Latest,
+    };
+
+    let send = query_packet_event(chain, request(WithBlockDataType::SendPacket))?;
+    let recv = query_packet_event(counterparty_chain, request(WithBlockDataType::RecvPacket))?;
+    let write_ack = query_packet_event(counterparty_chain, request(WithBlockDataType::WriteAck))?;
+    let ack = query_packet_event(chain, request(WithBlockDataType::AckPacket))?;
+    let timeout = match query_packet_event(chain, request(WithBlockDataType::Timeout))? {
+        Some(timeout) => Some(timeout),
+        None => query_packet_event(chain, request(WithBlockDataType::TimeoutOnClose))?,
+    };
+
+    // The proof of the packet commitment can only be verified
+    // against a consensus state past the height at which it was sent.
+    let recv_client_update = match (&recv, &send) {
+        (Some(recv), Some(send)) => find_client_update_for_proof(
+            counterparty_chain,
+            counterparty_client_id,
+            recv.height(),
+            |cs| cs.height > send.height(),
+        )?,
+        _ => None,
+    };
+
+    // Likewise for the proof of the acknowledgement
+    let ack_client_update = match (&ack, &write_ack) {
+        (Some(ack), Some(write_ack)) => {
+            find_client_update_for_proof(chain, client_id, ack.height(), |cs| {
+                cs.height > write_ack.height()
+            })?
+        }
+        _ => None,
+    };
+
+    // The proof of non-receipt can only be verified against
+    // a consensus state past the timeout of the packet.
+    let timeout_client_update = match &timeout {
+        Some(IbcEvent::TimeoutPacket(timeout)) => {
+            find_client_update_for_proof(chain, client_id, timeout.height, |cs| {
+                has_timed_out(&timeout.packet, cs)
+            })?
+        }
+        _ => None,
+    };
+
+    let status = if ack.is_some() {
+        PacketStatus::Acknowledged
+    } else if timeout.is_some() {
+        PacketStatus::TimedOut
+    } else if recv.is_some() || write_ack.is_some() {
+        PacketStatus::Received
+    } else if send.is_some() {
+        PacketStatus::Sent
+    } else {
+        PacketStatus::NotFound
+    };
+
+    let lifecycle_event =
+        |chain_id: ChainId, event: Option<IbcEvent>, client_update: Option<IbcEvent>| {
+            event.map(|event| PacketLifecycleEvent {
+                chain_id,
+                event,
+                client_update,
+            })
+        };
+
+    Ok(PacketLifecycle {
+        sequence,
+        status,
+        send: lifecycle_event(chain.id(), send, None),
+        recv: lifecycle_event(counterparty_chain.id(), recv, recv_client_update),
+        write_ack: lifecycle_event(counterparty_chain.id(), write_ack, None),
+        ack: lifecycle_event(chain.id(), ack, ack_client_update),
+        timeout: lifecycle_event(chain.id(), timeout, timeout_client_update),
+    })
+}
+
+fn query_packet_event(
+    chain: &impl ChainHandle,
+    request: QueryPacketEventDataRequest,
+) -> Result<Option<IbcEvent>, Error> {
+    let events = chain
+        .query_txs(QueryTxRequest::Packet(request))
+        .map_err(Error::relayer)?;
+
+    Ok(events.into_iter().next())
+}
+
+/// Returns the update of the client `client_id` hosted by `chain` which installed
+/// the earliest consensus state satisfying `can_verify`, if that update happened
+/// no later than `height`.
+fn find_client_update_for_proof(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+    height: Height,
+    can_verify: impl Fn(&AnyConsensusStateWithHeight) -> bool,
+) -> Result<Option<IbcEvent>, Error> {
+    let consensus_states = chain
+        .query_consensus_states(QueryConsensusStatesRequest {
+            client_id: client_id.clone(),
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::relayer)?;
+
+    let consensus_height = match consensus_states
+        .iter()
+        .filter(|cs| can_verify(cs))
+        .map(|cs| cs.height)
+        .min()
+    {
+        Some(consensus_height) => consensus_height,
+        None => return Ok(None),
+    };
+
+    let events = chain
+        .query_txs(QueryTxRequest::Client(QueryClientEventRequest {
+            query_height: QueryHeight::Specific(height),
+            event_id: WithBlockDataType::UpdateClient,
+            client_id: client_id.clone(),
+            consensus_height,
+        }))
+        .map_err(Error::relayer)?;
+
+    Ok(events.into_iter().next())
+}
+
+/// Whether the given consensus state of the destination chain of a packet
+/// is past the timeout height or timestamp of the packet.
+fn has_timed_out(packet: &Packet, consensus_state: &AnyConsensusStateWithHeight) -> bool {
+    let height_reached = match packet.timeout_height {
+        TimeoutHeight::At(timeout_height) => consensus_state.height >= timeout_height,
+        TimeoutHeight::Never => false,
+    };
+
+    let timestamp_reached = packet.timeout_timestamp != Timestamp::none()
+        && !packet
+            .timeout_timestamp
+            .after(&consensus_state.consensus_state.timestamp());
+
+    height_reached || timestamp_reached
+}
@@ -15,6 +15,7 @@ use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectio
 use ibc::core::ics03_connection::version::{get_compatible_versions, Version};
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::events::IbcEvent;
@@ -27,13 +28,14 @@ use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
 use crate::chain::requests::{
-    QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
-    QueryClientConnectionsRequest, QueryClientStateRequest, QueryClientStatesRequest,
-    QueryConnectionChannelsRequest, QueryConnectionRequest, QueryConnectionsRequest,
-    QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
-    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelUpgradeRequest,
+    QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
+    QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
+    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
+    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest,
+    QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
@@ -43,6 +45,7 @@ use crate::error::{Error, QUERY_PROOF_EXPECT_MSG};
 use crate::event::monitor::{EventReceiver, TxMonitorCmd};
 use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::LightClient;
+use crate::upgrade_chain::UpgradePlan;
 
 use super::requests::{
     IncludeProof, QueryBlockRequest, QueryHeight, QueryPacketAcknowledgementRequest,
@@ -114,6 +117,14 @@ pub trait ChainEndpoint: Sized {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Same as `send_messages_and_wait_commit`, but the transactions are signed by the key
+    /// with the given name in the keybase, instead of the relayer key.
+    fn send_messages_with_key_and_wait_commit(
+        &mut self,
+        key_name: &str,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error>;
+
     /// Sends one or more transactions with `msgs` to chain.
     /// Non-blocking alternative to `send_messages_and_wait_commit` interface.
     fn send_messages_and_wait_check_tx(
@@ -141,6 +152,17 @@ pub trait ChainEndpoint: Sized {
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
+    /// Query the upgrade plan currently scheduled on the chain, if any.
+    /// Chains without an upgrade module never have one.
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        Ok(None)
+    }
+
+    /// Query the upgrades applied on the chain so far, along with their heights.
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        Ok(Vec::new())
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error>;
 
     fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
@@ -313,6 +335,33 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the sequence of the latest upgrade attempt of a channel,
+    /// stored at path `path::ChannelUpgradePath::Sequence`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the timeout of the upgrade in progress on a channel,
+    /// stored at path `path::ChannelUpgradePath::Timeout`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the error receipt of the last aborted upgrade of a channel,
+    /// stored at path `path::ChannelUpgradePath::Error`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error>;
 
     fn query_blocks(
@@ -20,6 +20,7 @@ use ibc::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, UpgradeTimeout},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -38,6 +39,7 @@ use crate::{
     error::Error,
     event::monitor::{EventBatch, Result as MonitorResult},
     keyring::KeyEntry,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{
@@ -45,14 +47,15 @@ use super::{
     endpoint::{ChainStatus, HealthCheck},
     requests::{
         IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-        QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-        QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-        QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-        QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-        QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-        QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
-        QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-        QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+        QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+        QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+        QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+        QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
+        QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+        QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+        QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryTxRequest,
+        QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+        QueryUpgradedConsensusStateRequest,
     },
     tracking::TrackedMsgs,
 };
@@ -60,9 +63,11 @@ use super::{
 mod base;
 mod cache;
 mod counting;
+mod faulty;
 
 pub use base::BaseChainHandle;
 pub use counting::CountingChainHandle;
+pub use faulty::{Fault, FaultRule, FaultSchedule, FaultyChainHandle, InjectedFault};
 
 pub type CachingChainHandle = cache::CachingChainHandle<BaseChainHandle>;
 pub type CountingAndCachingChainHandle =
@@ -124,6 +129,12 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEvent>>,
     },
 
+    SendMessagesWithKeyAndWaitCommit {
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<IbcEvent>>,
+    },
+
     SendMessagesAndWaitCheckTx {
         tracked_msgs: TrackedMsgs,
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
@@ -161,6 +172,14 @@ pub enum ChainRequest {
         reply_to: ReplyTo<DenomTrace>,
     },
 
+    QueryUpgradePlan {
+        reply_to: ReplyTo<Option<UpgradePlan>>,
+    },
+
+    QueryAppliedUpgrades {
+        reply_to: ReplyTo<Vec<UpgradePlan>>,
+    },
+
     QueryApplicationStatus {
         reply_to: ReplyTo<ChainStatus>,
     },
@@ -282,6 +301,24 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(Sequence, Option<MerkleProof>)>,
     },
 
+    QueryChannelUpgradeSequence {
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(u64, Option<MerkleProof>)>,
+    },
+
+    QueryChannelUpgradeTimeout {
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(UpgradeTimeout, Option<MerkleProof>)>,
+    },
+
+    QueryChannelUpgradeError {
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    },
+
     BuildChannelProofs {
         port_id: PortId,
         channel_id: ChannelId,
@@ -374,6 +411,14 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Same as [`ChainHandle::send_messages_and_wait_commit`], but the transactions are signed
+    /// by the key with the given name in the keyring of the chain, instead of the relayer key.
+    fn send_messages_with_key_and_wait_commit(
+        &self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error>;
+
     /// Submit messages asynchronously.
     /// Does not block waiting on the chain to produce the
     /// resulting events. Instead of events, this method
@@ -401,6 +446,12 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
+    /// Query the upgrade plan currently scheduled on the chain, if any.
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error>;
+
+    /// Query the upgrades applied on the chain so far, along with their heights.
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error>;
+
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
@@ -487,6 +538,33 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the sequence of the latest upgrade attempt of a channel,
+    /// stored at path `path::ChannelUpgradePath::Sequence`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the timeout of the upgrade in progress on a channel,
+    /// stored at path `path::ChannelUpgradePath::Timeout`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the error receipt of the last aborted upgrade of a channel,
+    /// stored at path `path::ChannelUpgradePath::Error`. A proof can optionally
+    /// be returned along with the result.
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     /// Performs a query to retrieve all the channels of a chain.
     fn query_channels(
         &self,
@@ -578,7 +656,8 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
     ) -> Result<(Vec<Sequence>, Height), Error>;
 
     /// Performs a query to retrieve a given packet receipt, stored on the chain at path
-    /// `path::CommitmentsPath`. A proof can optionally be returned along with the result.
+    /// `path::CommitmentsPath`. A proof can optionally
+    /// be returned along with the result.
     fn query_packet_receipt(
         &self,
         request: QueryPacketReceiptRequest,
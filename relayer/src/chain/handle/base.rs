@@ -14,6 +14,7 @@ use ibc::{
         ics03_connection::version::Version,
         ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
         ics04_channel::packet::{PacketMsgType, Sequence},
+        ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
@@ -32,13 +33,14 @@ use crate::{
         endpoint::ChainStatus,
         requests::{
             IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-            QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-            QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-            QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-            QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-            QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-            QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
-            QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+            QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+            QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+            QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+            QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
+            QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+            QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+            QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryTxRequest,
+            QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
             QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
         },
         tracking::TrackedMsgs,
@@ -48,6 +50,7 @@ use crate::{
     denom::DenomTrace,
     error::Error,
     keyring::KeyEntry,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{reply_channel, ChainHandle, ChainRequest, HealthCheck, ReplyTo, Subscription};
@@ -116,6 +119,18 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.send(|reply_to| ChainRequest::SendMessagesWithKeyAndWaitCommit {
+            key_name,
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
     fn send_messages_and_wait_check_tx(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -158,6 +173,14 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryDenomTrace { hash, reply_to })
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgradePlan { reply_to })
+    }
+
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        self.send(|reply_to| ChainRequest::QueryAppliedUpgrades { reply_to })
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.send(|reply_to| ChainRequest::QueryApplicationStatus { reply_to })
     }
@@ -267,6 +290,42 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryChannelUpgradeSequence {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryChannelUpgradeTimeout {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryChannelUpgradeError {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
@@ -6,6 +6,7 @@ use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::{
     core::ics02_client::header::AnyHeader,
@@ -30,14 +31,15 @@ use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::{
     IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-    QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-    QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHeight,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-    QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
-    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+    QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+    QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryHeight, QueryHostConsensusStateRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryTxRequest,
+    QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
@@ -46,6 +48,7 @@ use crate::denom::DenomTrace;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
 use crate::telemetry;
+use crate::upgrade_chain::UpgradePlan;
 
 /// A chain handle with support for caching.
 /// To be used for the passive relaying mode (i.e., `start` CLI).
@@ -105,6 +108,15 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().send_messages_and_wait_commit(tracked_msgs)
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.inner()
+            .send_messages_with_key_and_wait_commit(key_name, tracked_msgs)
+    }
+
     fn send_messages_and_wait_check_tx(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -137,7 +149,24 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
-        self.inner().query_denom_trace(hash)
+        let handle = self.inner();
+        let (result, in_cache) = self
+            .cache
+            .get_or_try_insert_denom_trace_with(&hash, || handle.query_denom_trace(hash.clone()))?;
+
+        if in_cache == CacheStatus::Hit {
+            telemetry!(query_cache_hit, &self.id(), "query_denom_trace");
+        }
+
+        Ok(result)
+    }
+
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.inner().query_upgrade_plan()
+    }
+
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        self.inner().query_applied_upgrades()
     }
 
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
@@ -294,6 +323,33 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             .query_next_sequence_receive(request, include_proof)
     }
 
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        self.inner()
+            .query_channel_upgrade_sequence(request, include_proof)
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        self.inner()
+            .query_channel_upgrade_timeout(request, include_proof)
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inner()
+            .query_channel_upgrade_error(request, include_proof)
+    }
+
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
@@ -6,6 +6,7 @@ use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::{
     core::ics02_client::header::AnyHeader,
@@ -30,10 +31,10 @@ use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::{
     IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-    QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-    QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+    QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+    QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
     QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
     QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
     QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
@@ -43,6 +44,7 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
 use crate::denom::DenomTrace;
 use crate::error::Error;
+use crate::upgrade_chain::UpgradePlan;
 use crate::util::lock::LockExt;
 use crate::{connection::ConnectionMsgType, keyring::KeyEntry};
 
@@ -124,6 +126,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().send_messages_and_wait_commit(tracked_msgs)
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.inc_metric("send_messages_with_key_and_wait_commit");
+        self.inner()
+            .send_messages_with_key_and_wait_commit(key_name, tracked_msgs)
+    }
+
     fn send_messages_and_wait_check_tx(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -167,6 +179,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_denom_trace(hash)
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.inc_metric("query_upgrade_plan");
+        self.inner().query_upgrade_plan()
+    }
+
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        self.inc_metric("query_applied_upgrades");
+        self.inner().query_applied_upgrades()
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.inc_metric("query_application_status");
         self.inner().query_application_status()
@@ -283,6 +305,36 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
             .query_next_sequence_receive(request, include_proof)
     }
 
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        self.inc_metric("query_channel_upgrade_sequence");
+        self.inner()
+            .query_channel_upgrade_sequence(request, include_proof)
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        self.inc_metric("query_channel_upgrade_timeout");
+        self.inner()
+            .query_channel_upgrade_timeout(request, include_proof)
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inc_metric("query_channel_upgrade_error");
+        self.inner()
+            .query_channel_upgrade_error(request, include_proof)
+    }
+
     fn query_channels(
         &self,
         request: QueryChannelsRequest,
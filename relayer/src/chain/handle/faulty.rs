@@ -0,0 +1,949 @@
+//! A [`ChainHandle`] decorator which injects faults into the requests
+//! it forwards, following a deterministic, seeded [`FaultSchedule`].
+//!
+//! Wrapping the handles of in-process chains with a [`FaultyChainHandle`]
+//! allows testing that the relayer copes with flaky RPC endpoints, eg. that
+//! every packet is eventually delivered exactly once despite failed queries,
+//! lost transaction confirmations or lagging nodes.
+
+use core::fmt;
+use core::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use crossbeam_channel as channel;
+use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
+use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
+use ibc::core::ics02_client::events::UpdateClient;
+use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
+use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::{
+    core::ics02_client::header::AnyHeader,
+    core::ics03_connection::connection::ConnectionEnd,
+    core::ics03_connection::version::Version,
+    core::ics04_channel::channel::ChannelEnd,
+    core::ics23_commitment::commitment::CommitmentPrefix,
+    core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
+    events::IbcEvent,
+    proofs::Proofs,
+    signer::Signer,
+    Height,
+};
+use serde::{Serialize, Serializer};
+use tracing::warn;
+
+use crate::account::Balance;
+use crate::chain::client::ClientSettings;
+use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::requests::{
+    IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
+    QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+    QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+    QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
+    QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
+    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+};
+use crate::chain::tracking::TrackedMsgs;
+use crate::config::ChainConfig;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::upgrade_chain::UpgradePlan;
+use crate::util::lock::LockExt;
+use crate::{connection::ConnectionMsgType, keyring::KeyEntry};
+
+/// A fault which a [`FaultyChainHandle`] can inject into a request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// The request fails without reaching the chain.
+    Fail,
+
+    /// The request reaches the chain, but its response is lost after the
+    /// given delay. Transactions are therefore committed even though the
+    /// caller is told that they timed out.
+    Timeout(Duration),
+
+    /// The latest height reported by the chain is the one it reported to
+    /// the previous query, as if served by a lagging full node.
+    /// Only applies to `query_latest_height` and `query_application_status`.
+    StaleHeight,
+
+    /// The confirmations of the transactions in a batch are returned
+    /// in a shuffled order.
+    /// Only applies to `send_messages_and_wait_commit` and
+    /// `send_messages_and_wait_check_tx`.
+    Reorder,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::Fail => write!(f, "failure"),
+            Fault::Timeout(delay) => write!(f, "timeout after {:?}", delay),
+            Fault::StaleHeight => write!(f, "stale height"),
+            Fault::Reorder => write!(f, "reordered confirmations"),
+        }
+    }
+}
+
+/// Injects a [`Fault`] into the requests with a given name, eg.
+/// `query_packet_commitments`, with a given probability.
+#[derive(Clone, Debug)]
+pub struct FaultRule {
+    /// The name of the [`ChainHandle`] method to inject the fault into,
+    /// or `*` to match every method the fault applies to.
+    pub request: String,
+    pub fault: Fault,
+    /// The probability, between 0 and 1, that a matching request is faulty.
+    pub probability: f64,
+    /// The maximum number of faults this rule injects, if any.
+    pub limit: Option<usize>,
+}
+
+impl FaultRule {
+    pub fn new(request: impl Into<String>, fault: Fault) -> Self {
+        Self {
+            request: request.into(),
+            fault,
+            probability: 1.0,
+            limit: None,
+        }
+    }
+
+    pub fn with_probability(self, probability: f64) -> Self {
+        Self {
+            probability,
+            ..self
+        }
+    }
+
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    fn matches(&self, request: &str) -> bool {
+        self.request == "*" || self.request == request
+    }
+}
+
+/// A seeded list of [`FaultRule`]s.
+///
+/// Rules are evaluated in order for every request, and the first one to fire
+/// decides the fault. Given the same seed and the same sequence of requests,
+/// the same faults are injected.
+#[derive(Clone, Debug, Default)]
+pub struct FaultSchedule {
+    seed: u64,
+    rules: Vec<FaultRule>,
+}
+
+impl FaultSchedule {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rules: Vec::new(),
+        }
+    }
+
+    pub fn with_rule(mut self, rule: FaultRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+}
+
+/// A fault injected by a [`FaultyChainHandle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InjectedFault {
+    pub request: &'static str,
+    pub fault: Fault,
+}
+
+/// A SplitMix64 pseudo-random number generator, which is all we need
+/// to draw reproducible faults from a seed.
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number uniformly drawn from `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FaultState {
+    rules: Vec<FaultRule>,
+    rng: Rng,
+    /// How many faults each rule injected so far.
+    injected_by_rule: Vec<usize>,
+    injected: Vec<InjectedFault>,
+    /// The latest height observed by the previous height query.
+    previous_height: Option<Height>,
+}
+
+impl FaultState {
+    fn new(schedule: FaultSchedule) -> Self {
+        Self {
+            injected_by_rule: vec![0; schedule.rules.len()],
+            rules: schedule.rules,
+            rng: Rng(schedule.seed),
+            injected: Vec::new(),
+            previous_height: None,
+        }
+    }
+
+    fn next_fault(
+        &mut self,
+        request: &'static str,
+        applies: impl Fn(Fault) -> bool,
+    ) -> Option<Fault> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !rule.matches(request) || !applies(rule.fault) {
+                continue;
+            }
+
+            if matches!(rule.limit, Some(limit) if self.injected_by_rule[index] >= limit) {
+                continue;
+            }
+
+            if self.rng.next_f64() < rule.probability {
+                self.injected_by_rule[index] += 1;
+                self.injected.push(InjectedFault {
+                    request,
+                    fault: rule.fault,
+                });
+
+                return Some(rule.fault);
+            }
+        }
+
+        None
+    }
+
+    /// Draws the fault of a height query. A stale height can only
+    /// be reported once a previous query has observed a height.
+    fn next_height_fault(&mut self, request: &'static str) -> Option<Fault> {
+        let has_previous_height = self.previous_height.is_some();
+
+        self.next_fault(request, |fault| {
+            is_failure(fault) || (fault == Fault::StaleHeight && has_previous_height)
+        })
+    }
+}
+
+fn is_failure(fault: Fault) -> bool {
+    matches!(fault, Fault::Fail | Fault::Timeout(_))
+}
+
+/// A [`ChainHandle`] which injects faults into the requests it forwards to
+/// the inner handle, as dictated by its [`FaultSchedule`].
+///
+/// All clones of a handle share the same schedule and random number generator.
+/// Faults are only reproducible if the requests are made in the same order,
+/// which is not guaranteed when the handle is shared by several threads.
+#[derive(Debug, Clone)]
+pub struct FaultyChainHandle<Handle> {
+    inner: Handle,
+    state: Arc<RwLock<FaultState>>,
+}
+
+impl<Handle> FaultyChainHandle<Handle> {
+    /// Wraps the given handle without injecting any fault until a schedule is set.
+    pub fn new(handle: Handle) -> Self {
+        Self::with_schedule(handle, FaultSchedule::default())
+    }
+
+    pub fn with_schedule(handle: Handle, schedule: FaultSchedule) -> Self {
+        Self {
+            inner: handle,
+            state: Arc::new(RwLock::new(FaultState::new(schedule))),
+        }
+    }
+
+    /// Replaces the schedule of this handle and all its clones,
+    /// reseeding the random number generator.
+    pub fn set_schedule(&self, schedule: FaultSchedule) {
+        *self.state.acquire_write() = FaultState::new(schedule);
+    }
+
+    /// Returns the faults injected since the schedule was set, in order.
+    pub fn injected_faults(&self) -> Vec<InjectedFault> {
+        self.state.acquire_read().injected.clone()
+    }
+
+    fn inner(&self) -> &Handle {
+        &self.inner
+    }
+
+    fn next_fault(&self, request: &'static str, applies: impl Fn(Fault) -> bool) -> Option<Fault> {
+        self.state.acquire_write().next_fault(request, applies)
+    }
+}
+
+impl<Handle: ChainHandle> FaultyChainHandle<Handle> {
+    fn injected_error(&self, request: &'static str, fault: Fault) -> Error {
+        warn!(chain = %self.id(), "injecting {} into {}", fault, request);
+        Error::injected_fault(self.id(), request.to_string(), fault.to_string())
+    }
+
+    /// Forwards a request to the inner handle, unless the schedule
+    /// decides that it fails or times out.
+    fn call<T>(
+        &self,
+        request: &'static str,
+        f: impl FnOnce(&Handle) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        match self.next_fault(request, is_failure) {
+            None => f(self.inner()),
+            Some(fault) => self.fail(request, fault, f),
+        }
+    }
+
+    fn fail<T>(
+        &self,
+        request: &'static str,
+        fault: Fault,
+        f: impl FnOnce(&Handle) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        if let Fault::Timeout(delay) = fault {
+            // The response to the request is lost, not the request itself.
+            let _ = f(self.inner());
+            thread::sleep(delay);
+        }
+
+        Err(self.injected_error(request, fault))
+    }
+
+    /// Forwards a height query, possibly reporting the height observed by
+    /// the previous query instead of the latest one.
+    fn query_height<T>(
+        &self,
+        request: &'static str,
+        f: impl FnOnce(&Handle) -> Result<T, Error>,
+        height: impl Fn(&mut T) -> &mut Height,
+    ) -> Result<T, Error> {
+        let fault = self.state.acquire_write().next_height_fault(request);
+
+        match fault {
+            Some(fault) if is_failure(fault) => self.fail(request, fault, f),
+            fault => {
+                let mut response = f(self.inner())?;
+
+                let mut state = self.state.acquire_write();
+                let latest = *height(&mut response);
+                if let (Some(Fault::StaleHeight), Some(previous)) = (fault, state.previous_height) {
+                    warn!(chain = %self.id(), "injecting {} into {}", Fault::StaleHeight, request);
+                    *height(&mut response) = previous;
+                }
+                state.previous_height = Some(latest);
+
+                Ok(response)
+            }
+        }
+    }
+
+    /// Forwards a batch of transactions, possibly shuffling their confirmations.
+    fn send<T>(
+        &self,
+        request: &'static str,
+        f: impl FnOnce(&Handle) -> Result<Vec<T>, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let fault = self.next_fault(request, |fault| {
+            is_failure(fault) || fault == Fault::Reorder
+        });
+
+        match fault {
+            Some(fault) if is_failure(fault) => self.fail(request, fault, f),
+            Some(fault) => {
+                let mut confirmations = f(self.inner())?;
+                warn!(chain = %self.id(), "injecting {} into {}", fault, request);
+                self.state.acquire_write().rng.shuffle(&mut confirmations);
+                Ok(confirmations)
+            }
+            None => f(self.inner()),
+        }
+    }
+}
+
+impl<Handle: Serialize> Serialize for FaultyChainHandle<Handle> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<Handle: ChainHandle> ChainHandle for FaultyChainHandle<Handle> {
+    fn new(chain_id: ChainId, sender: channel::Sender<ChainRequest>) -> Self {
+        Self::new(Handle::new(chain_id, sender))
+    }
+
+    fn id(&self) -> ChainId {
+        self.inner().id()
+    }
+
+    fn shutdown(&self) -> Result<(), Error> {
+        self.inner().shutdown()
+    }
+
+    fn health_check(&self) -> Result<HealthCheck, Error> {
+        self.call("health_check", |handle| handle.health_check())
+    }
+
+    fn subscribe(&self) -> Result<Subscription, Error> {
+        self.call("subscribe", |handle| handle.subscribe())
+    }
+
+    fn send_messages_and_wait_commit(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.send("send_messages_and_wait_commit", |handle| {
+            handle.send_messages_and_wait_commit(tracked_msgs)
+        })
+    }
+
+    fn send_messages_with_key_and_wait_commit(
+        &self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.send("send_messages_with_key_and_wait_commit", |handle| {
+            handle.send_messages_with_key_and_wait_commit(key_name, tracked_msgs)
+        })
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
+        self.send("send_messages_and_wait_check_tx", |handle| {
+            handle.send_messages_and_wait_check_tx(tracked_msgs)
+        })
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        self.call("get_signer", |handle| handle.get_signer())
+    }
+
+    fn config(&self) -> Result<ChainConfig, Error> {
+        self.call("config", |handle| handle.config())
+    }
+
+    fn get_key(&self) -> Result<KeyEntry, Error> {
+        self.call("get_key", |handle| handle.get_key())
+    }
+
+    fn add_key(&self, key_name: String, key: KeyEntry) -> Result<(), Error> {
+        self.call("add_key", |handle| handle.add_key(key_name, key))
+    }
+
+    fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
+        self.call("ibc_version", |handle| handle.ibc_version())
+    }
+
+    fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
+        self.call("query_balance", |handle| handle.query_balance(key_name))
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.call("query_denom_trace", |handle| handle.query_denom_trace(hash))
+    }
+
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.call("query_upgrade_plan", |handle| handle.query_upgrade_plan())
+    }
+
+    fn query_applied_upgrades(&self) -> Result<Vec<UpgradePlan>, Error> {
+        self.call("query_applied_upgrades", |handle| {
+            handle.query_applied_upgrades()
+        })
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        self.query_height(
+            "query_application_status",
+            |handle| handle.query_application_status(),
+            |status| &mut status.height,
+        )
+    }
+
+    fn query_latest_height(&self) -> Result<Height, Error> {
+        self.query_height(
+            "query_latest_height",
+            |handle| handle.query_latest_height(),
+            |height| height,
+        )
+    }
+
+    fn query_clients(
+        &self,
+        request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        self.call("query_clients", |handle| handle.query_clients(request))
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        self.call("query_client_state", |handle| {
+            handle.query_client_state(request, include_proof)
+        })
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.call("query_client_connections", |handle| {
+            handle.query_client_connections(request)
+        })
+    }
+
+    fn query_consensus_states(
+        &self,
+        request: QueryConsensusStatesRequest,
+    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
+        self.call("query_consensus_states", |handle| {
+            handle.query_consensus_states(request)
+        })
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        self.call("query_consensus_state", |handle| {
+            handle.query_consensus_state(request, include_proof)
+        })
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        self.call("query_upgraded_client_state", |handle| {
+            handle.query_upgraded_client_state(request)
+        })
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        self.call("query_upgraded_consensus_state", |handle| {
+            handle.query_upgraded_consensus_state(request)
+        })
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        self.call("query_commitment_prefix", |handle| {
+            handle.query_commitment_prefix()
+        })
+    }
+
+    fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
+        self.call("query_compatible_versions", |handle| {
+            handle.query_compatible_versions()
+        })
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        self.call("query_connection", |handle| {
+            handle.query_connection(request, include_proof)
+        })
+    }
+
+    fn query_connections(
+        &self,
+        request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.call("query_connections", |handle| {
+            handle.query_connections(request)
+        })
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.call("query_connection_channels", |handle| {
+            handle.query_connection_channels(request)
+        })
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        self.call("query_next_sequence_receive", |handle| {
+            handle.query_next_sequence_receive(request, include_proof)
+        })
+    }
+
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        self.call("query_channel_upgrade_sequence", |handle| {
+            handle.query_channel_upgrade_sequence(request, include_proof)
+        })
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        self.call("query_channel_upgrade_timeout", |handle| {
+            handle.query_channel_upgrade_timeout(request, include_proof)
+        })
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.call("query_channel_upgrade_error", |handle| {
+            handle.query_channel_upgrade_error(request, include_proof)
+        })
+    }
+
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.call("query_channels", |handle| handle.query_channels(request))
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        self.call("query_channel", |handle| {
+            handle.query_channel(request, include_proof)
+        })
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        self.call("query_channel_client_state", |handle| {
+            handle.query_channel_client_state(request)
+        })
+    }
+
+    fn build_header(
+        &self,
+        trusted_height: Height,
+        target_height: Height,
+        client_state: AnyClientState,
+    ) -> Result<(AnyHeader, Vec<AnyHeader>), Error> {
+        self.call("build_header", |handle| {
+            handle.build_header(trusted_height, target_height, client_state)
+        })
+    }
+
+    /// Constructs a client state at the given height
+    fn build_client_state(
+        &self,
+        height: Height,
+        options: ClientSettings,
+    ) -> Result<AnyClientState, Error> {
+        self.call("build_client_state", |handle| {
+            handle.build_client_state(height, options)
+        })
+    }
+
+    /// Constructs a consensus state at the given height
+    fn build_consensus_state(
+        &self,
+        trusted: Height,
+        target: Height,
+        client_state: AnyClientState,
+    ) -> Result<AnyConsensusState, Error> {
+        self.call("build_consensus_state", |handle| {
+            handle.build_consensus_state(trusted, target, client_state)
+        })
+    }
+
+    fn check_misbehaviour(
+        &self,
+        update: UpdateClient,
+        client_state: AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        self.call("check_misbehaviour", |handle| {
+            handle.check_misbehaviour(update, client_state)
+        })
+    }
+
+    fn build_connection_proofs_and_client_state(
+        &self,
+        message_type: ConnectionMsgType,
+        connection_id: &ConnectionId,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
+        self.call("build_connection_proofs_and_client_state", |handle| {
+            handle.build_connection_proofs_and_client_state(
+                message_type,
+                connection_id,
+                client_id,
+                height,
+            )
+        })
+    }
+
+    fn build_channel_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.call("build_channel_proofs", |handle| {
+            handle.build_channel_proofs(port_id, channel_id, height)
+        })
+    }
+
+    fn build_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.call("build_packet_proofs", |handle| {
+            handle.build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        })
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.call("query_packet_commitment", |handle| {
+            handle.query_packet_commitment(request, include_proof)
+        })
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.call("query_packet_commitments", |handle| {
+            handle.query_packet_commitments(request)
+        })
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.call("query_packet_receipt", |handle| {
+            handle.query_packet_receipt(request, include_proof)
+        })
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.call("query_unreceived_packets", |handle| {
+            handle.query_unreceived_packets(request)
+        })
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.call("query_packet_acknowledgement", |handle| {
+            handle.query_packet_acknowledgement(request, include_proof)
+        })
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.call("query_packet_acknowledgements", |handle| {
+            handle.query_packet_acknowledgements(request)
+        })
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.call("query_unreceived_acknowledgements", |handle| {
+            handle.query_unreceived_acknowledgements(request)
+        })
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
+        self.call("query_txs", |handle| handle.query_txs(request))
+    }
+
+    fn query_blocks(
+        &self,
+        request: QueryBlockRequest,
+    ) -> Result<(Vec<IbcEvent>, Vec<IbcEvent>), Error> {
+        self.call("query_blocks", |handle| handle.query_blocks(request))
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        request: QueryHostConsensusStateRequest,
+    ) -> Result<AnyConsensusState, Error> {
+        self.call("query_host_consensus_state", |handle| {
+            handle.query_host_consensus_state(request)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use ibc::Height;
+
+    use super::{is_failure, Fault, FaultRule, FaultSchedule, FaultState, InjectedFault, Rng};
+
+    const REQUESTS: [&str; 3] = [
+        "query_latest_height",
+        "query_txs",
+        "send_messages_and_wait_commit",
+    ];
+
+    fn schedule(seed: u64) -> FaultSchedule {
+        FaultSchedule::new(seed)
+            .with_rule(FaultRule::new("query_txs", Fault::Fail).with_probability(0.3))
+            .with_rule(
+                FaultRule::new("*", Fault::Timeout(Duration::ZERO))
+                    .with_probability(0.1)
+                    .with_limit(5),
+            )
+            .with_rule(FaultRule::new("*", Fault::Reorder).with_probability(0.2))
+    }
+
+    /// Draws the faults of a fixed sequence of requests.
+    fn draw(schedule: FaultSchedule) -> Vec<InjectedFault> {
+        let mut state = FaultState::new(schedule);
+
+        for request in REQUESTS.iter().copied().cycle().take(300) {
+            state.next_fault(request, |_| true);
+        }
+
+        state.injected
+    }
+
+    #[test]
+    fn same_seed_injects_same_faults() {
+        assert_eq!(draw(schedule(42)), draw(schedule(42)));
+        assert_ne!(draw(schedule(42)), draw(schedule(43)));
+    }
+
+    #[test]
+    fn rules_match_requests_and_respect_limits() {
+        let injected = draw(schedule(42));
+
+        assert!(injected
+            .iter()
+            .filter(|fault| fault.fault == Fault::Fail)
+            .all(|fault| fault.request == "query_txs"));
+
+        let timeouts = injected
+            .iter()
+            .filter(|fault| matches!(fault.fault, Fault::Timeout(_)))
+            .count();
+        assert!(timeouts <= 5);
+
+        assert!(injected.iter().any(|fault| fault.fault == Fault::Reorder));
+    }
+
+    #[test]
+    fn inapplicable_faults_are_not_injected() {
+        let mut state =
+            FaultState::new(FaultSchedule::new(0).with_rule(FaultRule::new("*", Fault::Reorder)));
+
+        assert_eq!(state.next_fault("query_txs", is_failure), None);
+        assert!(state.injected.is_empty());
+    }
+
+    #[test]
+    fn stale_height_requires_previous_height() {
+        let mut state = FaultState::new(
+            FaultSchedule::new(0)
+                .with_rule(FaultRule::new("query_latest_height", Fault::StaleHeight)),
+        );
+
+        // No height has been observed yet, so none can be reported instead of the latest one
+        assert_eq!(state.next_height_fault("query_latest_height"), None);
+        assert!(state.injected.is_empty());
+
+        state.previous_height = Some(Height::new(0, 10).unwrap());
+
+        assert_eq!(
+            state.next_height_fault("query_latest_height"),
+            Some(Fault::StaleHeight)
+        );
+        assert_eq!(state.injected.len(), 1);
+    }
+
+    #[test]
+    fn same_seed_shuffles_the_same() {
+        let shuffle = |seed| {
+            let mut items: Vec<u32> = (0..20).collect();
+            Rng(seed).shuffle(&mut items);
+            items
+        };
+
+        assert_eq!(shuffle(7), shuffle(7));
+
+        let mut sorted = shuffle(7);
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+}
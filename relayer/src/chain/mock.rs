@@ -1,52 +1,65 @@
 use alloc::sync::Arc;
-use core::ops::Add;
-use core::time::Duration;
-use ibc::core::ics23_commitment::merkle::MerkleProof;
+use std::sync::Mutex;
 
 use crossbeam_channel as channel;
-use tendermint_testgen::light_block::TmLightBlock;
+use sha2::{Digest, Sha256};
+use tendermint::abci::transaction::Hash as TxHash;
+use tendermint::abci::{Code, Data, Log};
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tokio::runtime::Runtime;
 
-use ibc::clients::ics07_tendermint::client_state::{
-    AllowUpdate, ClientState as TendermintClientState,
-};
-use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as TendermintConsensusState;
-use ibc::clients::ics07_tendermint::header::Header as TendermintHeader;
+use ibc::applications::transfer::acknowledgement::Acknowledgement;
+use ibc::applications::transfer::msgs::transfer::{MsgTransfer, TYPE_URL as TRANSFER_TYPE_URL};
+use ibc::applications::transfer::relay::send_transfer::send_transfer;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
-use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
-use ibc::core::ics04_channel::context::ChannelReader;
-use ibc::core::ics04_channel::packet::Sequence;
-use ibc::core::ics23_commitment::{commitment::CommitmentPrefix, specs::ProofSpecs};
-use ibc::core::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc::core::ics03_connection::context::ConnectionReader;
+use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, Order};
+use ibc::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use ibc::core::ics04_channel::handler::write_acknowledgement;
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, UpgradeTimeout};
+use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::core::ics24_host::identifier::{ChainId, ConnectionId, PortId};
+use ibc::core::ics26_routing::context::{ModuleId, RouterBuilder};
+use ibc::core::ics26_routing::error::Error as RoutingError;
+use ibc::core::ics26_routing::handler::{deliver, MsgReceipt};
 use ibc::events::IbcEvent;
-use ibc::mock::context::MockContext;
+use ibc::handler::HandlerOutput;
+use ibc::mock::client_state::{MockClientState, MockConsensusState};
+use ibc::mock::context::{MockContext, MockIbcStore, MockRouterBuilder};
+use ibc::mock::header::MockHeader;
 use ibc::mock::host::HostType;
-use ibc::relayer::ics18_relayer::context::Ics18Context;
+use ibc::relayer::ics18_relayer::error::Error as Ics18Error;
 use ibc::signer::Signer;
-use ibc::test_utils::get_dummy_account_id;
+use ibc::test_utils::{get_dummy_account_id, DummyTransferModule};
+use ibc::timestamp::Timestamp;
 use ibc::Height;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ics23::CommitmentProof;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
 use crate::chain::requests::{
-    QueryChannelClientStateRequest, QueryChannelRequest, QueryClientStatesRequest,
+    QueryChannelClientStateRequest, QueryChannelRequest, QueryClientStatesRequest, QueryHeight,
 };
 use crate::config::ChainConfig;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::monitor::{EventReceiver, EventSender, TxMonitorCmd};
-use crate::keyring::{KeyEntry, KeyRing};
+use crate::event::monitor::{EventBatch, EventReceiver, EventSender, TxMonitorCmd};
+use crate::keyring::{KeyEntry, KeyRing, Store};
 use crate::light_client::Verified;
 use crate::light_client::{mock::LightClient as MockLightClient, LightClient};
 
 use super::requests::{
-    IncludeProof, QueryBlockRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
-    QueryClientStateRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    IncludeProof, QueryBlockRequest, QueryChannelUpgradeRequest, QueryChannelsRequest,
+    QueryClientConnectionsRequest, QueryClientStateRequest, QueryConnectionChannelsRequest,
+    QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
     QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
     QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
     QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
@@ -54,45 +67,238 @@ use super::requests::{
 };
 use super::tracking::TrackedMsgs;
 
+/// The timestamp of every header produced by a mock chain.
+///
+/// Mock chains only produce blocks when they process transactions, so the clocks of two chains
+/// drift apart quickly. Stamping all the headers with the same instant in the past ensures that
+/// no counterparty ever sees a consensus state from the future, at the cost of not supporting
+/// packets that time out on timestamps.
+const HEADER_TIMESTAMP_NANOS: u64 = 1;
+
+/// Returns the header of a mock chain at the given height, as seen by its counterparties.
+pub fn header(height: Height) -> MockHeader {
+    MockHeader::new(height).with_timestamp(
+        Timestamp::from_nanoseconds(HEADER_TIMESTAMP_NANOS)
+            .expect("the mock header timestamp is valid"),
+    )
+}
+
+/// A transaction processed by a mock chain, recorded to answer transaction queries.
+struct MockTx {
+    hash: TxHash,
+    height: Height,
+    events: Vec<IbcEvent>,
+}
+
 /// The representation of a mocked chain as the relayer sees it.
-/// The relayer runtime and the light client will engage with the MockChain to query/send tx; the
-/// primary interface for doing so is captured by `ICS18Context` which this struct can access via
-/// the `context` field.
+///
+/// The chain is backed by a [`MockContext`] running the IBC handlers of the `ibc` crate, with
+/// the ICS-20 transfer port bound to a [`DummyTransferModule`]. Two mock chains exchange
+/// messages in memory through the relayer, which makes it possible to run client, connection and
+/// channel handshakes as well as packet relaying in plain unit tests.
+///
+/// The context store is not versioned: queries at past heights observe the latest state. Since
+/// a proof at height `h` is verified against the header at `h + 1`, a query requesting a proof at
+/// the latest height seals the current block by advancing the chain by one empty block.
+/// Proofs are not verified by the mock clients and are thus returned as placeholders.
 pub struct MockChain {
     config: ChainConfig,
-    context: MockContext,
+    context: Mutex<MockContext>,
+    ibc_store: Arc<Mutex<MockIbcStore>>,
+    keybase: KeyRing,
+    txs: Vec<MockTx>,
 
-    // keep a reference to event sender to prevent it from being dropped
-    _event_sender: EventSender,
+    event_sender: EventSender,
     event_receiver: EventReceiver,
 }
 
 impl MockChain {
-    fn trusting_period(&self) -> Duration {
-        self.config
-            .trusting_period
-            .unwrap_or_else(|| Duration::from_secs(14 * 24 * 60 * 60)) // 14 days
+    /// Returns a placeholder proof for queries at `height`, sealing the latest block first if
+    /// the proof is for the latest height.
+    fn proof(
+        &self,
+        height: QueryHeight,
+        include_proof: IncludeProof,
+    ) -> Result<Option<MerkleProof>, Error> {
+        if let IncludeProof::No = include_proof {
+            return Ok(None);
+        }
+
+        let mut context = self.context.lock().unwrap();
+        let latest_height = context.host_height();
+
+        let seal = match height {
+            QueryHeight::Latest => true,
+            QueryHeight::Specific(height) if height > latest_height => {
+                return Err(Error::query(format!(
+                    "proof at height {} beyond latest height {}",
+                    height, latest_height
+                )));
+            }
+            QueryHeight::Specific(height) => height == latest_height,
+        };
+
+        if seal {
+            context.advance_host_chain_height();
+        }
+
+        Ok(Some(MerkleProof::from(RawMerkleProof {
+            proofs: vec![CommitmentProof::default()],
+        })))
+    }
+
+    fn latest_height(&self) -> Height {
+        self.context.lock().unwrap().host_height()
+    }
+
+    /// Delivers the messages of a transaction to the context, then commits them in a new block.
+    /// The whole transaction is reverted if any of its messages fails.
+    fn deliver_tx(&mut self, tracked_msgs: TrackedMsgs) -> Result<(TxHash, Vec<IbcEvent>), Error> {
+        let TrackedMsgs { msgs, tracking_id } = tracked_msgs;
+
+        let mut context = self.context.lock().unwrap();
+        let snapshot = self.ibc_store.lock().unwrap().clone();
+
+        let mut hasher = Sha256::new();
+        hasher.update(context.host_height().to_string());
+
+        let mut events = Vec::new();
+        for msg in msgs {
+            hasher.update(&msg.type_url);
+            hasher.update(&msg.value);
+
+            match deliver_msg(&mut context, msg) {
+                Ok(msg_events) => events.extend(msg_events),
+                Err(e) => {
+                    *self.ibc_store.lock().unwrap() = snapshot;
+                    return Err(Error::ics18(e));
+                }
+            }
+        }
+
+        context.advance_host_chain_height();
+        let height = context.host_height();
+        drop(context);
+
+        // Module events carry no height and are not reported by the relayer.
+        events.retain(|event| !matches!(event, IbcEvent::AppModule(_)));
+        for event in events.iter_mut() {
+            event.set_height(height);
+        }
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hasher.finalize());
+        let hash = TxHash::new(hash);
+
+        self.txs.push(MockTx {
+            hash,
+            height,
+            events: events.clone(),
+        });
+
+        if !events.is_empty() {
+            // Nobody may be listening for events, which is fine.
+            let _ = self.event_sender.send(Ok(EventBatch::new(
+                self.id().clone(),
+                tracking_id,
+                height,
+                events.clone(),
+            )));
+        }
+
+        Ok((hash, events))
     }
 }
 
+/// Delivers a single message to the context, writing the acknowledgements of the received
+/// packets on behalf of the transfer module.
+fn deliver_msg(context: &mut MockContext, msg: Any) -> Result<Vec<IbcEvent>, Ics18Error> {
+    // Transfers are submitted to the application rather than to the IBC handlers.
+    if msg.type_url == TRANSFER_TYPE_URL {
+        let msg = MsgTransfer::try_from(msg).map_err(|e| {
+            Ics18Error::transaction_failed(RoutingError::ics20_fungible_token_transfer(e))
+        })?;
+
+        let mut module = DummyTransferModule::new(context.ibc_store_share());
+        let mut output = HandlerOutput::builder();
+        send_transfer(&mut module, &mut output, msg).map_err(|e| {
+            Ics18Error::transaction_failed(RoutingError::ics20_fungible_token_transfer(e))
+        })?;
+
+        return Ok(output.with_result(()).events);
+    }
+
+    let MsgReceipt { events, .. } =
+        deliver(context, msg).map_err(Ics18Error::transaction_failed)?;
+
+    let mut all_events = Vec::with_capacity(events.len());
+    for event in events {
+        let received = match &event {
+            IbcEvent::ReceivePacket(ev) => Some(ev.packet.clone()),
+            _ => None,
+        };
+
+        all_events.push(event);
+
+        if let Some(packet) = received {
+            all_events.extend(write_ack(context, packet)?);
+        }
+    }
+
+    Ok(all_events)
+}
+
+/// Writes a successful ICS-20 acknowledgement for the given received packet.
+fn write_ack(context: &mut MockContext, packet: Packet) -> Result<Vec<IbcEvent>, Ics18Error> {
+    let ack = Acknowledgement::success().as_ref().to_vec();
+
+    let HandlerOutput { result, events, .. } =
+        write_acknowledgement::process(&*context, packet, ack)
+            .map_err(|e| Ics18Error::transaction_failed(RoutingError::ics04_channel(e)))?;
+
+    context
+        .store_packet_result(result)
+        .map_err(|e| Ics18Error::transaction_failed(RoutingError::ics04_channel(e)))?;
+
+    Ok(events)
+}
+
 impl ChainEndpoint for MockChain {
-    type LightBlock = TmLightBlock;
-    type Header = TendermintHeader;
-    type ConsensusState = TendermintConsensusState;
-    type ClientState = TendermintClientState;
+    type LightBlock = MockHeader;
+    type Header = MockHeader;
+    type ConsensusState = MockConsensusState;
+    type ClientState = MockClientState;
     type LightClient = MockLightClient;
 
     fn bootstrap(config: ChainConfig, _rt: Arc<Runtime>) -> Result<Self, Error> {
         let (sender, receiver) = channel::unbounded();
+
+        let mut context = MockContext::new(
+            config.id.clone(),
+            HostType::Mock,
+            50,
+            Height::new(config.id.version(), 20).unwrap(),
+        );
+
+        let module_id: ModuleId = "transfer".parse().unwrap();
+        let module = DummyTransferModule::new(context.ibc_store_share());
+        let router = MockRouterBuilder::default()
+            .add_route(module_id.clone(), module)
+            .unwrap()
+            .build();
+        context = context.with_router(router);
+        context.scope_port_to_module(PortId::transfer(), module_id);
+
+        let keybase = KeyRing::new(Store::Memory, &config.account_prefix, &config.id)
+            .map_err(Error::key_base)?;
+
         Ok(MockChain {
-            config: config.clone(),
-            context: MockContext::new(
-                config.id.clone(),
-                HostType::SyntheticTendermint,
-                50,
-                Height::new(config.id.version(), 20).unwrap(),
-            ),
-            _event_sender: sender,
+            config,
+            ibc_store: context.ibc_store_share(),
+            context: Mutex::new(context),
+            keybase,
+            txs: Vec::new(),
+            event_sender: sender,
             event_receiver: receiver,
         })
     }
@@ -122,28 +328,43 @@ impl ChainEndpoint for MockChain {
     }
 
     fn keybase(&self) -> &KeyRing {
-        unimplemented!()
+        &self.keybase
     }
 
     fn keybase_mut(&mut self) -> &mut KeyRing {
-        unimplemented!()
+        &mut self.keybase
     }
 
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEvent>, Error> {
-        // Use the ICS18Context interface to submit the set of messages.
-        let events = self.context.send(tracked_msgs.msgs).map_err(Error::ics18)?;
+        let (_, events) = self.deliver_tx(tracked_msgs)?;
 
         Ok(events)
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &mut self,
+        _key_name: &str,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.send_messages_and_wait_commit(tracked_msgs)
+    }
+
     fn send_messages_and_wait_check_tx(
         &mut self,
-        _tracked_msgs: TrackedMsgs,
-    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
-        todo!()
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<Response>, Error> {
+        // Transactions are committed right away, their events can be queried by hash.
+        let (hash, _) = self.deliver_tx(tracked_msgs)?;
+
+        Ok(vec![Response {
+            code: Code::Ok,
+            data: Data::from(Vec::new()),
+            log: Log::from(""),
+            hash,
+        }])
     }
 
     fn get_signer(&mut self) -> Result<Signer, Error> {
@@ -155,11 +376,13 @@ impl ChainEndpoint for MockChain {
     }
 
     fn get_key(&mut self) -> Result<KeyEntry, Error> {
-        unimplemented!()
+        self.keybase
+            .get_key(&self.config.key_name)
+            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))
     }
 
-    fn add_key(&mut self, _key_name: &str, _key: KeyEntry) -> Result<(), Error> {
-        unimplemented!()
+    fn add_key(&mut self, key_name: &str, key: KeyEntry) -> Result<(), Error> {
+        self.keybase.add_key(key_name, key).map_err(Error::key_base)
     }
 
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
@@ -167,21 +390,29 @@ impl ChainEndpoint for MockChain {
     }
 
     fn query_balance(&self, _key_name: Option<String>) -> Result<Balance, Error> {
-        unimplemented!()
+        // The transfer module of the mock chain does not keep track of balances.
+        Ok(Balance {
+            amount: "0".to_string(),
+            denom: self.config.gas_price.denom.clone(),
+        })
     }
 
-    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
-        unimplemented!()
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        Err(Error::query(format!("denomination trace {}", hash)))
     }
 
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
-        unimplemented!()
+        let context = self.context.lock().unwrap();
+
+        Ok(ConnectionReader::commitment_prefix(&*context))
     }
 
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        let context = self.context.lock().unwrap();
+
         Ok(ChainStatus {
-            height: self.context.host_height(),
-            timestamp: self.context.host_timestamp(),
+            height: context.host_height(),
+            timestamp: context.host_timestamp(),
         })
     }
 
@@ -189,193 +420,508 @@ impl ChainEndpoint for MockChain {
         &self,
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        Ok(store
+            .clients
+            .iter()
+            .filter_map(|(client_id, record)| {
+                record
+                    .client_state
+                    .clone()
+                    .map(|cs| IdentifiedAnyClientState::new(client_id.clone(), cs))
+            })
+            .collect())
     }
 
     fn query_client_state(
         &self,
         request: QueryClientStateRequest,
-        _include_proof: IncludeProof,
+        include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
-        // TODO: unclear what are the scenarios where we need to take height into account.
         let client_state = self
-            .context
-            .query_client_full_state(&request.client_id)
+            .ibc_store
+            .lock()
+            .unwrap()
+            .clients
+            .get(&request.client_id)
+            .and_then(|record| record.client_state.clone())
             .ok_or_else(Error::empty_response_value)?;
 
-        Ok((client_state, None))
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((client_state, proof))
     }
 
     fn query_upgraded_client_state(
         &self,
-        _request: QueryUpgradedClientStateRequest,
+        request: QueryUpgradedClientStateRequest,
     ) -> Result<(AnyClientState, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::query(format!(
+            "upgraded client state at height {}",
+            request.upgrade_height
+        )))
     }
 
     fn query_connection(
         &self,
-        _request: QueryConnectionRequest,
-        _include_proof: IncludeProof,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
     ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
-        unimplemented!()
+        let connection_end = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .connections
+            .get(&request.connection_id)
+            .cloned()
+            .ok_or_else(|| Error::connection_not_found(request.connection_id.clone()))?;
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((connection_end, proof))
     }
 
     fn query_client_connections(
         &self,
-        _request: QueryClientConnectionsRequest,
+        request: QueryClientConnectionsRequest,
     ) -> Result<Vec<ConnectionId>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        Ok(store
+            .connections
+            .iter()
+            .filter(|(_, connection_end)| connection_end.client_id() == &request.client_id)
+            .map(|(connection_id, _)| connection_id.clone())
+            .collect())
     }
 
     fn query_connections(
         &self,
         _request: QueryConnectionsRequest,
     ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        Ok(store
+            .connections
+            .iter()
+            .map(|(id, end)| IdentifiedConnectionEnd::new(id.clone(), end.clone()))
+            .collect())
     }
 
     fn query_connection_channels(
         &self,
-        _request: QueryConnectionChannelsRequest,
+        request: QueryConnectionChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        let channels = store
+            .connection_channels
+            .get(&request.connection_id)
+            .map(|channels| {
+                channels
+                    .iter()
+                    .filter_map(|(port_id, channel_id)| {
+                        store
+                            .channels
+                            .get(&(port_id.clone(), channel_id.clone()))
+                            .map(|end| {
+                                IdentifiedChannelEnd::new(
+                                    port_id.clone(),
+                                    channel_id.clone(),
+                                    end.clone(),
+                                )
+                            })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(channels)
     }
 
     fn query_channels(
         &self,
         _request: QueryChannelsRequest,
     ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        Ok(store
+            .channels
+            .iter()
+            .map(|((port_id, channel_id), end)| {
+                IdentifiedChannelEnd::new(port_id.clone(), channel_id.clone(), end.clone())
+            })
+            .collect())
     }
 
     fn query_channel(
         &self,
-        _request: QueryChannelRequest,
-        _include_proof: IncludeProof,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
-        unimplemented!()
+        // A missing channel is reported as an uninitialized channel end, like on Cosmos chains.
+        let channel_end = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .channels
+            .get(&(request.port_id, request.channel_id))
+            .cloned()
+            .unwrap_or_default();
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((channel_end, proof))
     }
 
     fn query_channel_client_state(
         &self,
-        _request: QueryChannelClientStateRequest,
+        request: QueryChannelClientStateRequest,
     ) -> Result<Option<IdentifiedAnyClientState>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        let client_state = store
+            .channels
+            .get(&(request.port_id, request.channel_id))
+            .and_then(|channel_end| channel_end.connection_hops().first())
+            .and_then(|connection_id| store.connections.get(connection_id))
+            .and_then(|connection_end| {
+                let client_id = connection_end.client_id();
+                store
+                    .clients
+                    .get(client_id)
+                    .and_then(|record| record.client_state.clone())
+                    .map(|cs| IdentifiedAnyClientState::new(client_id.clone(), cs))
+            });
+
+        Ok(client_state)
     }
 
     fn query_packet_commitment(
         &self,
-        _request: QueryPacketCommitmentRequest,
-        _include_proof: IncludeProof,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        unimplemented!()
+        let commitment = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_commitment
+            .get(&(request.port_id, request.channel_id, request.sequence))
+            .map(|commitment| commitment.clone().into_vec())
+            .unwrap_or_default();
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((commitment, proof))
     }
 
     fn query_packet_commitments(
         &self,
-        _request: QueryPacketCommitmentsRequest,
+        request: QueryPacketCommitmentsRequest,
     ) -> Result<(Vec<Sequence>, Height), Error> {
-        unimplemented!()
+        let height = self.latest_height();
+        let store = self.ibc_store.lock().unwrap();
+
+        let sequences = store
+            .packet_commitment
+            .keys()
+            .filter(|(port_id, channel_id, _)| {
+                port_id == &request.port_id && channel_id == &request.channel_id
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, height))
     }
 
     fn query_packet_receipt(
         &self,
-        _request: QueryPacketReceiptRequest,
-        _include_proof: IncludeProof,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        unimplemented!()
+        let receipt = if self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_receipt
+            .contains_key(&(request.port_id, request.channel_id, request.sequence))
+        {
+            vec![1]
+        } else {
+            Vec::new()
+        };
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((receipt, proof))
     }
 
     fn query_unreceived_packets(
         &self,
-        _request: QueryUnreceivedPacketsRequest,
+        request: QueryUnreceivedPacketsRequest,
     ) -> Result<Vec<Sequence>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+        let port_channel_id = (request.port_id, request.channel_id);
+
+        let ordered = store
+            .channels
+            .get(&port_channel_id)
+            .map_or(false, |channel_end| channel_end.ordering == Order::Ordered);
+
+        let next_sequence_recv = store.next_sequence_recv.get(&port_channel_id).copied();
+
+        Ok(request
+            .packet_commitment_sequences
+            .into_iter()
+            .filter(|sequence| {
+                if ordered {
+                    next_sequence_recv.map_or(true, |next| *sequence >= next)
+                } else {
+                    !store.packet_receipt.contains_key(&(
+                        port_channel_id.0.clone(),
+                        port_channel_id.1.clone(),
+                        *sequence,
+                    ))
+                }
+            })
+            .collect())
     }
 
     fn query_packet_acknowledgement(
         &self,
-        _request: QueryPacketAcknowledgementRequest,
-        _include_proof: IncludeProof,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
     ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
-        unimplemented!()
+        let ack = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_acknowledgement
+            .get(&(request.port_id, request.channel_id, request.sequence))
+            .map(|ack| ack.clone().into_vec())
+            .unwrap_or_default();
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((ack, proof))
     }
 
     fn query_packet_acknowledgements(
         &self,
-        _request: QueryPacketAcknowledgementsRequest,
+        request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<Sequence>, Height), Error> {
-        unimplemented!()
+        let height = self.latest_height();
+        let store = self.ibc_store.lock().unwrap();
+
+        let sequences = store
+            .packet_acknowledgement
+            .keys()
+            .filter(|(port_id, channel_id, sequence)| {
+                port_id == &request.port_id
+                    && channel_id == &request.channel_id
+                    && (request.packet_commitment_sequences.is_empty()
+                        || request.packet_commitment_sequences.contains(sequence))
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, height))
     }
 
     fn query_unreceived_acknowledgements(
         &self,
-        _request: QueryUnreceivedAcksRequest,
+        request: QueryUnreceivedAcksRequest,
     ) -> Result<Vec<Sequence>, Error> {
-        unimplemented!()
+        let store = self.ibc_store.lock().unwrap();
+
+        // An acknowledgement is pending for as long as the packet commitment exists.
+        Ok(request
+            .packet_ack_sequences
+            .into_iter()
+            .filter(|sequence| {
+                store.packet_commitment.contains_key(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    *sequence,
+                ))
+            })
+            .collect())
     }
 
     fn query_next_sequence_receive(
         &self,
-        _request: QueryNextSequenceReceiveRequest,
-        _include_proof: IncludeProof,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
     ) -> Result<(Sequence, Option<MerkleProof>), Error> {
-        unimplemented!()
+        let sequence = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .next_sequence_recv
+            .get(&(request.port_id, request.channel_id))
+            .copied()
+            .ok_or_else(Error::empty_response_value)?;
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((sequence, proof))
+    }
+
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(u64, Option<MerkleProof>), Error> {
+        // No upgrade was ever attempted on the channel if the sequence is not set.
+        let sequence = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_sequence
+            .get(&(request.port_id, request.channel_id))
+            .copied()
+            .unwrap_or_default();
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((sequence, proof))
     }
 
-    fn query_txs(&self, _request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
-        unimplemented!()
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(UpgradeTimeout, Option<MerkleProof>), Error> {
+        let timeout = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_timeout
+            .get(&(request.port_id, request.channel_id))
+            .cloned()
+            .ok_or_else(Error::empty_response_value)?;
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((timeout, proof))
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        let error_receipt = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_error_receipt
+            .get(&(request.port_id, request.channel_id))
+            .cloned()
+            .ok_or_else(Error::empty_response_value)?;
+
+        let proof = self.proof(request.height, include_proof)?;
+
+        Ok((error_receipt, proof))
+    }
+
+    /// Answers the transaction queries from the transactions recorded by this chain, following
+    /// the semantics of the Cosmos implementation.
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
+        let below = |height: Height, query_height: &QueryHeight| match query_height {
+            QueryHeight::Latest => true,
+            QueryHeight::Specific(query_height) => height <= *query_height,
+        };
+
+        match request {
+            QueryTxRequest::Packet(request) => Ok(request
+                .sequences
+                .iter()
+                .filter_map(|sequence| {
+                    self.txs
+                        .iter()
+                        .filter(|tx| below(tx.height, &request.height))
+                        .flat_map(|tx| tx.events.iter())
+                        .find(|event| {
+                            event.event_type().as_str() == request.event_id.as_str()
+                                && event.packet().map_or(false, |packet| {
+                                    packet.source_port == request.source_port_id
+                                        && packet.source_channel == request.source_channel_id
+                                        && packet.destination_port == request.destination_port_id
+                                        && packet.destination_channel
+                                            == request.destination_channel_id
+                                        && packet.sequence == *sequence
+                                })
+                        })
+                        .cloned()
+                })
+                .collect()),
+
+            QueryTxRequest::Client(request) => Ok(self
+                .txs
+                .iter()
+                .filter(|tx| below(tx.height, &request.query_height))
+                .flat_map(|tx| tx.events.iter())
+                .find(|event| match event {
+                    IbcEvent::UpdateClient(update) => {
+                        update.client_id() == &request.client_id
+                            && update.consensus_height() == request.consensus_height
+                    }
+                    _ => false,
+                })
+                .cloned()
+                .into_iter()
+                .collect()),
+
+            QueryTxRequest::Transaction(tx) => Ok(self
+                .txs
+                .iter()
+                .find(|recorded| recorded.hash == tx.0)
+                .map(|recorded| recorded.events.clone())
+                .unwrap_or_default()),
+        }
     }
 
     fn query_blocks(
         &self,
         _request: QueryBlockRequest,
     ) -> Result<(Vec<IbcEvent>, Vec<IbcEvent>), Error> {
-        unimplemented!()
+        // Mock chains emit no begin or end block events.
+        Ok((Vec::new(), Vec::new()))
     }
 
     fn query_host_consensus_state(
         &self,
-        _request: QueryHostConsensusStateRequest,
+        request: QueryHostConsensusStateRequest,
     ) -> Result<Self::ConsensusState, Error> {
-        unimplemented!()
+        let latest_height = self.latest_height();
+
+        let height = match request.height {
+            QueryHeight::Latest => latest_height,
+            QueryHeight::Specific(height) if height > latest_height => {
+                return Err(Error::query(format!("host consensus state at {}", height)));
+            }
+            QueryHeight::Specific(height) => height,
+        };
+
+        Ok(MockConsensusState::new(header(height)))
     }
 
     fn build_client_state(
         &self,
         height: Height,
-        settings: ClientSettings,
+        _settings: ClientSettings,
     ) -> Result<Self::ClientState, Error> {
-        let ClientSettings::Tendermint(settings) = settings;
-        let trusting_period = settings
-            .trusting_period
-            .unwrap_or_else(|| self.trusting_period());
-
-        let client_state = TendermintClientState::new(
-            self.id().clone(),
-            settings.trust_threshold,
-            trusting_period,
-            self.trusting_period().add(Duration::from_secs(1000)),
-            settings.max_clock_drift,
-            height,
-            ProofSpecs::default(),
-            vec!["upgrade/upgradedClient".to_string()],
-            AllowUpdate {
-                after_expiry: false,
-                after_misbehaviour: false,
-            },
-        )
-        .map_err(Error::ics07)?;
-
-        Ok(client_state)
+        Ok(MockClientState::new(header(height)).with_chain_id(self.id().clone()))
     }
 
     fn build_consensus_state(
         &self,
         light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
-        Ok(Self::ConsensusState::from(light_block.signed_header.header))
+        Ok(MockConsensusState::new(light_block))
     }
 
     fn build_header(
@@ -385,36 +931,32 @@ impl ChainEndpoint for MockChain {
         client_state: &AnyClientState,
         light_client: &mut Self::LightClient,
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
-        let succ_trusted = light_client.fetch(trusted_height.increment())?;
-
         let Verified { target, supporting } =
-            light_client.verify(trusted_height, target_height, client_state)?;
-
-        let target_header = Self::Header {
-            signed_header: target.signed_header,
-            validator_set: target.validators,
-            trusted_height,
-            trusted_validator_set: succ_trusted.validators.clone(),
-        };
-
-        let supporting_headers = supporting
-            .into_iter()
-            .map(|h| Self::Header {
-                signed_header: h.signed_header,
-                validator_set: h.validators,
-                trusted_height,
-                trusted_validator_set: succ_trusted.validators.clone(),
-            })
-            .collect();
+            light_client.header_and_minimal_set(trusted_height, target_height, client_state)?;
 
-        Ok((target_header, supporting_headers))
+        Ok((target, supporting))
     }
 
     fn query_consensus_states(
         &self,
         request: QueryConsensusStatesRequest,
     ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
-        Ok(self.context.consensus_states(&request.client_id))
+        let store = self.ibc_store.lock().unwrap();
+
+        Ok(store
+            .clients
+            .get(&request.client_id)
+            .map(|record| {
+                record
+                    .consensus_states
+                    .iter()
+                    .map(|(height, consensus_state)| AnyConsensusStateWithHeight {
+                        height: *height,
+                        consensus_state: consensus_state.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
     }
 
     fn query_consensus_state(
@@ -422,37 +964,54 @@ impl ChainEndpoint for MockChain {
         request: QueryConsensusStateRequest,
         include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
-        // IncludeProof::Yes not implemented
-        assert!(matches!(include_proof, IncludeProof::No));
-
-        let consensus_states = self.context.consensus_states(&request.client_id);
-        let consensus_state = consensus_states
-            .into_iter()
-            .find(|s| s.height == request.consensus_height)
-            .ok_or_else(|| Error::query("Invalid consensus height".into()))?
-            .consensus_state;
-        Ok((consensus_state, None))
+        let consensus_state: AnyConsensusState = self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .clients
+            .get(&request.client_id)
+            .and_then(|record| record.consensus_states.get(&request.consensus_height))
+            .cloned()
+            .ok_or_else(|| Error::query("Invalid consensus height".into()))?;
+
+        let proof = self.proof(request.query_height, include_proof)?;
+
+        Ok((consensus_state, proof))
     }
 
     fn query_upgraded_consensus_state(
         &self,
-        _request: QueryUpgradedConsensusStateRequest,
+        request: QueryUpgradedConsensusStateRequest,
     ) -> Result<(AnyConsensusState, MerkleProof), Error> {
-        unimplemented!()
+        Err(Error::query(format!(
+            "upgraded consensus state at height {}",
+            request.upgrade_height
+        )))
     }
 }
 
 // For integration tests with the modules
 #[cfg(test)]
 pub mod test_utils {
+    use alloc::sync::Arc;
     use core::str::FromStr;
     use core::time::Duration;
 
-    use ibc::core::ics24_host::identifier::ChainId;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use ibc::core::ics04_channel::channel::Order;
+    use ibc::core::ics04_channel::Version;
+    use ibc::core::ics24_host::identifier::{ChainId, PortId};
 
     use crate::{
+        chain::handle::ChainHandle,
+        chain::mock::MockChain,
+        chain::runtime::ChainRuntime,
         chain::ChainType,
+        channel::Channel,
         config::{AddressType, ChainConfig, GasPrice, PacketFilter},
+        connection::Connection,
+        foreign_client::ForeignClient,
     };
 
     /// Returns a very minimal chain configuration, to be used in initializing `MockChain`s.
@@ -482,8 +1041,407 @@ pub mod test_utils {
             trust_threshold: Default::default(),
             packet_filter: PacketFilter::default(),
             address_type: AddressType::default(),
+            wallet_refill: None,
+            dry_run: false,
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         }
     }
+
+    /// Spawns the runtime of a `MockChain`, returning a handle to it.
+    pub fn spawn_chain<Handle: ChainHandle>(id: &str, rt: Arc<TokioRuntime>) -> Handle {
+        let mut config = get_basic_chain_config(id);
+        // The handshakes retry every tenth of the maximum block time.
+        config.max_block_time = Duration::from_secs(1);
+
+        ChainRuntime::<MockChain>::spawn::<Handle>(config, rt).unwrap()
+    }
+
+    /// Creates the clients, connection and ICS-20 channel between two chains.
+    pub fn open_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
+        a_chain: &ChainA,
+        b_chain: &ChainB,
+    ) -> Channel<ChainA, ChainB> {
+        let client_on_a = ForeignClient::new(a_chain.clone(), b_chain.clone()).unwrap();
+        let client_on_b = ForeignClient::new(b_chain.clone(), a_chain.clone()).unwrap();
+
+        let connection = Connection::new(client_on_a, client_on_b, Duration::ZERO).unwrap();
+
+        Channel::new(
+            connection,
+            Order::Unordered,
+            PortId::transfer(),
+            PortId::transfer(),
+            Some(Version::ics20()),
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::time::Duration;
+
+    use test_log::test;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+    use ibc::events::IbcEvent;
+
+    use crate::chain::handle::{
+        BaseChainHandle, ChainHandle, Fault, FaultRule, FaultSchedule, FaultyChainHandle,
+        Subscription,
+    };
+    use crate::chain::mock::test_utils::{open_channel, spawn_chain};
+    use crate::chain::requests::QueryPacketCommitmentsRequest;
+    use crate::link::error::LinkError;
+    use crate::link::{Link, LinkParameters, RelayPath, Resubmit};
+    use crate::transfer::{build_and_send_transfer_messages, TransferOptions};
+    use crate::util::retry::{retry_with_index, RetryResult};
+    use crate::worker::retry_strategy;
+
+    fn transfer_options(channel_id: &ChannelId, number_msgs: usize) -> TransferOptions {
+        TransferOptions {
+            packet_src_port_id: PortId::transfer(),
+            packet_src_channel_id: channel_id.clone(),
+            amount: 42u64.into(),
+            denom: "uatom".to_string(),
+            receiver: None,
+            timeout_height_offset: 1000,
+            timeout_duration: Duration::ZERO,
+            number_msgs,
+        }
+    }
+
+    fn link_parameters(channel_id: &ChannelId) -> LinkParameters {
+        LinkParameters {
+            src_port_id: PortId::transfer(),
+            src_channel_id: channel_id.clone(),
+        }
+    }
+
+    fn count(events: &[IbcEvent], matches: impl Fn(&IbcEvent) -> bool) -> usize {
+        events.iter().filter(|event| matches(event)).count()
+    }
+
+    /// Drains the events a chain emitted so far.
+    fn drain(subscription: &Subscription) -> Vec<IbcEvent> {
+        let mut events = Vec::new();
+
+        while let Ok(batch) = subscription.recv_timeout(Duration::from_millis(500)) {
+            if let Ok(batch) = batch.as_ref() {
+                events.extend(batch.events.iter().cloned());
+            }
+        }
+
+        events
+    }
+
+    /// Repeats a relaying operation with the workers' retry strategy,
+    /// until there is nothing left to relay.
+    fn relay_until_done(relay: impl Fn() -> Result<Vec<IbcEvent>, LinkError>) {
+        retry_with_index(retry_strategy::worker_default_strategy(), |_| {
+            match relay() {
+                Ok(events) if events.is_empty() => RetryResult::Ok(()),
+                Ok(_) => RetryResult::Retry("packets left to relay".to_string()),
+                Err(e) => RetryResult::Retry(e.to_string()),
+            }
+        })
+        .unwrap();
+    }
+
+    /// Relays the pending packets of a path the way the packet workers do when transactions are
+    /// confirmed: the transactions are submitted without waiting for them to be committed, and
+    /// confirmed afterwards. Stops once `done` holds, and returns the events of the confirmed
+    /// transactions.
+    fn relay_async_until_done<ChainA: ChainHandle, ChainB: ChainHandle>(
+        path: &mut RelayPath<ChainA, ChainB>,
+        done: impl Fn(&[IbcEvent]) -> bool,
+    ) -> Vec<IbcEvent> {
+        let mut confirmed = Vec::new();
+
+        for _ in 0..20 {
+            if path.schedule_packet_clearing(None).is_ok() {
+                let _ = path.execute_schedule();
+            }
+
+            confirmed.extend(path.process_pending_txs(Resubmit::No).events);
+
+            if done(&confirmed) {
+                return confirmed;
+            }
+        }
+
+        panic!("packets left to relay, confirmed events: {:?}", confirmed);
+    }
+
+    /// Runs the client, connection and channel handshakes between two mock chains, then relays
+    /// token transfers from chain a to chain b and their acknowledgements back to chain a.
+    #[test]
+    fn handshakes_and_packet_relaying() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let a_chain: BaseChainHandle = spawn_chain("chain_a", rt.clone());
+        let b_chain: BaseChainHandle = spawn_chain("chain_b", rt);
+
+        let a_channel_id = open_channel(&a_chain, &b_chain)
+            .a_channel_id()
+            .unwrap()
+            .clone();
+
+        let events = build_and_send_transfer_messages(
+            &a_chain,
+            &b_chain,
+            &transfer_options(&a_channel_id, 2),
+        )
+        .unwrap();
+        assert_eq!(count(&events, |e| matches!(e, IbcEvent::SendPacket(_))), 2);
+
+        let link = Link::new_from_opts(
+            a_chain.clone(),
+            b_chain,
+            link_parameters(&a_channel_id),
+            false,
+        )
+        .unwrap();
+
+        let events = link.relay_recv_packet_and_timeout_messages().unwrap();
+        assert_eq!(
+            count(&events, |e| matches!(e, IbcEvent::ReceivePacket(_))),
+            2
+        );
+        assert_eq!(
+            count(&events, |e| matches!(e, IbcEvent::WriteAcknowledgement(_))),
+            2
+        );
+
+        let events = link
+            .reverse(false)
+            .unwrap()
+            .relay_ack_packet_messages()
+            .unwrap();
+        assert_eq!(
+            count(&events, |e| matches!(e, IbcEvent::AcknowledgePacket(_))),
+            2
+        );
+
+        // The acknowledgements cleared the packet commitments on chain a.
+        let (commitments, _) = a_chain
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: PortId::transfer(),
+                channel_id: a_channel_id,
+                pagination: None,
+            })
+            .unwrap();
+        assert!(commitments.is_empty());
+
+        // Nothing is left to relay in either direction.
+        assert!(link
+            .relay_recv_packet_and_timeout_messages()
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Relays token transfers between two mock chains whose handles fail requests, lose
+    /// transaction confirmations and report stale heights, and checks that every packet
+    /// is nevertheless received and acknowledged exactly once.
+    #[test]
+    fn packet_relaying_with_faults() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let a_chain: FaultyChainHandle<BaseChainHandle> = spawn_chain("chain_a", rt.clone());
+        let b_chain: FaultyChainHandle<BaseChainHandle> = spawn_chain("chain_b", rt);
+
+        let a_channel_id = open_channel(&a_chain, &b_chain)
+            .a_channel_id()
+            .unwrap()
+            .clone();
+
+        let events = build_and_send_transfer_messages(
+            &a_chain,
+            &b_chain,
+            &transfer_options(&a_channel_id, 3),
+        )
+        .unwrap();
+        assert_eq!(count(&events, |e| matches!(e, IbcEvent::SendPacket(_))), 3);
+
+        let a_to_b = Link::new_from_opts(
+            a_chain.clone(),
+            b_chain.clone(),
+            link_parameters(&a_channel_id),
+            false,
+        )
+        .unwrap();
+        let b_to_a = a_to_b.reverse(false).unwrap();
+
+        let a_events = a_chain.subscribe().unwrap();
+        let b_events = b_chain.subscribe().unwrap();
+
+        // The transactions of the first batch sent to chain a are committed, but the relayer
+        // is told that they timed out, while the events of the first batch sent to chain b
+        // are returned in a shuffled order.
+        let timeout = Fault::Timeout(Duration::from_millis(10));
+
+        a_chain.set_schedule(
+            FaultSchedule::new(7)
+                .with_rule(FaultRule::new("send_messages_and_wait_commit", timeout).with_limit(1))
+                .with_rule(
+                    FaultRule::new("query_latest_height", Fault::StaleHeight)
+                        .with_probability(0.5)
+                        .with_limit(2),
+                )
+                .with_rule(
+                    FaultRule::new("*", Fault::Fail)
+                        .with_probability(0.1)
+                        .with_limit(1),
+                ),
+        );
+        b_chain.set_schedule(
+            FaultSchedule::new(11)
+                .with_rule(
+                    FaultRule::new("send_messages_and_wait_commit", Fault::Reorder).with_limit(1),
+                )
+                .with_rule(FaultRule::new("query_unreceived_packets", Fault::Fail).with_limit(1))
+                .with_rule(
+                    FaultRule::new("*", Fault::Fail)
+                        .with_probability(0.1)
+                        .with_limit(1),
+                ),
+        );
+
+        relay_until_done(|| a_to_b.relay_recv_packet_and_timeout_messages());
+        relay_until_done(|| b_to_a.relay_ack_packet_messages());
+
+        assert!(!a_chain.injected_faults().is_empty());
+        assert!(b_chain
+            .injected_faults()
+            .iter()
+            .any(|injected| injected.fault == Fault::Reorder));
+
+        a_chain.set_schedule(FaultSchedule::default());
+        b_chain.set_schedule(FaultSchedule::default());
+
+        let b_events = drain(&b_events);
+        assert_eq!(
+            count(&b_events, |e| matches!(e, IbcEvent::ReceivePacket(_))),
+            3
+        );
+
+        let a_events = drain(&a_events);
+        assert_eq!(
+            count(&a_events, |e| matches!(e, IbcEvent::AcknowledgePacket(_))),
+            3
+        );
+
+        let (commitments, _) = a_chain
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: PortId::transfer(),
+                channel_id: a_channel_id,
+                pagination: None,
+            })
+            .unwrap();
+        assert!(commitments.is_empty());
+    }
+
+    /// Relays token transfers through the pending transactions of the packet workers, while the
+    /// chain handles lose the replies to submitted transactions and fail to query transactions,
+    /// and checks that every packet is received and acknowledged exactly once.
+    #[test]
+    fn async_packet_relaying_with_faults() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let a_chain: FaultyChainHandle<BaseChainHandle> = spawn_chain("chain_a", rt.clone());
+        let b_chain: FaultyChainHandle<BaseChainHandle> = spawn_chain("chain_b", rt);
+
+        let a_channel_id = open_channel(&a_chain, &b_chain)
+            .a_channel_id()
+            .unwrap()
+            .clone();
+
+        build_and_send_transfer_messages(&a_chain, &b_chain, &transfer_options(&a_channel_id, 3))
+            .unwrap();
+
+        let mut a_to_b = Link::new_from_opts(
+            a_chain.clone(),
+            b_chain.clone(),
+            link_parameters(&a_channel_id),
+            true,
+        )
+        .unwrap();
+        let mut b_to_a = a_to_b.reverse(true).unwrap();
+
+        let a_events = a_chain.subscribe().unwrap();
+        let b_events = b_chain.subscribe().unwrap();
+
+        // The transactions of the first batch sent to chain a are committed, but the relayer
+        // is told that they timed out, so they are never confirmed.
+        a_chain.set_schedule(
+            FaultSchedule::new(5).with_rule(
+                FaultRule::new(
+                    "send_messages_and_wait_check_tx",
+                    Fault::Timeout(Duration::from_millis(10)),
+                )
+                .with_limit(1),
+            ),
+        );
+        // The first attempt to confirm a transaction submitted to chain b fails.
+        b_chain.set_schedule(
+            FaultSchedule::new(13)
+                .with_rule(
+                    FaultRule::new("send_messages_and_wait_check_tx", Fault::Reorder).with_limit(1),
+                )
+                .with_rule(FaultRule::new("query_txs", Fault::Fail).with_limit(1)),
+        );
+
+        let confirmed = relay_async_until_done(&mut a_to_b.a_to_b, |confirmed| {
+            count(confirmed, |e| {
+                matches!(e, IbcEvent::WriteAcknowledgement(_))
+            }) == 3
+        });
+        assert_eq!(
+            count(&confirmed, |e| matches!(e, IbcEvent::ReceivePacket(_))),
+            3
+        );
+
+        let packet_commitments = || {
+            a_chain
+                .query_packet_commitments(QueryPacketCommitmentsRequest {
+                    port_id: PortId::transfer(),
+                    channel_id: a_channel_id.clone(),
+                    pagination: None,
+                })
+                .map(|(commitments, _)| commitments)
+        };
+
+        relay_async_until_done(
+            &mut b_to_a.a_to_b,
+            |_| matches!(packet_commitments(), Ok(commitments) if commitments.is_empty()),
+        );
+
+        let injected = |chain: &FaultyChainHandle<BaseChainHandle>, fault: Fault| {
+            chain
+                .injected_faults()
+                .iter()
+                .any(|injected| injected.fault == fault)
+        };
+        assert!(injected(
+            &a_chain,
+            Fault::Timeout(Duration::from_millis(10))
+        ));
+        assert!(injected(&b_chain, Fault::Reorder));
+        assert!(injected(&b_chain, Fault::Fail));
+
+        a_chain.set_schedule(FaultSchedule::default());
+        b_chain.set_schedule(FaultSchedule::default());
+
+        let b_events = drain(&b_events);
+        assert_eq!(
+            count(&b_events, |e| matches!(e, IbcEvent::ReceivePacket(_))),
+            3
+        );
+
+        let a_events = drain(&a_events);
+        assert_eq!(
+            count(&a_events, |e| matches!(e, IbcEvent::AcknowledgePacket(_))),
+            3
+        );
+    }
 }
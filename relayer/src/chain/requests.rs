@@ -383,6 +383,15 @@ impl From<QueryNextSequenceReceiveRequest> for RawQueryNextSequenceReceiveReques
     }
 }
 
+/// Used to query the channel upgrade state (upgrade sequence, timeout, or error receipt) of a
+/// channel. There is no gRPC endpoint for it, so the state is read from the store directly.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryChannelUpgradeRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub height: QueryHeight,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryHostConsensusStateRequest {
     pub height: QueryHeight,
@@ -21,6 +21,7 @@ use ibc::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, UpgradeTimeout},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -43,6 +44,7 @@ use crate::{
     },
     keyring::KeyEntry,
     light_client::LightClient,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{
@@ -51,14 +53,15 @@ use super::{
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::{
         IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
-        QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
-        QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-        QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-        QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-        QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-        QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
-        QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-        QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+        QueryChannelUpgradeRequest, QueryChannelsRequest, QueryClientConnectionsRequest,
+        QueryClientStateRequest, QueryClientStatesRequest, QueryConnectionChannelsRequest,
+        QueryConnectionRequest, QueryConnectionsRequest, QueryConsensusStateRequest,
+        QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
+        QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+        QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+        QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryTxRequest,
+        QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+        QueryUpgradedConsensusStateRequest,
     },
     tracking::TrackedMsgs,
 };
@@ -255,6 +258,10 @@ where
                             self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
                         },
 
+                        Ok(ChainRequest::SendMessagesWithKeyAndWaitCommit { key_name, tracked_msgs, reply_to }) => {
+                            self.send_messages_with_key_and_wait_commit(key_name, tracked_msgs, reply_to)?
+                        },
+
                         Ok(ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, reply_to }) => {
                             self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
                         },
@@ -311,6 +318,14 @@ where
                             self.query_denom_trace(hash, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryUpgradePlan { reply_to }) => {
+                            self.query_upgrade_plan(reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryAppliedUpgrades { reply_to }) => {
+                            self.query_applied_upgrades(reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryApplicationStatus { reply_to }) => {
                             self.query_application_status(reply_to)?
                         },
@@ -411,6 +426,18 @@ where
                             self.query_next_sequence_receive(request, include_proof, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryChannelUpgradeSequence { request, include_proof, reply_to }) => {
+                            self.query_channel_upgrade_sequence(request, include_proof, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryChannelUpgradeTimeout { request, include_proof, reply_to }) => {
+                            self.query_channel_upgrade_timeout(request, include_proof, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryChannelUpgradeError { request, include_proof, reply_to }) => {
+                            self.query_channel_upgrade_error(request, include_proof, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryPacketEventDataFromTxs { request, reply_to }) => {
                             self.query_txs(request, reply_to)?
                         },
@@ -464,6 +491,18 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn send_messages_with_key_and_wait_commit(
+        &mut self,
+        key_name: String,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<IbcEvent>>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .send_messages_with_key_and_wait_commit(&key_name, tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
@@ -487,6 +526,16 @@ where
         reply_to.send(denom_trace).map_err(Error::send)
     }
 
+    fn query_upgrade_plan(&self, reply_to: ReplyTo<Option<UpgradePlan>>) -> Result<(), Error> {
+        let plan = self.chain.query_upgrade_plan();
+        reply_to.send(plan).map_err(Error::send)
+    }
+
+    fn query_applied_upgrades(&self, reply_to: ReplyTo<Vec<UpgradePlan>>) -> Result<(), Error> {
+        let upgrades = self.chain.query_applied_upgrades();
+        reply_to.send(upgrades).map_err(Error::send)
+    }
+
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
         reply_to.send(latest_timestamp).map_err(Error::send)
@@ -868,6 +917,42 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn query_channel_upgrade_sequence(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(u64, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_channel_upgrade_sequence(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_channel_upgrade_timeout(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(UpgradeTimeout, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_channel_upgrade_timeout(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_channel_upgrade_error(
+        &self,
+        request: QueryChannelUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_channel_upgrade_error(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_txs(
         &self,
         request: QueryTxRequest,
@@ -5,6 +5,7 @@ use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
 pub use error::ChannelError;
+use error::ChannelErrorDetail;
 use ibc::core::ics04_channel::channel::{
     ChannelEnd, Counterparty, IdentifiedChannelEnd, Order, State,
 };
@@ -14,20 +15,31 @@ use ibc::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use ibc::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use ibc::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use ibc::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use ibc::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use ibc::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
 use ibc::core::ics04_channel::Version;
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use ibc::events::IbcEvent;
+use ibc::events::{IbcEvent, IbcEventType};
+use ibc::proofs::Proofs;
+use ibc::signer::Signer;
 use ibc::tx_msg::Msg;
 use ibc::Height;
 
 use crate::chain::counterparty::{channel_connection_client, channel_state_on_destination};
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
-    IncludeProof, PageRequest, QueryChannelRequest, QueryConnectionChannelsRequest,
-    QueryConnectionRequest, QueryHeight,
+    IncludeProof, PageRequest, QueryChannelRequest, QueryChannelUpgradeRequest,
+    QueryClientStateRequest, QueryConnectionChannelsRequest, QueryConnectionRequest, QueryHeight,
 };
 use crate::chain::tracking::TrackedMsgs;
+use crate::channel::multihop::{wrap_hop_proofs, HopProofs, MultihopHop, MultihopPath};
 use crate::connection::Connection;
+use crate::error::Error as RelayerError;
 use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
 use crate::object::Channel as WorkerChannelObject;
 use crate::supervisor::error::Error as SupervisorError;
@@ -36,6 +48,7 @@ use crate::util::retry::{retry_count, RetryResult};
 use crate::util::task::Next;
 
 pub mod error;
+pub mod multihop;
 pub mod version;
 
 mod handshake_retry {
@@ -91,6 +104,8 @@ pub struct ChannelSide<Chain: ChainHandle> {
     port_id: PortId,
     channel_id: Option<ChannelId>,
     version: Option<Version>,
+    #[serde(skip)]
+    multihop_hops: Vec<MultihopHop<Chain>>,
 }
 
 impl<Chain: ChainHandle> ChannelSide<Chain> {
@@ -109,9 +124,17 @@ impl<Chain: ChainHandle> ChannelSide<Chain> {
             port_id,
             channel_id,
             version,
+            multihop_hops: Vec::new(),
         }
     }
 
+    /// Sets the intermediate chains proving the state of the counterparty chain to this side,
+    /// for a channel whose connection hops go through other chains.
+    pub fn with_multihop_hops(mut self, hops: Vec<MultihopHop<Chain>>) -> Self {
+        self.multihop_hops = hops;
+        self
+    }
+
     pub fn chain_id(&self) -> ChainId {
         self.chain.id()
     }
@@ -136,10 +159,38 @@ impl<Chain: ChainHandle> ChannelSide<Chain> {
         self.version.as_ref()
     }
 
+    /// The connection hops of the channel end on this side, starting with its own connection.
+    pub fn connection_hops(&self) -> Vec<ConnectionId> {
+        core::iter::once(self.connection_id.clone())
+            .chain(
+                self.multihop_hops
+                    .iter()
+                    .map(|hop| hop.connection_id.clone()),
+            )
+            .collect()
+    }
+
+    pub fn multihop_hops(&self) -> &[MultihopHop<Chain>] {
+        &self.multihop_hops
+    }
+
+    pub fn is_multihop(&self) -> bool {
+        !self.multihop_hops.is_empty()
+    }
+
     pub fn map_chain<ChainB: ChainHandle>(
         self,
         mapper: impl Fn(Chain) -> ChainB,
     ) -> ChannelSide<ChainB> {
+        let multihop_hops = self
+            .multihop_hops
+            .into_iter()
+            .map(|hop| MultihopHop {
+                chain: mapper(hop.chain),
+                connection_id: hop.connection_id,
+            })
+            .collect();
+
         ChannelSide {
             chain: mapper(self.chain),
             client_id: self.client_id,
@@ -147,6 +198,7 @@ impl<Chain: ChainHandle> ChannelSide<Chain> {
             port_id: self.port_id,
             channel_id: self.channel_id,
             version: self.version,
+            multihop_hops,
         }
     }
 }
@@ -466,6 +518,36 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         Ok(a_block_time.max(b_block_time))
     }
 
+    /// Whether the transactions are simulated instead of being submitted, see `--dry-run`.
+    fn is_dry_run(&self) -> Result<bool, ChannelError> {
+        let a_dry_run = self
+            .a_chain()
+            .config()
+            .map_err(ChannelError::relayer)?
+            .dry_run;
+        let b_dry_run = self
+            .b_chain()
+            .config()
+            .map_err(ChannelError::relayer)?
+            .dry_run;
+        Ok(a_dry_run || b_dry_run)
+    }
+
+    /// Sets the intermediate chains of a channel whose connection hops go through other chains.
+    pub fn with_multihop_path(mut self, path: MultihopPath<ChainA, ChainB>) -> Self {
+        if let Some(connection_id) = path.b_connection_id {
+            self.b_side.connection_id = connection_id;
+        }
+
+        if let Some(client_id) = path.b_client_id {
+            self.b_side.client_id = client_id;
+        }
+
+        self.a_side = self.a_side.with_multihop_hops(path.a_hops);
+        self.b_side = self.b_side.with_multihop_hops(path.b_hops);
+        self
+    }
+
     pub fn flipped(&self) -> Channel<ChainB, ChainA> {
         Channel {
             ordering: self.ordering,
@@ -677,16 +759,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     /// Executes the channel handshake protocol (ICS004)
     fn handshake(&mut self) -> Result<(), ChannelError> {
         let max_block_times = self.max_block_times()?;
+        let dry_run = self.is_dry_run()?;
 
         retry_with_index(handshake_retry::default_strategy(max_block_times), |_| {
-            if let Err(e) = self.do_chan_open_handshake() {
-                if e.is_expired_or_frozen_error() {
-                    RetryResult::Err(e)
-                } else {
-                    RetryResult::Retry(e)
-                }
-            } else {
-                RetryResult::Ok(())
+            match self.do_chan_open_handshake() {
+                Ok(()) => RetryResult::Ok(()),
+                Err(e) if e.is_expired_or_frozen_error() => RetryResult::Err(e),
+                // Nothing is submitted in a dry run,
+                // so the handshake cannot progress past its first step
+                Err(e) if dry_run => match e.detail() {
+                    ChannelErrorDetail::HandshakeFinalize(_) => RetryResult::Ok(()),
+                    _ => RetryResult::Err(e),
+                },
+                Err(e) => RetryResult::Retry(e),
             }
         })
         .map_err(|err| {
@@ -734,6 +819,40 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             // return anyway as the final step is to be done by the counterparty worker.
             (State::TryOpen, State::Open) => return Ok((None, Next::Abort)),
 
+            // Channel upgrade handshake. An upgrade proposed on this end is first checked for
+            // having been rejected or having timed out on the counterparty.
+            (State::InitUpgrade, State::Open) => {
+                if self.upgrade_aborted_on_dst()? {
+                    Some(self.flipped().build_chan_upgrade_cancel_and_send()?)
+                } else if self.upgrade_timed_out_on_dst()? {
+                    Some(self.flipped().build_chan_upgrade_timeout_and_send()?)
+                } else {
+                    Some(self.build_chan_upgrade_try_and_send()?)
+                }
+            }
+            (State::InitUpgrade, State::InitUpgrade) => {
+                Some(self.build_chan_upgrade_try_and_send()?)
+            }
+            (State::TryUpgrade, State::InitUpgrade) | (State::TryUpgrade, State::TryUpgrade) => {
+                Some(self.build_chan_upgrade_ack_and_send()?)
+            }
+            (State::Open, State::TryUpgrade) => {
+                if self.upgrade_aborted_on_src()? {
+                    Some(self.build_chan_upgrade_cancel_and_send()?)
+                } else {
+                    Some(self.build_chan_upgrade_confirm_and_send()?)
+                }
+            }
+            // The upgrade was proposed by the counterparty, whose worker relays it unless
+            // this end rejected it.
+            (State::Open, State::InitUpgrade) => {
+                if self.upgrade_aborted_on_src()? {
+                    Some(self.build_chan_upgrade_cancel_and_send()?)
+                } else {
+                    None
+                }
+            }
+
             _ => None,
         };
 
@@ -768,6 +887,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             IbcEvent::OpenTryChannel(_) => State::TryOpen,
             IbcEvent::OpenAckChannel(_) => State::Open,
             IbcEvent::OpenConfirmChannel(_) => State::Open,
+
+            // The application may reject an upgrade step, in which case the channel is
+            // restored to its previous state, so the state is not derived from the event.
+            IbcEvent::UpgradeInitChannel(_)
+            | IbcEvent::UpgradeTryChannel(_)
+            | IbcEvent::UpgradeAckChannel(_)
+            | IbcEvent::UpgradeTimeoutChannel(_)
+            | IbcEvent::UpgradeCancelChannel(_) => match self.src_channel_state() {
+                Ok(state) => state,
+                Err(e) => {
+                    error!("failed to query the state of the upgrading channel: {}", e);
+                    return RetryResult::Retry(index);
+                }
+            },
             _ => State::Uninitialized,
         };
 
@@ -775,6 +908,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, ChannelError> {
+        // The destination client of a multi-hop channel tracks the adjacent intermediate chain,
+        // which is kept up to date by the relayer of that hop.
+        if self.b_side.is_multihop() {
+            return Ok(vec![]);
+        }
+
         let client = ForeignClient::restore(
             self.dst_client_id().clone(),
             self.dst_chain().clone(),
@@ -786,6 +925,36 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         })
     }
 
+    /// Returns the height at which the source chain must be queried for the values proven to
+    /// the destination chain, along with the proofs of the intermediate chains wrapping them
+    /// if the channel goes through any.
+    fn src_proofs_path(&self) -> Result<(Height, Option<HopProofs>), ChannelError> {
+        if !self.b_side.is_multihop() {
+            let query_height = self
+                .src_chain()
+                .query_latest_height()
+                .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+            return Ok((query_height, None));
+        }
+
+        let (client_state, _) = self
+            .dst_chain()
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.dst_client_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let hop_proofs =
+            HopProofs::query(self.b_side.multihop_hops(), client_state.latest_height())?;
+
+        Ok((hop_proofs.key_height(), Some(hop_proofs)))
+    }
+
     pub fn build_chan_open_init(&self) -> Result<Vec<Any>, ChannelError> {
         let signer = self
             .dst_chain()
@@ -816,7 +985,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             State::Init,
             self.ordering,
             counterparty,
-            vec![self.dst_connection_id().clone()],
+            self.b_side.connection_hops(),
             version,
         );
 
@@ -895,7 +1064,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             highest_state,
             self.ordering,
             counterparty,
-            vec![self.dst_connection_id().clone()],
+            self.b_side.connection_hops(),
             Version::empty(),
         );
 
@@ -963,15 +1132,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
 
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -986,7 +1153,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             State::TryOpen,
             *src_channel.ordering(),
             counterparty,
-            vec![self.dst_connection_id().clone()],
+            self.b_side.connection_hops(),
             version,
         );
 
@@ -1083,15 +1250,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
 
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1191,15 +1356,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
 
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1362,15 +1525,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             )
             .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
 
-        let query_height = self
-            .src_chain()
-            .query_latest_height()
-            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
 
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1424,6 +1585,447 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
+    /// Queries the current state of the channel end on the source chain.
+    fn src_channel_state(&self) -> Result<State, ChannelError> {
+        let channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let (channel_end, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        Ok(*channel_end.state())
+    }
+
+    fn src_upgrade_request(
+        &self,
+        height: QueryHeight,
+    ) -> Result<QueryChannelUpgradeRequest, ChannelError> {
+        let channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        Ok(QueryChannelUpgradeRequest {
+            port_id: self.src_port_id().clone(),
+            channel_id: channel_id.clone(),
+            height,
+        })
+    }
+
+    /// Returns `true` if the source chain wrote an error receipt that aborts the upgrade
+    /// currently in progress on the destination chain.
+    fn upgrade_aborted_on_src(&self) -> Result<bool, ChannelError> {
+        // No error receipt was written on the source chain.
+        let error_receipt = match self.src_chain().query_channel_upgrade_error(
+            self.src_upgrade_request(QueryHeight::Latest)?,
+            IncludeProof::No,
+        ) {
+            Ok((error_receipt, _)) => error_receipt,
+            Err(_) => return Ok(false),
+        };
+
+        let (dst_sequence, _) = self
+            .dst_chain()
+            .query_channel_upgrade_sequence(
+                self.flipped().src_upgrade_request(QueryHeight::Latest)?,
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        Ok(error_receipt.sequence >= dst_sequence)
+    }
+
+    fn upgrade_aborted_on_dst(&self) -> Result<bool, ChannelError> {
+        self.flipped().upgrade_aborted_on_src()
+    }
+
+    /// Returns `true` if the timeout of the upgrade proposed on the source chain has passed
+    /// on the destination chain, in which case the destination can no longer accept it.
+    fn upgrade_timed_out_on_dst(&self) -> Result<bool, ChannelError> {
+        let (timeout, _) = self
+            .src_chain()
+            .query_channel_upgrade_timeout(
+                self.src_upgrade_request(QueryHeight::Latest)?,
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        Ok(timeout.has_expired(&dst_status.timestamp, dst_status.height))
+    }
+
+    /// Submits the given messages to the destination chain and returns the first event of
+    /// the expected type found in the response.
+    fn send_upgrade_msgs_to_dst(
+        &self,
+        dst_msgs: Vec<Any>,
+        tracking_id: &'static str,
+        expected_event: IbcEventType,
+    ) -> Result<IbcEvent, ChannelError> {
+        let tm = TrackedMsgs::new_static(dst_msgs, tracking_id);
+
+        let events = self
+            .dst_chain()
+            .send_messages_and_wait_commit(tm)
+            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+
+        let result = events
+            .into_iter()
+            .find(|event| {
+                event.event_type() == expected_event || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::missing_event(format!(
+                    "no {} event was in the response",
+                    expected_event.as_str()
+                ))
+            })?;
+
+        match result {
+            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e)),
+            _ => {
+                info!("🎊  {} => {:#?}\n", self.dst_chain().id(), result);
+                Ok(result)
+            }
+        }
+    }
+
+    pub fn build_chan_upgrade_try(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
+
+        // The upgrade must have been proposed on the source chain
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        if !src_channel.state_matches(&State::InitUpgrade) {
+            return Err(ChannelError::invalid_channel(format!(
+                "channel {} on chain {} is in state {}, expected {}",
+                src_channel_id,
+                self.src_chain().id(),
+                src_channel.state(),
+                State::InitUpgrade
+            )));
+        }
+
+        let request = self.src_upgrade_request(QueryHeight::Specific(query_height))?;
+
+        let (counterparty_sequence, sequence_proof) = self
+            .src_chain()
+            .query_channel_upgrade_sequence(request.clone(), IncludeProof::Yes)
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (timeout, timeout_proof) = self
+            .src_chain()
+            .query_channel_upgrade_timeout(request, IncludeProof::Yes)
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
+
+        // The channel end the destination chain moves to, mirroring the proposal
+        let proposed_upgrade_channel = ChannelEnd::new(
+            State::TryUpgrade,
+            *src_channel.ordering(),
+            Counterparty::new(self.src_port_id().clone(), Some(src_channel_id.clone())),
+            self.b_side.connection_hops(),
+            src_channel.version().clone(),
+        );
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeTry {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel: src_channel,
+            counterparty_sequence,
+            proposed_upgrade_channel,
+            timeout,
+            proofs,
+            proof_upgrade_timeout: upgrade_proof_bytes(timeout_proof, &hop_proofs)?,
+            proof_upgrade_sequence: upgrade_proof_bytes(sequence_proof, &hop_proofs)?,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_try()?;
+
+        self.send_upgrade_msgs_to_dst(
+            dst_msgs,
+            "ChannelUpgradeTry",
+            IbcEventType::UpgradeTryChannel,
+        )
+    }
+
+    pub fn build_chan_upgrade_ack(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
+
+        // The upgrade must have been accepted on the source chain
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        if !src_channel.state_matches(&State::TryUpgrade) {
+            return Err(ChannelError::invalid_channel(format!(
+                "channel {} on chain {} is in state {}, expected {}",
+                src_channel_id,
+                self.src_chain().id(),
+                src_channel.state(),
+                State::TryUpgrade
+            )));
+        }
+
+        let (_, sequence_proof) = self
+            .src_chain()
+            .query_channel_upgrade_sequence(
+                self.src_upgrade_request(QueryHeight::Specific(query_height))?,
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeAck {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel: src_channel,
+            proofs,
+            proof_upgrade_sequence: upgrade_proof_bytes(sequence_proof, &hop_proofs)?,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_ack()?;
+
+        self.send_upgrade_msgs_to_dst(
+            dst_msgs,
+            "ChannelUpgradeAck",
+            IbcEventType::UpgradeAckChannel,
+        )
+    }
+
+    /// Builds the message carrying a proof of the current source channel end, as used by
+    /// `ChannelUpgradeConfirm` and `ChannelUpgradeTimeout`.
+    fn build_chan_upgrade_with_src_channel<M: Msg>(
+        &self,
+        build_msg: impl FnOnce(PortId, ChannelId, ChannelEnd, Proofs, Signer) -> M,
+    ) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
+
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = build_msg(
+            self.dst_port_id().clone(),
+            dst_channel_id.clone(),
+            src_channel,
+            proofs,
+            signer,
+        );
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_confirm(&self) -> Result<Vec<Any>, ChannelError> {
+        self.build_chan_upgrade_with_src_channel(MsgChannelUpgradeConfirm::new)
+    }
+
+    pub fn build_chan_upgrade_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_confirm()?;
+
+        self.send_upgrade_msgs_to_dst(
+            dst_msgs,
+            "ChannelUpgradeConfirm",
+            IbcEventType::UpgradeConfirmChannel,
+        )
+    }
+
+    /// Builds the message that aborts the upgrade in progress on the destination chain,
+    /// once its upgrade timeout has passed on the source chain.
+    pub fn build_chan_upgrade_timeout(&self) -> Result<Vec<Any>, ChannelError> {
+        self.build_chan_upgrade_with_src_channel(MsgChannelUpgradeTimeout::new)
+    }
+
+    pub fn build_chan_upgrade_timeout_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_timeout()?;
+
+        self.send_upgrade_msgs_to_dst(
+            dst_msgs,
+            "ChannelUpgradeTimeout",
+            IbcEventType::UpgradeTimeoutChannel,
+        )
+    }
+
+    /// Builds the message that aborts the upgrade in progress on the destination chain,
+    /// using the error receipt written by the source chain.
+    pub fn build_chan_upgrade_cancel(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (query_height, hop_proofs) = self.src_proofs_path()?;
+
+        let (error_receipt, error_proof) = self
+            .src_chain()
+            .query_channel_upgrade_error(
+                self.src_upgrade_request(QueryHeight::Specific(query_height))?,
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = Proofs::new(
+            upgrade_proof_bytes(error_proof, &None)?,
+            None,
+            None,
+            None,
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeCancel::new(
+            self.dst_port_id().clone(),
+            dst_channel_id.clone(),
+            error_receipt,
+            proofs,
+            signer,
+        );
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_cancel_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_cancel()?;
+
+        self.send_upgrade_msgs_to_dst(
+            dst_msgs,
+            "ChannelUpgradeCancel",
+            IbcEventType::UpgradeCancelChannel,
+        )
+    }
+
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
         self,
         mapper_a: impl Fn(ChainA) -> ChainC,
@@ -1444,11 +2046,33 @@ pub fn extract_channel_id(event: &IbcEvent) -> Result<&ChannelId, ChannelError>
         IbcEvent::OpenTryChannel(ev) => ev.channel_id(),
         IbcEvent::OpenAckChannel(ev) => ev.channel_id(),
         IbcEvent::OpenConfirmChannel(ev) => ev.channel_id(),
+        IbcEvent::UpgradeInitChannel(ev) => ev.channel_id(),
+        IbcEvent::UpgradeTryChannel(ev) => ev.channel_id(),
+        IbcEvent::UpgradeAckChannel(ev) => ev.channel_id(),
+        IbcEvent::UpgradeConfirmChannel(ev) => ev.channel_id(),
         _ => None,
     }
     .ok_or_else(|| ChannelError::missing_event("cannot extract channel_id from result".to_string()))
 }
 
+/// Converts a proof of a channel upgrade value into the form carried by upgrade messages,
+/// wrapping it into multi-hop proofs if the channel goes through intermediate chains.
+fn upgrade_proof_bytes(
+    proof: Option<MerkleProof>,
+    hop_proofs: &Option<HopProofs>,
+) -> Result<CommitmentProofBytes, ChannelError> {
+    let proof =
+        proof.ok_or_else(|| ChannelError::channel_proof(RelayerError::empty_response_proof()))?;
+
+    let proof = CommitmentProofBytes::try_from(proof)
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+    match hop_proofs {
+        Some(hop_proofs) => hop_proofs.wrap(proof),
+        None => Ok(proof),
+    }
+}
+
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ChannelMsgType {
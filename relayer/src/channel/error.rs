@@ -2,8 +2,10 @@ use core::time::Duration;
 use flex_error::define_error;
 use ibc::core::ics02_client::error::Error as ClientError;
 use ibc::core::ics04_channel::channel::State;
+use ibc::core::ics04_channel::error::Error as ChannelProtocolError;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortChannelId, PortId};
 use ibc::events::IbcEvent;
+use ibc::Height;
 
 use crate::error::Error as RelayerError;
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
@@ -177,6 +179,21 @@ define_error! {
                     e.event)
             },
 
+        MultihopProof
+            [ ChannelProtocolError ]
+            |_| { "failed to build multi-hop proofs" },
+
+        MultihopPathBehind
+            {
+                chain_id: ChainId,
+                proven_height: Height,
+                required_height: Height,
+            }
+            | e | {
+                format_args!("the intermediate chains of the multi-hop channel prove chain {0} up to height {1}, but height {2} is required",
+                    e.chain_id, e.proven_height, e.required_height)
+            },
+
         MaxRetry
             {
                 description: String,
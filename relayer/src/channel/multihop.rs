@@ -0,0 +1,327 @@
+//! Assembly of the proofs for channels whose connection hops go through intermediate chains.
+//!
+//! A value stored on the counterparty chain of a multi-hop channel is not proven directly
+//! against a consensus state of the verifying chain: each intermediate chain on the path
+//! proves the connection end of its hop, the client state of the next chain and the consensus
+//! state that it holds for the next chain, down to the counterparty chain where the value
+//! itself is proven.
+
+use ibc::core::ics02_client::client_state::ClientState;
+use ibc::core::ics04_channel::multihop::{
+    ClientStateHopProof, ConnectionHopProof, ConsensusStateHopProof, MultihopProofs,
+};
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+use ibc::proofs::Proofs;
+use ibc::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{
+    IncludeProof, QueryClientStateRequest, QueryConnectionRequest, QueryConsensusStateRequest,
+    QueryHeight,
+};
+use crate::error::Error as RelayerError;
+use crate::spawn::SpawnError;
+use crate::supervisor::Error as SupervisorError;
+
+use super::error::ChannelError;
+
+/// An intermediate chain on the path of a multi-hop channel, along with the connection
+/// that leads from it to the next chain towards the counterparty chain.
+#[derive(Clone, Debug)]
+pub struct MultihopHop<Chain> {
+    pub chain: Chain,
+    pub connection_id: ConnectionId,
+}
+
+/// The intermediate chains of a multi-hop channel, as seen from each of its ends.
+///
+/// The hops of a side prove the state of the other side to it, and are ordered starting from
+/// the chain adjacent to that side. Both are empty for a channel over a single connection.
+///
+/// The connection of side a does not lead to side b directly, so the path also records the
+/// connection of side b and the client it is built on.
+#[derive(Clone, Debug)]
+pub struct MultihopPath<ChainA, ChainB> {
+    pub a_hops: Vec<MultihopHop<ChainA>>,
+    pub b_hops: Vec<MultihopHop<ChainB>>,
+    pub b_connection_id: Option<ConnectionId>,
+    pub b_client_id: Option<ClientId>,
+}
+
+impl<ChainA, ChainB> Default for MultihopPath<ChainA, ChainB> {
+    fn default() -> Self {
+        Self {
+            a_hops: Vec::new(),
+            b_hops: Vec::new(),
+            b_connection_id: None,
+            b_client_id: None,
+        }
+    }
+}
+
+/// Resolves the intermediate chains of a channel whose end on `chain` has the given
+/// `connection_hops`, walking the connections from `chain` towards the counterparty chain,
+/// which is returned along with them.
+///
+/// `get_chain` returns the handle of a chain from its identifier, as found in the client
+/// state of each connection on the path.
+pub fn resolve_multihop_path<Chain, F>(
+    chain: &Chain,
+    connection_hops: &[ConnectionId],
+    mut get_chain: F,
+) -> Result<(Chain, MultihopPath<Chain, Chain>), ChannelError>
+where
+    Chain: ChainHandle,
+    F: FnMut(&ChainId) -> Result<Chain, SpawnError>,
+{
+    let mut path = MultihopPath::default();
+    let mut current = chain.clone();
+
+    for (i, connection_id) in connection_hops.iter().enumerate() {
+        let (connection_end, _) = current
+            .query_connection(
+                QueryConnectionRequest {
+                    connection_id: connection_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(current.id(), e))?;
+
+        let (client_state, _) = current
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: connection_end.client_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(current.id(), e))?;
+
+        let next = get_chain(&client_state.chain_id())
+            .map_err(|e| ChannelError::supervisor(SupervisorError::spawn(e)))?;
+
+        if let Some(next_connection_id) = connection_hops.get(i + 1) {
+            // The counterparty of this connection leads from the next chain back to `chain`.
+            let counterparty_connection_id = connection_end
+                .counterparty()
+                .connection_id()
+                .cloned()
+                .ok_or_else(ChannelError::missing_counterparty_connection)?;
+
+            path.a_hops.push(MultihopHop {
+                chain: next.clone(),
+                connection_id: next_connection_id.clone(),
+            });
+
+            path.b_hops.push(MultihopHop {
+                chain: next.clone(),
+                connection_id: counterparty_connection_id,
+            });
+        } else if i > 0 {
+            path.b_connection_id = connection_end.counterparty().connection_id().cloned();
+            path.b_client_id = Some(connection_end.counterparty().client_id().clone());
+        }
+
+        current = next;
+    }
+
+    path.b_hops.reverse();
+
+    Ok((current, path))
+}
+
+/// The proofs of the intermediate chains of a multi-hop channel, down to the height of the
+/// counterparty chain at which the values proven over this path must be queried.
+#[derive(Clone, Debug)]
+pub struct HopProofs {
+    proof_height: Height,
+    key_height: Height,
+    connection_proofs: Vec<ConnectionHopProof>,
+    client_state_proofs: Vec<ClientStateHopProof>,
+    consensus_proofs: Vec<ConsensusStateHopProof>,
+}
+
+impl HopProofs {
+    /// Queries the intermediate chains of a multi-hop channel for the proofs of their hop.
+    ///
+    /// The `hops` are ordered starting from the chain adjacent to the chain verifying the
+    /// proofs, and `proof_height` is the height of that chain whose consensus state the
+    /// verifying chain holds. Each intermediate chain is queried for the connection end of its
+    /// hop, and for the client state and latest consensus state of the next chain held by the
+    /// client of that connection.
+    pub fn query<Chain: ChainHandle>(
+        hops: &[MultihopHop<Chain>],
+        proof_height: Height,
+    ) -> Result<Self, ChannelError> {
+        let mut height = proof_height;
+        let mut connection_proofs = Vec::with_capacity(hops.len());
+        let mut client_state_proofs = Vec::with_capacity(hops.len());
+        let mut consensus_proofs = Vec::with_capacity(hops.len());
+
+        for hop in hops {
+            // Proofs at some height are for the state committed in the block before it.
+            let query_height = QueryHeight::Specific(
+                height
+                    .decrement()
+                    .map_err(|e| ChannelError::query(hop.chain.id(), RelayerError::ics02(e)))?,
+            );
+
+            let (connection_end, connection_proof) = hop
+                .chain
+                .query_connection(
+                    QueryConnectionRequest {
+                        connection_id: hop.connection_id.clone(),
+                        height: query_height,
+                    },
+                    IncludeProof::Yes,
+                )
+                .map_err(|e| ChannelError::query(hop.chain.id(), e))?;
+
+            let client_id = connection_end.client_id().clone();
+
+            let (client_state, client_state_proof) = hop
+                .chain
+                .query_client_state(
+                    QueryClientStateRequest {
+                        client_id: client_id.clone(),
+                        height: query_height,
+                    },
+                    IncludeProof::Yes,
+                )
+                .map_err(|e| ChannelError::query(hop.chain.id(), e))?;
+
+            let consensus_height = client_state.latest_height();
+
+            let (consensus_state, consensus_proof) = hop
+                .chain
+                .query_consensus_state(
+                    QueryConsensusStateRequest {
+                        client_id: client_id.clone(),
+                        consensus_height,
+                        query_height,
+                    },
+                    IncludeProof::Yes,
+                )
+                .map_err(|e| ChannelError::query(hop.chain.id(), e))?;
+
+            connection_proofs.push(ConnectionHopProof {
+                connection_id: hop.connection_id.clone(),
+                connection_end,
+                proof: hop_proof_bytes(connection_proof)?,
+            });
+
+            client_state_proofs.push(ClientStateHopProof {
+                client_id: client_id.clone(),
+                client_state,
+                proof: hop_proof_bytes(client_state_proof)?,
+            });
+
+            consensus_proofs.push(ConsensusStateHopProof {
+                client_id,
+                consensus_height,
+                consensus_state,
+                proof: hop_proof_bytes(consensus_proof)?,
+            });
+
+            height = consensus_height;
+        }
+
+        let key_height = height
+            .decrement()
+            .map_err(|e| ChannelError::channel_proof(RelayerError::ics02(e)))?;
+
+        Ok(Self {
+            proof_height,
+            key_height,
+            connection_proofs,
+            client_state_proofs,
+            consensus_proofs,
+        })
+    }
+
+    /// The height of the chain verifying the proofs whose consensus state roots them.
+    pub fn proof_height(&self) -> Height {
+        self.proof_height
+    }
+
+    /// The height at which the counterparty chain must be queried for the proven values.
+    pub fn key_height(&self) -> Height {
+        self.key_height
+    }
+
+    /// Wraps the proof of a value queried at [`HopProofs::key_height`] on the counterparty
+    /// chain into multi-hop proofs.
+    pub fn wrap(
+        &self,
+        key_proof: CommitmentProofBytes,
+    ) -> Result<CommitmentProofBytes, ChannelError> {
+        let multihop_proofs = MultihopProofs {
+            key_proof,
+            connection_proofs: self.connection_proofs.clone(),
+            client_state_proofs: self.client_state_proofs.clone(),
+            consensus_proofs: self.consensus_proofs.clone(),
+        };
+
+        CommitmentProofBytes::try_from(multihop_proofs).map_err(ChannelError::multihop_proof)
+    }
+
+    /// Wraps the proofs built at [`HopProofs::key_height`] on the counterparty chain, e.g. with
+    /// [`ChainHandle::build_channel_proofs`], into proofs at [`HopProofs::proof_height`].
+    pub fn wrap_proofs(&self, key_proofs: Proofs) -> Result<Proofs, ChannelError> {
+        let object_proof = self.wrap(key_proofs.object_proof().clone())?;
+
+        let other_proof = key_proofs
+            .other_proof()
+            .clone()
+            .map(|proof| self.wrap(proof))
+            .transpose()?;
+
+        Proofs::new(object_proof, None, None, other_proof, self.proof_height)
+            .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))
+    }
+}
+
+/// Builds the proofs of a value stored on the counterparty chain of a multi-hop channel.
+///
+/// `build_key_proofs` is called with the query height on the counterparty chain to build the
+/// proofs of the value itself, e.g. with [`ChainHandle::build_channel_proofs`], which are then
+/// wrapped into multi-hop proofs as described in [`HopProofs::query`].
+pub fn build_multihop_proofs<Chain, F>(
+    hops: &[MultihopHop<Chain>],
+    proof_height: Height,
+    build_key_proofs: F,
+) -> Result<Proofs, ChannelError>
+where
+    Chain: ChainHandle,
+    F: FnOnce(Height) -> Result<Proofs, RelayerError>,
+{
+    let hop_proofs = HopProofs::query(hops, proof_height)?;
+
+    let key_proofs =
+        build_key_proofs(hop_proofs.key_height()).map_err(ChannelError::channel_proof)?;
+
+    hop_proofs.wrap_proofs(key_proofs)
+}
+
+/// Wraps proofs built on the counterparty chain into multi-hop proofs, if the channel goes
+/// through intermediate chains.
+pub fn wrap_hop_proofs(
+    hop_proofs: &Option<HopProofs>,
+    proofs: Proofs,
+) -> Result<Proofs, ChannelError> {
+    match hop_proofs {
+        Some(hop_proofs) => hop_proofs.wrap_proofs(proofs),
+        None => Ok(proofs),
+    }
+}
+
+fn hop_proof_bytes(proof: Option<MerkleProof>) -> Result<CommitmentProofBytes, ChannelError> {
+    let proof =
+        proof.ok_or_else(|| ChannelError::channel_proof(RelayerError::empty_response_proof()))?;
+
+    CommitmentProofBytes::try_from(proof)
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))
+}
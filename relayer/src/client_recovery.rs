@@ -0,0 +1,219 @@
+//! Recovery of expired IBC clients through a governance proposal.
+//!
+//! An expired client can no longer be updated by the relayer. The only way to bring it
+//! back is to create a fresh substitute client tracking the same chain with the same
+//! parameters, and to submit a `ClientUpdateProposal` which, once passed, copies the latest
+//! consensus state of the substitute client over to the expired (subject) client.
+
+use core::time::Duration;
+
+use flex_error::define_error;
+use tracing::info;
+
+use tendermint::abci::transaction::Hash as TxHash;
+
+use ibc::clients::ics07_tendermint::client_state::ClientState as TendermintClientState;
+use ibc::core::ics02_client::client_state::AnyClientState;
+use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc::downcast;
+use ibc::Height;
+use ibc_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::ClientUpdateProposal;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use crate::chain::tracking::TrackedMsgs;
+use crate::error::Error;
+use crate::foreign_client::{extract_client_id, CreateOptions, ForeignClient, ForeignClientError};
+
+define_error! {
+    ClientRecoveryError {
+        Query
+            [ Error ]
+            |_| { "error during a query" },
+
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        ForeignClient
+            [ ForeignClientError ]
+            |_| { "foreign client error" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed while submitting the ClientUpdateProposal to chain {0}", e.chain_id)
+            },
+
+        TendermintOnly
+            |_| { "only Tendermint clients can be recovered" },
+
+        NotRecovered
+            {
+                client_id: ClientId,
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("client {0} on chain {1} has not been recovered: {2}",
+                    e.client_id, e.chain_id, e.reason)
+            },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientUpdateProposalOptions {
+    pub subject_client_id: ClientId,
+    pub substitute_client_id: ClientId,
+    pub amount: u64,
+    pub denom: String,
+}
+
+/// Creates a substitute client for the given (expired) client, on the same host chain
+/// and tracking the same chain, with the same trusting period, trust threshold and
+/// maximum clock drift, so that the substitute matches the subject client parameters.
+pub fn create_substitute_client<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    subject: &ForeignClient<DstChain, SrcChain>,
+) -> Result<ForeignClient<DstChain, SrcChain>, ClientRecoveryError> {
+    let client_state = query_tendermint_client_state(&subject.dst_chain(), subject.id())?;
+
+    let options = CreateOptions {
+        max_clock_drift: Some(client_state.max_clock_drift),
+        trusting_period: Some(client_state.trusting_period),
+        trust_threshold: Some(client_state.trust_level),
+    };
+
+    let event = subject
+        .build_create_client_and_send(options)
+        .map_err(ClientRecoveryError::foreign_client)?;
+
+    let substitute_client_id = extract_client_id(&event)
+        .map_err(ClientRecoveryError::foreign_client)?
+        .clone();
+
+    info!(
+        chain = %subject.dst_chain().id(),
+        "created substitute client {} for client {}",
+        substitute_client_id,
+        subject.id()
+    );
+
+    Ok(ForeignClient::restore(
+        substitute_client_id,
+        subject.dst_chain(),
+        subject.src_chain(),
+    ))
+}
+
+pub fn build_and_send_client_update_proposal(
+    dst_chain: impl ChainHandle, // the chain which hosts both the subject and the substitute client
+    opts: &ClientUpdateProposalOptions,
+) -> Result<TxHash, ClientRecoveryError> {
+    let proposal = ClientUpdateProposal {
+        title: format!("recover client {}", opts.subject_client_id),
+        description: format!(
+            "replace the expired client {} with the substitute client {}",
+            opts.subject_client_id, opts.substitute_client_id
+        ),
+        subject_client_id: opts.subject_client_id.to_string(),
+        substitute_client_id: opts.substitute_client_id.to_string(),
+    };
+
+    let mut buf_proposal = Vec::new();
+    prost::Message::encode(&proposal, &mut buf_proposal).unwrap();
+    let any_proposal = Any {
+        type_url: "/ibc.core.client.v1.ClientUpdateProposal".to_string(),
+        value: buf_proposal,
+    };
+
+    // build the msg submit proposal
+    let proposer = dst_chain.get_signer().map_err(ClientRecoveryError::key)?;
+
+    let coins = ibc_proto::cosmos::base::v1beta1::Coin {
+        denom: opts.denom.clone(),
+        amount: opts.amount.to_string(),
+    };
+
+    let msg = MsgSubmitProposal {
+        content: Some(any_proposal),
+        initial_deposit: vec![coins],
+        proposer: proposer.to_string(),
+    };
+
+    let mut buf_msg = Vec::new();
+    prost::Message::encode(&msg, &mut buf_msg).unwrap();
+    let any_msg = Any {
+        type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_string(),
+        value: buf_msg,
+    };
+
+    // As for the upgrade proposals, no IBC event is emitted by the submission
+    // of the proposal, so only wait for the transaction to pass `CheckTx`.
+    let responses = dst_chain
+        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(any_msg, "recover client"))
+        .map_err(|e| ClientRecoveryError::submit(dst_chain.id(), e))?;
+
+    Ok(responses[0].hash)
+}
+
+/// Verifies that a client has been recovered once the `ClientUpdateProposal` has passed,
+/// ie. that it is active again and that it has caught up with the substitute client.
+/// Returns the time left until the recovered client expires.
+pub fn verify_recovered_client<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    subject: &ForeignClient<DstChain, SrcChain>,
+    substitute_client_id: &ClientId,
+) -> Result<Duration, ClientRecoveryError> {
+    let not_recovered = |reason: String| {
+        ClientRecoveryError::not_recovered(subject.id().clone(), subject.dst_chain().id(), reason)
+    };
+
+    let subject_height = query_latest_height(&subject.dst_chain(), subject.id())?;
+    let substitute_height = query_latest_height(&subject.dst_chain(), substitute_client_id)?;
+
+    if subject_height < substitute_height {
+        return Err(not_recovered(format!(
+            "its latest height {} is lower than the latest height {} of the substitute client {}",
+            subject_height, substitute_height, substitute_client_id
+        )));
+    }
+
+    let expires_in = subject
+        .time_until_expiry()
+        .map_err(|e| not_recovered(e.to_string()))?;
+
+    match expires_in {
+        Some(expires_in) if expires_in.is_zero() => {
+            Err(not_recovered("the client is still expired".to_string()))
+        }
+        Some(expires_in) => Ok(expires_in),
+        None => Err(ClientRecoveryError::tendermint_only()),
+    }
+}
+
+fn query_tendermint_client_state(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<TendermintClientState, ClientRecoveryError> {
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(ClientRecoveryError::query)?;
+
+    downcast!(client_state => AnyClientState::Tendermint)
+        .ok_or_else(ClientRecoveryError::tendermint_only)
+}
+
+fn query_latest_height(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<Height, ClientRecoveryError> {
+    Ok(query_tendermint_client_state(chain, client_id)?.latest_height())
+}
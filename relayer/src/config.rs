@@ -1,5 +1,6 @@
 //! Relayer configuration
 
+pub mod chain_registry;
 pub mod error;
 pub mod filter;
 pub mod proof_specs;
@@ -73,6 +74,22 @@ pub mod default {
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
+
+    pub fn client_watchdog_safety_margin() -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    pub fn client_watchdog_interval() -> Duration {
+        Duration::from_secs(10 * 60)
+    }
+
+    pub fn client_upgrades_interval() -> Duration {
+        Duration::from_secs(30)
+    }
+
+    pub fn client_upgrades_max_retries() -> u32 {
+        10
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -86,6 +103,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub tracing: TracingConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -148,6 +167,8 @@ impl Default for ModeConfig {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                watchdog: ClientWatchdog::default(),
+                upgrades: ClientUpgrades::default(),
             },
             connections: Connections { enabled: false },
             channels: Channels { enabled: false },
@@ -169,6 +190,71 @@ pub struct Clients {
     pub refresh: bool,
     #[serde(default)]
     pub misbehaviour: bool,
+    #[serde(default)]
+    pub watchdog: ClientWatchdog,
+    #[serde(default)]
+    pub upgrades: ClientUpgrades,
+}
+
+/// Watches the expiry of all the clients hosted by the configured chains that track
+/// another configured chain, and refreshes those about to expire. Unlike the `refresh`
+/// setting, this applies to every such client, whether or not the relayer relays on it.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientWatchdog {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Refresh a client once the time left until it expires drops below this margin.
+    #[serde(
+        default = "default::client_watchdog_safety_margin",
+        with = "humantime_serde"
+    )]
+    pub safety_margin: Duration,
+    /// How often to check the expiry of the clients.
+    #[serde(
+        default = "default::client_watchdog_interval",
+        with = "humantime_serde"
+    )]
+    pub interval: Duration,
+}
+
+impl Default for ClientWatchdog {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            safety_margin: default::client_watchdog_safety_margin(),
+            interval: default::client_watchdog_interval(),
+        }
+    }
+}
+
+/// Watches the configured chains for a scheduled upgrade plan and, once a chain has been
+/// upgraded and produces blocks again, upgrades all the clients of that chain hosted on
+/// the other configured chains.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientUpgrades {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How often to check the chains for upgrade plans and upgrade progress.
+    #[serde(
+        default = "default::client_upgrades_interval",
+        with = "humantime_serde"
+    )]
+    pub interval: Duration,
+    /// How many times to retry the upgrade of a client before giving up on it.
+    #[serde(default = "default::client_upgrades_max_retries")]
+    pub max_retries: u32,
+}
+
+impl Default for ClientUpgrades {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: default::client_upgrades_interval(),
+            max_retries: default::client_upgrades_max_retries(),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
@@ -261,6 +347,28 @@ impl Default for TelemetryConfig {
     }
 }
 
+/// Configuration of the export of the spans tracing each batch of events
+/// through the relaying pipeline to an OpenTelemetry collector, over OTLP.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TracingConfig {
+    pub enabled: bool,
+    /// The URL to which the spans are sent, using OTLP over HTTP.
+    pub endpoint: String,
+    /// The name of the service reported with the spans.
+    pub service_name: String,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://127.0.0.1:4318/v1/traces".to_string(),
+            service_name: "hermes".to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
@@ -371,6 +479,29 @@ pub struct ChainConfig {
     pub packet_filter: PacketFilter,
     #[serde(default)]
     pub address_type: AddressType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_refill: Option<WalletRefill>,
+    /// Simulate the transactions instead of submitting them.
+    /// Only set by the `--dry-run` command-line flag, never read from the configuration file.
+    #[serde(skip)]
+    pub dry_run: bool,
+}
+
+/// Policy for refilling the wallet of the relayer on a chain from a treasury account.
+///
+/// Whenever the balance of the relayer key in the `gas_price` denomination drops
+/// below `threshold`, the treasury key sends enough tokens to bring it back to `target`,
+/// without sending more than `max_per_day` over any 24 hours.
+/// The refills sent are recorded under `~/.hermes/wallet_refills`, so that this cap
+/// also holds across restarts of the relayer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WalletRefill {
+    /// Name of the key, in the keyring of the chain, holding the treasury funds.
+    pub treasury_key_name: String,
+    pub threshold: u64,
+    pub target: u64,
+    pub max_per_day: u64,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -405,7 +536,9 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{load, store_writer};
+    use core::time::Duration;
+
+    use super::{load, store_writer, ClientWatchdog};
     use test_log::test;
 
     #[test]
@@ -432,4 +565,13 @@ mod tests {
         let mut buffer = Vec::new();
         store_writer(&config, &mut buffer).unwrap();
     }
+
+    #[test]
+    fn client_watchdog_defaults() {
+        let watchdog: ClientWatchdog = toml::from_str("safety_margin = '1h'").unwrap();
+
+        assert!(!watchdog.enabled);
+        assert_eq!(watchdog.safety_margin, Duration::from_secs(60 * 60));
+        assert_eq!(watchdog.interval, Duration::from_secs(10 * 60));
+    }
 }
@@ -0,0 +1,265 @@
+//! Chain metadata in the format of the [Cosmos chain registry], used to
+//! generate the configuration of a chain.
+//!
+//! [Cosmos chain registry]: https://github.com/cosmos/chain-registry
+
+use core::str::FromStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_derive::Deserialize;
+use tendermint_light_client_verifier::types::TrustThreshold;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::config::{default, AddressType, ChainConfig, Error, GasPrice, PacketFilter};
+use crate::keyring::Store;
+
+/// The metadata of a chain, as found in the `chain.json` file of a chain registry entry.
+///
+/// Only the fields that are needed to configure the relayer are deserialized.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChainMetadata {
+    pub chain_id: String,
+    pub bech32_prefix: String,
+    #[serde(default)]
+    pub fees: Fees,
+    #[serde(default)]
+    pub apis: Apis,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Fees {
+    #[serde(default)]
+    pub fee_tokens: Vec<FeeToken>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FeeToken {
+    pub denom: String,
+    pub fixed_min_gas_price: Option<f64>,
+    pub low_gas_price: Option<f64>,
+    pub average_gas_price: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Apis {
+    #[serde(default)]
+    pub rpc: Vec<Endpoint>,
+    #[serde(default)]
+    pub grpc: Vec<Endpoint>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Endpoint {
+    pub address: String,
+}
+
+impl ChainMetadata {
+    /// Parse the chain metadata stored in the given `chain.json` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(Error::io)?;
+
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::decode_chain_metadata(path.to_path_buf(), e))
+    }
+
+    /// Find the metadata of the chain with the given identifier in a local
+    /// copy of a chain registry.
+    ///
+    /// The registry directory is expected to hold one sub-directory per chain,
+    /// each containing a `chain.json` file, as in the Cosmos chain registry.
+    /// A `chain.json` file located directly in the registry directory is also
+    /// considered. Fails if any of these files cannot be parsed.
+    pub fn find(registry: impl AsRef<Path>, chain_id: &ChainId) -> Result<Self, Error> {
+        let registry = registry.as_ref();
+
+        let mut candidates: Vec<PathBuf> = vec![registry.join("chain.json")];
+
+        for entry in fs::read_dir(registry).map_err(Error::io)? {
+            let path = entry.map_err(Error::io)?.path();
+
+            if path.is_dir() {
+                candidates.push(path.join("chain.json"));
+            } else if path.extension().map_or(false, |ext| ext == "json") {
+                candidates.push(path);
+            }
+        }
+
+        let metadata = candidates
+            .into_iter()
+            .filter(|path| path.is_file())
+            .map(Self::load)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        metadata
+            .into_iter()
+            .find(|metadata| metadata.chain_id == chain_id.as_str())
+            .ok_or_else(|| {
+                Error::chain_metadata_not_found(chain_id.clone(), registry.to_path_buf())
+            })
+    }
+
+    /// Build the configuration of this chain, using the first RPC and gRPC endpoints
+    /// and the first fee token listed in the metadata, and defaults for everything else.
+    ///
+    /// The `trusting_period`, `max_block_time` and `packet_filter` fields are left to
+    /// their defaults, as they can only be derived by querying the chain itself.
+    pub fn to_chain_config(&self, key_name: &str) -> Result<ChainConfig, Error> {
+        let id = ChainId::from_string(&self.chain_id);
+
+        let rpc_addr = self
+            .apis
+            .rpc
+            .first()
+            .ok_or_else(|| Error::invalid_chain_metadata(id.clone(), "no RPC endpoint".into()))
+            .and_then(|endpoint| parse_url(&id, &endpoint.address))?;
+
+        let grpc_addr = self
+            .apis
+            .grpc
+            .first()
+            .ok_or_else(|| Error::invalid_chain_metadata(id.clone(), "no gRPC endpoint".into()))
+            .and_then(|endpoint| parse_grpc_url(&id, &endpoint.address))?;
+
+        let websocket_addr = websocket_url(&id, &rpc_addr)?;
+
+        let token = self
+            .fees
+            .fee_tokens
+            .first()
+            .ok_or_else(|| Error::invalid_chain_metadata(id.clone(), "no fee token".into()))?;
+
+        let price = token
+            .average_gas_price
+            .or(token.low_gas_price)
+            .or(token.fixed_min_gas_price)
+            .ok_or_else(|| {
+                Error::invalid_chain_metadata(
+                    id.clone(),
+                    format!("no gas price for fee token '{}'", token.denom),
+                )
+            })?;
+
+        let gas_price = GasPrice::new(price, token.denom.clone());
+
+        Ok(ChainConfig {
+            id,
+            r#type: default::chain_type(),
+            rpc_addr,
+            websocket_addr,
+            grpc_addr,
+            rpc_timeout: default::rpc_timeout(),
+            account_prefix: self.bech32_prefix.clone(),
+            key_name: key_name.to_string(),
+            key_store_type: Store::default(),
+            store_prefix: "ibc".to_string(),
+            default_gas: None,
+            max_gas: None,
+            gas_adjustment: None,
+            gas_multiplier: None,
+            fee_granter: None,
+            max_msg_num: MaxMsgNum::default(),
+            max_tx_size: MaxTxSize::default(),
+            clock_drift: default::clock_drift(),
+            max_block_time: default::max_block_time(),
+            trusting_period: None,
+            memo_prefix: Memo::default(),
+            proof_specs: Default::default(),
+            trust_threshold: TrustThreshold::default(),
+            gas_price,
+            packet_filter: PacketFilter::default(),
+            address_type: AddressType::default(),
+            wallet_refill: None,
+            dry_run: false,
+        })
+    }
+}
+
+fn parse_url(chain_id: &ChainId, address: &str) -> Result<tendermint_rpc::Url, Error> {
+    tendermint_rpc::Url::from_str(address).map_err(|e| {
+        Error::invalid_chain_metadata(
+            chain_id.clone(),
+            format!("invalid URL '{}': {}", address, e),
+        )
+    })
+}
+
+/// gRPC endpoints are usually listed without a scheme in the chain registry.
+fn parse_grpc_url(chain_id: &ChainId, address: &str) -> Result<tendermint_rpc::Url, Error> {
+    if address.contains("://") {
+        parse_url(chain_id, address)
+    } else {
+        parse_url(chain_id, &format!("http://{}", address))
+    }
+}
+
+/// The WebSocket endpoint is served by the RPC server under the `/websocket` path.
+fn websocket_url(
+    chain_id: &ChainId,
+    rpc_addr: &tendermint_rpc::Url,
+) -> Result<tendermint_rpc::Url, Error> {
+    let rpc_addr = rpc_addr.to_string();
+
+    let (scheme, rest) = rpc_addr.split_once("://").ok_or_else(|| {
+        Error::invalid_chain_metadata(chain_id.clone(), format!("invalid URL '{}'", rpc_addr))
+    })?;
+
+    let scheme = if scheme == "https" { "wss" } else { "ws" };
+
+    parse_url(
+        chain_id,
+        &format!("{}://{}/websocket", scheme, rest.trim_end_matches('/')),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChainMetadata;
+    use test_log::test;
+
+    const METADATA: &str = r#"{
+        "chain_name": "cosmoshub",
+        "chain_id": "cosmoshub-4",
+        "bech32_prefix": "cosmos",
+        "fees": {
+            "fee_tokens": [
+                { "denom": "uatom", "low_gas_price": 0.01, "average_gas_price": 0.025 }
+            ]
+        },
+        "apis": {
+            "rpc": [ { "address": "https://rpc.cosmos.network", "provider": "cosmos" } ],
+            "grpc": [ { "address": "grpc.cosmos.network:9090", "provider": "cosmos" } ]
+        }
+    }"#;
+
+    #[test]
+    fn chain_config_from_metadata() {
+        let metadata: ChainMetadata = serde_json::from_str(METADATA).unwrap();
+        let config = metadata.to_chain_config("testkey").unwrap();
+
+        assert_eq!(config.id.as_str(), "cosmoshub-4");
+        assert_eq!(config.account_prefix, "cosmos");
+        assert_eq!(config.gas_price.denom, "uatom");
+        assert_eq!(config.gas_price.price, 0.025);
+        assert!(config
+            .grpc_addr
+            .to_string()
+            .starts_with("http://grpc.cosmos.network:9090"));
+        assert_eq!(
+            config.websocket_addr.to_string(),
+            "wss://rpc.cosmos.network/websocket"
+        );
+    }
+
+    #[test]
+    fn chain_config_requires_gas_price() {
+        let metadata =
+            METADATA.replace(r#", "low_gas_price": 0.01, "average_gas_price": 0.025"#, "");
+        let metadata: ChainMetadata = serde_json::from_str(&metadata).unwrap();
+
+        assert!(metadata.to_chain_config("testkey").is_err());
+    }
+}
@@ -1,4 +1,7 @@
+use std::path::PathBuf;
+
 use flex_error::{define_error, TraceError};
+use ibc::core::ics24_host::identifier::ChainId;
 
 define_error! {
     Error {
@@ -13,5 +16,27 @@ define_error! {
         Encode
             [ TraceError<toml::ser::Error> ]
             |_| { "invalid configuration" },
+
+        ChainMetadataNotFound
+            {
+                chain_id: ChainId,
+                registry: PathBuf,
+            }
+            |e| {
+                format!("no metadata found for chain '{}' in chain registry '{}'",
+                    e.chain_id, e.registry.display())
+            },
+
+        DecodeChainMetadata
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("invalid chain metadata file '{}'", e.path.display()) },
+
+        InvalidChainMetadata
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| { format!("invalid metadata for chain '{}': {}", e.chain_id, e.reason) },
     }
 }
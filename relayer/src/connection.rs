@@ -30,6 +30,7 @@ use crate::util::task::Next;
 
 mod error;
 pub use error::ConnectionError;
+use error::ConnectionErrorDetail;
 
 /// Maximum value allowed for packet delay on any new connection that the relayer establishes.
 pub const MAX_PACKET_DELAY: Duration = Duration::from_secs(120);
@@ -446,6 +447,21 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
         Ok(a_block_time.max(b_block_time))
     }
 
+    /// Whether the transactions are simulated instead of being submitted, see `--dry-run`.
+    fn is_dry_run(&self) -> Result<bool, ConnectionError> {
+        let a_dry_run = self
+            .a_chain()
+            .config()
+            .map_err(ConnectionError::relayer)?
+            .dry_run;
+        let b_dry_run = self
+            .b_chain()
+            .config()
+            .map_err(ConnectionError::relayer)?
+            .dry_run;
+        Ok(a_dry_run || b_dry_run)
+    }
+
     pub fn flipped(&self) -> Connection<ChainB, ChainA> {
         Connection {
             a_side: self.b_side.clone(),
@@ -649,16 +665,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
     /// Executes the connection handshake protocol (ICS003)
     fn handshake(&mut self) -> Result<(), ConnectionError> {
         let max_block_times = self.max_block_times()?;
+        let dry_run = self.is_dry_run()?;
 
         retry_with_index(handshake_retry::default_strategy(max_block_times), |_| {
-            if let Err(e) = self.do_conn_open_handshake() {
-                if e.is_expired_or_frozen_error() {
-                    RetryResult::Err(e)
-                } else {
-                    RetryResult::Retry(e)
-                }
-            } else {
-                RetryResult::Ok(())
+            match self.do_conn_open_handshake() {
+                Ok(()) => RetryResult::Ok(()),
+                Err(e) if e.is_expired_or_frozen_error() => RetryResult::Err(e),
+                // Nothing is submitted in a dry run,
+                // so the handshake cannot progress past its first step
+                Err(e) if dry_run => match e.detail() {
+                    ConnectionErrorDetail::HandshakeFinalize(_) => RetryResult::Ok(()),
+                    _ => RetryResult::Err(e),
+                },
+                Err(e) => RetryResult::Retry(e),
             }
         })
         .map_err(|err| {
@@ -511,6 +511,19 @@ define_error! {
             format_args!(
                 "Query/DenomTrace RPC returned an empty denom trace for trace hash: {}", e.hash)
         },
+
+        InjectedFault
+            {
+                chain_id: ChainId,
+                request: String,
+                fault: String,
+            }
+            |e| {
+                format_args!(
+                    "injected {} into request '{}' to chain '{}'",
+                    e.fault, e.request, e.chain_id
+                )
+            },
     }
 }
 
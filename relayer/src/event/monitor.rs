@@ -9,15 +9,17 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, Span};
 
 use tendermint_rpc::{
-    event::Event as RpcEvent, query::Query, Error as RpcError, SubscriptionClient, Url,
-    WebSocketClient, WebSocketClientDriver,
+    event::{Event as RpcEvent, EventData as RpcEventData},
+    query::Query,
+    Error as RpcError, SubscriptionClient, Url, WebSocketClient, WebSocketClientDriver,
 };
 
 use ibc::{
     core::ics02_client::height::Height, core::ics24_host::identifier::ChainId, events::IbcEvent,
+    timestamp::Timestamp,
 };
 
 use crate::{
@@ -56,6 +58,38 @@ pub struct EventBatch {
     pub tracking_id: TrackingId,
     pub height: Height,
     pub events: Vec<IbcEvent>,
+    /// The time of the block at the height of the batch, if the NewBlock
+    /// event of that block was received along with the batch.
+    pub time: Option<Timestamp>,
+    /// The root span tracing this batch through the relaying pipeline.
+    /// See [`crate::trace`].
+    pub span: Span,
+}
+
+impl EventBatch {
+    /// Create a new batch of events, starting its root span.
+    pub fn new(
+        chain_id: ChainId,
+        tracking_id: TrackingId,
+        height: Height,
+        events: Vec<IbcEvent>,
+    ) -> Self {
+        let span = crate::trace::event_batch_span(&chain_id, tracking_id, height, &events);
+
+        Self {
+            chain_id,
+            tracking_id,
+            height,
+            events,
+            time: None,
+            span,
+        }
+    }
+
+    /// Sets the time of the block at the height of the batch.
+    pub fn with_time(self, time: Option<Timestamp>) -> Self {
+        Self { time, ..self }
+    }
 }
 
 type SubscriptionResult = core::result::Result<RpcEvent, RpcError>;
@@ -196,6 +230,8 @@ impl EventMonitor {
 
         trace!("[{}] subscribed to all queries", self.chain_id);
 
+        telemetry!(ws_connected, &self.chain_id, true);
+
         Ok(())
     }
 
@@ -361,6 +397,7 @@ impl EventMonitor {
                         });
 
                         telemetry!(ws_reconnect, &self.chain_id);
+                        telemetry!(ws_connected, &self.chain_id, false);
 
                         // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
                         self.reconnect();
@@ -374,6 +411,7 @@ impl EventMonitor {
                         error!("[{}] failed to collect events: {}", self.chain_id, e);
 
                         telemetry!(ws_reconnect, &self.chain_id);
+                        telemetry!(ws_connected, &self.chain_id, false);
 
                         // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
                         self.reconnect();
@@ -416,13 +454,21 @@ impl EventMonitor {
     }
 }
 
-/// Collect the IBC events from an RPC event
+/// Collect the IBC events from an RPC event, along with the time
+/// of their block if the RPC event is a NewBlock event
 fn collect_events(
     chain_id: &ChainId,
     event: RpcEvent,
-) -> impl Stream<Item = Result<(Height, IbcEvent)>> {
+) -> impl Stream<Item = Result<(Height, Option<Timestamp>, IbcEvent)>> {
+    let time = match &event.data {
+        RpcEventData::NewBlock {
+            block: Some(block), ..
+        } => Some(Timestamp::from(block.header.time)),
+        _ => None,
+    };
+
     let events = crate::event::rpc::get_all_events(chain_id, event).unwrap_or_default();
-    stream::iter(events).map(Ok)
+    stream::iter(events).map(move |(height, event)| Ok((height, time, event)))
 }
 
 /// Convert a stream of RPC event into a stream of event batches
@@ -439,25 +485,23 @@ fn stream_batches(
         .try_flatten();
 
     // Group events by height
-    let grouped = try_group_while(events, |(h0, _), (h1, _)| h0 == h1);
+    let grouped = try_group_while(events, |(h0, _, _), (h1, _, _)| h0 == h1);
 
     // Convert each group to a batch
     grouped.map_ok(move |events| {
         let height = events
             .first()
-            .map(|(h, _)| h)
+            .map(|(h, _, _)| h)
             .copied()
             .expect("internal error: found empty group"); // SAFETY: upheld by `group_while`
 
-        let mut events = events.into_iter().map(|(_, e)| e).collect::<Vec<_>>();
+        // The NewBlock event of a block is emitted before the events of its transactions.
+        let time = events.iter().find_map(|(_, t, _)| *t);
+
+        let mut events = events.into_iter().map(|(_, _, e)| e).collect::<Vec<_>>();
         sort_events(&mut events);
 
-        EventBatch {
-            height,
-            events,
-            chain_id: chain_id.clone(),
-            tracking_id: TrackingId::new_uuid(),
-        }
+        EventBatch::new(chain_id.clone(), TrackingId::new_uuid(), height, events).with_time(time)
     })
 }
 
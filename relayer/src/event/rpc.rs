@@ -253,5 +253,45 @@ fn extract_block_events(
         extract_events(height, block_events, "channel_close_confirm", "channel_id"),
         height,
     );
+    append_events::<ChannelEvents::UpgradeInit>(
+        &mut events,
+        extract_events(height, block_events, "channel_upgrade_init", "channel_id"),
+        height,
+    );
+    append_events::<ChannelEvents::UpgradeTry>(
+        &mut events,
+        extract_events(height, block_events, "channel_upgrade_try", "channel_id"),
+        height,
+    );
+    append_events::<ChannelEvents::UpgradeAck>(
+        &mut events,
+        extract_events(height, block_events, "channel_upgrade_ack", "channel_id"),
+        height,
+    );
+    append_events::<ChannelEvents::UpgradeConfirm>(
+        &mut events,
+        extract_events(
+            height,
+            block_events,
+            "channel_upgrade_confirm",
+            "channel_id",
+        ),
+        height,
+    );
+    append_events::<ChannelEvents::UpgradeTimeout>(
+        &mut events,
+        extract_events(
+            height,
+            block_events,
+            "channel_upgrade_timeout",
+            "channel_id",
+        ),
+        height,
+    );
+    append_events::<ChannelEvents::UpgradeCancel>(
+        &mut events,
+        extract_events(height, block_events, "channel_upgrade_cancel", "channel_id"),
+        height,
+    );
     events
 }
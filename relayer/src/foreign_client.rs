@@ -770,6 +770,47 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    /// Returns the time left until the latest consensus state of the client falls out of
+    /// its trusting period, ie. until the client expires if it is not updated in the meantime.
+    /// Returns a zero duration if the client is already expired, and `None` if the client
+    /// has no trusting period.
+    pub fn time_until_expiry(&self) -> Result<Option<Duration>, ForeignClientError> {
+        let (client_state, _) = self
+            .dst_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| {
+                ForeignClientError::client_query(self.id().clone(), self.dst_chain.id(), e)
+            })?;
+
+        if client_state.is_frozen() {
+            return Err(ForeignClientError::expired_or_frozen(
+                self.id().clone(),
+                self.dst_chain.id(),
+                "client state reports that client is frozen".into(),
+            ));
+        }
+
+        let trusting_period = match client_state.trusting_period() {
+            Some(trusting_period) => trusting_period,
+            None => return Ok(None),
+        };
+
+        let elapsed = match self
+            .check_consensus_state_trusting_period(&client_state, &client_state.latest_height())?
+        {
+            ConsensusStateTrusted::NotTrusted { elapsed, .. } => elapsed,
+            ConsensusStateTrusted::Trusted { elapsed } => elapsed,
+        };
+
+        Ok(Some(trusting_period.saturating_sub(elapsed)))
+    }
+
     pub fn is_expired_or_frozen(&self) -> bool {
         match self.validated_client_state() {
             Ok(_) => false,
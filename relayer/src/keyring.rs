@@ -9,7 +9,9 @@ use bip39::{Language, Mnemonic, Seed};
 use bitcoin::{
     network::constants::Network,
     secp256k1::{Message, Secp256k1, SecretKey},
-    util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
+    util::bip32::{
+        ChainCode, ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
+    },
 };
 use hdpath::StandardHDPath;
 use ibc::core::ics24_host::identifier::ChainId;
@@ -22,6 +24,7 @@ use tiny_keccak::{Hasher, Keccak};
 use errors::Error;
 pub use pub_key::EncodedPubKey;
 
+mod armor;
 pub mod errors;
 mod pub_key;
 
@@ -333,6 +336,50 @@ impl KeyRing {
         // Get the private key from the mnemonic
         let private_key = private_key_from_mnemonic(mnemonic_words, hd_path)?;
 
+        self.key_from_extended_private_key(private_key, at)
+    }
+
+    /// Get key from the bytes of a raw secp256k1 private key.
+    ///
+    /// As the key is not derived from a seed, it is stored with an empty chain code.
+    pub fn key_from_private_key(
+        &self,
+        private_key_bytes: &[u8],
+        at: &AddressType,
+    ) -> Result<KeyEntry, Error> {
+        let secret_key =
+            SecretKey::from_slice(private_key_bytes).map_err(Error::invalid_key_raw)?;
+
+        let private_key = ExtendedPrivKey {
+            network: Network::Bitcoin,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::Normal { index: 0 },
+            private_key: secret_key,
+            chain_code: ChainCode::from(&[0u8; 32][..]),
+        };
+
+        self.key_from_extended_private_key(private_key, at)
+    }
+
+    /// Get key from an ASCII-armored private key, as exported by the Cosmos SDK
+    /// `keys export` command, and the passphrase it was encrypted with.
+    pub fn key_from_armor(
+        &self,
+        armored_key: &str,
+        passphrase: &str,
+        at: &AddressType,
+    ) -> Result<KeyEntry, Error> {
+        let private_key_bytes = armor::decrypt_private_key(armored_key, passphrase)?;
+
+        self.key_from_private_key(&private_key_bytes, at)
+    }
+
+    fn key_from_extended_private_key(
+        &self,
+        private_key: ExtendedPrivKey,
+        at: &AddressType,
+    ) -> Result<KeyEntry, Error> {
         // Get the public Key from the private key
         let public_key = ExtendedPubKey::from_priv(&Secp256k1::new(), &private_key);
 
@@ -484,8 +531,6 @@ fn keccak256_hash(bytes: &[u8]) -> Vec<u8> {
 }
 
 fn standard_path_to_derivation_path(path: &StandardHDPath) -> DerivationPath {
-    use bitcoin::util::bip32::ChildNumber;
-
     let child_numbers = vec![
         ChildNumber::from_hardened_idx(path.purpose().as_value().as_number())
             .expect("Purpose is not Hardened"),
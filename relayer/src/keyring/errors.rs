@@ -112,5 +112,12 @@ define_error! {
             |e| {
                 format!("invalid HD path: {0}", e.path)
             },
+
+        InvalidArmor
+            { reason: String }
+            |e| { format!("invalid armored private key: {}", e.reason) },
+
+        ArmorDecryption
+            |_| { "cannot decrypt the armored private key, the passphrase may be incorrect" },
     }
 }
@@ -21,9 +21,11 @@
 extern crate alloc;
 
 pub mod account;
+pub mod bank;
 pub mod cache;
 pub mod chain;
 pub mod channel;
+pub mod client_recovery;
 pub mod config;
 pub mod connection;
 pub mod denom;
@@ -42,6 +44,7 @@ pub mod sdk_error;
 pub mod spawn;
 pub mod supervisor;
 pub mod telemetry;
+pub mod trace;
 pub mod transfer;
 pub mod upgrade_chain;
 pub mod util;
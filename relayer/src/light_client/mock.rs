@@ -1,19 +1,15 @@
-use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
-use tendermint_testgen::light_block::TmLightBlock;
-
-use ibc::clients::ics07_tendermint::header::Header as TmHeader;
 use ibc::core::ics02_client::client_state::AnyClientState;
 use ibc::core::ics02_client::events::UpdateClient;
+use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc::mock::host::HostBlock;
+use ibc::mock::header::MockHeader;
 use ibc::Height;
 
 use crate::chain::endpoint::ChainEndpoint;
-use crate::chain::mock::MockChain;
+use crate::chain::mock::{header, MockChain};
 use crate::error::Error;
 
 use super::Verified;
-use ibc::timestamp::Timestamp;
 
 /// A light client serving a mock chain.
 pub struct LightClient {
@@ -28,8 +24,15 @@ impl LightClient {
     }
 
     /// Returns a LightBlock at the requested height `h`.
-    fn light_block(&self, h: Height) -> TmLightBlock {
-        HostBlock::generate_tm_block(self.chain_id.clone(), h.revision_height(), Timestamp::now())
+    fn light_block(&self, h: Height) -> Result<MockHeader, Error> {
+        if h.revision_number() != self.chain_id.version() {
+            return Err(Error::query(format!(
+                "header at height {} on chain {}",
+                h, self.chain_id
+            )));
+        }
+
+        Ok(header(h))
     }
 }
 
@@ -39,15 +42,15 @@ impl super::LightClient<MockChain> for LightClient {
         _trusted: Height,
         target: Height,
         _client_state: &AnyClientState,
-    ) -> Result<Verified<TmLightBlock>, Error> {
+    ) -> Result<Verified<MockHeader>, Error> {
         Ok(Verified {
-            target: self.light_block(target),
+            target: self.light_block(target)?,
             supporting: Vec::new(),
         })
     }
 
-    fn fetch(&mut self, height: Height) -> Result<TmLightBlock, Error> {
-        Ok(self.light_block(height))
+    fn fetch(&mut self, height: Height) -> Result<MockHeader, Error> {
+        self.light_block(height)
     }
 
     fn check_misbehaviour(
@@ -55,7 +58,8 @@ impl super::LightClient<MockChain> for LightClient {
         _update: UpdateClient,
         _client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
-        unimplemented!()
+        // Mock chains never fork, there is no misbehaviour to detect.
+        Ok(None)
     }
 
     fn header_and_minimal_set(
@@ -63,24 +67,7 @@ impl super::LightClient<MockChain> for LightClient {
         trusted_height: Height,
         target_height: Height,
         client_state: &AnyClientState,
-    ) -> Result<Verified<TmHeader>, Error> {
-        let Verified { target, supporting } =
-            self.verify(trusted_height, target_height, client_state)?;
-
-        assert!(supporting.is_empty());
-
-        let succ_trusted = self.fetch(trusted_height.increment())?;
-
-        let target = TmHeader {
-            signed_header: target.signed_header,
-            validator_set: target.validators,
-            trusted_height,
-            trusted_validator_set: succ_trusted.validators,
-        };
-
-        Ok(Verified {
-            target,
-            supporting: Vec::new(),
-        })
+    ) -> Result<Verified<MockHeader>, Error> {
+        self.verify(trusted_height, target_height, client_state)
     }
 }
@@ -7,6 +7,7 @@ use ibc::core::{
 use crate::chain::requests::{QueryChannelRequest, QueryHeight};
 use crate::chain::{counterparty::check_channel_counterparty, requests::QueryConnectionRequest};
 use crate::chain::{handle::ChainHandle, requests::IncludeProof};
+use crate::channel::multihop::MultihopPath;
 use crate::channel::{Channel, ChannelSide};
 use crate::link::error::LinkError;
 
@@ -21,6 +22,9 @@ mod relay_sender;
 mod relay_summary;
 mod tx_hashes;
 
+#[cfg(test)]
+mod mbt;
+
 use tx_hashes::TxHashes;
 
 // Re-export the telemetries summary
@@ -48,6 +52,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         })
     }
 
+    /// Sets the intermediate chains of a channel whose connection hops go through other chains.
+    pub fn with_multihop_path(self, path: MultihopPath<ChainA, ChainB>) -> Self {
+        Self {
+            a_to_b: self.a_to_b.with_multihop_path(path),
+        }
+    }
+
     pub fn new_from_opts(
         a_chain: ChainA,
         b_chain: ChainB,
@@ -0,0 +1,436 @@
+//! Model-based tests of packet relaying.
+//!
+//! The traces in `relayer/spec/traces` are executions of the model of the relayer
+//! in `relayer/spec/Relayer.tla`, in which packets are sent on both sides of a channel,
+//! relayed in any order, time out, and the relayer crashes and restarts.
+//!
+//! Each trace is replayed against `Link`s between two `MockChain`s. After every step,
+//! the chains must agree with the model, no packet may be received, acknowledged or
+//! timed out twice, and only the expired packets may be timed out. Once the trace is
+//! over, the relayer clears all the pending packets and acknowledgements, after which
+//! no packet may be left behind.
+
+mod itf;
+mod state;
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+use test_log::test;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::info;
+
+use ibc::core::ics04_channel::packet::Packet;
+use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc::events::IbcEvent;
+
+use crate::chain::handle::{
+    BaseChainHandle, ChainHandle, Fault, FaultRule, FaultSchedule, FaultyChainHandle, Subscription,
+};
+use crate::chain::mock::test_utils::{open_channel, spawn_chain};
+use crate::chain::requests::{QueryPacketCommitmentsRequest, QueryUnreceivedPacketsRequest};
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::event::monitor::EventBatch;
+use crate::link::error::LinkError;
+use crate::link::{Link, LinkParameters, Resubmit};
+use crate::transfer::{build_and_send_transfer_messages, TransferOptions};
+
+use itf::InformalTrace;
+use state::{Action, ActionName, Sequence, State};
+
+const TRACE_DIRECTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/spec/traces");
+
+/// The timeout height offset of the packets which never expire.
+const TIMEOUT_HEIGHT_OFFSET: u64 = 10_000;
+
+/// The timeout height offset of the expiring packets, which must be large
+/// enough for the blocks produced while relaying not to expire them.
+const EXPIRING_TIMEOUT_HEIGHT_OFFSET: u64 = 200;
+
+/// How many blocks a chain produces to expire the packets sent to it.
+const EXPIRY_BLOCKS: u64 = 250;
+
+type Handle = FaultyChainHandle<BaseChainHandle>;
+
+/// The chains of the model are numbered 1 and 2, which we use as indices.
+fn index(chain_id: u64) -> usize {
+    chain_id as usize - 1
+}
+
+fn sequence(packet: &Packet) -> Sequence {
+    packet.sequence.into()
+}
+
+struct Replay {
+    chains: [Handle; 2],
+    /// The end of the channel on each chain.
+    channels: [ChannelId; 2],
+    subscriptions: [Subscription; 2],
+    /// The events emitted by each chain so far.
+    events: [Vec<IbcEvent>; 2],
+    /// The links relaying the packets sent by each chain, unless the relayer is down.
+    links: Option<[Link<Handle, Handle>; 2]>,
+}
+
+impl Replay {
+    fn new(rt: Arc<TokioRuntime>) -> Self {
+        let a_chain: Handle = spawn_chain("chain_a", rt.clone());
+        let b_chain: Handle = spawn_chain("chain_b", rt);
+
+        let channel = open_channel(&a_chain, &b_chain);
+        let channels = [
+            channel.a_channel_id().unwrap().clone(),
+            channel.b_channel_id().unwrap().clone(),
+        ];
+
+        let subscriptions = [a_chain.subscribe().unwrap(), b_chain.subscribe().unwrap()];
+
+        let mut replay = Self {
+            chains: [a_chain, b_chain],
+            channels,
+            subscriptions,
+            events: [Vec::new(), Vec::new()],
+            links: None,
+        };
+
+        replay.links = Some(replay.connect());
+        replay
+    }
+
+    fn connect(&self) -> [Link<Handle, Handle>; 2] {
+        let link = |src: usize| {
+            Link::new_from_opts(
+                self.chains[src].clone(),
+                self.chains[1 - src].clone(),
+                LinkParameters {
+                    src_port_id: PortId::transfer(),
+                    src_channel_id: self.channels[src].clone(),
+                },
+                true,
+            )
+            .unwrap()
+        };
+
+        [link(0), link(1)]
+    }
+
+    fn link(&mut self, src: usize) -> &mut Link<Handle, Handle> {
+        &mut self.links.as_mut().expect("the relayer is running")[src]
+    }
+
+    fn step(&mut self, action: &Action) {
+        match action.name {
+            ActionName::Null => {}
+            ActionName::CrashRelayer => self.links = None,
+            ActionName::RestartRelayer => self.links = Some(self.connect()),
+            _ => self.step_on_chain(action, index(action.chain_id)),
+        }
+
+        self.drain_events();
+    }
+
+    /// Performs an action on the packets sent by `src`.
+    fn step_on_chain(&mut self, action: &Action, src: usize) {
+        let dst = 1 - src;
+
+        match action.name {
+            ActionName::SendPacket => {
+                let timeout_height_offset = if action.expiring {
+                    EXPIRING_TIMEOUT_HEIGHT_OFFSET
+                } else {
+                    TIMEOUT_HEIGHT_OFFSET
+                };
+
+                let opts = TransferOptions {
+                    packet_src_port_id: PortId::transfer(),
+                    packet_src_channel_id: self.channels[src].clone(),
+                    amount: 42u64.into(),
+                    denom: "uatom".to_string(),
+                    receiver: None,
+                    timeout_height_offset,
+                    timeout_duration: Duration::ZERO,
+                    number_msgs: 1,
+                };
+
+                let events =
+                    build_and_send_transfer_messages(&self.chains[src], &self.chains[dst], &opts)
+                        .unwrap();
+
+                assert!(events.iter().any(|event| matches!(
+                    event,
+                    IbcEvent::SendPacket(ev) if sequence(&ev.packet) == action.sequence
+                )));
+            }
+            ActionName::ExpirePackets => {
+                for _ in 0..EXPIRY_BLOCKS {
+                    self.chains[dst]
+                        .send_messages_and_wait_commit(TrackedMsgs::new_static(
+                            vec![],
+                            "expire packets",
+                        ))
+                        .unwrap();
+                }
+            }
+            ActionName::RelayRecvPacket | ActionName::RelayTimeoutPacket => {
+                let event = self.send_packet_event(src, action.sequence);
+                self.relay_event(src, event).unwrap();
+            }
+            ActionName::RelayRecvPacketAndCrash => {
+                // The transaction is committed, but its confirmation is lost.
+                self.chains[dst].set_schedule(
+                    FaultSchedule::new(0).with_rule(
+                        FaultRule::new(
+                            "send_messages_and_wait_check_tx",
+                            Fault::Timeout(Duration::ZERO),
+                        )
+                        .with_limit(1),
+                    ),
+                );
+
+                let event = self.send_packet_event(src, action.sequence);
+                let _ = self.relay_event(src, event);
+                assert_eq!(self.chains[dst].injected_faults().len(), 1);
+
+                self.chains[dst].set_schedule(FaultSchedule::default());
+                self.links = None;
+            }
+            ActionName::RelayAckPacket => {
+                let event = self.write_ack_event(src, action.sequence);
+                self.relay_event(dst, event).unwrap();
+            }
+            ActionName::ClearPackets => {
+                self.link(src)
+                    .relay_recv_packet_and_timeout_messages()
+                    .unwrap();
+            }
+            ActionName::ClearAcks => {
+                self.link(dst).relay_ack_packet_messages().unwrap();
+            }
+            ActionName::Null | ActionName::CrashRelayer | ActionName::RestartRelayer => {
+                unreachable!("not an action on packets")
+            }
+        }
+    }
+
+    /// Feeds an event to the packet worker logic of the link relaying
+    /// the packets sent by `src`, as if it came from the event monitor.
+    fn relay_event(&mut self, src: usize, event: IbcEvent) -> Result<(), LinkError> {
+        // Both `SendPacket` and `WriteAcknowledgement` events are emitted by the source chain.
+        let chain_id = self.chains[src].id();
+        let path = &mut self.link(src).a_to_b;
+
+        path.update_schedule(EventBatch::new(
+            chain_id,
+            TrackingId::new_static("mbt"),
+            event.height(),
+            vec![event],
+        ))?;
+        path.refresh_schedule()?;
+        path.execute_schedule()?;
+        path.process_pending_txs(Resubmit::No);
+
+        Ok(())
+    }
+
+    fn send_packet_event(&self, src: usize, sequence: Sequence) -> IbcEvent {
+        self.find_event(
+            src,
+            |event| match event {
+                IbcEvent::SendPacket(ev) => Some(&ev.packet),
+                _ => None,
+            },
+            src,
+            sequence,
+        )
+    }
+
+    /// Finds the `WriteAcknowledgement` event of a packet sent by `src`.
+    fn write_ack_event(&self, src: usize, sequence: Sequence) -> IbcEvent {
+        self.find_event(
+            1 - src,
+            |event| match event {
+                IbcEvent::WriteAcknowledgement(ev) => Some(&ev.packet),
+                _ => None,
+            },
+            src,
+            sequence,
+        )
+    }
+
+    fn find_event(
+        &self,
+        chain: usize,
+        packet: impl Fn(&IbcEvent) -> Option<&Packet>,
+        src: usize,
+        sequence: Sequence,
+    ) -> IbcEvent {
+        self.events[chain]
+            .iter()
+            .find(|event| {
+                packet(event).map_or(false, |packet| {
+                    packet.source_channel == self.channels[src]
+                        && self::sequence(packet) == sequence
+                })
+            })
+            .cloned()
+            .expect("event was emitted")
+    }
+
+    fn drain_events(&mut self) {
+        for (subscription, events) in self.subscriptions.iter().zip(self.events.iter_mut()) {
+            while let Ok(batch) = subscription.recv_timeout(Duration::from_millis(100)) {
+                if let Ok(batch) = batch.as_ref() {
+                    events.extend(batch.events.iter().cloned());
+                }
+            }
+        }
+    }
+
+    /// Returns the sequences of the packets whose events match `packet`
+    /// among the events of `chain`, checking that each packet occurs once.
+    fn sequences_once(
+        &self,
+        chain: usize,
+        packet: impl Fn(&IbcEvent) -> Option<&Packet>,
+    ) -> BTreeSet<Sequence> {
+        let mut occurrences = BTreeMap::new();
+        for packet in self.events[chain].iter().filter_map(&packet) {
+            *occurrences.entry(sequence(packet)).or_insert(0) += 1;
+        }
+
+        for (sequence, count) in occurrences.iter() {
+            assert_eq!(*count, 1, "packet {} relayed {} times", sequence, count);
+        }
+
+        occurrences.into_keys().collect()
+    }
+
+    /// Checks that the chains agree with the given state of the model.
+    fn check(&self, state: &State) {
+        assert_eq!(self.links.is_some(), state.relayer_running);
+
+        for (src, chain) in self.chains.iter().enumerate() {
+            let dst = 1 - src;
+            let model = state.chain(src as u64 + 1);
+            let counterparty = state.chain(dst as u64 + 1);
+
+            let (committed, _) = chain
+                .query_packet_commitments(QueryPacketCommitmentsRequest {
+                    port_id: PortId::transfer(),
+                    channel_id: self.channels[src].clone(),
+                    pagination: None,
+                })
+                .unwrap();
+            let committed: BTreeSet<Sequence> = committed.into_iter().map(u64::from).collect();
+            assert_eq!(committed, model.committed.to_set());
+
+            let sent: Vec<Sequence> = (1..counterparty.next_sequence).collect();
+            let unreceived: BTreeSet<Sequence> = chain
+                .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                    port_id: PortId::transfer(),
+                    channel_id: self.channels[src].clone(),
+                    packet_commitment_sequences: sent.iter().copied().map(Into::into).collect(),
+                })
+                .unwrap()
+                .into_iter()
+                .map(u64::from)
+                .collect();
+            let received: BTreeSet<Sequence> = sent
+                .into_iter()
+                .filter(|sequence| !unreceived.contains(sequence))
+                .collect();
+            assert_eq!(received, model.received.to_set());
+
+            let received = self.sequences_once(src, |event| match event {
+                IbcEvent::ReceivePacket(ev) => Some(&ev.packet),
+                _ => None,
+            });
+            assert_eq!(received, model.received.to_set());
+
+            let acknowledged = self.sequences_once(src, |event| match event {
+                IbcEvent::AcknowledgePacket(ev) => Some(&ev.packet),
+                _ => None,
+            });
+            assert_eq!(acknowledged, model.acknowledged.to_set());
+
+            let timed_out = self.sequences_once(src, |event| match event {
+                IbcEvent::TimeoutPacket(ev) => Some(&ev.packet),
+                _ => None,
+            });
+            assert_eq!(timed_out, model.timed_out.to_set());
+            assert!(timed_out.is_subset(&model.expired.to_set()));
+        }
+    }
+
+    /// Restarts the relayer if needed and clears every pending packet and acknowledgement,
+    /// after which every packet sent must have been either acknowledged or timed out.
+    fn finish(&mut self, state: &State) {
+        if self.links.is_none() {
+            self.links = Some(self.connect());
+        }
+
+        for src in 0..2 {
+            self.link(src)
+                .relay_recv_packet_and_timeout_messages()
+                .unwrap();
+        }
+        for src in 0..2 {
+            self.link(1 - src).relay_ack_packet_messages().unwrap();
+        }
+
+        self.drain_events();
+
+        for src in 0..2 {
+            let acknowledged = self.sequences_once(src, |event| match event {
+                IbcEvent::AcknowledgePacket(ev) => Some(&ev.packet),
+                _ => None,
+            });
+            let timed_out = self.sequences_once(src, |event| match event {
+                IbcEvent::TimeoutPacket(ev) => Some(&ev.packet),
+                _ => None,
+            });
+            assert!(acknowledged.is_disjoint(&timed_out));
+
+            let sent: BTreeSet<Sequence> = (1..state.chain(src as u64 + 1).next_sequence).collect();
+            let resolved: BTreeSet<Sequence> = acknowledged.union(&timed_out).copied().collect();
+            assert_eq!(resolved, sent, "packets were lost");
+        }
+    }
+}
+
+fn replay(trace: &InformalTrace<State>) {
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+    let mut replay = Replay::new(rt);
+
+    for state in trace.states.iter() {
+        info!(action = ?state.action, "replaying step");
+
+        replay.step(&state.action);
+        replay.check(state);
+    }
+
+    if let Some(last) = trace.states.last() {
+        replay.finish(last);
+    }
+}
+
+#[test]
+fn relaying_follows_the_model() {
+    let mut paths: Vec<_> = fs::read_dir(TRACE_DIRECTORY)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".itf.json"))
+        .collect();
+    paths.sort();
+
+    assert!(!paths.is_empty(), "no trace found in {}", TRACE_DIRECTORY);
+
+    for path in paths {
+        info!(trace = %path.display(), "replaying trace");
+
+        let trace = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        replay(&trace);
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Deserializer};
+
+/// A trace in the Informal Trace Format, as produced by Apalache.
+#[derive(Debug, Deserialize)]
+pub struct InformalTrace<S> {
+    pub states: Vec<S>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Map<K, V>(pub Vec<(K, V)>);
+
+impl<'de, K, V> Deserialize<'de> for Map<K, V>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Meta<K, V> {
+            #[serde(rename = "#map")]
+            map: Vec<(K, V)>,
+        }
+        let s: Meta<_, _> = Deserialize::deserialize(deserializer)?;
+        Ok(Self(s.map))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Set<E>(pub Vec<E>);
+
+impl<'de, E> Deserialize<'de> for Set<E>
+where
+    E: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Meta<E> {
+            #[serde(rename = "#set")]
+            set: Vec<E>,
+        }
+        let s: Meta<_> = Deserialize::deserialize(deserializer)?;
+        Ok(Self(s.set))
+    }
+}
+
+impl<E: Copy + Ord> Set<E> {
+    pub fn to_set(&self) -> BTreeSet<E> {
+        self.0.iter().copied().collect()
+    }
+}
@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+use super::itf::{Map, Set};
+
+pub type ChainId = u64;
+pub type Sequence = u64;
+
+/// A state of the model in `relayer/spec/Relayer.tla`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct State {
+    pub chains: Map<ChainId, Chain>,
+
+    #[serde(rename = "relayerRunning")]
+    pub relayer_running: bool,
+
+    pub action: Action,
+}
+
+impl State {
+    pub fn chain(&self, chain_id: ChainId) -> &Chain {
+        self.chains
+            .0
+            .iter()
+            .find(|(id, _)| *id == chain_id)
+            .map(|(_, chain)| chain)
+            .expect("chain is part of the model")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chain {
+    pub next_sequence: Sequence,
+    pub committed: Set<Sequence>,
+    pub expired: Set<Sequence>,
+    pub acknowledged: Set<Sequence>,
+    pub timed_out: Set<Sequence>,
+    pub received: Set<Sequence>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Action {
+    pub name: ActionName,
+    pub chain_id: ChainId,
+    pub sequence: Sequence,
+    pub expiring: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum ActionName {
+    Null,
+    SendPacket,
+    ExpirePackets,
+    RelayRecvPacket,
+    RelayRecvPacketAndCrash,
+    RelayAckPacket,
+    RelayTimeoutPacket,
+    ClearPackets,
+    ClearAcks,
+    CrashRelayer,
+    RestartRelayer,
+}
@@ -3,7 +3,7 @@ use core::iter;
 use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
-use tracing::{debug, info};
+use tracing::{debug, info, info_span, Span};
 
 use ibc::core::ics02_client::client_state::ClientState;
 use ibc::core::ics04_channel::context::calculate_block_delay;
@@ -42,6 +42,7 @@ impl fmt::Display for OperationalDataTarget {
 pub struct TrackedEvents {
     events: Vec<IbcEvent>,
     tracking_id: TrackingId,
+    span: Span,
 }
 
 impl TrackedEvents {
@@ -49,9 +50,16 @@ impl TrackedEvents {
         Self {
             events,
             tracking_id,
+            span: Span::none(),
         }
     }
 
+    /// Attach the root span of the event batch these events come from.
+    /// See [`crate::trace`].
+    pub fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
@@ -64,6 +72,10 @@ impl TrackedEvents {
         self.tracking_id
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
     pub fn len(&self) -> usize {
         self.events.len()
     }
@@ -97,6 +109,9 @@ pub struct OperationalData {
     /// A unique ID for tracking this batch of events starting from when they were received
     /// until the transactions corresponding to those events is submitted.
     pub tracking_id: TrackingId,
+    /// The root span of the batch of events, under which the spans of assembling,
+    /// submitting and confirming the messages are recorded. See [`crate::trace`].
+    pub span: Span,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
 }
@@ -106,6 +121,7 @@ impl OperationalData {
         proofs_height: Height,
         target: OperationalDataTarget,
         tracking_id: TrackingId,
+        span: Span,
         connection_delay: Duration,
     ) -> Self {
         let connection_delay = if !connection_delay.is_zero() {
@@ -120,6 +136,7 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            span,
         }
     }
 
@@ -137,13 +154,15 @@ impl OperationalData {
         }
     }
 
-    /// Transforms `self` into the list of events accompanied with the tracking ID.
+    /// Transforms `self` into the list of events accompanied with the tracking ID
+    /// and the span of the batch they come from.
     pub fn into_events(self) -> TrackedEvents {
         let events = self.batch.into_iter().map(|gm| gm.event).collect();
 
         TrackedEvents {
             events,
             tracking_id: self.tracking_id,
+            span: self.span,
         }
     }
 
@@ -154,6 +173,14 @@ impl OperationalData {
         &self,
         relay_path: &RelayPath<ChainA, ChainB>,
     ) -> Result<TrackedMsgs, LinkError> {
+        let _trace_span = info_span!(
+            target: crate::trace::TARGET,
+            parent: &self.span,
+            "assemble_msgs",
+            target_chain = %self.target,
+            msgs = self.batch.len(),
+        );
+
         // For zero delay we prepend the client update msgs.
         let client_update_msg = if !self.conn_delay_needed() {
             let update_height = self.proofs_height.increment();
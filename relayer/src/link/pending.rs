@@ -2,7 +2,7 @@ use core::iter::Iterator;
 use core::time::Duration;
 use std::time::Instant;
 
-use tracing::{debug, error, trace, trace_span};
+use tracing::{debug, error, info_span, trace, trace_span, Span};
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
@@ -26,13 +26,16 @@ pub const TIMEOUT: Duration = Duration::from_secs(300);
 ///     - hashes for all transactions in that op. data,
 ///     - the target chain to query for confirmations,
 ///     - timestamp to track time-outs and declare an
-///         operational data as pending.
+///         operational data as pending,
+///     - the span recording the confirmation of the transactions,
+///         which ends when the pending data is dropped.
 #[derive(Clone)]
 pub struct PendingData {
     pub original_od: OperationalData,
     pub tx_hashes: TxHashes,
     pub submit_time: Instant,
     pub error_events: Vec<IbcEvent>,
+    pub span: Span,
 }
 
 impl PendingData {
@@ -116,11 +119,22 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             }
         }
 
+        let tx_hashes = TxHashes(tx_hashes);
+
+        let span = info_span!(
+            target: crate::trace::TARGET,
+            parent: &od.span,
+            "confirm_tx",
+            chain = %self.chain_id(),
+            tx_hashes = %tx_hashes,
+        );
+
         let u = PendingData {
             original_od: od,
-            tx_hashes: TxHashes(tx_hashes),
+            tx_hashes,
             submit_time: Instant::now(),
             error_events,
+            span,
         };
 
         self.pending_queue.push_back(u);
@@ -164,6 +178,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                 return Ok(Some(RelaySummary::from_events(pending.error_events)));
             }
 
+            let _confirm = pending.span.clone().entered();
+
             let span = trace_span!(
                 "processing pending tx",
                 chain = %self.chain_id(),
@@ -5,7 +5,7 @@ use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
-use tracing::{debug, error, info, span, trace, warn, Level};
+use tracing::{debug, error, info, info_span, span, trace, warn, Level};
 
 use crate::chain::counterparty::unreceived_acknowledgements;
 use crate::chain::counterparty::unreceived_packets;
@@ -14,6 +14,7 @@ use crate::chain::handle::ChainHandle;
 use crate::chain::requests::IncludeProof;
 use crate::chain::requests::QueryChannelRequest;
 use crate::chain::requests::QueryClientEventRequest;
+use crate::chain::requests::QueryClientStateRequest;
 use crate::chain::requests::QueryHeight;
 use crate::chain::requests::QueryHostConsensusStateRequest;
 use crate::chain::requests::QueryNextSequenceReceiveRequest;
@@ -24,6 +25,7 @@ use crate::chain::requests::QueryUnreceivedPacketsRequest;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
+use crate::channel::multihop::{wrap_hop_proofs, HopProofs, MultihopPath};
 use crate::channel::Channel;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
@@ -40,6 +42,7 @@ use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
 use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
 use crate::util::queue::Queue;
 use ibc::{
     core::{
@@ -55,7 +58,7 @@ use ibc::{
                 recv_packet::MsgRecvPacket, timeout::MsgTimeout,
                 timeout_on_close::MsgTimeoutOnClose,
             },
-            packet::{Packet, PacketMsgType},
+            packet::{Packet, PacketMsgType, Sequence},
         },
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
@@ -68,6 +71,13 @@ use ibc::{
 
 const MAX_RETRIES: usize = 5;
 
+/// Number of consecutive clearing passes in which the lowest unreceived packet of an
+/// ordered channel must stay the same before the channel is considered blocked.
+const ORDERED_CHANNEL_STALL_PASSES: u32 = 3;
+
+/// Maximum number of recoveries attempted for the packet an ordered channel is blocked on.
+const MAX_ORDERED_CHANNEL_RECOVERIES: u32 = 3;
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -112,6 +122,14 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // For ordered channels, the lowest sequence of a packet whose timeout was
+    // confirmed on the source chain. A timeout closes an ordered channel, so no
+    // packet with a higher sequence can be received by the destination chain anymore.
+    ordered_timeout_sequence: RwArc<Option<Sequence>>,
+
+    // For ordered channels, tracks whether relaying is stalled on the same packet.
+    ordered_channel_stall: RwArc<OrderedChannelStall>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -156,9 +174,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            ordered_timeout_sequence: RwArc::new_lock(None),
+            ordered_channel_stall: RwArc::new_lock(OrderedChannelStall::default()),
         })
     }
 
+    /// Sets the intermediate chains of a channel whose connection hops go through other chains.
+    pub fn with_multihop_path(mut self, path: MultihopPath<ChainA, ChainB>) -> Self {
+        self.channel = self.channel.with_multihop_path(path);
+        self
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -317,7 +344,50 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         self.channel.ordering == Order::Ordered
     }
 
+    /// Records that the packet with the given sequence timed out on an ordered channel.
+    fn record_ordered_timeout(&self, sequence: Sequence) {
+        let mut timeout_sequence = self.ordered_timeout_sequence.acquire_write();
+
+        match *timeout_sequence {
+            Some(current) if current <= sequence => {}
+            _ => {
+                warn!(
+                    "packet with sequence {} timed out on ordered channel {}, \
+                    the channel will be closed and later packets will not be relayed",
+                    sequence,
+                    self.src_channel_id()
+                );
+                *timeout_sequence = Some(sequence);
+            }
+        }
+    }
+
+    /// Returns `true` if the given event is a `SendPacket` event for a packet that follows
+    /// a timed-out packet on an ordered channel, and therefore must not be relayed. Besides
+    /// the recorded timeouts, the packet may follow a timeout scheduled along with it, which
+    /// is only recorded once the timeout is confirmed.
+    fn follows_ordered_timeout(&self, event: &IbcEvent, scheduled: Option<Sequence>) -> bool {
+        let recorded = *self.ordered_timeout_sequence.acquire_read();
+        let timeout_sequence = match (recorded, scheduled) {
+            (Some(recorded), Some(scheduled)) => Some(recorded.min(scheduled)),
+            (recorded, scheduled) => recorded.or(scheduled),
+        };
+
+        match (event, timeout_sequence) {
+            (IbcEvent::SendPacket(send_packet_ev), Some(timeout_sequence)) => {
+                send_packet_ev.packet.sequence > timeout_sequence
+            }
+            _ => false,
+        }
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, LinkError> {
+        // The destination client of a multi-hop channel tracks the adjacent intermediate chain,
+        // which is kept up to date by the relayer of that hop.
+        if self.channel.b_side.is_multihop() {
+            return Ok(vec![]);
+        }
+
         let client = self.restore_dst_client();
         client
             .wait_and_build_update_client(height)
@@ -325,18 +395,108 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     pub fn build_update_client_on_src(&self, height: Height) -> Result<Vec<Any>, LinkError> {
+        // See `build_update_client_on_dst`.
+        if self.channel.a_side.is_multihop() {
+            return Ok(vec![]);
+        }
+
         let client = self.restore_src_client();
         client
             .wait_and_build_update_client(height)
             .map_err(LinkError::client)
     }
 
+    /// Returns the height at which the source chain must be queried for the values proven to
+    /// the destination chain, along with the proofs of the intermediate chains wrapping them
+    /// if the channel goes through any.
+    ///
+    /// Without intermediate chains, this is the given `height` at which the values are known
+    /// to exist. Otherwise it is the height proven by the intermediate chains, which must not
+    /// be lower than `height`.
+    fn src_proofs_path(&self, height: Height) -> Result<(Height, Option<HopProofs>), LinkError> {
+        if !self.channel.b_side.is_multihop() {
+            return Ok((height, None));
+        }
+
+        let (client_state, _) = self
+            .dst_chain()
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.dst_client_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let hop_proofs = HopProofs::query(
+            self.channel.b_side.multihop_hops(),
+            client_state.latest_height(),
+        )
+        .map_err(LinkError::channel)?;
+
+        if hop_proofs.key_height() < height {
+            return Err(LinkError::channel(ChannelError::multihop_path_behind(
+                self.src_chain().id(),
+                hop_proofs.key_height(),
+                height,
+            )));
+        }
+
+        Ok((hop_proofs.key_height(), Some(hop_proofs)))
+    }
+
+    /// Same as `src_proofs_path`, for values of the destination chain proven to the source chain.
+    fn dst_proofs_path(&self, height: Height) -> Result<(Height, Option<HopProofs>), LinkError> {
+        if !self.channel.a_side.is_multihop() {
+            return Ok((height, None));
+        }
+
+        let (client_state, _) = self
+            .src_chain()
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.src_client_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+        let hop_proofs = HopProofs::query(
+            self.channel.a_side.multihop_hops(),
+            client_state.latest_height(),
+        )
+        .map_err(LinkError::channel)?;
+
+        if hop_proofs.key_height() < height {
+            return Err(LinkError::channel(ChannelError::multihop_path_behind(
+                self.dst_chain().id(),
+                hop_proofs.key_height(),
+                height,
+            )));
+        }
+
+        Ok((hop_proofs.key_height(), Some(hop_proofs)))
+    }
+
+    /// Whether the chain targeted by operational data verifies proofs through intermediate
+    /// chains.
+    fn is_multihop_target(&self, target: OperationalDataTarget) -> bool {
+        match target {
+            OperationalDataTarget::Source => self.channel.a_side.is_multihop(),
+            OperationalDataTarget::Destination => self.channel.b_side.is_multihop(),
+        }
+    }
+
     fn build_chan_close_confirm_from_event(&self, event: &IbcEvent) -> Result<Any, LinkError> {
         let src_channel_id = self.src_channel_id();
+        let (query_height, hop_proofs) = self.src_proofs_path(event.height())?;
         let proofs = self
             .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, event.height())
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
             .map_err(|e| LinkError::channel(ChannelError::channel_proof(e)))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs).map_err(LinkError::channel)?;
 
         // Build the domain type message
         let new_msg = MsgChannelCloseConfirm {
@@ -437,12 +597,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Generate & schedule operational data from the input `batch` of IBC events.
     pub fn update_schedule(&self, batch: EventBatch) -> Result<(), LinkError> {
         // Collect relevant events from the incoming batch & adjust their height.
-        let events = self.filter_relaying_events(batch.events, batch.tracking_id);
+        let events = self
+            .filter_relaying_events(batch.events, batch.tracking_id)
+            .with_span(batch.span);
 
         // Update telemetry info
         telemetry!({
             for e in events.events() {
-                self.record_send_packet_and_acknowledgment_history(e);
+                self.record_send_packet_and_acknowledgment_history(e, batch.time);
             }
         });
 
@@ -484,12 +646,25 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let span = span!(Level::DEBUG, "generate", id = %events.tracking_id());
         let _enter = span.enter();
 
-        let input = events.events();
-        let src_height = match input.get(0) {
+        let _trace_span = info_span!(
+            target: crate::trace::TARGET,
+            parent: events.span(),
+            "generate_operational_data",
+            events = events.len(),
+        );
+
+        let src_height = match events.events().get(0) {
             None => return Ok((None, None)),
             Some(ev) => ev.height(),
         };
 
+        let mut input = events.events().to_vec();
+
+        // Packets on ordered channels must be processed strictly in sequence.
+        if self.ordered_channel() {
+            sort_packet_events_by_sequence(&mut input);
+        }
+
         let dst_latest_info = self
             .dst_chain()
             .query_application_status()
@@ -502,6 +677,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             dst_latest_height,
             OperationalDataTarget::Source,
             events.tracking_id(),
+            events.span().clone(),
             self.channel.connection_delay,
         );
 
@@ -510,10 +686,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             src_height,
             OperationalDataTarget::Destination,
             events.tracking_id(),
+            events.span().clone(),
             self.channel.connection_delay,
         );
 
-        for event in input {
+        // The sequence of a packet on an ordered channel found to time out below. The input
+        // is sorted, so any later packet on the channel follows it.
+        let mut scheduled_timeout = None;
+
+        for event in input.iter() {
             trace!("processing event: {}", event);
             let (dst_msg, src_msg) = match event {
                 IbcEvent::CloseInitChannel(_) => {
@@ -524,11 +705,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     // the chain closes the channel but no close init event is emitted, instead
                     // we get a timeout packet event (this happens for both unordered and ordered channels)
                     // Here we check that the channel is closed on src and send a channel close confirm
-                    // to the counterparty.
+                    // to the counterparty, unless the counterparty closed its end already.
+                    if self.ordered_channel() {
+                        self.record_ordered_timeout(timeout_ev.packet.sequence);
+                    }
+
                     if self.ordered_channel()
                         && self
                             .src_channel(QueryHeight::Specific(timeout_ev.height))?
                             .state_matches(&ChannelState::Closed)
+                        && !self
+                            .dst_channel(QueryHeight::Latest)?
+                            .state_matches(&ChannelState::Closed)
                     {
                         (Some(self.build_chan_close_confirm_from_event(event)?), None)
                     } else {
@@ -539,11 +727,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     if self.send_packet_event_handled(send_packet_ev)? {
                         debug!("{} already handled", send_packet_ev);
                         (None, None)
+                    } else if self.follows_ordered_timeout(event, scheduled_timeout) {
+                        debug!(
+                            "{} follows a timed-out packet on an ordered channel, skipping",
+                            send_packet_ev
+                        );
+                        (None, None)
                     } else {
-                        self.build_recv_or_timeout_from_send_packet_event(
-                            send_packet_ev,
-                            &dst_latest_info,
-                        )?
+                        let (dst_msg, src_msg) = self
+                            .build_recv_or_timeout_from_send_packet_event(
+                                send_packet_ev,
+                                &dst_latest_info,
+                            )?;
+
+                        if self.ordered_channel() && src_msg.is_some() {
+                            scheduled_timeout = Some(send_packet_ev.packet.sequence);
+                        }
+
+                        (dst_msg, src_msg)
                     }
                 }
                 IbcEvent::WriteAcknowledgement(ref write_ack_ev) => {
@@ -639,7 +840,21 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     // This error means we could retry
                     error!("error {}", e.event);
                     if i + 1 == MAX_RETRIES {
-                        error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES)
+                        error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES);
+
+                        // The packet with the lowest sequence blocks all the others on an
+                        // ordered channel, so try to recover it on its own.
+                        if self.ordered_channel()
+                            && odata.target == OperationalDataTarget::Destination
+                        {
+                            if let Some(sequence) = lowest_send_packet_sequence(&odata) {
+                                if let Err(e) = self
+                                    .recover_blocked_ordered_channel(sequence, odata.tracking_id)
+                                {
+                                    error!("failed to recover blocked ordered channel: {}", e);
+                                }
+                            }
+                        }
                     } else {
                         // If we haven't exhausted all retries, regenerate the op. data & retry
                         match self.regenerate_operational_data(odata.clone()) {
@@ -755,10 +970,30 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let msgs = odata.assemble_msgs(self)?;
 
-        match odata.target {
+        // Mark the packets as relayed by Hermes before submitting, as the resulting
+        // events may be observed before the submission returns.
+        telemetry!(self.record_packets_submitted(odata, true));
+
+        let _trace_span = info_span!(
+            target: crate::trace::TARGET,
+            parent: &odata.span,
+            "submit_tx",
+            target_chain = %odata.target,
+            msgs = msgs.msgs.len(),
+        );
+
+        let reply = match odata.target {
             OperationalDataTarget::Source => S::submit(self.src_chain(), msgs),
             OperationalDataTarget::Destination => S::submit(self.dst_chain(), msgs),
-        }
+        };
+
+        telemetry!({
+            if reply.is_err() {
+                self.record_packets_submitted(odata, false);
+            }
+        });
+
+        reply
     }
 
     fn enqueue_pending_tx(&self, reply: AsyncReply, odata: OperationalData) {
@@ -1066,6 +1301,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
+        // An ordered channel only accepts the packet with the next expected sequence,
+        // so if that packet is not among the ones to relay, the channel is blocked.
+        // This is only checked once relaying made no progress for a few passes.
+        let stalled = self.ordered_channel() && self.observe_ordered_channel_progress(&sequences);
+        if stalled {
+            if let Some(blocked_sequence) = self.ordered_channel_blocked_on(&sequences)? {
+                self.recover_blocked_ordered_channel(blocked_sequence, tracking_id)?;
+            }
+        }
+
         // Skip: no relevant events found.
         if sequences.is_empty() {
             return Ok(());
@@ -1078,6 +1323,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             sequences.iter().take(10).format(", "), sequences.len()
         );
 
+        let lowest_sequence = sequences.iter().min().copied();
+        let mut lowest_sequence_found = false;
+
         // Chunk-up the list of sequence nrs. into smaller parts,
         // and schedule operational data incrementally across each chunk.
         for events_chunk in query_packet_events_with(
@@ -1087,6 +1335,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             &self.path_id,
             query_send_packet_events,
         ) {
+            lowest_sequence_found |= events_chunk.iter().any(|event| {
+                matches!(event, IbcEvent::SendPacket(send_packet_ev)
+                    if Some(send_packet_ev.packet.sequence) == lowest_sequence)
+            });
+
             // Update telemetry info
             telemetry!({
                 for e in events_chunk.clone() {
@@ -1096,9 +1349,175 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
         }
 
+        // On an ordered channel, none of the packets found can be received
+        // without the one with the lowest sequence.
+        if stalled && !lowest_sequence_found {
+            if let Some(sequence) = lowest_sequence {
+                self.recover_blocked_ordered_channel(sequence, tracking_id)?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Records the lowest sequence among the given sequences of unreceived packets, and
+    /// returns whether relaying on this ordered channel is stalled on it.
+    fn observe_ordered_channel_progress(&self, sequences: &[Sequence]) -> bool {
+        let progress = self
+            .ordered_channel_stall
+            .acquire_write()
+            .observe(sequences.iter().min().copied());
+
+        if progress == StallProgress::Progressed {
+            telemetry!({
+                let (chain, counterparty, channel_id, port_id) =
+                    self.target_info(OperationalDataTarget::Destination);
+
+                ibc_telemetry::global().ordered_channel_blocked(
+                    0,
+                    &chain,
+                    channel_id,
+                    port_id,
+                    &counterparty,
+                );
+            });
+        }
+
+        progress == StallProgress::Stalled
+    }
+
+    /// Checks whether an ordered channel is head-of-line blocked, given the sequences
+    /// of the packets committed on the source chain and not yet received on the destination.
+    ///
+    /// The channel is blocked if the next sequence expected by the destination chain
+    /// is lower than all of these sequences. Returns the sequence the channel is blocked on,
+    /// and updates the corresponding telemetry gauge.
+    fn ordered_channel_blocked_on(
+        &self,
+        sequences: &[Sequence],
+    ) -> Result<Option<Sequence>, LinkError> {
+        let blocked_sequence = match sequences.iter().min() {
+            None => None,
+            Some(lowest_sequence) => {
+                let (next_sequence_received, _) = self
+                    .dst_chain()
+                    .query_next_sequence_receive(
+                        QueryNextSequenceReceiveRequest {
+                            port_id: self.dst_port_id().clone(),
+                            channel_id: self.dst_channel_id().clone(),
+                            height: QueryHeight::Latest,
+                        },
+                        IncludeProof::No,
+                    )
+                    .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+                if next_sequence_received < *lowest_sequence {
+                    warn!(
+                        "ordered channel {} on {} is blocked: it expects the packet with sequence {}, \
+                        but the lowest unreceived packet on {} has sequence {}",
+                        self.dst_channel_id(),
+                        self.dst_chain().id(),
+                        next_sequence_received,
+                        self.src_chain().id(),
+                        lowest_sequence
+                    );
+
+                    Some(next_sequence_received)
+                } else {
+                    None
+                }
+            }
+        };
+
+        telemetry!({
+            let (chain, counterparty, channel_id, port_id) =
+                self.target_info(OperationalDataTarget::Destination);
+
+            ibc_telemetry::global().ordered_channel_blocked(
+                blocked_sequence.map_or(0, u64::from),
+                &chain,
+                channel_id,
+                port_id,
+                &counterparty,
+            );
+        });
+
+        Ok(blocked_sequence)
+    }
+
+    /// Targeted recovery for an ordered channel blocked on the packet with the given sequence.
+    ///
+    /// Re-queries the `SendPacket` event for exactly that sequence and schedules it, which
+    /// yields either a `MsgRecvPacket` for the destination chain or, if the packet is eligible
+    /// for a timeout, a `MsgTimeout` for the source chain. At most
+    /// [`MAX_ORDERED_CHANNEL_RECOVERIES`] recoveries are attempted for the same packet.
+    fn recover_blocked_ordered_channel(
+        &self,
+        sequence: Sequence,
+        tracking_id: TrackingId,
+    ) -> Result<(), LinkError> {
+        if !self
+            .ordered_channel_stall
+            .acquire_write()
+            .try_recovery(sequence)
+        {
+            debug!(
+                "giving up on recovering ordered channel {} blocked on packet with sequence {}",
+                self.src_channel_id(),
+                sequence
+            );
+
+            return Ok(());
+        }
+
+        warn!(
+            "recovering ordered channel {} blocked on packet with sequence {}",
+            self.src_channel_id(),
+            sequence
+        );
+
+        telemetry!({
+            let (chain, counterparty, channel_id, port_id) =
+                self.target_info(OperationalDataTarget::Destination);
+
+            ibc_telemetry::global().ordered_channel_blocked(
+                sequence.into(),
+                &chain,
+                channel_id,
+                port_id,
+                &counterparty,
+            );
+        });
+
+        let query_height = self.src_latest_height()?;
+
+        let events: Vec<IbcEvent> = query_send_packet_events(
+            self.src_chain(),
+            &self.path_id,
+            vec![sequence],
+            query_height,
+        )?
+        .into_iter()
+        .filter(|event| {
+            matches!(event, IbcEvent::SendPacket(send_packet_ev)
+                if send_packet_ev.packet.sequence == sequence)
+        })
+        .collect();
+
+        if events.is_empty() {
+            warn!(
+                "no SendPacket event found on {} for sequence {}, ordered channel {} remains blocked",
+                self.src_chain().id(),
+                sequence,
+                self.src_channel_id()
+            );
+
+            return Ok(());
+        }
+
+        self.events_to_operational_data(TrackedEvents::new(events, tracking_id))
+    }
+
     /// Schedules the relaying of [`MsgAcknowledgement`] messages.
     ///
     /// The `opt_query_height` parameter allows to optionally use a specific height on the source
@@ -1144,6 +1563,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
+        let (query_height, hop_proofs) = self.src_proofs_path(height)?;
         let proofs = self
             .src_chain()
             .build_packet_proofs(
@@ -1151,9 +1571,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.source_port,
                 &packet.source_channel,
                 packet.sequence,
-                height,
+                query_height,
             )
             .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs).map_err(LinkError::channel)?;
 
         let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
 
@@ -1172,6 +1593,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
 
+        let (query_height, hop_proofs) = self.src_proofs_path(event.height)?;
         let proofs = self
             .src_chain()
             .build_packet_proofs(
@@ -1179,9 +1601,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.destination_port,
                 &packet.destination_channel,
                 packet.sequence,
-                event.height,
+                query_height,
             )
             .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs).map_err(LinkError::channel)?;
 
         let msg = MsgAcknowledgement::new(
             packet,
@@ -1205,6 +1628,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         height: Height,
     ) -> Result<Option<Any>, LinkError> {
         let dst_channel_id = self.dst_channel_id();
+        let (height, hop_proofs) = self.dst_proofs_path(height)?;
 
         debug!("build timeout for channel");
         let (packet_type, next_sequence_received) = if self.ordered_channel() {
@@ -1235,6 +1659,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 height,
             )
             .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs).map_err(LinkError::channel)?;
 
         let msg = MsgTimeout::new(
             packet.clone(),
@@ -1257,6 +1682,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         packet: &Packet,
         height: Height,
     ) -> Result<Option<Any>, LinkError> {
+        let (query_height, hop_proofs) = self.dst_proofs_path(height)?;
         let proofs = self
             .dst_chain()
             .build_packet_proofs(
@@ -1264,9 +1690,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.destination_port,
                 &packet.destination_channel,
                 packet.sequence,
-                height,
+                query_height,
             )
             .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = wrap_hop_proofs(&hop_proofs, proofs).map_err(LinkError::channel)?;
 
         let msg = MsgTimeoutOnClose::new(
             packet.clone(),
@@ -1516,6 +1943,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let mut timed_out: HashMap<usize, OperationalData> = HashMap::default();
 
+        // The lowest sequence of a packet on an ordered channel found to time out below.
+        let mut scheduled_timeout = None;
+
         // For each operational data targeting the destination chain...
         for (odata_pos, odata) in all_dst_odata.iter_mut().enumerate() {
             // ... check each `SendPacket` event, whether it should generate a timeout message
@@ -1529,10 +1959,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                         // Catch any SendPacket event that timed-out
                         if self.send_packet_event_handled(e)? {
                             debug!("already handled send packet {}", e);
+                        } else if self.follows_ordered_timeout(event, scheduled_timeout) {
+                            debug!("send packet {} follows an ordered channel timeout", e);
                         } else if let Some(new_msg) =
                             self.build_timeout_from_send_packet_event(e, &dst_status)?
                         {
                             debug!("found a timed-out msg in the op data {}", odata.info(),);
+
+                            if self.ordered_channel() {
+                                let sequence = e.packet.sequence;
+                                scheduled_timeout =
+                                    Some(scheduled_timeout.map_or(sequence, |s| s.min(sequence)));
+                            }
+
                             timed_out
                                 .entry(odata_pos)
                                 .or_insert_with(|| {
@@ -1540,6 +1979,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                                         dst_current_height,
                                         OperationalDataTarget::Source,
                                         odata.tracking_id,
+                                        odata.span.clone(),
                                         self.channel.connection_delay,
                                     )
                                 })
@@ -1567,6 +2007,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             odata.batch = retain_batch;
         }
 
+        // A timeout found above closes an ordered channel, so drop any packet that
+        // follows it, including those retained before the timeout was found.
+        if self.ordered_channel() {
+            for odata in all_dst_odata.iter_mut() {
+                odata
+                    .batch
+                    .retain(|gm| !self.follows_ordered_timeout(&gm.event, scheduled_timeout));
+            }
+        }
+
         // Possibly some op. data became empty (if no events were kept).
         // Retain only the non-empty ones.
         all_dst_odata.retain(|o| !o.batch.is_empty());
@@ -1610,7 +2060,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         // Update clients ahead of scheduling the operational data, if the delays are non-zero.
         // If the connection-delay must be taken into account, set the `scheduled_time` to an
         // instant in the past, i.e. when this client update was first processed (`processed_time`)
-        let scheduled_time = if od.conn_delay_needed() {
+        let scheduled_time = if od.conn_delay_needed() && self.is_multihop_target(od.target) {
+            // The chain verifying the proofs of a multi-hop channel enforces the connection
+            // delay from the update of the adjacent intermediate chain's client, which is not
+            // relayed by this path, so start counting from the current height of that chain.
+            debug!("connection delay must be taken into account for a multi-hop channel");
+            let update_height = match od.target {
+                OperationalDataTarget::Source => self.src_latest_height()?,
+                OperationalDataTarget::Destination => self.dst_latest_height()?,
+            };
+            od.set_update_height(update_height);
+            Instant::now()
+        } else if od.conn_delay_needed() {
             debug!("connection delay must be taken into account: updating client");
             let target_height = od.proofs_height.increment();
             match od.target {
@@ -1735,7 +2196,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     #[cfg(feature = "telemetry")]
-    fn record_send_packet_and_acknowledgment_history(&self, event: &IbcEvent) {
+    fn record_send_packet_and_acknowledgment_history(
+        &self,
+        event: &IbcEvent,
+        time: Option<Timestamp>,
+    ) {
         match event {
             IbcEvent::SendPacket(send_packet_ev) => {
                 ibc_telemetry::global().record_send_history(
@@ -1746,6 +2211,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     self.src_port_id(),
                     &self.dst_chain().id(),
                 );
+                ibc_telemetry::global().packet_sent(
+                    time,
+                    send_packet_ev.packet.sequence.into(),
+                    &self.src_chain().id(),
+                    self.src_channel_id(),
+                    self.src_port_id(),
+                    &self.dst_chain().id(),
+                );
             }
             IbcEvent::WriteAcknowledgement(write_ack_ev) => {
                 ibc_telemetry::global().record_ack_history(
@@ -1756,11 +2229,66 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     self.dst_port_id(),
                     &self.src_chain().id(),
                 );
+                // The packet was sent by the destination chain of this path.
+                ibc_telemetry::global().packet_received(
+                    time,
+                    write_ack_ev.packet.sequence.into(),
+                    &self.dst_chain().id(),
+                    self.dst_channel_id(),
+                    self.dst_port_id(),
+                );
             }
             _ => {}
         }
     }
 
+    /// Marks the packets relayed by the messages of the given operational data
+    /// as being relayed by Hermes, or unmarks them if `submitted` is false.
+    #[cfg(feature = "telemetry")]
+    fn record_packets_submitted(&self, odata: &OperationalData, submitted: bool) {
+        use ibc_telemetry::state::PacketStage;
+
+        // Both messages go to the destination chain of this path. The packets of the
+        // RecvPacket messages were sent by the source chain, and those of the
+        // Acknowledgement messages by the destination chain.
+        if odata.target != OperationalDataTarget::Destination {
+            return;
+        }
+
+        let telemetry = ibc_telemetry::global();
+
+        for gm in odata.batch.iter() {
+            let (stage, packet) = match &gm.event {
+                IbcEvent::SendPacket(ev) => (PacketStage::Recv, &ev.packet),
+                IbcEvent::WriteAcknowledgement(ev) => (PacketStage::Ack, &ev.packet),
+                _ => continue,
+            };
+
+            let chain_id = match stage {
+                PacketStage::Recv => self.src_chain().id(),
+                PacketStage::Ack => self.dst_chain().id(),
+            };
+
+            if submitted {
+                telemetry.packet_submitted(
+                    stage,
+                    packet.sequence.into(),
+                    &chain_id,
+                    &packet.source_channel,
+                    &packet.source_port,
+                );
+            } else {
+                telemetry.packet_submission_failed(
+                    stage,
+                    packet.sequence.into(),
+                    &chain_id,
+                    &packet.source_channel,
+                    &packet.source_port,
+                );
+            }
+        }
+    }
+
     #[cfg(feature = "telemetry")]
     fn record_cleared_send_packet_and_acknowledgment(&self, event: IbcEvent) {
         match event {
@@ -1796,3 +2324,241 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 }
+
+/// Returns the lowest sequence among the `SendPacket` events of the given operational data.
+fn lowest_send_packet_sequence(odata: &OperationalData) -> Option<Sequence> {
+    odata
+        .batch
+        .iter()
+        .filter_map(|gm| match &gm.event {
+            IbcEvent::SendPacket(send_packet_ev) => Some(send_packet_ev.packet.sequence),
+            _ => None,
+        })
+        .min()
+}
+
+/// Sorts the `SendPacket` and `WriteAcknowledgement` events among the given events of an
+/// ordered channel by sequence, so that the packets are relayed strictly in order.
+///
+/// The sort is stable and only moves packet events across the positions occupied by packet
+/// events of the same kind, so that the other events keep their relative order and position.
+fn sort_packet_events_by_sequence(events: &mut [IbcEvent]) {
+    sort_events_by_key(events, |event| match event {
+        IbcEvent::SendPacket(send_packet_ev) => Some(send_packet_ev.packet.sequence),
+        _ => None,
+    });
+    sort_events_by_key(events, |event| match event {
+        IbcEvent::WriteAcknowledgement(write_ack_ev) => Some(write_ack_ev.packet.sequence),
+        _ => None,
+    });
+}
+
+fn sort_events_by_key(events: &mut [IbcEvent], key: impl Fn(&IbcEvent) -> Option<Sequence>) {
+    let positions: Vec<usize> = events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| key(event).is_some())
+        .map(|(pos, _)| pos)
+        .collect();
+
+    let mut sorted: Vec<IbcEvent> = positions.iter().map(|&pos| events[pos].clone()).collect();
+    sorted.sort_by_key(|event| key(event));
+
+    for (pos, event) in positions.into_iter().zip(sorted) {
+        events[pos] = event;
+    }
+}
+
+/// Progress of an ordered channel between two clearing passes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum StallProgress {
+    /// The lowest unreceived packet changed.
+    Progressed,
+    /// The lowest unreceived packet is the same, but not for long enough to be a stall.
+    Unchanged,
+    /// The lowest unreceived packet stayed the same for [`ORDERED_CHANNEL_STALL_PASSES`] passes.
+    Stalled,
+}
+
+/// Tracks the lowest unreceived packet of an ordered channel across clearing passes,
+/// and the recoveries attempted for the packet the channel is blocked on.
+#[derive(Debug, Default)]
+struct OrderedChannelStall {
+    lowest_sequence: Option<Sequence>,
+    passes: u32,
+    recovered_sequence: Option<Sequence>,
+    recoveries: u32,
+}
+
+impl OrderedChannelStall {
+    fn observe(&mut self, lowest_sequence: Option<Sequence>) -> StallProgress {
+        if lowest_sequence != self.lowest_sequence {
+            self.lowest_sequence = lowest_sequence;
+            self.passes = 1;
+            return StallProgress::Progressed;
+        }
+
+        if lowest_sequence.is_none() {
+            return StallProgress::Unchanged;
+        }
+
+        self.passes = self.passes.saturating_add(1);
+
+        if self.passes >= ORDERED_CHANNEL_STALL_PASSES {
+            StallProgress::Stalled
+        } else {
+            StallProgress::Unchanged
+        }
+    }
+
+    /// Returns whether a recovery of the packet with the given sequence may be attempted,
+    /// and counts it if so.
+    fn try_recovery(&mut self, sequence: Sequence) -> bool {
+        if self.recovered_sequence != Some(sequence) {
+            self.recovered_sequence = Some(sequence);
+            self.recoveries = 0;
+        }
+
+        if self.recoveries >= MAX_ORDERED_CHANNEL_RECOVERIES {
+            return false;
+        }
+
+        self.recoveries += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc::core::ics04_channel::events::{SendPacket, TimeoutPacket, WriteAcknowledgement};
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            ..Default::default()
+        }
+    }
+
+    fn send_packet(sequence: u64) -> IbcEvent {
+        IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 10).unwrap(),
+            packet: packet(sequence),
+        })
+    }
+
+    fn write_ack(sequence: u64) -> IbcEvent {
+        IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+            height: Height::new(0, 10).unwrap(),
+            packet: packet(sequence),
+            ack: Vec::new(),
+        })
+    }
+
+    fn timeout(sequence: u64) -> IbcEvent {
+        IbcEvent::TimeoutPacket(TimeoutPacket {
+            height: Height::new(0, 10).unwrap(),
+            packet: packet(sequence),
+        })
+    }
+
+    #[test]
+    fn sort_keeps_non_packet_events_in_place() {
+        let mut events = vec![timeout(9), send_packet(3), timeout(1), send_packet(2)];
+
+        sort_packet_events_by_sequence(&mut events);
+
+        assert_eq!(
+            events,
+            vec![timeout(9), send_packet(2), timeout(1), send_packet(3)]
+        );
+    }
+
+    #[test]
+    fn sort_orders_each_kind_of_packet_event_separately() {
+        let mut events = vec![
+            write_ack(5),
+            send_packet(4),
+            write_ack(1),
+            send_packet(1),
+            send_packet(3),
+        ];
+
+        sort_packet_events_by_sequence(&mut events);
+
+        assert_eq!(
+            events,
+            vec![
+                write_ack(1),
+                send_packet(1),
+                write_ack(5),
+                send_packet(3),
+                send_packet(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_is_stable_for_equal_sequences() {
+        let first = IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 1).unwrap(),
+            packet: packet(1),
+        });
+        let second = IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 2).unwrap(),
+            packet: packet(1),
+        });
+        let mut events = vec![send_packet(2), first.clone(), second.clone()];
+
+        sort_packet_events_by_sequence(&mut events);
+
+        assert_eq!(events, vec![first, second, send_packet(2)]);
+    }
+
+    #[test]
+    fn ordered_channel_stall_requires_consecutive_passes() {
+        let mut stall = OrderedChannelStall::default();
+        let sequence = Some(Sequence::from(4));
+
+        assert_eq!(stall.observe(sequence), StallProgress::Progressed);
+        for _ in 1..ORDERED_CHANNEL_STALL_PASSES - 1 {
+            assert_eq!(stall.observe(sequence), StallProgress::Unchanged);
+        }
+        assert_eq!(stall.observe(sequence), StallProgress::Stalled);
+        assert_eq!(stall.observe(sequence), StallProgress::Stalled);
+
+        // Relaying the blocked packet resets the detection.
+        assert_eq!(
+            stall.observe(Some(Sequence::from(5))),
+            StallProgress::Progressed
+        );
+        assert_eq!(
+            stall.observe(Some(Sequence::from(5))),
+            StallProgress::Unchanged
+        );
+    }
+
+    #[test]
+    fn ordered_channel_without_unreceived_packets_never_stalls() {
+        let mut stall = OrderedChannelStall::default();
+
+        for _ in 0..ORDERED_CHANNEL_STALL_PASSES * 2 {
+            assert_ne!(stall.observe(None), StallProgress::Stalled);
+        }
+    }
+
+    #[test]
+    fn ordered_channel_recoveries_are_capped_per_packet() {
+        let mut stall = OrderedChannelStall::default();
+        let sequence = Sequence::from(4);
+
+        for _ in 0..MAX_ORDERED_CHANNEL_RECOVERIES {
+            assert!(stall.try_recovery(sequence));
+        }
+        assert!(!stall.try_recovery(sequence));
+
+        // A packet with another sequence gets its own attempts.
+        assert!(stall.try_recovery(Sequence::from(5)));
+    }
+}
@@ -16,7 +16,13 @@ use ibc::{
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
+    chain::{
+        endpoint::HealthCheck,
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryChannelRequest, QueryHeight},
+        tracking::TrackingId,
+    },
+    channel::multihop::{resolve_multihop_path, MultihopPath},
     config::Config,
     event::monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
     object::Object,
@@ -34,6 +40,12 @@ use crate::{
 pub mod client_state_filter;
 use client_state_filter::{FilterPolicy, Permission};
 
+pub mod client_watchdog;
+use client_watchdog::spawn_client_watchdog;
+
+pub mod upgrade_watcher;
+use upgrade_watcher::spawn_upgrade_watcher;
+
 pub mod error;
 pub use error::{Error, ErrorDetail};
 
@@ -50,6 +62,7 @@ use self::{scan::ChainScanner, spawn::SpawnContext};
 
 type ArcBatch = Arc<monitor::Result<EventBatch>>;
 type Subscription = Receiver<ArcBatch>;
+type ObjectPath<Chain> = (Chain, MultihopPath<Chain, Chain>);
 
 /**
     A wrapper around the SupervisorCmd sender so that we can
@@ -124,6 +137,12 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     cmd_rx: Receiver<SupervisorCmd>,
     options: SupervisorOptions,
 ) -> Result<Vec<TaskHandle>, Error> {
+    telemetry!({
+        for chain_config in &config.chains {
+            ibc_telemetry::global().chain_configured(&chain_config.id);
+        }
+    });
+
     if options.health_check {
         health_check(&config, &mut registry.write());
     }
@@ -163,6 +182,16 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
 
+    if config.mode.clients.watchdog.enabled {
+        let watchdog_task = spawn_client_watchdog(config.clone(), registry.clone());
+        tasks.push(watchdog_task);
+    }
+
+    if config.mode.clients.upgrades.enabled {
+        let upgrade_task = spawn_upgrade_watcher(config.clone(), registry.clone());
+        tasks.push(upgrade_task);
+    }
+
     if let Some(rest_rx) = rest_rx {
         let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
         tasks.push(rest_task);
@@ -190,6 +219,8 @@ fn spawn_batch_workers<Chain: ChainHandle>(
             tracing::Span::none(),
             Some(Duration::from_millis(5)),
             move || -> Result<Next, TaskError<Infallible>> {
+                telemetry!(ibc_telemetry::global().supervisor_tick(&chain.id()));
+
                 if let Ok(batch) = subscription.try_recv() {
                     handle_batch(
                         &config,
@@ -415,6 +446,19 @@ pub fn collect_events(
                     })
                 });
             }
+            IbcEvent::UpgradeInitChannel(..)
+            | IbcEvent::UpgradeTryChannel(..)
+            | IbcEvent::UpgradeAckChannel(..)
+            | IbcEvent::UpgradeTimeoutChannel(..)
+            | IbcEvent::UpgradeCancelChannel(..) => {
+                // The channel worker relays the next step of the upgrade handshake,
+                // or the messages aborting it, to the counterparty chain.
+                collect_event(&mut collected, event, mode.channels.enabled, || {
+                    event.clone().channel_attributes().and_then(|attr| {
+                        Object::channel_from_chan_open_events(&attr, src_chain).ok()
+                    })
+                });
+            }
             IbcEvent::OpenAckChannel(open_ack) => {
                 // Create client and packet workers here as channel end must be opened
                 let attributes = open_ack.clone().into();
@@ -491,9 +535,18 @@ fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Cha
 
         match chain {
             Ok(chain) => match chain.health_check() {
-                Ok(Healthy) => info!(chain = %id, "chain is healthy"),
-                Ok(Unhealthy(e)) => warn!(chain = %id, "chain is unhealthy: {}", e),
-                Err(e) => error!(chain = %id, "failed to perform health check: {}", e),
+                Ok(Healthy) => {
+                    info!(chain = %id, "chain is healthy");
+                    telemetry!(chain_healthy, id);
+                }
+                Ok(Unhealthy(e)) => {
+                    warn!(chain = %id, "chain is unhealthy: {}", e);
+                    telemetry!(chain_unhealthy, id, &e);
+                }
+                Err(e) => {
+                    error!(chain = %id, "failed to perform health check: {}", e);
+                    telemetry!(chain_unhealthy, id, &e);
+                }
             },
             Err(e) => {
                 error!(
@@ -501,6 +554,7 @@ fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Cha
                     "skipping health check, reason: failed to spawn chain runtime with error: {}",
                     e
                 );
+                telemetry!(chain_unhealthy, id, &e);
             }
         }
     }
@@ -529,11 +583,17 @@ fn init_subscriptions<Chain: ChainHandle>(
         };
 
         match chain.subscribe() {
-            Ok(subscription) => subscriptions.push((chain, subscription)),
-            Err(e) => error!(
-                "failed to subscribe to events of {}: {}",
-                chain_config.id, e
-            ),
+            Ok(subscription) => {
+                telemetry!(chain_subscribed, &chain_config.id, true);
+                subscriptions.push((chain, subscription));
+            }
+            Err(e) => {
+                error!(
+                    "failed to subscribe to events of {}: {}",
+                    chain_config.id, e
+                );
+                telemetry!(chain_subscribed, &chain_config.id, false);
+            }
         }
     }
 
@@ -598,6 +658,41 @@ fn clear_pending_packets(workers: &mut WorkerMap, chain_id: &ChainId) -> Result<
     Ok(())
 }
 
+/// Resolves the counterparty chain and the intermediate chains of the channel relayed by the
+/// worker for a channel or packet object, if its connection hops go through other chains.
+fn resolve_object_path<Chain: ChainHandle>(
+    registry: &mut Registry<Chain>,
+    src_chain: &Chain,
+    object: &Object,
+) -> Result<Option<ObjectPath<Chain>>, Error> {
+    let (port_id, channel_id) = match object {
+        Object::Channel(channel) => (&channel.src_port_id, &channel.src_channel_id),
+        Object::Packet(packet) => (&packet.src_port_id, &packet.src_channel_id),
+        _ => return Ok(None),
+    };
+
+    let (channel_end, _) = src_chain
+        .query_channel(
+            QueryChannelRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    if channel_end.connection_hops().len() <= 1 {
+        return Ok(None);
+    }
+
+    resolve_multihop_path(src_chain, channel_end.connection_hops(), |chain_id| {
+        registry.get_or_spawn(chain_id)
+    })
+    .map(Some)
+    .map_err(|e| Error::multihop_path(channel_id.clone(), src_chain.id(), e.to_string()))
+}
+
 /// Process a batch of events received from a chain.
 fn process_batch<Chain: ChainHandle>(
     config: &Config,
@@ -611,6 +706,22 @@ fn process_batch<Chain: ChainHandle>(
 
     telemetry!(received_event_batch, batch.tracking_id);
 
+    // AcknowledgePacket events are not relayed, and thus not collected below,
+    // but they end the lifecycle of the packets tracked by the telemetry.
+    telemetry!({
+        for event in &batch.events {
+            if let IbcEvent::AcknowledgePacket(ack_ev) = event {
+                ibc_telemetry::global().packet_acknowledged(
+                    batch.time,
+                    ack_ev.packet.sequence.into(),
+                    &batch.chain_id,
+                    &ack_ev.packet.source_channel,
+                    &ack_ev.packet.source_port,
+                );
+            }
+        }
+    });
+
     let collected = collect_events(config, workers, &src_chain, batch);
 
     // If there is a NewBlock event, forward this event first to any workers affected by it.
@@ -679,13 +790,24 @@ fn process_batch<Chain: ChainHandle>(
             });
         }
 
-        let worker = workers.get_or_spawn(object, src, dst, config);
+        let (dst, path) = if workers.contains(&object) {
+            (dst, MultihopPath::default())
+        } else {
+            match resolve_object_path(registry, &src, &object)? {
+                Some((counterparty_chain, path)) => (counterparty_chain, path),
+                None => (dst, MultihopPath::default()),
+            }
+        };
+
+        let worker = workers.get_or_spawn(object, src, dst, path, config);
 
         worker.send_events(
             batch.height,
             events,
+            batch.time,
             batch.chain_id.clone(),
             batch.tracking_id,
+            batch.span.clone(),
         );
     }
 
@@ -0,0 +1,219 @@
+//! The client expiry watchdog watches all the clients hosted by the configured
+//! chains which track another configured chain, and refreshes those about to expire.
+//!
+//! Unlike the refresh task of the client workers, which only runs for the clients
+//! found by the supervisor scan, the watchdog covers every such client, even when
+//! the relayer does not relay on it or when packet relaying is disabled.
+
+use core::convert::Infallible;
+use core::time::Duration;
+use std::time::Instant;
+
+use tracing::{debug, error_span, info, warn};
+
+use ibc::core::ics02_client::client_state::{ClientState, IdentifiedAnyClientState};
+
+use crate::{
+    chain::{
+        handle::ChainHandle,
+        requests::{PageRequest, QueryClientStatesRequest},
+    },
+    config::{ChainConfig, ClientWatchdog, Config},
+    foreign_client::{ForeignClient, HasExpiredOrFrozenError},
+    registry::SharedRegistry,
+    telemetry,
+    util::duration::format_duration,
+    util::task::{spawn_background_task, Next, TaskError, TaskHandle},
+};
+
+pub fn spawn_client_watchdog<Chain: ChainHandle>(
+    config: Config,
+    registry: SharedRegistry<Chain>,
+) -> TaskHandle {
+    let watchdog = config.mode.clients.watchdog;
+
+    let mut last_check: Option<Instant> = None;
+
+    spawn_background_task(
+        error_span!("client_watchdog"),
+        Some(Duration::from_secs(1)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if last_check.map_or(false, |time| time.elapsed() < watchdog.interval) {
+                return Ok(Next::Continue);
+            }
+
+            last_check = Some(Instant::now());
+
+            for chain_config in &config.chains {
+                check_chain_clients(&config, chain_config, &registry, &watchdog);
+            }
+
+            Ok(Next::Continue)
+        },
+    )
+}
+
+fn check_chain_clients<Chain: ChainHandle>(
+    config: &Config,
+    chain_config: &ChainConfig,
+    registry: &SharedRegistry<Chain>,
+    watchdog: &ClientWatchdog,
+) {
+    let chain = match registry.get_or_spawn(&chain_config.id) {
+        Ok(chain) => chain,
+        Err(e) => {
+            warn!(chain = %chain_config.id, "failed to spawn chain runtime: {}", e);
+            return;
+        }
+    };
+
+    let clients = match chain.query_clients(QueryClientStatesRequest {
+        pagination: Some(PageRequest::all()),
+    }) {
+        Ok(clients) => clients,
+        Err(e) => {
+            warn!(chain = %chain_config.id, "failed to query clients: {}", e);
+            return;
+        }
+    };
+
+    for client in clients {
+        let counterparty_chain_id = client.client_state.chain_id();
+
+        // Only watch the clients of the chains we care about
+        if !config.has_chain(&counterparty_chain_id) || client.client_state.is_frozen() {
+            continue;
+        }
+
+        let counterparty_chain = match registry.get_or_spawn(&counterparty_chain_id) {
+            Ok(counterparty_chain) => counterparty_chain,
+            Err(e) => {
+                warn!(
+                    chain = %counterparty_chain_id,
+                    "failed to spawn chain runtime: {}", e
+                );
+                continue;
+            }
+        };
+
+        check_client(chain.clone(), counterparty_chain, client, watchdog);
+    }
+}
+
+fn check_client<Chain: ChainHandle>(
+    chain: Chain,
+    counterparty_chain: Chain,
+    client: IdentifiedAnyClientState,
+    watchdog: &ClientWatchdog,
+) {
+    let client = ForeignClient::restore(client.client_id, chain, counterparty_chain);
+
+    let expires_in = match client.time_until_expiry() {
+        Ok(Some(expires_in)) => expires_in,
+        Ok(None) => return,
+        Err(e) if e.is_expired_or_frozen_error() => return,
+        Err(e) => {
+            warn!(client = %client.id, "failed to compute the expiry of the client: {}", e);
+            return;
+        }
+    };
+
+    telemetry!(
+        client_expiry,
+        &client.dst_chain.id(),
+        &client.id,
+        &client.src_chain.id(),
+        expires_in.as_secs()
+    );
+
+    match expiry_action(expires_in, watchdog.safety_margin) {
+        ExpiryAction::Expired => {
+            warn!(
+                client = %client.id,
+                chain = %client.dst_chain.id(),
+                "client has expired and can no longer be refreshed"
+            );
+        }
+        ExpiryAction::RefreshIn(refresh_in) => {
+            debug!(
+                client = %client.id,
+                chain = %client.dst_chain.id(),
+                "client expires in {}, will be refreshed in {}",
+                format_duration(expires_in),
+                format_duration(refresh_in),
+            );
+        }
+        ExpiryAction::Refresh => {
+            info!(
+                client = %client.id,
+                chain = %client.dst_chain.id(),
+                "client expires in {}, within the safety margin, refreshing it",
+                format_duration(expires_in),
+            );
+
+            match client.build_latest_update_client_and_send() {
+                Ok(_) => {
+                    telemetry!(ibc_client_updates, &client.dst_chain.id(), &client.id, 1);
+                }
+                Err(e) => warn!(client = %client.id, "failed to refresh client: {}", e),
+            }
+        }
+    }
+}
+
+/// What to do with a client, given the time left until it expires.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ExpiryAction {
+    /// The client has expired, and can no longer be refreshed.
+    Expired,
+    /// The client expires within the safety margin, and must be refreshed now.
+    Refresh,
+    /// The client will enter the safety margin after the given duration.
+    RefreshIn(Duration),
+}
+
+fn expiry_action(expires_in: Duration, safety_margin: Duration) -> ExpiryAction {
+    if expires_in.is_zero() {
+        return ExpiryAction::Expired;
+    }
+
+    match expires_in.checked_sub(safety_margin) {
+        Some(refresh_in) if !refresh_in.is_zero() => ExpiryAction::RefreshIn(refresh_in),
+        _ => ExpiryAction::Refresh,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{expiry_action, ExpiryAction};
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    #[test]
+    fn expiry_thresholds() {
+        let margin = 24 * HOUR;
+
+        assert_eq!(
+            expiry_action(48 * HOUR, margin),
+            ExpiryAction::RefreshIn(24 * HOUR)
+        );
+
+        assert_eq!(
+            expiry_action(margin + Duration::from_secs(1), margin),
+            ExpiryAction::RefreshIn(Duration::from_secs(1))
+        );
+
+        // Refreshed as soon as the client enters the safety margin
+        assert_eq!(expiry_action(margin, margin), ExpiryAction::Refresh);
+        assert_eq!(expiry_action(HOUR, margin), ExpiryAction::Refresh);
+        assert_eq!(
+            expiry_action(Duration::from_secs(1), margin),
+            ExpiryAction::Refresh
+        );
+
+        // Too late to refresh
+        assert_eq!(expiry_action(Duration::ZERO, margin), ExpiryAction::Expired);
+    }
+}
@@ -55,6 +55,17 @@ define_error! {
         MissingCounterpartyChannelId
             |_| { "failed due to missing counterparty channel id" },
 
+        MultihopPath
+            {
+                channel_id: ChannelId,
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format_args!("failed to resolve the intermediate chains of channel {0} on chain {1}: {2}",
+                    e.channel_id, e.chain_id, e.reason)
+            },
+
         Relayer
             [ RelayerError ]
             |_| { "relayer error" },
@@ -9,11 +9,11 @@ use ibc::core::{
 
 use crate::{
     chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
+    channel::multihop::{resolve_multihop_path, MultihopPath},
     config::Config,
     object::{Channel, Client, Connection, Object, Packet, Wallet},
     registry::Registry,
     supervisor::error::Error as SupervisorError,
-    telemetry,
     worker::WorkerMap,
 };
 
@@ -69,10 +69,17 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             self.spawn_workers_for_client(chain.clone(), client_scan);
         }
 
-        // Let's only spawn the wallet worker if telemetry is enabled,
-        // otherwise the worker just ends up issuing queries to the node
-        // without making anything of the result
-        telemetry!(self.spawn_wallet_worker(chain));
+        // Let's only spawn the wallet worker if telemetry is enabled or if the wallet
+        // is to be refilled from a treasury, otherwise the worker just ends up issuing
+        // queries to the node without making anything of the result
+        let refill_enabled = self
+            .config
+            .find_chain(&scan.chain_id)
+            .map_or(false, |chain_config| chain_config.wallet_refill.is_some());
+
+        if cfg!(feature = "telemetry") || refill_enabled {
+            self.spawn_wallet_worker(chain);
+        }
     }
 
     pub fn spawn_wallet_worker(&mut self, chain: Chain) {
@@ -81,7 +88,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
         });
 
         self.workers
-            .spawn(chain.clone(), chain, &wallet_object, self.config)
+            .spawn(
+                chain.clone(),
+                chain,
+                MultihopPath::default(),
+                &wallet_object,
+                self.config,
+            )
             .then(|| {
                 info!("spawning Wallet worker: {}", wallet_object.short_name());
             });
@@ -191,7 +204,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             });
 
             self.workers
-                .spawn(chain, counterparty_chain, &connection_object, self.config)
+                .spawn(
+                    chain,
+                    counterparty_chain,
+                    MultihopPath::default(),
+                    &connection_object,
+                    self.config,
+                )
                 .then(|| {
                     info!(
                         "spawning Connection worker: {}",
@@ -215,10 +234,8 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
     ) -> Result<bool, Error> {
         let mode = &self.config.mode;
 
-        let counterparty_chain = self
-            .registry
-            .get_or_spawn(&client.client_state.chain_id())
-            .map_err(SupervisorError::spawn)?;
+        let (counterparty_chain, path) =
+            self.resolve_channel_path(&chain, client, &channel_scan)?;
 
         let chan_state_src = channel_scan.channel.channel_end.state;
         let chan_state_dst = channel_scan
@@ -251,6 +268,7 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     .spawn(
                         counterparty_chain.clone(),
                         chain.clone(),
+                        MultihopPath::default(),
                         &client_object,
                         self.config,
                     )
@@ -286,6 +304,7 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                         .spawn(
                             chain.clone(),
                             counterparty_chain.clone(),
+                            path.clone(),
                             &path_object,
                             self.config,
                         )
@@ -295,10 +314,12 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
 
             Ok(mode.clients.enabled)
         } else if mode.channels.enabled
-            && !chan_state_dst.is_open()
-            && chan_state_dst.less_or_equal_progress(chan_state_src)
+            && ((!chan_state_dst.is_open()
+                && chan_state_dst.less_or_equal_progress(chan_state_src))
+                || chan_state_src.is_upgrading())
         {
-            // create worker for channel handshake that will advance the remote state
+            // create worker for channel handshake that will advance the remote state,
+            // or for the upgrade handshake in progress on the source channel
             let channel_object = Object::Channel(Channel {
                 dst_chain_id: counterparty_chain.id(),
                 src_chain_id: chain.id(),
@@ -307,7 +328,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             });
 
             self.workers
-                .spawn(chain, counterparty_chain, &channel_object, self.config)
+                .spawn(
+                    chain,
+                    counterparty_chain,
+                    path,
+                    &channel_object,
+                    self.config,
+                )
                 .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
 
             Ok(true)
@@ -316,6 +343,39 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
         }
     }
 
+    /// Returns the counterparty chain of a channel, along with its intermediate chains if its
+    /// connection hops go through any.
+    fn resolve_channel_path(
+        &mut self,
+        chain: &Chain,
+        client: &IdentifiedAnyClientState,
+        channel_scan: &ChannelScan,
+    ) -> Result<(Chain, MultihopPath<Chain, Chain>), Error> {
+        let connection_hops = channel_scan.channel.channel_end.connection_hops();
+
+        if connection_hops.len() <= 1 {
+            let counterparty_chain = self
+                .registry
+                .get_or_spawn(&client.client_state.chain_id())
+                .map_err(SupervisorError::spawn)?;
+
+            return Ok((counterparty_chain, MultihopPath::default()));
+        }
+
+        let registry = &mut *self.registry;
+
+        resolve_multihop_path(chain, connection_hops, |chain_id| {
+            registry.get_or_spawn(chain_id)
+        })
+        .map_err(|e| {
+            SupervisorError::multihop_path(
+                channel_scan.channel.channel_id.clone(),
+                chain.id(),
+                e.to_string(),
+            )
+        })
+    }
+
     pub fn shutdown_workers_for_chain(&mut self, chain_id: &ChainId) {
         let affected_workers = self.workers.objects_for_chain(chain_id);
         for object in affected_workers {
@@ -0,0 +1,484 @@
+//! The upgrade watcher watches the configured chains for a scheduled upgrade plan and,
+//! once a chain has been upgraded and produces blocks again, upgrades all the clients
+//! of that chain hosted on the other configured chains.
+//!
+//! The upgrade of a client is retried at every check until it succeeds, or until
+//! the configured maximum number of retries is reached.
+//!
+//! As the upgrade plan is removed from the chain once applied, the watcher looks up
+//! the last upgrade applied on each chain when it starts, so that the clients left
+//! behind by an upgrade which happened while the relayer was down are upgraded too.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::convert::Infallible;
+use core::time::Duration;
+use std::time::Instant;
+
+use tracing::{debug, error, error_span, info, warn};
+
+use ibc::core::ics02_client::client_state::ClientState;
+use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc::Height;
+
+use crate::{
+    chain::{
+        handle::ChainHandle,
+        requests::{
+            IncludeProof, PageRequest, QueryClientStateRequest, QueryClientStatesRequest,
+            QueryHeight,
+        },
+    },
+    config::{ClientUpgrades, Config},
+    foreign_client::ForeignClient,
+    registry::SharedRegistry,
+    telemetry,
+    upgrade_chain::UpgradePlan,
+    util::task::{spawn_background_task, Next, TaskError, TaskHandle},
+};
+
+/// An upgrade plan scheduled on a chain, along with the clients of that chain
+/// which are left to upgrade once the upgrade has happened.
+#[derive(Debug)]
+struct PendingUpgrade {
+    plan: UpgradePlan,
+    /// The height of the plan, in the revision of the chain it was scheduled on.
+    height: Height,
+    /// The clients left to upgrade, per host chain, with the number of
+    /// failed attempts so far. `None` until the chain has been upgraded.
+    clients: Option<BTreeMap<(ChainId, ClientId), u32>>,
+}
+
+impl PendingUpgrade {
+    fn new(chain_id: &ChainId, plan: UpgradePlan) -> Option<Self> {
+        match Height::new(chain_id.version(), plan.height) {
+            Ok(height) => Some(Self {
+                plan,
+                height,
+                clients: None,
+            }),
+            Err(e) => {
+                error!(
+                    chain = %chain_id,
+                    "invalid height for upgrade plan '{}': {}", plan.name, e
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Whether the chain has been upgraded, and produces blocks past the upgrade height.
+    fn is_due(&self, latest_height: Height) -> bool {
+        latest_height > self.height
+    }
+}
+
+pub fn spawn_upgrade_watcher<Chain: ChainHandle>(
+    config: Config,
+    registry: SharedRegistry<Chain>,
+) -> TaskHandle {
+    let upgrades = config.mode.clients.upgrades;
+
+    let mut pending: BTreeMap<ChainId, PendingUpgrade> = BTreeMap::new();
+    let mut recovered: BTreeSet<ChainId> = BTreeSet::new();
+    let mut last_check: Option<Instant> = None;
+
+    spawn_background_task(
+        error_span!("upgrade_watcher"),
+        Some(Duration::from_secs(1)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if last_check.map_or(false, |time| time.elapsed() < upgrades.interval) {
+                return Ok(Next::Continue);
+            }
+
+            last_check = Some(Instant::now());
+
+            for chain_config in &config.chains {
+                let chain = match registry.get_or_spawn(&chain_config.id) {
+                    Ok(chain) => chain,
+                    Err(e) => {
+                        warn!(chain = %chain_config.id, "failed to spawn chain runtime: {}", e);
+                        continue;
+                    }
+                };
+
+                check_chain(
+                    &config,
+                    &registry,
+                    &upgrades,
+                    &chain,
+                    &mut pending,
+                    &mut recovered,
+                );
+            }
+
+            Ok(Next::Continue)
+        },
+    )
+}
+
+fn check_chain<Chain: ChainHandle>(
+    config: &Config,
+    registry: &SharedRegistry<Chain>,
+    upgrades: &ClientUpgrades,
+    chain: &Chain,
+    pending: &mut BTreeMap<ChainId, PendingUpgrade>,
+    recovered: &mut BTreeSet<ChainId>,
+) {
+    let chain_id = chain.id();
+
+    let latest_height = match chain.query_latest_height() {
+        Ok(height) => height,
+        Err(e) => {
+            // Expected while the chain is halted for the upgrade
+            debug!(chain = %chain_id, "failed to query latest height: {}", e);
+            return;
+        }
+    };
+
+    let plan = match chain.query_upgrade_plan() {
+        Ok(plan) => plan,
+        Err(e) => {
+            debug!(chain = %chain_id, "failed to query upgrade plan: {}", e);
+            return;
+        }
+    };
+
+    if !recovered.contains(&chain_id) {
+        // A plan still scheduled supersedes any upgrade applied before
+        if plan.is_none() {
+            match chain.query_applied_upgrades() {
+                Ok(applied) => recover_upgrade(pending, &chain_id, applied),
+                Err(e) => {
+                    debug!(chain = %chain_id, "failed to query applied upgrades: {}", e);
+                    return;
+                }
+            }
+        }
+
+        recovered.insert(chain_id.clone());
+    }
+
+    track_plan(pending, &chain_id, plan, latest_height);
+
+    let upgrade = match pending.get_mut(&chain_id) {
+        Some(upgrade) => upgrade,
+        None => return,
+    };
+
+    // Wait until the upgraded chain is producing blocks past the upgrade height
+    if !upgrade.is_due(latest_height) {
+        return;
+    }
+
+    let upgrade_height = upgrade.height;
+
+    let clients = upgrade
+        .clients
+        .get_or_insert_with(|| find_clients(config, registry, &chain_id));
+
+    clients.retain(|(host_chain_id, client_id), attempts| {
+        let host_chain = match registry.get_or_spawn(host_chain_id) {
+            Ok(host_chain) => host_chain,
+            Err(e) => {
+                warn!(chain = %host_chain_id, "failed to spawn chain runtime: {}", e);
+                return true;
+            }
+        };
+
+        let client = ForeignClient::restore(client_id.clone(), host_chain, chain.clone());
+
+        upgrade_client(&client, upgrade_height, attempts, upgrades.max_retries)
+    });
+
+    if clients.is_empty() {
+        info!(
+            chain = %chain_id,
+            "done upgrading the clients for upgrade plan '{}'", upgrade.plan.name
+        );
+
+        pending.remove(&chain_id);
+    }
+}
+
+/// Updates the upgrade pending on a chain given the plan currently scheduled on it.
+fn track_plan(
+    pending: &mut BTreeMap<ChainId, PendingUpgrade>,
+    chain_id: &ChainId,
+    plan: Option<UpgradePlan>,
+    latest_height: Height,
+) {
+    match (plan, pending.get(chain_id)) {
+        (Some(plan), Some(upgrade)) if upgrade.plan == plan => {}
+        (Some(plan), _) => {
+            info!(
+                chain = %chain_id,
+                "upgrade plan '{}' scheduled at height {}, will upgrade the clients of the chain once it is reached",
+                plan.name, plan.height
+            );
+
+            match PendingUpgrade::new(chain_id, plan) {
+                Some(upgrade) => pending.insert(chain_id.clone(), upgrade),
+                None => pending.remove(chain_id),
+            };
+        }
+        // The plan is removed from the upgrade module once applied,
+        // so it is only cancelled if its height has not been reached.
+        (None, Some(upgrade)) if latest_height < upgrade.height => {
+            info!(
+                chain = %chain_id,
+                "upgrade plan '{}' has been cancelled", upgrade.plan.name
+            );
+
+            pending.remove(chain_id);
+        }
+        (None, _) => {}
+    }
+}
+
+/// Resumes the upgrade of the clients of a chain for the last upgrade applied on it,
+/// as the relayer may have missed it while it was down.
+fn recover_upgrade(
+    pending: &mut BTreeMap<ChainId, PendingUpgrade>,
+    chain_id: &ChainId,
+    applied: Vec<UpgradePlan>,
+) {
+    let plan = match applied.into_iter().max_by_key(|plan| plan.height) {
+        Some(plan) => plan,
+        None => return,
+    };
+
+    debug!(
+        chain = %chain_id,
+        "checking the clients of the chain for the last upgrade applied, '{}' at height {}",
+        plan.name, plan.height
+    );
+
+    if let Some(upgrade) = PendingUpgrade::new(chain_id, plan) {
+        pending.insert(chain_id.clone(), upgrade);
+    }
+}
+
+/// Collects the clients of the given chain hosted on the other configured chains.
+fn find_clients<Chain: ChainHandle>(
+    config: &Config,
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+) -> BTreeMap<(ChainId, ClientId), u32> {
+    let mut clients = BTreeMap::new();
+
+    for host_config in config.chains.iter().filter(|c| c.id != *chain_id) {
+        let host_chain = match registry.get_or_spawn(&host_config.id) {
+            Ok(host_chain) => host_chain,
+            Err(e) => {
+                warn!(chain = %host_config.id, "failed to spawn chain runtime: {}", e);
+                continue;
+            }
+        };
+
+        let host_clients = match host_chain.query_clients(QueryClientStatesRequest {
+            pagination: Some(PageRequest::all()),
+        }) {
+            Ok(host_clients) => host_clients,
+            Err(e) => {
+                warn!(chain = %host_config.id, "failed to query clients: {}", e);
+                continue;
+            }
+        };
+
+        for client in host_clients {
+            if client.client_state.chain_id() == *chain_id && !client.client_state.is_frozen() {
+                clients.insert((host_config.id.clone(), client.client_id), 0);
+            }
+        }
+    }
+
+    clients
+}
+
+/// Upgrades the given client, unless it has already been upgraded.
+/// Returns whether the upgrade should be retried.
+fn upgrade_client<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    upgrade_height: Height,
+    attempts: &mut u32,
+    max_retries: u32,
+) -> bool {
+    let host_chain_id = client.dst_chain().id();
+
+    // The client may have been upgraded by another relayer in the meantime
+    let client_height = client
+        .dst_chain()
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client.id().clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map(|(client_state, _)| client_state.latest_height());
+
+    if matches!(client_height, Ok(height) if height >= upgrade_height) {
+        info!(
+            client = %client.id(),
+            chain = %host_chain_id,
+            "client has already been upgraded"
+        );
+
+        return false;
+    }
+
+    match client.upgrade(upgrade_height) {
+        Ok(_) => {
+            info!(
+                client = %client.id(),
+                chain = %host_chain_id,
+                "upgraded client at height {}", upgrade_height
+            );
+
+            telemetry!(ibc_client_upgrades, &host_chain_id, client.id(), 1);
+
+            false
+        }
+        Err(e) => {
+            *attempts += 1;
+
+            telemetry!(ibc_client_upgrade_failures, &host_chain_id, client.id(), 1);
+
+            if *attempts > max_retries {
+                error!(
+                    client = %client.id(),
+                    chain = %host_chain_id,
+                    "giving up on upgrading client after {} attempts: {}", attempts, e
+                );
+
+                false
+            } else {
+                warn!(
+                    client = %client.id(),
+                    chain = %host_chain_id,
+                    "failed to upgrade client (attempt {}), will retry: {}", attempts, e
+                );
+
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+    use ibc::Height;
+
+    use super::{recover_upgrade, track_plan, PendingUpgrade};
+    use crate::upgrade_chain::UpgradePlan;
+
+    fn plan(name: &str, height: u64) -> UpgradePlan {
+        UpgradePlan {
+            name: name.to_string(),
+            height,
+        }
+    }
+
+    fn height(revision_number: u64, revision_height: u64) -> Height {
+        Height::new(revision_number, revision_height).unwrap()
+    }
+
+    #[test]
+    fn plan_is_tracked_until_applied() {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let mut pending = BTreeMap::new();
+
+        track_plan(
+            &mut pending,
+            &chain_id,
+            Some(plan("v2", 100)),
+            height(0, 50),
+        );
+
+        let upgrade = &pending[&chain_id];
+        assert_eq!(upgrade.height, height(0, 100));
+        assert!(!upgrade.is_due(height(0, 50)));
+        assert!(!upgrade.is_due(height(0, 100)));
+        assert!(upgrade.is_due(height(0, 101)));
+
+        // A newer revision is past the upgrade, whatever its revision height
+        assert!(upgrade.is_due(height(1, 1)));
+
+        // The plan is removed once applied, which does not cancel the upgrade
+        track_plan(&mut pending, &chain_id, None, height(1, 1));
+        assert_eq!(pending[&chain_id].plan, plan("v2", 100));
+
+        track_plan(&mut pending, &chain_id, None, height(0, 101));
+        assert_eq!(pending[&chain_id].plan, plan("v2", 100));
+    }
+
+    #[test]
+    fn plan_is_cancelled_or_replaced_before_its_height() {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let client = (ChainId::new("other".to_string(), 0), ClientId::default());
+        let mut pending = BTreeMap::new();
+
+        track_plan(
+            &mut pending,
+            &chain_id,
+            Some(plan("v2", 100)),
+            height(0, 50),
+        );
+
+        // Seeing the same plan again keeps the clients left to upgrade
+        pending.get_mut(&chain_id).unwrap().clients = Some(BTreeMap::from([(client, 0)]));
+        track_plan(
+            &mut pending,
+            &chain_id,
+            Some(plan("v2", 100)),
+            height(0, 60),
+        );
+        assert!(pending[&chain_id].clients.is_some());
+
+        // A new plan replaces the previous one
+        track_plan(
+            &mut pending,
+            &chain_id,
+            Some(plan("v3", 200)),
+            height(0, 70),
+        );
+        assert_eq!(pending[&chain_id].plan, plan("v3", 200));
+        assert_eq!(pending[&chain_id].height, height(0, 200));
+        assert!(pending[&chain_id].clients.is_none());
+
+        // Removed before its height, the plan has been cancelled
+        track_plan(&mut pending, &chain_id, None, height(0, 80));
+        assert!(pending.is_empty());
+
+        // A plan at an invalid height is ignored
+        track_plan(&mut pending, &chain_id, Some(plan("v4", 0)), height(0, 90));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn last_applied_upgrade_is_recovered() {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let mut pending: BTreeMap<ChainId, PendingUpgrade> = BTreeMap::new();
+
+        recover_upgrade(&mut pending, &chain_id, vec![]);
+        assert!(pending.is_empty());
+
+        recover_upgrade(
+            &mut pending,
+            &chain_id,
+            vec![plan("v3", 300), plan("v2", 100), plan("v1", 10)],
+        );
+
+        let upgrade = &pending[&chain_id];
+        assert_eq!(upgrade.plan, plan("v3", 300));
+        assert!(upgrade.is_due(height(0, 400)));
+
+        // No longer scheduled, the recovered upgrade is kept until its clients are upgraded
+        track_plan(&mut pending, &chain_id, None, height(0, 400));
+        assert_eq!(pending[&chain_id].plan, plan("v3", 300));
+    }
+}
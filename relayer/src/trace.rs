@@ -0,0 +1,184 @@
+//! Spans tracing an [`EventBatch`](crate::event::monitor::EventBatch) through the
+//! relaying pipeline, from the moment it is received from a chain until the
+//! transactions relaying its events are confirmed.
+//!
+//! Each batch holding IBC events starts a root span, and the operational data,
+//! messages and transactions generated from it record their own spans as children
+//! of that root span, even though they are processed later on by other threads.
+//!
+//! These spans are all recorded under the [`TARGET`] target. They are not part of
+//! the log output, but are exported to an OpenTelemetry collector when the
+//! `[tracing]` section of the configuration is enabled.
+//!
+//! Unlike the spans used for logging, these spans are never entered, so as to not
+//! change the context of the log output. They start when they are created and
+//! end once all their handles are dropped.
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::IbcEvent;
+use ibc::Height;
+use tracing::{info_span, Span};
+
+use crate::chain::tracking::TrackingId;
+
+/// The target of the spans tracing the event batches.
+pub const TARGET: &str = "ibc_relayer::trace";
+
+/// Start the root span of a batch of events received from a chain,
+/// or return a disabled span if the batch does not hold any IBC event
+/// to be relayed, as is the case for most blocks.
+pub fn event_batch_span(
+    chain_id: &ChainId,
+    tracking_id: TrackingId,
+    height: Height,
+    events: &[IbcEvent],
+) -> Span {
+    if events.iter().all(|e| matches!(e, IbcEvent::NewBlock(_))) {
+        return Span::none();
+    }
+
+    info_span!(
+        target: TARGET,
+        parent: None,
+        "event_batch",
+        chain = %chain_id,
+        height = %height,
+        tracking_id = %tracking_id,
+        events = events.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::time::Duration;
+    use std::sync::Mutex;
+
+    use tokio::runtime::Runtime as TokioRuntime;
+    use tracing::span::{Attributes, Id};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    use ibc::core::ics24_host::identifier::PortId;
+
+    use super::TARGET;
+    use crate::chain::handle::{BaseChainHandle, ChainHandle};
+    use crate::chain::mock::test_utils::{open_channel, spawn_chain};
+    use crate::chain::tracking::TrackingId;
+    use crate::event::monitor::EventBatch;
+    use crate::link::{Link, LinkParameters, Resubmit};
+    use crate::transfer::{build_and_send_transfer_messages, TransferOptions};
+
+    /// The name of a span, along with the name of its parent.
+    type SpanNames = (&'static str, Option<&'static str>);
+
+    /// Records the names of every span under [`TARGET`].
+    #[derive(Clone, Default)]
+    struct CaptureLayer {
+        spans: Arc<Mutex<Vec<SpanNames>>>,
+    }
+
+    impl<S> Layer<S> for CaptureLayer
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, ctx: Context<'_, S>) {
+            if attrs.metadata().target() != TARGET {
+                return;
+            }
+
+            let parent = attrs
+                .parent()
+                .and_then(|id| ctx.span(id))
+                .map(|span| span.name());
+
+            self.spans
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name(), parent));
+        }
+    }
+
+    /// Relays a batch of SendPacket events through the asynchronous path of the packet
+    /// workers, and checks that the spans of every relaying step are recorded as children
+    /// of the root span of the batch.
+    #[test]
+    fn event_batch_span_reaches_every_step() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let a_chain: BaseChainHandle = spawn_chain("chain_a", rt.clone());
+        let b_chain: BaseChainHandle = spawn_chain("chain_b", rt);
+
+        let a_channel_id = open_channel(&a_chain, &b_chain)
+            .a_channel_id()
+            .unwrap()
+            .clone();
+
+        let events = build_and_send_transfer_messages(
+            &a_chain,
+            &b_chain,
+            &TransferOptions {
+                packet_src_port_id: PortId::transfer(),
+                packet_src_channel_id: a_channel_id.clone(),
+                amount: 42u64.into(),
+                denom: "uatom".to_string(),
+                receiver: None,
+                timeout_height_offset: 1000,
+                timeout_duration: Duration::ZERO,
+                number_msgs: 1,
+            },
+        )
+        .unwrap();
+
+        let mut link = Link::new_from_opts(
+            a_chain.clone(),
+            b_chain,
+            LinkParameters {
+                src_port_id: PortId::transfer(),
+                src_channel_id: a_channel_id,
+            },
+            true,
+        )
+        .unwrap();
+
+        let capture = CaptureLayer::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let batch = EventBatch::new(
+                a_chain.id(),
+                TrackingId::new_uuid(),
+                events[0].height(),
+                events,
+            );
+
+            let path = &mut link.a_to_b;
+            path.update_schedule(batch).unwrap();
+            path.execute_schedule().unwrap();
+
+            for _ in 0..10 {
+                if !path.process_pending_txs(Resubmit::No).is_empty() {
+                    break;
+                }
+            }
+        });
+
+        let spans = capture.spans.lock().unwrap();
+
+        assert!(spans.contains(&("event_batch", None)));
+
+        for step in [
+            "generate_operational_data",
+            "assemble_msgs",
+            "submit_tx",
+            "confirm_tx",
+        ] {
+            assert!(
+                spans.contains(&(step, Some("event_batch"))),
+                "missing span {} in {:?}",
+                step,
+                spans
+            );
+        }
+    }
+}
@@ -5,6 +5,7 @@ use core::time::Duration;
 
 use bytes::BufMut;
 use flex_error::define_error;
+use serde::Serialize;
 
 use tendermint::abci::transaction::Hash as TxHash;
 
@@ -51,6 +52,14 @@ define_error! {
     }
 }
 
+/// An upgrade plan scheduled in the upgrade module of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradePlan {
+    pub name: String,
+    /// The height at which the chain halts to perform the upgrade
+    pub height: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct UpgradePlanOptions {
     pub src_chain_config: ChainConfig,
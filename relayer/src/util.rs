@@ -2,6 +2,7 @@ mod block_on;
 pub use block_on::block_on;
 
 pub mod diff;
+pub mod duration;
 pub mod iter;
 pub mod lock;
 pub mod queue;
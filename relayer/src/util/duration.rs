@@ -0,0 +1,6 @@
+use core::time::Duration;
+
+/// Format a duration with a precision of one second, eg. `1day 2h 3m 4s`.
+pub fn format_duration(duration: Duration) -> String {
+    humantime::format_duration(Duration::from_secs(duration.as_secs())).to_string()
+}
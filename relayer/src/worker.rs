@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 use tracing::error;
 
+use crate::channel::multihop::MultihopPath;
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
 use crate::{
@@ -55,6 +56,7 @@ impl fmt::Display for WorkerId {
 
 pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     chains: ChainHandlePair<ChainA, ChainB>,
+    path: MultihopPath<ChainA, ChainB>,
     id: WorkerId,
     object: Object,
     config: &Config,
@@ -103,22 +105,23 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
         Object::Channel(channel) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let channel_task = channel::spawn_channel_worker(channel.clone(), chains, cmd_rx);
+            let channel_task = channel::spawn_channel_worker(channel.clone(), chains, path, cmd_rx);
             task_handles.push(channel_task);
 
             (Some(cmd_tx), None)
         }
-        Object::Packet(path) => {
+        Object::Packet(packet) => {
             let packets_config = config.mode.packets;
             let link_res = Link::new_from_opts(
                 chains.a.clone(),
                 chains.b,
                 LinkParameters {
-                    src_port_id: path.src_port_id.clone(),
-                    src_channel_id: path.src_channel_id.clone(),
+                    src_port_id: packet.src_port_id.clone(),
+                    src_channel_id: packet.src_channel_id.clone(),
                 },
                 packets_config.tx_confirmation,
-            );
+            )
+            .map(|link| link.with_multihop_path(path));
 
             match link_res {
                 Ok(link) => {
@@ -135,11 +138,11 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                         link.clone(),
                         should_clear_on_start,
                         packets_config.clear_interval,
-                        path.clone(),
+                        packet.clone(),
                     );
                     task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
+                    let link_task = packet::spawn_packet_worker(packet.clone(), link, resubmit);
                     task_handles.push(link_task);
 
                     (Some(cmd_tx), None)
@@ -154,7 +157,11 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         Object::Wallet(wallet) => {
             assert_eq!(wallet.chain_id, chains.a.id());
 
-            let wallet_task = wallet::spawn_wallet_worker(chains.a);
+            let refill = config
+                .find_chain(&wallet.chain_id)
+                .and_then(|chain_config| chain_config.wallet_refill.clone());
+
+            let wallet_task = wallet::spawn_wallet_worker(chains.a, refill);
             task_handles.push(wallet_task);
 
             (None, None)
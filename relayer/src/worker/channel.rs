@@ -1,7 +1,9 @@
 use core::time::Duration;
 use crossbeam_channel::Receiver;
+use ibc::events::IbcEvent;
 use tracing::{debug, error_span};
 
+use crate::channel::multihop::MultihopPath;
 use crate::channel::Channel as RelayChannel;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
@@ -17,6 +19,7 @@ use super::WorkerCmd;
 pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     channel: Channel,
     chains: ChainHandlePair<ChainA, ChainB>,
+    path: MultihopPath<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -31,13 +34,33 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         let last_event = batch.events.last();
                         debug!("starts processing {:#?}", last_event);
 
-                        if let Some(event) = last_event {
+                        if let Some(event) = last_event.filter(|ev| is_upgrade_event(ev)) {
+                            // The application may have rejected the upgrade step, in which
+                            // case the channel was restored to its previous state. The next
+                            // step is therefore derived from the channel end at the height of
+                            // the event rather than from the event itself.
+                            let (handshake_channel, state) = RelayChannel::restore_from_state(
+                                chains.a.clone(),
+                                chains.b.clone(),
+                                channel.clone(),
+                                event.height(),
+                            )
+                            .map_err(|e| TaskError::Fatal(RunError::channel(e)))?;
+                            let mut handshake_channel =
+                                handshake_channel.with_multihop_path(path.clone());
+
+                            retry_with_index(retry_strategy::worker_default_strategy(), |index| {
+                                handshake_channel.step_state(state, index)
+                            })
+                            .map_err(|e| TaskError::Fatal(RunError::retry(e)))
+                        } else if let Some(event) = last_event {
                             let mut handshake_channel = RelayChannel::restore_from_event(
                                 chains.a.clone(),
                                 chains.b.clone(),
                                 event.clone(),
                             )
-                            .map_err(|e| TaskError::Fatal(RunError::channel(e)))?;
+                            .map_err(|e| TaskError::Fatal(RunError::channel(e)))?
+                            .with_multihop_path(path.clone());
 
                             retry_with_index(retry_strategy::worker_default_strategy(), |index| {
                                 handshake_channel.step_event(event.clone(), index)
@@ -57,13 +80,15 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                             .decrement()
                             .map_err(|e| TaskError::Fatal(RunError::ics02(e)))?;
 
-                        let (mut handshake_channel, state) = RelayChannel::restore_from_state(
+                        let (handshake_channel, state) = RelayChannel::restore_from_state(
                             chains.a.clone(),
                             chains.b.clone(),
                             channel.clone(),
                             height,
                         )
                         .map_err(|e| TaskError::Fatal(RunError::channel(e)))?;
+                        let mut handshake_channel =
+                            handshake_channel.with_multihop_path(path.clone());
 
                         retry_with_index(retry_strategy::worker_default_strategy(), |index| {
                             handshake_channel.step_state(state, index)
@@ -80,3 +105,15 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
         },
     )
 }
+
+/// Events emitted by a step of the channel upgrade handshake.
+fn is_upgrade_event(event: &IbcEvent) -> bool {
+    matches!(
+        event,
+        IbcEvent::UpgradeInitChannel(_)
+            | IbcEvent::UpgradeTryChannel(_)
+            | IbcEvent::UpgradeAckChannel(_)
+            | IbcEvent::UpgradeTimeoutChannel(_)
+            | IbcEvent::UpgradeCancelChannel(_)
+    )
+}
@@ -4,11 +4,12 @@ use core::mem;
 use crossbeam_channel::Sender;
 use serde::Deserialize;
 use serde::Serialize;
-use tracing::{debug, trace};
+use tracing::{debug, trace, Span};
 
 use ibc::{
     core::{ics02_client::events::NewBlock, ics24_host::identifier::ChainId},
     events::IbcEvent,
+    timestamp::Timestamp,
     Height,
 };
 
@@ -68,14 +69,18 @@ impl WorkerHandle {
         &self,
         height: Height,
         events: Vec<IbcEvent>,
+        time: Option<Timestamp>,
         chain_id: ChainId,
         tracking_id: TrackingId,
+        span: Span,
     ) {
         let batch = EventBatch {
             chain_id,
             height,
             events,
+            time,
             tracking_id,
+            span,
         };
 
         self.try_send_command(WorkerCmd::IbcEvents { batch });
@@ -8,6 +8,7 @@ use tracing::{debug, trace};
 
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
+    channel::multihop::MultihopPath,
     config::Config,
     object::Object,
     telemetry,
@@ -122,12 +123,13 @@ impl WorkerMap {
         object: Object,
         src: Chain,
         dst: Chain,
+        path: MultihopPath<Chain, Chain>,
         config: &Config,
     ) -> &WorkerHandle {
         if self.workers.contains_key(&object) {
             &self.workers[&object]
         } else {
-            let worker = self.spawn_worker(src, dst, &object, config);
+            let worker = self.spawn_worker(src, dst, path, &object, config);
             self.workers.entry(object).or_insert(worker)
         }
     }
@@ -139,11 +141,12 @@ impl WorkerMap {
         &mut self,
         src: Chain,
         dst: Chain,
+        path: MultihopPath<Chain, Chain>,
         object: &Object,
         config: &Config,
     ) -> bool {
         if !self.workers.contains_key(object) {
-            let worker = self.spawn_worker(src, dst, object, config);
+            let worker = self.spawn_worker(src, dst, path, object, config);
             self.workers.entry(object.clone()).or_insert(worker);
             true
         } else {
@@ -156,6 +159,7 @@ impl WorkerMap {
         &mut self,
         src: Chain,
         dst: Chain,
+        path: MultihopPath<Chain, Chain>,
         object: &Object,
         config: &Config,
     ) -> WorkerHandle {
@@ -163,6 +167,7 @@ impl WorkerMap {
 
         spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
+            path,
             self.next_worker_id(),
             object.clone(),
             config,
@@ -211,19 +211,28 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
         trace!("produced relay summary: {:?}", summary);
     }
 
-    telemetry!(packet_metrics(_path, &summary));
+    telemetry!(packet_metrics(&link.a_to_b, _path, &summary));
 
     Ok(())
 }
 
 #[cfg(feature = "telemetry")]
-use crate::link::RelaySummary;
+use crate::{
+    denom::DenomTrace,
+    error::Error,
+    link::{RelayPath, RelaySummary},
+};
 
 #[cfg(feature = "telemetry")]
-fn packet_metrics(path: &Packet, summary: &RelaySummary) {
+fn packet_metrics<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relay_path: &RelayPath<ChainA, ChainB>,
+    path: &Packet,
+    summary: &RelaySummary,
+) {
     receive_packet_metrics(path, summary);
     acknowledgment_metrics(path, summary);
     timeout_metrics(path, summary);
+    ics20_metrics(relay_path, summary);
 }
 
 #[cfg(feature = "telemetry")]
@@ -281,3 +290,231 @@ fn timeout_metrics(path: &Packet, summary: &RelaySummary) {
         count as u64,
     );
 }
+
+/// The fields of the ICS-20 packet data holding the transferred tokens,
+/// as found in the `packet_data` attribute of the packet events.
+#[cfg(feature = "telemetry")]
+#[derive(serde::Deserialize)]
+struct TransferPacketData {
+    denom: String,
+    amount: String,
+}
+
+/// Record the amount of tokens relayed by the ICS-20 packets in the summary,
+/// per source chain, channel and port of the packets, and per base denom.
+/// Packets whose data cannot be decoded as ICS-20 packet data, such as the
+/// packets of other applications, are ignored.
+#[cfg(feature = "telemetry")]
+fn ics20_metrics<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: &RelayPath<ChainA, ChainB>,
+    summary: &RelaySummary,
+) {
+    use ibc::events::IbcEvent::{
+        AcknowledgePacket, TimeoutOnClosePacket, TimeoutPacket, WriteAcknowledgement,
+    };
+
+    let telemetry = ibc_telemetry::global();
+
+    let src_transfer =
+        |data: &[u8]| transfer_amount(data, |hash| path.src_chain().query_denom_trace(hash));
+    let dst_transfer =
+        |data: &[u8]| transfer_amount(data, |hash| path.dst_chain().query_denom_trace(hash));
+
+    for event in summary.events.iter() {
+        // The packets received by the destination chain, and those which timed out, were
+        // sent by the source chain of the path, while the acknowledgements relayed to the
+        // destination chain are those of the packets it sent.
+        let (chain_id, packet, transfer) = match event {
+            WriteAcknowledgement(ev) if is_successful_ack(&ev.ack) => (
+                path.src_chain().id(),
+                &ev.packet,
+                src_transfer(&ev.packet.data),
+            ),
+            AcknowledgePacket(ev) => (
+                path.dst_chain().id(),
+                &ev.packet,
+                dst_transfer(&ev.packet.data),
+            ),
+            TimeoutPacket(ev) => (
+                path.src_chain().id(),
+                &ev.packet,
+                src_transfer(&ev.packet.data),
+            ),
+            TimeoutOnClosePacket(ev) => (
+                path.src_chain().id(),
+                &ev.packet,
+                src_transfer(&ev.packet.data),
+            ),
+            _ => continue,
+        };
+
+        let (denom, amount) = match transfer {
+            Some(transfer) => transfer,
+            None => continue,
+        };
+
+        let (channel_id, port_id) = (&packet.source_channel, &packet.source_port);
+
+        match event {
+            WriteAcknowledgement(_) => {
+                telemetry.ics20_received_amount(&chain_id, channel_id, port_id, &denom, amount)
+            }
+            AcknowledgePacket(_) => {
+                telemetry.ics20_acknowledged_amount(&chain_id, channel_id, port_id, &denom, amount)
+            }
+            _ => telemetry.ics20_timeout_amount(&chain_id, channel_id, port_id, &denom, amount),
+        }
+    }
+}
+
+/// Whether the given acknowledgement is an ICS-20 success acknowledgement,
+/// ie. `{"result":"AQ=="}`, as opposed to an error acknowledgement.
+#[cfg(feature = "telemetry")]
+fn is_successful_ack(ack: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Value>(ack)
+        .map(|ack| ack.get("result").is_some())
+        .unwrap_or(false)
+}
+
+/// Decode the given packet data as ICS-20 packet data, and return the base denom
+/// and the amount of the transferred tokens. Amounts which do not fit in a `u64`
+/// are saturated, as the amounts are recorded by `u64` counters.
+#[cfg(feature = "telemetry")]
+fn transfer_amount(
+    data: &[u8],
+    query_denom_trace: impl FnOnce(String) -> Result<DenomTrace, Error>,
+) -> Option<(String, u64)> {
+    use core::str::FromStr;
+    use ibc::applications::transfer::Amount;
+    use ibc::bigint::U256;
+
+    let data = serde_json::from_slice::<TransferPacketData>(data).ok()?;
+
+    let amount = U256::from(Amount::from_str(&data.amount).ok()?);
+    let amount = if amount > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        amount.low_u64()
+    };
+
+    Some((base_denom(&data.denom, query_denom_trace), amount))
+}
+
+/// Resolve the base denom of a denom found in ICS-20 packet data.
+///
+/// The denom is usually the full trace of the tokens, eg. `transfer/channel-0/uatom`,
+/// whose base denom follows the `{port}/{channel}` prefixes. Hashed IBC denoms, eg.
+/// `ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2`, are
+/// resolved by querying their trace from the source chain of the packet, which
+/// holds these tokens.
+#[cfg(feature = "telemetry")]
+fn base_denom(
+    denom: &str,
+    query_denom_trace: impl FnOnce(String) -> Result<DenomTrace, Error>,
+) -> String {
+    use core::str::FromStr;
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        return match query_denom_trace(hash.to_string()) {
+            Ok(trace) => trace.base_denom,
+            Err(e) => {
+                tracing::debug!("failed to query the trace of denom {}: {}", denom, e);
+                denom.to_string()
+            }
+        };
+    }
+
+    let parts: Vec<&str> = denom.split('/').collect();
+
+    let mut start = 0;
+    while start + 2 < parts.len()
+        && PortId::from_str(parts[start]).is_ok()
+        && parts[start + 1].starts_with("channel-")
+        && ChannelId::from_str(parts[start + 1]).is_ok()
+    {
+        start += 2;
+    }
+
+    parts[start..].join("/")
+}
+
+#[cfg(all(test, feature = "telemetry"))]
+mod tests {
+    use crate::denom::DenomTrace;
+    use crate::error::Error;
+
+    use super::{base_denom, is_successful_ack, transfer_amount};
+
+    fn no_trace(hash: String) -> Result<DenomTrace, Error> {
+        panic!("unexpected query for the trace of {}", hash)
+    }
+
+    #[test]
+    fn base_denom_strips_the_trace() {
+        assert_eq!(base_denom("uatom", no_trace), "uatom");
+        assert_eq!(base_denom("transfer/channel-0/uatom", no_trace), "uatom");
+        assert_eq!(
+            base_denom("transfer/channel-0/transfer/channel-12/uatom", no_trace),
+            "uatom"
+        );
+        assert_eq!(
+            base_denom("transfer/channel-0/gamm/pool/1", no_trace),
+            "gamm/pool/1"
+        );
+        // Not a trace, as there is no channel
+        assert_eq!(base_denom("transfer/uatom", no_trace), "transfer/uatom");
+    }
+
+    #[test]
+    fn base_denom_resolves_hashed_denoms() {
+        let trace = |hash: String| {
+            assert_eq!(hash, "27394FB0");
+
+            Ok(DenomTrace {
+                path: "transfer/channel-1".to_string(),
+                base_denom: "uosmo".to_string(),
+            })
+        };
+        assert_eq!(base_denom("ibc/27394FB0", trace), "uosmo");
+
+        // The hashed denom is kept as is if its trace cannot be queried
+        let unknown = |_| Err(Error::empty_response_value());
+        assert_eq!(base_denom("ibc/27394FB0", unknown), "ibc/27394FB0");
+    }
+
+    #[test]
+    fn successful_acks() {
+        assert!(is_successful_ack(br#"{"result":"AQ=="}"#));
+        assert!(!is_successful_ack(br#"{"error":"insufficient funds"}"#));
+        assert!(!is_successful_ack(b"AQ=="));
+    }
+
+    #[test]
+    fn transfer_amounts() {
+        let data = |amount: &str| {
+            format!(
+                r#"{{"denom":"transfer/channel-0/uatom","amount":"{}","sender":"a","receiver":"b"}}"#,
+                amount
+            )
+        };
+
+        assert_eq!(
+            transfer_amount(data("100").as_bytes(), no_trace),
+            Some(("uatom".to_string(), 100))
+        );
+
+        // 2^128 does not fit in a u64
+        assert_eq!(
+            transfer_amount(
+                data("340282366920938463463374607431768211456").as_bytes(),
+                no_trace
+            ),
+            Some(("uatom".to_string(), u64::MAX))
+        );
+
+        assert_eq!(transfer_amount(data("1.5").as_bytes(), no_trace), None);
+        assert_eq!(transfer_amount(data("-1").as_bytes(), no_trace), None);
+        assert_eq!(transfer_amount(b"\x01\x02", no_trace), None);
+    }
+}
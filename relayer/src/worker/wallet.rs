@@ -1,18 +1,43 @@
-use std::{ops::Div, time::Duration};
+use alloc::collections::VecDeque;
+use std::{
+    fs,
+    ops::Div,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use tracing::{error_span, trace};
+use serde::{Deserialize, Serialize};
+use tracing::{error_span, info, trace, warn};
 
 use ibc::bigint::U256;
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::signer::Signer;
+use ibc_proto::cosmos::base::v1beta1::Coin;
 
 use crate::{
+    bank::send_tokens_with_key,
     chain::handle::ChainHandle,
+    config::WalletRefill,
+    keyring::KeyRing,
     telemetry,
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
 
-pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
+/// The period over which the amount sent by refills is capped by `max_per_day`.
+const REFILL_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Folder, relative to the home directory, holding the refill history of each chain.
+const REFILL_HISTORY_FOLDER: &str = ".hermes/wallet_refills/";
+
+pub fn spawn_wallet_worker<Chain: ChainHandle>(
+    chain: Chain,
+    refill: Option<WalletRefill>,
+) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
 
+    let chain_id = chain.id();
+    let mut refiller = refill.map(|policy| Refiller::new(&chain_id, policy));
+
     spawn_background_task(span, Some(Duration::from_secs(5)), move || {
         let key = chain.get_key().map_err(|e| {
             TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
@@ -56,10 +81,214 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
             );
         }
 
+        if let Some(refiller) = refiller.as_mut() {
+            refiller
+                .refill(&chain, &key.account, amount, &balance.denom)
+                .map_err(TaskError::Ignore)?;
+        }
+
         Ok(Next::Continue)
     })
 }
 
+/// Refills the wallet of the relayer from a treasury account, according to a [`WalletRefill`] policy.
+struct Refiller {
+    policy: WalletRefill,
+    /// Address of the treasury key, looked up in the keyring on the first refill.
+    treasury: Option<Signer>,
+    /// The refills sent during the last [`REFILL_WINDOW`], persisted so that
+    /// the daily budget also holds across restarts of the relayer.
+    history: RefillHistory,
+    /// Where the history is persisted, if the home directory can be found.
+    history_path: Option<PathBuf>,
+}
+
+impl Refiller {
+    fn new(chain_id: &ChainId, policy: WalletRefill) -> Self {
+        let history_path = dirs_next::home_dir().map(|home| {
+            home.join(REFILL_HISTORY_FOLDER)
+                .join(format!("{chain_id}.json"))
+        });
+
+        let history = match &history_path {
+            Some(path) => RefillHistory::load(path).unwrap_or_else(|e| {
+                warn!("failed to load the wallet refill history, starting afresh: {e}");
+                RefillHistory::default()
+            }),
+            None => {
+                warn!(
+                    "home directory unavailable, the wallet refill history will not be persisted"
+                );
+                RefillHistory::default()
+            }
+        };
+
+        Self {
+            policy,
+            treasury: None,
+            history,
+            history_path,
+        }
+    }
+
+    fn refill<Chain: ChainHandle>(
+        &mut self,
+        chain: &Chain,
+        account: &str,
+        balance: U256,
+        denom: &str,
+    ) -> Result<(), String> {
+        let now = unix_time();
+
+        self.history.prune(now);
+
+        let amount = match refill_amount(&self.policy, balance, self.history.sent()) {
+            Some(amount) => amount,
+            None => return Ok(()),
+        };
+
+        let receiver = chain
+            .get_signer()
+            .map_err(|e| format!("failed to get the address of the relayer key: {e}"))?;
+
+        let treasury = self.treasury(chain)?;
+
+        info!(
+            %amount, %denom, %account, treasury = %self.policy.treasury_key_name,
+            "refilling wallet from treasury"
+        );
+
+        send_tokens_with_key(
+            chain,
+            &self.policy.treasury_key_name,
+            &treasury,
+            &receiver,
+            Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            },
+        )
+        .map_err(|e| format!("failed to refill wallet from treasury: {e}"))?;
+
+        self.history.push(now, amount);
+
+        if let Some(path) = &self.history_path {
+            if let Err(e) = self.history.save(path) {
+                warn!("failed to persist the wallet refill history: {e}");
+            }
+        }
+
+        telemetry!(wallet_refill, &chain.id(), account, amount, denom);
+
+        Ok(())
+    }
+
+    fn treasury<Chain: ChainHandle>(&mut self, chain: &Chain) -> Result<Signer, String> {
+        if let Some(treasury) = &self.treasury {
+            return Ok(treasury.clone());
+        }
+
+        let config = chain
+            .config()
+            .map_err(|e| format!("failed to get the chain configuration: {e}"))?;
+
+        let keyring = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
+            .map_err(|e| format!("failed to open the keyring: {e}"))?;
+
+        let treasury: Signer = keyring
+            .get_key(&self.policy.treasury_key_name)
+            .map_err(|e| format!("failed to get the treasury key: {e}"))?
+            .account
+            .parse()
+            .map_err(|e| format!("invalid treasury address: {e}"))?;
+
+        self.treasury = Some(treasury.clone());
+
+        Ok(treasury)
+    }
+}
+
+/// A refill sent at `time`, in seconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Refill {
+    time: u64,
+    amount: u64,
+}
+
+/// The refills sent over the last [`REFILL_WINDOW`], oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct RefillHistory {
+    refills: VecDeque<Refill>,
+}
+
+impl RefillHistory {
+    /// Load the history from the given file, which is fine to be missing.
+    fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)
+                .map_err(|e| format!("failed to create {}: {e}", folder.display()))?;
+        }
+
+        let contents = serde_json::to_string(self).map_err(|e| e.to_string())?;
+
+        fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+
+    /// Forget the refills sent before the current window.
+    fn prune(&mut self, now: u64) {
+        while let Some(refill) = self.refills.front() {
+            if now.saturating_sub(refill.time) < REFILL_WINDOW.as_secs() {
+                break;
+            }
+
+            self.refills.pop_front();
+        }
+    }
+
+    fn push(&mut self, time: u64, amount: u64) {
+        self.refills.push_back(Refill { time, amount });
+    }
+
+    /// The amount sent over the current window.
+    fn sent(&self) -> u64 {
+        self.refills
+            .iter()
+            .fold(0, |sent, refill| sent.saturating_add(refill.amount))
+    }
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// The amount to send to bring a balance that dropped below the refill threshold
+/// back to the target, capped by what is left of the daily refill budget.
+fn refill_amount(policy: &WalletRefill, balance: U256, sent_in_window: u64) -> Option<u64> {
+    if balance >= U256::from(policy.threshold) || balance >= U256::from(policy.target) {
+        return None;
+    }
+
+    // The balance is below the target, which fits in a `u64`.
+    let missing = policy.target - balance.as_u64();
+    let budget = policy.max_per_day.saturating_sub(sent_in_window);
+
+    Some(missing.min(budget)).filter(|amount| *amount > 0)
+}
+
 /// Scale down the given amount by a factor of 10^6,
 /// and return it as a `u64` if it fits.
 fn scale_down(amount: U256) -> Option<u64> {
@@ -68,7 +297,8 @@ fn scale_down(amount: U256) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
-    use super::scale_down;
+    use super::{refill_amount, scale_down, RefillHistory, REFILL_WINDOW};
+    use crate::config::WalletRefill;
     use ibc::bigint::U256;
 
     #[test]
@@ -79,4 +309,55 @@ mod tests {
         let s = scale_down(u);
         assert_eq!(s, Some(349999631379421_u64));
     }
+
+    #[test]
+    fn refill_up_to_target_within_budget() {
+        let policy = WalletRefill {
+            treasury_key_name: "treasury".to_string(),
+            threshold: 1_000,
+            target: 5_000,
+            max_per_day: 6_000,
+        };
+
+        // Above the threshold, no refill
+        assert_eq!(refill_amount(&policy, U256::from(1_000_u64), 0), None);
+
+        // Below the threshold, refill up to the target
+        assert_eq!(refill_amount(&policy, U256::from(500_u64), 0), Some(4_500));
+
+        // Capped by what is left of the daily budget
+        assert_eq!(
+            refill_amount(&policy, U256::from(500_u64), 4_000),
+            Some(2_000)
+        );
+
+        // Daily budget exhausted
+        assert_eq!(refill_amount(&policy, U256::from(500_u64), 6_000), None);
+    }
+
+    #[test]
+    fn refill_history_window_and_persistence() {
+        let window = REFILL_WINDOW.as_secs();
+
+        let mut history = RefillHistory::default();
+        history.push(1_000, 2_000);
+        history.push(1_000 + window / 2, 3_000);
+        assert_eq!(history.sent(), 5_000);
+
+        // The first refill falls out of the window
+        history.prune(1_000 + window);
+        assert_eq!(history.sent(), 3_000);
+
+        let path = std::env::temp_dir()
+            .join(format!("hermes-wallet-refills-{}", std::process::id()))
+            .join("ibc-0.json");
+
+        // A missing file is an empty history
+        assert_eq!(RefillHistory::load(&path), Ok(RefillHistory::default()));
+
+        history.save(&path).unwrap();
+        assert_eq!(RefillHistory::load(&path), Ok(history));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
 }
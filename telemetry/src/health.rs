@@ -0,0 +1,276 @@
+//! Health and readiness of the relayer, served by the telemetry server
+//! over the `/healthz` and `/readyz` endpoints.
+//!
+//! The state is reported by the relayer itself: the supervisor records
+//! the configured chains, the outcome of their health check and of the
+//! subscription to their events, and ticks on every iteration of its
+//! event loops, one per chain, while the event monitors record whether
+//! their WebSocket connection is up.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+/// How long an event loop of the supervisor may go without ticking
+/// before the supervisor is considered stalled.
+const SUPERVISOR_TICK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The outcome of the health check of a chain, performed when the supervisor starts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HealthCheckStatus {
+    /// The health check was not performed (yet).
+    NotPerformed,
+    Healthy,
+    Unhealthy {
+        reason: String,
+    },
+}
+
+impl Default for HealthCheckStatus {
+    fn default() -> Self {
+        Self::NotPerformed
+    }
+}
+
+/// The health of a configured chain.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ChainHealth {
+    pub health_check: HealthCheckStatus,
+    /// Whether the supervisor is subscribed to the events of the chain.
+    pub subscribed: bool,
+    /// Whether the WebSocket connection of the event monitor is up.
+    pub websocket_connected: bool,
+}
+
+impl ChainHealth {
+    /// A chain is ready if it did not fail its health check, and its events
+    /// are being received over the WebSocket connection.
+    pub fn is_ready(&self) -> bool {
+        !matches!(self.health_check, HealthCheckStatus::Unhealthy { .. })
+            && self.subscribed
+            && self.websocket_connected
+    }
+}
+
+/// The state of the supervisor's event loops.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisorStatus {
+    /// The supervisor is scanning the chains and spawning the workers,
+    /// and has not started processing events yet.
+    Starting,
+    Running,
+    /// One of the event loops of the supervisor has not ticked for too long.
+    Stalled,
+}
+
+/// The response of the `/healthz` endpoint.
+#[derive(Clone, Debug, Serialize)]
+pub struct Liveness {
+    pub alive: bool,
+    pub supervisor: SupervisorStatus,
+    /// Milliseconds since the last tick of the event loop which ticked
+    /// the least recently, if any ticked.
+    pub last_tick_ms: Option<u64>,
+}
+
+/// The response of the `/readyz` endpoint.
+#[derive(Clone, Debug, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub supervisor: SupervisorStatus,
+    pub chains: BTreeMap<String, ChainHealth>,
+}
+
+#[derive(Debug, Default)]
+pub struct HealthState {
+    /// The moment of the last tick of each event loop of the supervisor,
+    /// keyed by the chain whose events the loop processes.
+    ticks: RwLock<BTreeMap<String, Instant>>,
+    chains: RwLock<BTreeMap<String, ChainHealth>>,
+}
+
+impl HealthState {
+    pub fn supervisor_tick(&self, chain_id: &ChainId) {
+        let now = Instant::now();
+
+        if let Ok(mut ticks) = self.ticks.write() {
+            match ticks.get_mut(chain_id.as_str()) {
+                Some(tick) => *tick = now,
+                None => {
+                    ticks.insert(chain_id.to_string(), now);
+                }
+            }
+        }
+    }
+
+    pub fn chain_configured(&self, chain_id: &ChainId) {
+        self.update_chain(chain_id, |_| {});
+    }
+
+    pub fn health_check(&self, chain_id: &ChainId, status: HealthCheckStatus) {
+        self.update_chain(chain_id, |chain| chain.health_check = status);
+    }
+
+    pub fn subscribed(&self, chain_id: &ChainId, subscribed: bool) {
+        self.update_chain(chain_id, |chain| chain.subscribed = subscribed);
+    }
+
+    pub fn websocket_connected(&self, chain_id: &ChainId, connected: bool) {
+        self.update_chain(chain_id, |chain| chain.websocket_connected = connected);
+    }
+
+    fn update_chain(&self, chain_id: &ChainId, f: impl FnOnce(&mut ChainHealth)) {
+        if let Ok(mut chains) = self.chains.write() {
+            f(chains.entry(chain_id.to_string()).or_default());
+        }
+    }
+
+    /// The time elapsed since the last tick of the event loop
+    /// which ticked the least recently, if any ticked.
+    fn last_tick_elapsed(&self) -> Option<Duration> {
+        let ticks = self.ticks.read().ok()?;
+        ticks.values().map(Instant::elapsed).max()
+    }
+
+    fn supervisor_status(&self) -> SupervisorStatus {
+        match self.last_tick_elapsed() {
+            None => SupervisorStatus::Starting,
+            Some(elapsed) if elapsed > SUPERVISOR_TICK_TIMEOUT => SupervisorStatus::Stalled,
+            Some(_) => SupervisorStatus::Running,
+        }
+    }
+
+    /// The process is alive as long as the supervisor is starting or
+    /// all of its event loops keep ticking.
+    pub fn liveness(&self) -> Liveness {
+        let supervisor = self.supervisor_status();
+
+        Liveness {
+            alive: supervisor != SupervisorStatus::Stalled,
+            supervisor,
+            last_tick_ms: self.last_tick_elapsed().map(|d| d.as_millis() as u64),
+        }
+    }
+
+    /// The relayer is ready once the supervisor is running,
+    /// and all the configured chains are ready.
+    pub fn readiness(&self) -> Readiness {
+        let supervisor = self.supervisor_status();
+
+        let chains = self
+            .chains
+            .read()
+            .map(|chains| chains.clone())
+            .unwrap_or_default();
+
+        let ready = supervisor == SupervisorStatus::Running
+            && !chains.is_empty()
+            && chains.values().all(ChainHealth::is_ready);
+
+        Readiness {
+            ready,
+            supervisor,
+            chains,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::{HealthCheckStatus, HealthState, SupervisorStatus};
+
+    #[test]
+    fn liveness_reports_the_oldest_tick() {
+        let health = HealthState::default();
+        let chain_a = ChainId::new("chain-a".to_string(), 0);
+
+        let liveness = health.liveness();
+        assert_eq!(liveness.supervisor, SupervisorStatus::Starting);
+        assert!(liveness.alive);
+        assert_eq!(liveness.last_tick_ms, None);
+
+        health.supervisor_tick(&chain_a);
+
+        let liveness = health.liveness();
+        assert_eq!(liveness.supervisor, SupervisorStatus::Running);
+        assert!(liveness.alive);
+
+        // The event loop of chain b stopped ticking two minutes ago,
+        // while the one of chain a keeps ticking
+        let stalled_at = Instant::now() - Duration::from_secs(120);
+        health
+            .ticks
+            .write()
+            .unwrap()
+            .insert("chain-b".to_string(), stalled_at);
+        health.supervisor_tick(&chain_a);
+
+        let liveness = health.liveness();
+        assert_eq!(liveness.supervisor, SupervisorStatus::Stalled);
+        assert!(!liveness.alive);
+        assert!(liveness.last_tick_ms.unwrap() >= 120_000);
+    }
+
+    #[test]
+    fn readiness_requires_every_chain_to_be_ready() {
+        let health = HealthState::default();
+        let chain_a = ChainId::new("chain-a".to_string(), 0);
+        let chain_b = ChainId::new("chain-b".to_string(), 0);
+
+        health.chain_configured(&chain_a);
+        health.chain_configured(&chain_b);
+
+        // Not ready while the supervisor is starting
+        health.subscribed(&chain_a, true);
+        health.websocket_connected(&chain_a, true);
+        assert!(!health.readiness().ready);
+
+        health.supervisor_tick(&chain_a);
+        health.supervisor_tick(&chain_b);
+
+        // Chain b is not subscribed to yet
+        let readiness = health.readiness();
+        assert_eq!(readiness.supervisor, SupervisorStatus::Running);
+        assert!(readiness.chains[chain_a.as_str()].is_ready());
+        assert!(!readiness.chains[chain_b.as_str()].is_ready());
+        assert!(!readiness.ready);
+
+        health.subscribed(&chain_b, true);
+        health.websocket_connected(&chain_b, true);
+        assert!(health.readiness().ready);
+
+        // The WebSocket connection of chain b is reconnecting
+        health.websocket_connected(&chain_b, false);
+        assert!(!health.readiness().ready);
+        health.websocket_connected(&chain_b, true);
+
+        health.health_check(&chain_a, HealthCheckStatus::Healthy);
+        assert!(health.readiness().ready);
+
+        health.health_check(
+            &chain_a,
+            HealthCheckStatus::Unhealthy {
+                reason: "unreachable".to_string(),
+            },
+        );
+        assert!(!health.readiness().ready);
+    }
+
+    #[test]
+    fn no_chain_is_not_ready() {
+        let health = HealthState::default();
+        health.supervisor_tick(&ChainId::new("chain-a".to_string(), 0));
+
+        assert!(!health.readiness().ready);
+    }
+}
@@ -1,5 +1,6 @@
 extern crate alloc;
 
+pub mod health;
 mod path_identifier;
 pub mod server;
 pub mod state;
@@ -2,7 +2,7 @@
 /// to track the SendPacket and WriteAcknowledgement for a given
 /// chain, channel and port.
 
-#[derive(Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct PathIdentifier {
     chain_id: String,
     channel_id: String,
@@ -3,24 +3,34 @@ use std::{error::Error, net::ToSocketAddrs};
 
 use prometheus::{Encoder, TextEncoder};
 use rouille::{Request, Response, Server};
+use serde::Serialize;
 
 use crate::state::TelemetryState;
 
 enum Route {
     Metrics,
+    Healthz,
+    Readyz,
     Other,
 }
 
 impl Route {
     fn from_request(request: &Request) -> Route {
-        if request.url() == "/metrics" {
-            Route::Metrics
-        } else {
-            Route::Other
+        match request.url().as_str() {
+            "/metrics" => Route::Metrics,
+            "/healthz" => Route::Healthz,
+            "/readyz" => Route::Readyz,
+            _ => Route::Other,
         }
     }
 }
 
+/// Serve the given value as JSON, with a 200 status code if `ok`, or 503 otherwise.
+fn status_response(ok: bool, body: &impl Serialize) -> Response {
+    let status_code = if ok { 200 } else { 503 };
+    Response::json(body).with_status_code(status_code)
+}
+
 pub fn listen(
     address: impl ToSocketAddrs,
     telemetry_state: Arc<TelemetryState>,
@@ -37,6 +47,18 @@ pub fn listen(
                 rouille::Response::from_data(encoder.format_type().to_string(), buffer)
             }
 
+            // The liveness probe: the process is alive and the supervisor is ticking
+            Route::Healthz => {
+                let liveness = telemetry_state.health().liveness();
+                status_response(liveness.alive, &liveness)
+            }
+
+            // The readiness probe: all configured chains are healthy and their events are received
+            Route::Readyz => {
+                let readiness = telemetry_state.health().readiness();
+                status_response(readiness.ready, &readiness)
+            }
+
             // Any other route
             // Return an empty response with a 404 status code.
             Route::Other => rouille::Response::empty_404(),
@@ -1,4 +1,5 @@
 use core::fmt;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use opentelemetry::{
@@ -10,15 +11,22 @@ use opentelemetry_prometheus::PrometheusExporter;
 use prometheus::proto::MetricFamily;
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc::timestamp::Timestamp;
 use tendermint::Time;
 
 use dashmap::DashMap;
 
+use crate::health::{HealthCheckStatus, HealthState};
 use crate::path_identifier::PathIdentifier;
 
 const NO_PENDING_PACKETS: u64 = 0;
 const HISTORY_SET_CAPACITY: usize = 1000;
 const HISTORY_RESET_THRESHOLD: usize = 900;
+/// The maximum number of denoms labelling the `ics20_*_amount` metrics. Anyone can
+/// mint tokens and send them over a channel, so the tokens of any further denom are
+/// accounted for under the `other` denom, to bound the number of time series.
+const ICS20_DENOMS_CAPACITY: usize = 100;
+const ICS20_OTHER_DENOM: &str = "other";
 
 #[derive(Copy, Clone, Debug)]
 pub enum WorkerType {
@@ -41,6 +49,53 @@ impl fmt::Display for WorkerType {
     }
 }
 
+/// The stages of a packet's lifecycle that are relayed to a chain,
+/// either by Hermes or by another relayer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacketStage {
+    /// The packet is received on the destination chain.
+    Recv,
+    /// The acknowledgement is relayed back to the source chain.
+    Ack,
+}
+
+impl fmt::Display for PacketStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Recv => write!(f, "recv"),
+            Self::Ack => write!(f, "ack"),
+        }
+    }
+}
+
+/// The times of the blocks in which the stages of a packet's lifecycle were committed,
+/// if known, and whether Hermes has submitted the messages relaying it.
+#[derive(Debug)]
+struct PacketLifecycle {
+    counterparty_chain_id: String,
+    sent: Option<Timestamp>,
+    received: bool,
+    received_at: Option<Timestamp>,
+    recv_submitted: bool,
+    ack_submitted: bool,
+}
+
+impl PacketLifecycle {
+    fn submitted(&mut self, stage: PacketStage) -> &mut bool {
+        match stage {
+            PacketStage::Recv => &mut self.recv_submitted,
+            PacketStage::Ack => &mut self.ack_submitted,
+        }
+    }
+}
+
+/// The time elapsed between two blocks, in milliseconds,
+/// if both block times are known and in order.
+fn latency_millis(from: Option<Timestamp>, to: Option<Timestamp>) -> Option<u64> {
+    let latency = to?.duration_since(&from?)?;
+    Some(latency.as_millis() as u64)
+}
+
 pub struct TelemetryState {
     exporter: PrometheusExporter,
 
@@ -53,6 +108,12 @@ pub struct TelemetryState {
     /// Number of client misbehaviours per client
     ibc_client_misbehaviours: Counter<u64>,
 
+    /// Number of client upgrades performed per client
+    ibc_client_upgrades: Counter<u64>,
+
+    /// Number of failed attempts at upgrading a client, per client
+    ibc_client_upgrade_failures: Counter<u64>,
+
     /// Number of receive packets relayed, per channel
     receive_packets: Counter<u64>,
 
@@ -62,6 +123,18 @@ pub struct TelemetryState {
     /// Number of timeout packets relayed, per channel
     timeout_packets: Counter<u64>,
 
+    /// Amount of ICS-20 tokens successfully received, per channel and base denom
+    ics20_received_amount: Counter<u64>,
+
+    /// Amount of ICS-20 tokens whose transfer was acknowledged, per channel and base denom
+    ics20_acknowledged_amount: Counter<u64>,
+
+    /// Amount of ICS-20 tokens whose transfer timed out, per channel and base denom
+    ics20_timeout_amount: Counter<u64>,
+
+    /// The denoms labelling the `ics20_*_amount` metrics, up to `ICS20_DENOMS_CAPACITY`
+    ics20_denoms: DashMap<String, ()>,
+
     /// Number of queries emitted by the relayer, per chain and query type
     queries: Counter<u64>,
 
@@ -81,6 +154,14 @@ pub struct TelemetryState {
     /// The amount given is of unit: 10^6 * `denom`
     wallet_balance: ValueRecorder<u64>,
 
+    /// Number of refills of the wallets that Hermes is using from a treasury account,
+    /// per wallet, denom and chain.
+    wallet_refill_count: Counter<u64>,
+
+    /// Total amount sent from a treasury account to refill the wallets that Hermes is using,
+    /// per wallet, denom and chain. The amount given is of unit: `denom`
+    wallet_refill_amount: Counter<u64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
@@ -115,6 +196,43 @@ pub struct TelemetryState {
 
     /// History of SendPacket sequence numbers received and not yet Acknowledged.
     sequences_histories: DashMap<PathIdentifier, DashMap<u64, u64>>,
+
+    /// Records the sequence number on which an ordered channel is head-of-line
+    /// blocked, i.e. the next sequence expected by the receiving chain when that
+    /// packet cannot be relayed. The value is 0 if the channel is not blocked.
+    ordered_channel_blocked_sequence: ValueRecorder<u64>,
+
+    /// Records the time left until each client expires, unless it is updated
+    /// in the meantime, per client, chain and counterparty chain. Seconds.
+    client_expiry: ValueRecorder<u64>,
+
+    /// The lifecycle of the packets for which Hermes observed a SendPacket,
+    /// keyed by the source chain, channel and port of the packet, and its sequence number.
+    /// Used for computing the `packet_latency_*` and `packets_relayed` metrics.
+    packet_lifecycles: moka::sync::Cache<(PathIdentifier, u64), Arc<Mutex<PacketLifecycle>>>,
+
+    /// Indicates the latency between the block committing the SendPacket event
+    /// of a packet and the block committing the corresponding WriteAcknowledgement
+    /// on the destination chain, per source path. Milliseconds.
+    packet_latency_recv: ValueRecorder<u64>,
+
+    /// Indicates the latency between the block committing the WriteAcknowledgement
+    /// event of a packet and the block committing the corresponding
+    /// AcknowledgePacket on the source chain, per source path. Milliseconds.
+    packet_latency_ack: ValueRecorder<u64>,
+
+    /// Indicates the latency between the block committing the SendPacket event
+    /// of a packet and the block committing the corresponding AcknowledgePacket
+    /// on the source chain, per source path. Milliseconds.
+    packet_latency_total: ValueRecorder<u64>,
+
+    /// Counts the packets received or acknowledged, per source path, stage and
+    /// relayer, which is either `hermes` or `other`.
+    packets_relayed: Counter<u64>,
+
+    /// Health of the supervisor and of the configured chains,
+    /// served over the `/healthz` and `/readyz` endpoints.
+    health: HealthState,
 }
 
 impl TelemetryState {
@@ -123,6 +241,48 @@ impl TelemetryState {
         self.exporter.registry().gather()
     }
 
+    /// The health of the supervisor and of the configured chains
+    pub fn health(&self) -> &HealthState {
+        &self.health
+    }
+
+    /// Record that an iteration of the supervisor's event loop
+    /// processing the events of the given chain completed.
+    pub fn supervisor_tick(&self, chain_id: &ChainId) {
+        self.health.supervisor_tick(chain_id);
+    }
+
+    /// Record that the supervisor is configured to relay on the given chain.
+    pub fn chain_configured(&self, chain_id: &ChainId) {
+        self.health.chain_configured(chain_id);
+    }
+
+    /// Record that the given chain passed its health check.
+    pub fn chain_healthy(&self, chain_id: &ChainId) {
+        self.health
+            .health_check(chain_id, HealthCheckStatus::Healthy);
+    }
+
+    /// Record that the given chain failed its health check.
+    pub fn chain_unhealthy(&self, chain_id: &ChainId, reason: impl ToString) {
+        self.health.health_check(
+            chain_id,
+            HealthCheckStatus::Unhealthy {
+                reason: reason.to_string(),
+            },
+        );
+    }
+
+    /// Record whether the supervisor is subscribed to the events of the given chain.
+    pub fn chain_subscribed(&self, chain_id: &ChainId, subscribed: bool) {
+        self.health.subscribed(chain_id, subscribed);
+    }
+
+    /// Record whether the WebSocket connection of the event monitor of the given chain is up.
+    pub fn ws_connected(&self, chain_id: &ChainId, connected: bool) {
+        self.health.websocket_connected(chain_id, connected);
+    }
+
     /// Update the number of workers per object
     pub fn worker(&self, worker_type: WorkerType, count: i64) {
         let labels = &[KeyValue::new("type", worker_type.to_string())];
@@ -149,6 +309,26 @@ impl TelemetryState {
         self.ibc_client_misbehaviours.add(count, labels);
     }
 
+    /// Number of client upgrades performed per client
+    pub fn ibc_client_upgrades(&self, chain: &ChainId, client: &ClientId, count: u64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("client", client.to_string()),
+        ];
+
+        self.ibc_client_upgrades.add(count, labels);
+    }
+
+    /// Number of failed attempts at upgrading a client, per client
+    pub fn ibc_client_upgrade_failures(&self, chain: &ChainId, client: &ClientId, count: u64) {
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("client", client.to_string()),
+        ];
+
+        self.ibc_client_upgrade_failures.add(count, labels);
+    }
+
     /// Number of receive packets relayed, per channel
     pub fn ibc_receive_packets(
         &self,
@@ -200,6 +380,75 @@ impl TelemetryState {
         self.timeout_packets.add(count, labels);
     }
 
+    /// Amount of ICS-20 tokens successfully received, per channel and base denom
+    pub fn ics20_received_amount(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        denom: &str,
+        amount: u64,
+    ) {
+        let labels = self.ics20_labels(src_chain, src_channel, src_port, denom);
+        self.ics20_received_amount.add(amount, &labels);
+    }
+
+    /// Amount of ICS-20 tokens whose transfer was acknowledged, per channel and base denom
+    pub fn ics20_acknowledged_amount(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        denom: &str,
+        amount: u64,
+    ) {
+        let labels = self.ics20_labels(src_chain, src_channel, src_port, denom);
+        self.ics20_acknowledged_amount.add(amount, &labels);
+    }
+
+    /// Amount of ICS-20 tokens whose transfer timed out, per channel and base denom
+    pub fn ics20_timeout_amount(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        denom: &str,
+        amount: u64,
+    ) {
+        let labels = self.ics20_labels(src_chain, src_channel, src_port, denom);
+        self.ics20_timeout_amount.add(amount, &labels);
+    }
+
+    fn ics20_labels(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        denom: &str,
+    ) -> [KeyValue; 4] {
+        [
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+            KeyValue::new("denom", self.ics20_denom_label(denom)),
+        ]
+    }
+
+    /// The label of the given denom, which is the denom itself unless
+    /// `ICS20_DENOMS_CAPACITY` other denoms are labelled already.
+    fn ics20_denom_label(&self, denom: &str) -> String {
+        if self.ics20_denoms.contains_key(denom) {
+            return denom.to_string();
+        }
+
+        if self.ics20_denoms.len() < ICS20_DENOMS_CAPACITY {
+            self.ics20_denoms.insert(denom.to_string(), ());
+            return denom.to_string();
+        }
+
+        ICS20_OTHER_DENOM.to_string()
+    }
+
     /// Number of queries emitted by the relayer, per chain and query type
     pub fn query(&self, chain_id: &ChainId, query_type: &'static str) {
         let labels = &[
@@ -253,6 +502,19 @@ impl TelemetryState {
         self.wallet_balance.record(amount, labels);
     }
 
+    /// Record a refill of the wallet that Hermes is using from a treasury account,
+    /// per account, denom and chain. The amount given is of unit: `denom`
+    pub fn wallet_refill(&self, chain_id: &ChainId, account: &str, amount: u64, denom: &str) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.wallet_refill_count.add(1, labels);
+        self.wallet_refill_amount.add(amount, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -371,6 +633,228 @@ impl TelemetryState {
         self.cleared_count.add(1, labels);
     }
 
+    /// Record the sequence number on which an ordered channel is blocked, or 0 if it is not.
+    pub fn ordered_channel_blocked(
+        &self,
+        sequence: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.ordered_channel_blocked_sequence
+            .record(sequence, labels);
+    }
+
+    /// Record the time left until a client expires, in seconds, or 0 if it has expired.
+    pub fn client_expiry(
+        &self,
+        chain_id: &ChainId,
+        client_id: &ClientId,
+        counterparty_chain_id: &ChainId,
+        seconds: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("client", client_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+        ];
+
+        self.client_expiry.record(seconds, labels);
+    }
+
+    fn packet_lifecycle(
+        &self,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> Option<Arc<Mutex<PacketLifecycle>>> {
+        let uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        self.packet_lifecycles.get(&(uid, seq_nr))
+    }
+
+    /// Start tracking the lifecycle of a packet whose SendPacket event was committed
+    /// in a block with the given time, if known. The identifiers are those of the
+    /// source chain of the packet.
+    pub fn packet_sent(
+        &self,
+        time: Option<Timestamp>,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+    ) {
+        let uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        let lifecycle = PacketLifecycle {
+            counterparty_chain_id: counterparty_chain_id.to_string(),
+            sent: time,
+            received: false,
+            received_at: None,
+            recv_submitted: false,
+            ack_submitted: false,
+        };
+
+        self.packet_lifecycles
+            .insert((uid, seq_nr), Arc::new(Mutex::new(lifecycle)));
+    }
+
+    /// Mark a tracked packet as being relayed by Hermes for the given stage,
+    /// right before the corresponding message is submitted.
+    /// The identifiers are those of the source chain of the packet.
+    pub fn packet_submitted(
+        &self,
+        stage: PacketStage,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) {
+        if let Some(lifecycle) = self.packet_lifecycle(seq_nr, chain_id, channel_id, port_id) {
+            if let Ok(mut lifecycle) = lifecycle.lock() {
+                *lifecycle.submitted(stage) = true;
+            }
+        }
+    }
+
+    /// Unmark a tracked packet as being relayed by Hermes for the given stage,
+    /// after the submission of the corresponding message failed.
+    /// The identifiers are those of the source chain of the packet.
+    pub fn packet_submission_failed(
+        &self,
+        stage: PacketStage,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) {
+        if let Some(lifecycle) = self.packet_lifecycle(seq_nr, chain_id, channel_id, port_id) {
+            if let Ok(mut lifecycle) = lifecycle.lock() {
+                *lifecycle.submitted(stage) = false;
+            }
+        }
+    }
+
+    /// Record the latency until the WriteAcknowledgement event of a tracked packet
+    /// was committed in a block with the given time, if known, and which relayer
+    /// received the packet. The identifiers are those of the source chain of the packet.
+    pub fn packet_received(
+        &self,
+        time: Option<Timestamp>,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) {
+        let lifecycle = match self.packet_lifecycle(seq_nr, chain_id, channel_id, port_id) {
+            Some(lifecycle) => lifecycle,
+            None => return,
+        };
+
+        let mut lifecycle = match lifecycle.lock() {
+            Ok(lifecycle) => lifecycle,
+            Err(_) => return,
+        };
+
+        // The WriteAcknowledgement may be observed more than once,
+        // eg. when the packet worker is restarted.
+        if lifecycle.received {
+            return;
+        }
+
+        lifecycle.received = true;
+        lifecycle.received_at = time;
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", lifecycle.counterparty_chain_id.clone()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        if let Some(latency) = latency_millis(lifecycle.sent, time) {
+            self.packet_latency_recv.record(latency, labels);
+        }
+
+        self.packet_relayed(labels, PacketStage::Recv, lifecycle.recv_submitted);
+    }
+
+    /// Record the latencies until the AcknowledgePacket event of a tracked packet
+    /// was committed in a block with the given time, if known, and which relayer
+    /// acknowledged the packet. This ends the tracking of the packet.
+    /// The identifiers are those of the source chain of the packet.
+    pub fn packet_acknowledged(
+        &self,
+        time: Option<Timestamp>,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) {
+        let uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        let lifecycle = match self.packet_lifecycles.get(&(uid.clone(), seq_nr)) {
+            Some(lifecycle) => lifecycle,
+            None => return,
+        };
+
+        self.packet_lifecycles.invalidate(&(uid, seq_nr));
+
+        let lifecycle = match lifecycle.lock() {
+            Ok(lifecycle) => lifecycle,
+            Err(_) => return,
+        };
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", lifecycle.counterparty_chain_id.clone()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        if let Some(latency) = latency_millis(lifecycle.received_at, time) {
+            self.packet_latency_ack.record(latency, labels);
+        }
+
+        if let Some(latency) = latency_millis(lifecycle.sent, time) {
+            self.packet_latency_total.record(latency, labels);
+        }
+
+        self.packet_relayed(labels, PacketStage::Ack, lifecycle.ack_submitted);
+    }
+
+    fn packet_relayed(&self, path_labels: &[KeyValue; 4], stage: PacketStage, by_hermes: bool) {
+        let relayer = if by_hermes { "hermes" } else { "other" };
+
+        let mut labels = path_labels.to_vec();
+        labels.push(KeyValue::new("stage", stage.to_string()));
+        labels.push(KeyValue::new("relayer", relayer));
+
+        self.packets_relayed.add(1, &labels);
+    }
+
     pub fn record_send_history(
         &self,
         seq_nr: u64,
@@ -491,12 +975,17 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "wallet_balance" => Some(Arc::new(last_value())),
             "oldest_sequence" => Some(Arc::new(last_value())),
             "oldest_timestamp" => Some(Arc::new(last_value())),
+            "ordered_channel_blocked_sequence" => Some(Arc::new(last_value())),
+            "client_expiry" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.10.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
             // For the moment, disable histogram buckets since no values make sense for all use-cases.
             "tx_latency_submitted" => Some(Arc::new(histogram(descriptor, &[]))),
             "tx_latency_confirmed" => Some(Arc::new(histogram(descriptor, &[]))),
+            "packet_latency_recv" => Some(Arc::new(histogram(descriptor, &[]))),
+            "packet_latency_ack" => Some(Arc::new(histogram(descriptor, &[]))),
+            "packet_latency_total" => Some(Arc::new(histogram(descriptor, &[]))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -528,6 +1017,16 @@ impl Default for TelemetryState {
                 .with_description("Number of misbehaviours detected per client")
                 .init(),
 
+            ibc_client_upgrades: meter
+                .u64_counter("ibc_client_upgrades")
+                .with_description("Number of client upgrades performed per client")
+                .init(),
+
+            ibc_client_upgrade_failures: meter
+                .u64_counter("ibc_client_upgrade_failures")
+                .with_description("Number of failed attempts at upgrading a client, per client")
+                .init(),
+
             receive_packets: meter
                 .u64_counter("ibc_receive_packets")
                 .with_description("Number of receive packets relayed per channel")
@@ -543,6 +1042,21 @@ impl Default for TelemetryState {
                 .with_description("Number of timeout packets relayed per channel")
                 .init(),
 
+            ics20_received_amount: meter
+                .u64_counter("ics20_received_amount")
+                .with_description("Amount of ICS-20 tokens successfully received, per channel and base denom")
+                .init(),
+
+            ics20_acknowledged_amount: meter
+                .u64_counter("ics20_acknowledged_amount")
+                .with_description("Amount of ICS-20 tokens whose transfer was acknowledged, per channel and base denom")
+                .init(),
+
+            ics20_timeout_amount: meter
+                .u64_counter("ics20_timeout_amount")
+                .with_description("Amount of ICS-20 tokens whose transfer timed out, per channel and base denom")
+                .init(),
+
             queries: meter
                 .u64_counter("queries")
                 .with_description(
@@ -575,6 +1089,16 @@ impl Default for TelemetryState {
                 .with_description("The balance in each wallet that Hermes is using, per wallet, denom and chain. The amount is of unit: 10^6 * `denom`")
                 .init(),
 
+            wallet_refill_count: meter
+                .u64_counter("wallet_refill_count")
+                .with_description("Number of refills of each wallet that Hermes is using from a treasury account, per wallet, denom and chain")
+                .init(),
+
+            wallet_refill_amount: meter
+                .u64_counter("wallet_refill_amount")
+                .with_description("Total amount sent from a treasury account to each wallet that Hermes is using, per wallet, denom and chain. The amount is of unit: `denom`")
+                .init(),
+
             send_packet_count: meter
                 .u64_counter("send_packet_count")
                 .with_description("Number of SendPacket relayed")
@@ -613,6 +1137,8 @@ impl Default for TelemetryState {
 
             sequences_histories: DashMap::new(),
 
+            ics20_denoms: DashMap::new(),
+
             oldest_sequence: meter
                 .u64_value_recorder("oldest_sequence")
                 .with_description("The sequence number of the oldest pending SendPacket. If this value is 0, it means there are no pending SendPacket")
@@ -623,6 +1149,150 @@ impl Default for TelemetryState {
                 .with_unit(Unit::new("seconds"))
                 .with_description("The timestamp of the oldest sequence number in seconds")
                 .init(),
+
+            ordered_channel_blocked_sequence: meter
+                .u64_value_recorder("ordered_channel_blocked_sequence")
+                .with_description("The sequence number on which an ordered channel is blocked. If this value is 0, it means the channel is not blocked")
+                .init(),
+
+            client_expiry: meter
+                .u64_value_recorder("client_expiry")
+                .with_unit(Unit::new("seconds"))
+                .with_description("The time left until the client expires, unless it is updated in the meantime. If this value is 0, it means the client has expired")
+                .init(),
+
+            packet_lifecycles: moka::sync::Cache::builder()
+                .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
+                .build(),
+
+            packet_latency_recv: meter
+                .u64_value_recorder("packet_latency_recv")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency between the block committing the SendPacket event of a packet \
+                    and the block committing the corresponding WriteAcknowledgement on the destination chain. Milliseconds.")
+                .init(),
+
+            packet_latency_ack: meter
+                .u64_value_recorder("packet_latency_ack")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency between the block committing the WriteAcknowledgement event of a packet \
+                    and the block committing the corresponding AcknowledgePacket on the source chain. Milliseconds.")
+                .init(),
+
+            packet_latency_total: meter
+                .u64_value_recorder("packet_latency_total")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency between the block committing the SendPacket event of a packet \
+                    and the block committing the corresponding AcknowledgePacket on the source chain. Milliseconds.")
+                .init(),
+
+            packets_relayed: meter
+                .u64_counter("packets_relayed")
+                .with_description("Number of packets received or acknowledged, per stage and relayer, which is either `hermes` or `other`")
+                .init(),
+
+            health: HealthState::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::timestamp::Timestamp;
+
+    use super::{
+        latency_millis, PacketStage, TelemetryState, ICS20_DENOMS_CAPACITY, ICS20_OTHER_DENOM,
+    };
+
+    fn block_time(millis: u64) -> Timestamp {
+        (Timestamp::from_nanoseconds(1_000_000_000).unwrap() + Duration::from_millis(millis))
+            .unwrap()
+    }
+
+    #[test]
+    fn latency_between_block_times() {
+        assert_eq!(
+            latency_millis(Some(block_time(0)), Some(block_time(1500))),
+            Some(1500)
+        );
+        assert_eq!(
+            latency_millis(Some(block_time(1500)), Some(block_time(0))),
+            None
+        );
+        assert_eq!(latency_millis(None, Some(block_time(1500))), None);
+        assert_eq!(latency_millis(Some(block_time(0)), None), None);
+    }
+
+    #[test]
+    fn packet_lifecycle() {
+        let state = TelemetryState::default();
+
+        let chain_id = ChainId::new("chain-a".to_string(), 0);
+        let counterparty_chain_id = ChainId::new("chain-b".to_string(), 0);
+        let channel_id = ChannelId::new(0);
+        let port_id = PortId::transfer();
+
+        state.packet_sent(
+            Some(block_time(0)),
+            1,
+            &chain_id,
+            &channel_id,
+            &port_id,
+            &counterparty_chain_id,
+        );
+
+        state.packet_submitted(PacketStage::Recv, 1, &chain_id, &channel_id, &port_id);
+        state.packet_submitted(PacketStage::Ack, 1, &chain_id, &channel_id, &port_id);
+        state.packet_submission_failed(PacketStage::Ack, 1, &chain_id, &channel_id, &port_id);
+
+        let lifecycle = state
+            .packet_lifecycle(1, &chain_id, &channel_id, &port_id)
+            .unwrap();
+
+        {
+            let lifecycle = lifecycle.lock().unwrap();
+            assert!(lifecycle.recv_submitted);
+            assert!(!lifecycle.ack_submitted);
+            assert!(!lifecycle.received);
+        }
+
+        // Only the first WriteAcknowledgement of a packet is accounted for
+        state.packet_received(Some(block_time(1500)), 1, &chain_id, &channel_id, &port_id);
+        state.packet_received(Some(block_time(3000)), 1, &chain_id, &channel_id, &port_id);
+
+        {
+            let lifecycle = lifecycle.lock().unwrap();
+            assert!(lifecycle.received);
+            assert_eq!(lifecycle.received_at, Some(block_time(1500)));
         }
+
+        // Packets whose SendPacket event was not observed are not tracked
+        state.packet_received(Some(block_time(1500)), 2, &chain_id, &channel_id, &port_id);
+        assert!(state
+            .packet_lifecycle(2, &chain_id, &channel_id, &port_id)
+            .is_none());
+
+        state.packet_acknowledged(Some(block_time(4500)), 1, &chain_id, &channel_id, &port_id);
+        assert!(state
+            .packet_lifecycle(1, &chain_id, &channel_id, &port_id)
+            .is_none());
+    }
+
+    #[test]
+    fn ics20_denom_labels_are_capped() {
+        let state = TelemetryState::default();
+
+        for i in 0..ICS20_DENOMS_CAPACITY {
+            let denom = format!("denom{}", i);
+            assert_eq!(state.ics20_denom_label(&denom), denom);
+        }
+
+        assert_eq!(state.ics20_denom_label("uatom"), ICS20_OTHER_DENOM);
+
+        // The denoms labelled already keep their label
+        assert_eq!(state.ics20_denom_label("denom0"), "denom0");
     }
 }
@@ -0,0 +1,74 @@
+/*!
+    Benchmarks the throughput and latency of packet relaying between two
+    local test chains, for different batching parameters of the relayer.
+
+    The benchmark sets up two chains with a connected transfer channel,
+    sends a burst or a steady stream of ICS-20 transfers from chain A to
+    chain B, and waits for the relayer to deliver all of them. This is
+    repeated for each set of `max_msg_num` and `max_tx_size`, and a report
+    comparing the rounds is printed at the end:
+
+    ```bash
+    $ BENCH_PROFILE=burst BENCH_TRANSFERS=1000 BENCH_BATCH_PARAMS=1,10,50,100 \
+        cargo run --release --bin relay_benchmark
+    ...
+    | max_msg_num | max_tx_size | transfers | send (s) | relay (s) | transfers/s | recv submit | ...
+    |------------:|------------:|----------:|---------:|----------:|------------:|------------:| ...
+    ```
+
+    The latencies are taken from the `tx_latency_submitted` and
+    `tx_latency_confirmed` telemetry metrics recorded by the relayer.
+    See [`BenchmarkConfig::from_env`] for the environment variables
+    that configure the load and the batching parameters.
+*/
+
+use ibc_test_framework::bench::{run_benchmark, BenchmarkConfig};
+use ibc_test_framework::prelude::*;
+
+struct RelayBenchmark {
+    config: BenchmarkConfig,
+}
+
+impl TestOverrides for RelayBenchmark {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.clients.misbehaviour = false;
+    }
+
+    // The benchmark spawns its own supervisor for each round,
+    // with the batching parameters of the round.
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for RelayBenchmark {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let report = run_benchmark(&relayer, &chains, &channel, &self.config)?;
+
+        println!("{}", report);
+
+        Ok(())
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let config = BenchmarkConfig::from_env()?;
+
+    println!(
+        "Running relaying benchmark with a {} for {} sets of batching parameters",
+        config.profile,
+        config.batch_params.len()
+    );
+
+    run_binary_channel_test(&RelayBenchmark { config })
+}
@@ -149,6 +149,7 @@ impl TestOverrides for IbcTransferMBT {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: ConfigConnections { enabled: true },
             channels: ConfigChannels { enabled: true },
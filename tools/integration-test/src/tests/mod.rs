@@ -17,6 +17,7 @@ pub mod python;
 mod query_packet;
 pub mod supervisor;
 pub mod ternary_transfer;
+pub mod topology;
 pub mod transfer;
 
 #[cfg(any(doc, feature = "ordered"))]
@@ -0,0 +1,262 @@
+use ibc_test_framework::ibc::denom::{derive_transferred_denom, TaggedDenom};
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_line_topology_transfer_and_unwind() -> Result<(), Error> {
+    run_topology_test(&LineTopologyTransferTest)
+}
+
+#[test]
+fn test_star_topology_transfer() -> Result<(), Error> {
+    run_topology_test(&StarTopologyTransferTest)
+}
+
+pub struct LineTopologyTransferTest;
+
+pub struct StarTopologyTransferTest;
+
+impl TestOverrides for LineTopologyTransferTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.clients.misbehaviour = false;
+    }
+}
+
+impl TestOverrides for StarTopologyTransferTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.clients.misbehaviour = false;
+    }
+}
+
+impl TopologyOverride<4> for LineTopologyTransferTest {}
+
+impl TopologyOverride<3> for StarTopologyTransferTest {
+    fn topology(&self) -> Topology {
+        Topology::star(3).with_channels(vec![ChannelSpec::transfer(), ChannelSpec::transfer()])
+    }
+}
+
+impl TopologyTest<4> for LineTopologyTransferTest {
+    fn run<Handle: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedTopology<Handle, 4>,
+    ) -> Result<(), Error> {
+        let node_0 = chains.full_node_at::<0>()?;
+        let node_1 = chains.full_node_at::<1>()?;
+        let node_2 = chains.full_node_at::<2>()?;
+        let node_3 = chains.full_node_at::<3>()?;
+
+        let denom_0 = node_0.denom().cloned();
+
+        // Chain 3 will receive ibc/port-3/channel-3/port-2/channel-2/port-1/channel-1/denom
+
+        let denom_1 = transfer_hop(
+            &node_0,
+            &node_1,
+            &chains.channel_at::<0, 1>(0)?,
+            &denom_0,
+            5000,
+        )?;
+        let denom_2 = transfer_hop(
+            &node_1,
+            &node_2,
+            &chains.channel_at::<1, 2>(0)?,
+            &denom_1,
+            4000,
+        )?;
+        let denom_3 = transfer_hop(
+            &node_2,
+            &node_3,
+            &chains.channel_at::<2, 3>(0)?,
+            &denom_2,
+            3000,
+        )?;
+
+        let trace = chains.denoms_along(&[0, 1, 2, 3], denom_0.value())?;
+
+        assert_eq(
+            "denom trace along the line should match the received denom",
+            &trace[3].to_string(),
+            &denom_3.to_string(),
+        )?;
+
+        // Sending the tokens back along the same channels unwinds the trace
+        // one hop at a time, until chain 0 receives its base denom again.
+
+        let back_2 = transfer_hop(
+            &node_3,
+            &node_2,
+            &chains.channel_at::<3, 2>(0)?,
+            &denom_3,
+            2000,
+        )?;
+        let back_1 = transfer_hop(
+            &node_2,
+            &node_1,
+            &chains.channel_at::<2, 1>(0)?,
+            &back_2,
+            2000,
+        )?;
+        let back_0 = transfer_hop(
+            &node_1,
+            &node_0,
+            &chains.channel_at::<1, 0>(0)?,
+            &back_1,
+            2000,
+        )?;
+
+        assert_eq(
+            "unwinding to chain 2 should result in the original denom on chain 2",
+            &back_2.to_string(),
+            &denom_2.to_string(),
+        )?;
+
+        assert_eq(
+            "unwinding to chain 1 should result in the original denom on chain 1",
+            &back_1.to_string(),
+            &denom_1.to_string(),
+        )?;
+
+        assert_eq(
+            "unwinding to chain 0 should result in the base denom",
+            &back_0.to_string(),
+            &denom_0.to_string(),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl TopologyTest<3> for StarTopologyTransferTest {
+    fn run<Handle: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedTopology<Handle, 3>,
+    ) -> Result<(), Error> {
+        let hub = chains.full_node_at::<0>()?;
+        let zone_1 = chains.full_node_at::<1>()?;
+        let zone_2 = chains.full_node_at::<2>()?;
+
+        let path = chains
+            .topology()
+            .shortest_path(1, 2)
+            .ok_or_else(|| eyre!("expected zones to be connected through the hub"))?;
+
+        assert_eq(
+            "zones should be connected through the hub",
+            &path,
+            &vec![1, 0, 2],
+        )?;
+
+        let denom_1 = zone_1.denom().cloned();
+
+        let denom_hub = transfer_hop(
+            &zone_1,
+            &hub,
+            &chains.channel_at::<1, 0>(0)?,
+            &denom_1,
+            5000,
+        )?;
+        let denom_2 = transfer_hop(
+            &hub,
+            &zone_2,
+            &chains.channel_at::<0, 2>(0)?,
+            &denom_hub,
+            3000,
+        )?;
+
+        let trace = chains.denoms_along(&path, denom_1.value())?;
+
+        assert_eq(
+            "denom trace through the hub should match the received denom",
+            &trace[2].to_string(),
+            &denom_2.to_string(),
+        )?;
+
+        // Sending the tokens back to the hub over the second channel does
+        // not unwind the trace, as the tokens were received over the first one.
+
+        let denom_hub_2 = transfer_hop(
+            &zone_2,
+            &hub,
+            &chains.channel_at::<2, 0>(1)?,
+            &denom_2,
+            1000,
+        )?;
+
+        assert_not_eq(
+            "sending back over a different channel should not unwind the denom",
+            &denom_hub_2.to_string(),
+            &denom_hub.to_string(),
+        )?;
+
+        Ok(())
+    }
+}
+
+/**
+   Transfers `amount` of `denom` from the first user on `node_a` to the
+   first user on `node_b` over the given channel, waits until the tokens
+   are received, and returns the denom of the tokens on `node_b`.
+*/
+fn transfer_hop<ChainA: ChainHandle, ChainB: ChainHandle>(
+    node_a: &MonoTagged<ChainA, FullNode>,
+    node_b: &MonoTagged<ChainB, FullNode>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    denom: &TaggedDenom<ChainA>,
+    amount: u64,
+) -> Result<TaggedDenom<ChainB>, Error> {
+    let wallet_a = node_a.wallets().user1().cloned();
+    let wallet_b = node_b.wallets().user1().cloned();
+
+    let denom_b = derive_transferred_denom(channel, &denom.as_ref())?;
+
+    let balance_a = node_a
+        .chain_driver()
+        .query_balance(&wallet_a.address(), &denom.as_ref())?;
+
+    let balance_b = node_b
+        .chain_driver()
+        .query_balance(&wallet_b.address(), &denom_b.as_ref())?;
+
+    info!(
+        "Sending IBC transfer from chain {} to chain {} with amount of {} {}",
+        node_a.chain_id(),
+        node_b.chain_id(),
+        amount,
+        denom
+    );
+
+    node_a.chain_driver().ibc_transfer_token(
+        &channel.port_a.as_ref(),
+        &channel.channel_id_a.as_ref(),
+        &wallet_a.as_ref(),
+        &wallet_b.address(),
+        &denom.as_ref(),
+        amount,
+    )?;
+
+    node_a.chain_driver().assert_eventual_wallet_amount(
+        &wallet_a.address(),
+        balance_a - amount,
+        &denom.as_ref(),
+    )?;
+
+    node_b.chain_driver().assert_eventual_wallet_amount(
+        &wallet_b.address(),
+        balance_b + amount,
+        &denom_b.as_ref(),
+    )?;
+
+    Ok(denom_b)
+}
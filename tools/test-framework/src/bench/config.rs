@@ -0,0 +1,208 @@
+/*!
+   Configuration of a relaying benchmark.
+*/
+
+use core::fmt::{self, Display};
+use core::str::FromStr;
+use core::time::Duration;
+use eyre::eyre;
+use ibc_relayer::config::types::{MaxMsgNum, MaxTxSize};
+use std::env;
+use std::path::PathBuf;
+
+use super::load::LoadProfile;
+use crate::error::{handle_generic_error, Error};
+
+/**
+   The batching parameters of the relayer for one round of a benchmark.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchParams {
+    pub max_msg_num: MaxMsgNum,
+    pub max_tx_size: MaxTxSize,
+}
+
+/**
+   Configuration of a benchmark, which can be read from the environment
+   with [`BenchmarkConfig::from_env`].
+*/
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /**
+       The load that is sent in each round.
+    */
+    pub profile: LoadProfile,
+
+    /**
+       The number of transfer messages put in each transaction sent
+       to the source chain.
+    */
+    pub msgs_per_tx: usize,
+
+    /**
+       The amount of tokens sent in each transfer.
+    */
+    pub amount: u64,
+
+    /**
+       The batching parameters to run a round with, in order.
+    */
+    pub batch_params: Vec<BatchParams>,
+
+    /**
+       How long to wait for all transfers of a round to be received
+       on the destination chain before giving up.
+    */
+    pub relay_timeout: Duration,
+
+    /**
+       Where to write the final report to, in addition to printing it.
+    */
+    pub report_path: Option<PathBuf>,
+}
+
+impl BenchmarkConfig {
+    /**
+       Read the benchmark configuration from the following environment
+       variables, falling back to the [default](BenchmarkConfig::default)
+       for the ones that are not set:
+
+       - `BENCH_PROFILE`: either `burst` or `steady`.
+       - `BENCH_TRANSFERS`: the number of transfers sent by a burst.
+       - `BENCH_RATE`: the number of transfers sent per second by a steady stream.
+       - `BENCH_DURATION`: how many seconds a steady stream lasts.
+       - `BENCH_MSGS_PER_TX`: the number of transfers per transaction.
+       - `BENCH_BATCH_PARAMS`: a comma-separated list of `max_msg_num`, each
+         optionally followed by `:` and a `max_tx_size` in bytes,
+         e.g. `1,10:1048576,30`.
+       - `BENCH_RELAY_TIMEOUT`: how many seconds to wait for the transfers of
+         each round to be relayed.
+       - `BENCH_REPORT`: a path to write the report to.
+    */
+    pub fn from_env() -> Result<Self, Error> {
+        let default = Self::default();
+
+        let profile = match env::var("BENCH_PROFILE").as_deref() {
+            Ok("steady") => LoadProfile::Steady {
+                transfers_per_second: parse_env("BENCH_RATE")?.unwrap_or(50),
+                duration: Duration::from_secs(parse_env("BENCH_DURATION")?.unwrap_or(30)),
+            },
+            Ok("burst") | Err(_) => LoadProfile::Burst {
+                transfers: parse_env("BENCH_TRANSFERS")?.unwrap_or(500),
+            },
+            Ok(other) => {
+                return Err(Error::generic(eyre!(
+                    "unknown benchmark profile `{}`, expected `burst` or `steady`",
+                    other
+                )))
+            }
+        };
+
+        let batch_params = match env::var("BENCH_BATCH_PARAMS") {
+            Ok(value) => parse_batch_params(&value)?,
+            Err(_) => default.batch_params,
+        };
+
+        let relay_timeout = parse_env("BENCH_RELAY_TIMEOUT")?
+            .map(Duration::from_secs)
+            .unwrap_or(default.relay_timeout);
+
+        Ok(Self {
+            profile,
+            msgs_per_tx: parse_env("BENCH_MSGS_PER_TX")?.unwrap_or(default.msgs_per_tx),
+            amount: default.amount,
+            batch_params,
+            relay_timeout,
+            report_path: env::var("BENCH_REPORT").ok().map(PathBuf::from),
+        })
+    }
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        let batch_params = [1, 10, 30, 100]
+            .iter()
+            .map(|max_msg_num| BatchParams {
+                max_msg_num: MaxMsgNum::new(*max_msg_num).unwrap(),
+                max_tx_size: MaxTxSize::default(),
+            })
+            .collect();
+
+        Self {
+            profile: LoadProfile::Burst { transfers: 500 },
+            msgs_per_tx: 50,
+            amount: 1,
+            batch_params,
+            relay_timeout: Duration::from_secs(300),
+            report_path: None,
+        }
+    }
+}
+
+impl Display for BatchParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "max_msg_num={}, max_tx_size={}",
+            self.max_msg_num.to_usize(),
+            self.max_tx_size.to_usize()
+        )
+    }
+}
+
+/**
+   Parse a comma-separated list of `max_msg_num[:max_tx_size]`.
+*/
+pub fn parse_batch_params(value: &str) -> Result<Vec<BatchParams>, Error> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (max_msg_num, max_tx_size) = match entry.split_once(':') {
+                Some((max_msg_num, max_tx_size)) => (max_msg_num, Some(max_tx_size)),
+                None => (entry, None),
+            };
+
+            let max_msg_num =
+                MaxMsgNum::new(parse_value(max_msg_num)?).map_err(handle_generic_error)?;
+
+            let max_tx_size = match max_tx_size {
+                Some(max_tx_size) => {
+                    MaxTxSize::new(parse_value(max_tx_size)?).map_err(handle_generic_error)?
+                }
+                None => MaxTxSize::default(),
+            };
+
+            Ok(BatchParams {
+                max_msg_num,
+                max_tx_size,
+            })
+        })
+        .collect()
+}
+
+fn parse_env<T>(name: &str) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    env::var(name)
+        .ok()
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| Error::generic(eyre!("invalid value `{}` for {}: {}", value, name, e)))
+        })
+        .transpose()
+}
+
+fn parse_value<T>(value: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| Error::generic(eyre!("invalid batch parameter `{}`: {}", value, e)))
+}
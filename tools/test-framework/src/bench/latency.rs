@@ -0,0 +1,157 @@
+/*!
+   Snapshots of the transaction latency metrics recorded by the relayer
+   through [`ibc_telemetry`].
+
+   The supervisor spawned by the test framework runs in the same process
+   as the benchmark, so the latencies it records can be read directly
+   from the global telemetry state without going through the
+   Prometheus endpoint.
+*/
+
+use ibc::core::ics24_host::identifier::ChainId;
+use prometheus::proto::MetricFamily;
+use std::collections::BTreeMap;
+
+/**
+   Name of the histogram of the latencies between receiving a batch of
+   events and submitting the corresponding transactions.
+*/
+pub const TX_LATENCY_SUBMITTED: &str = "tx_latency_submitted";
+
+/**
+   Name of the histogram of the latencies between receiving a batch of
+   events and confirming the corresponding transactions.
+*/
+pub const TX_LATENCY_CONFIRMED: &str = "tx_latency_confirmed";
+
+/**
+   The number of transactions recorded in a latency histogram,
+   together with the sum of their latencies in milliseconds.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub sum_ms: f64,
+}
+
+/**
+   The latency histograms of all chains at a point in time.
+
+   As the histograms are cumulative, the latencies of the transactions
+   relayed between two points in time are obtained by subtracting
+   the earlier snapshot with [`LatencySnapshot::since`].
+*/
+#[derive(Debug, Clone, Default)]
+pub struct LatencySnapshot {
+    submitted: BTreeMap<String, LatencyStats>,
+    confirmed: BTreeMap<String, LatencyStats>,
+}
+
+impl LatencyStats {
+    /**
+       The mean latency in milliseconds, or `None` if no transaction
+       has been recorded.
+    */
+    pub fn mean_ms(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum_ms / self.count as f64)
+        }
+    }
+
+    fn add(&mut self, other: LatencyStats) {
+        self.count += other.count;
+        self.sum_ms += other.sum_ms;
+    }
+
+    fn since(&self, earlier: &LatencyStats) -> LatencyStats {
+        LatencyStats {
+            count: self.count.saturating_sub(earlier.count),
+            sum_ms: (self.sum_ms - earlier.sum_ms).max(0.0),
+        }
+    }
+}
+
+impl LatencySnapshot {
+    /**
+       Take a snapshot of the latency histograms currently recorded
+       by the global telemetry state.
+    */
+    pub fn gather() -> Self {
+        Self::from_metric_families(&ibc_telemetry::global().gather())
+    }
+
+    pub fn from_metric_families(families: &[MetricFamily]) -> Self {
+        let mut snapshot = Self::default();
+
+        for family in families {
+            let stats = match family.get_name() {
+                TX_LATENCY_SUBMITTED => &mut snapshot.submitted,
+                TX_LATENCY_CONFIRMED => &mut snapshot.confirmed,
+                _ => continue,
+            };
+
+            for metric in family.get_metric() {
+                let chain = metric
+                    .get_label()
+                    .iter()
+                    .find(|label| label.get_name() == "chain")
+                    .map(|label| label.get_value().to_string())
+                    .unwrap_or_default();
+
+                let histogram = metric.get_histogram();
+
+                stats.entry(chain).or_default().add(LatencyStats {
+                    count: histogram.get_sample_count(),
+                    sum_ms: histogram.get_sample_sum(),
+                });
+            }
+        }
+
+        snapshot
+    }
+
+    /**
+       The latencies recorded after the `earlier` snapshot was taken.
+    */
+    pub fn since(&self, earlier: &LatencySnapshot) -> LatencySnapshot {
+        fn diff(
+            later: &BTreeMap<String, LatencyStats>,
+            earlier: &BTreeMap<String, LatencyStats>,
+        ) -> BTreeMap<String, LatencyStats> {
+            later
+                .iter()
+                .map(|(chain, stats)| {
+                    let earlier_stats = earlier.get(chain).copied().unwrap_or_default();
+                    (chain.clone(), stats.since(&earlier_stats))
+                })
+                .collect()
+        }
+
+        LatencySnapshot {
+            submitted: diff(&self.submitted, &earlier.submitted),
+            confirmed: diff(&self.confirmed, &earlier.confirmed),
+        }
+    }
+
+    /**
+       The submit latencies of the transactions sent to the given chain.
+    */
+    pub fn submitted(&self, chain_id: &ChainId) -> LatencyStats {
+        self.submitted
+            .get(chain_id.as_str())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /**
+       The confirm latencies of the transactions sent to the given chain.
+    */
+    pub fn confirmed(&self, chain_id: &ChainId) -> LatencyStats {
+        self.confirmed
+            .get(chain_id.as_str())
+            .copied()
+            .unwrap_or_default()
+    }
+}
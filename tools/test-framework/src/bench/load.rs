@@ -0,0 +1,150 @@
+/*!
+   Generation of ICS-20 transfer load on a source chain.
+*/
+
+use core::fmt::{self, Display};
+use core::time::Duration;
+use ibc_relayer::chain::handle::ChainHandle;
+use std::thread::sleep;
+use std::time::Instant;
+use tracing::{debug, info};
+
+use crate::chain::driver::ChainDriver;
+use crate::chain::tagged::TaggedChainDriverExt;
+use crate::error::Error;
+use crate::ibc::denom::Denom;
+use crate::relayer::transfer::build_transfer_message;
+use crate::types::binary::channel::ConnectedChannel;
+use crate::types::tagged::*;
+use crate::types::wallet::{Wallet, WalletAddress};
+
+/**
+   The shape of the transfer load sent in each round of a benchmark.
+*/
+#[derive(Debug, Clone, Copy)]
+pub enum LoadProfile {
+    /**
+       Send the given number of transfers as fast as the source chain
+       accepts them.
+    */
+    Burst { transfers: u64 },
+
+    /**
+       Send the given number of transfers every second, for the
+       given duration.
+    */
+    Steady {
+        transfers_per_second: u64,
+        duration: Duration,
+    },
+}
+
+impl LoadProfile {
+    /**
+       The total number of transfers sent with this profile.
+    */
+    pub fn total_transfers(&self) -> u64 {
+        match self {
+            Self::Burst { transfers } => *transfers,
+            Self::Steady {
+                transfers_per_second,
+                duration,
+            } => transfers_per_second * duration.as_secs(),
+        }
+    }
+}
+
+impl Display for LoadProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Burst { transfers } => write!(f, "burst of {} transfers", transfers),
+            Self::Steady {
+                transfers_per_second,
+                duration,
+            } => write!(
+                f,
+                "steady stream of {} transfers/s for {}s",
+                transfers_per_second,
+                duration.as_secs()
+            ),
+        }
+    }
+}
+
+/**
+   Send the transfers of the given [`LoadProfile`] from `sender` on
+   `ChainA` to `recipient` on `ChainB` over `channel`, with at most
+   `msgs_per_tx` transfers in each transaction.
+
+   Each transaction is confirmed before the next one is sent, so a
+   steady stream falls behind its target rate if the source chain
+   cannot keep up with it. Returns the number of transfers sent.
+*/
+pub fn send_load<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chain_driver: &MonoTagged<ChainA, &ChainDriver>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    sender: &MonoTagged<ChainA, &Wallet>,
+    recipient: &MonoTagged<ChainB, &WalletAddress>,
+    denom: &MonoTagged<ChainA, &Denom>,
+    amount: u64,
+    msgs_per_tx: usize,
+    profile: &LoadProfile,
+) -> Result<u64, Error> {
+    let send_transfers = |count: u64| -> Result<(), Error> {
+        let mut remaining = count;
+
+        while remaining > 0 {
+            let batch_size = remaining.min(msgs_per_tx.max(1) as u64);
+
+            let messages = (0..batch_size)
+                .map(|_| {
+                    build_transfer_message(
+                        &channel.port_a.as_ref(),
+                        &channel.channel_id_a.as_ref(),
+                        sender,
+                        recipient,
+                        denom,
+                        amount,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            chain_driver.send_tx(sender, messages)?;
+
+            debug!("sent transaction with {} transfers", batch_size);
+
+            remaining -= batch_size;
+        }
+
+        Ok(())
+    };
+
+    match profile {
+        LoadProfile::Burst { transfers } => {
+            send_transfers(*transfers)?;
+        }
+        LoadProfile::Steady {
+            transfers_per_second,
+            duration,
+        } => {
+            for second in 0..duration.as_secs() {
+                let tick = Instant::now();
+
+                send_transfers(*transfers_per_second)?;
+
+                let elapsed = tick.elapsed();
+
+                if elapsed < Duration::from_secs(1) {
+                    sleep(Duration::from_secs(1) - elapsed);
+                } else {
+                    info!(
+                        "sending {} transfers took {:?} at second {}, falling behind the target rate",
+                        transfers_per_second, elapsed, second
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(profile.total_transfers())
+}
@@ -0,0 +1,26 @@
+/*!
+   Load generation and benchmarking of end-to-end packet relaying.
+
+   A benchmark sends a stream of ICS-20 transfers over a connected channel,
+   following a [`LoadProfile`], and measures how long it takes for the
+   relayer to deliver them to the counterparty chain. The same load is
+   replayed once for each set of [`BatchParams`], with the relayer
+   restarted with the corresponding `max_msg_num` and `max_tx_size`
+   in between, so that the effect of batching on the throughput and on the
+   `tx_latency_submitted` and `tx_latency_confirmed` telemetry metrics
+   can be compared in a single [`BenchmarkReport`](report::BenchmarkReport).
+
+   The executable `relay_benchmark` in the integration test crate runs
+   a benchmark against two local test chains.
+*/
+
+pub mod config;
+pub mod latency;
+pub mod load;
+pub mod report;
+pub mod runner;
+
+pub use config::{BatchParams, BenchmarkConfig};
+pub use load::LoadProfile;
+pub use report::{BenchmarkReport, RoundResult};
+pub use runner::run_benchmark;
@@ -0,0 +1,137 @@
+/*!
+   Results of a relaying benchmark.
+*/
+
+use core::fmt::{self, Display};
+use core::time::Duration;
+
+use super::config::BatchParams;
+use super::latency::LatencyStats;
+use super::load::LoadProfile;
+
+/**
+   The result of sending the load once, with the relayer running with
+   the given batching parameters.
+*/
+#[derive(Debug, Clone)]
+pub struct RoundResult {
+    pub params: BatchParams,
+
+    /**
+       The number of transfers sent and relayed.
+    */
+    pub transfers: u64,
+
+    /**
+       How long it took to send all transfers to the source chain.
+    */
+    pub send_duration: Duration,
+
+    /**
+       How long it took from sending the first transfer until all
+       transfers were received on the destination chain.
+    */
+    pub relay_duration: Duration,
+
+    /**
+       Latencies of the transactions submitted to the destination chain,
+       i.e. the `MsgRecvPacket`s.
+    */
+    pub recv_submitted: LatencyStats,
+    pub recv_confirmed: LatencyStats,
+
+    /**
+       Latencies of the transactions submitted to the source chain,
+       i.e. the `MsgAcknowledgement`s relayed so far.
+    */
+    pub ack_submitted: LatencyStats,
+    pub ack_confirmed: LatencyStats,
+}
+
+/**
+   The results of all rounds of a benchmark, which is displayed as a
+   Markdown table comparing the batching parameters.
+*/
+#[derive(Debug, Clone)]
+pub struct BenchmarkReport {
+    pub profile: LoadProfile,
+    pub msgs_per_tx: usize,
+    pub rounds: Vec<RoundResult>,
+}
+
+impl RoundResult {
+    /**
+       The number of transfers relayed per second.
+    */
+    pub fn throughput(&self) -> f64 {
+        let secs = self.relay_duration.as_secs_f64();
+
+        if secs > 0.0 {
+            self.transfers as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+impl BenchmarkReport {
+    pub fn new(profile: LoadProfile, msgs_per_tx: usize) -> Self {
+        Self {
+            profile,
+            msgs_per_tx,
+            rounds: Vec::new(),
+        }
+    }
+}
+
+fn format_mean(stats: &LatencyStats) -> String {
+    match stats.mean_ms() {
+        Some(mean) => format!("{:.0} ({})", mean, stats.count),
+        None => "-".to_string(),
+    }
+}
+
+impl Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Relaying benchmark: {}, {} transfers per transaction",
+            self.profile, self.msgs_per_tx
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Latencies are the mean in milliseconds, followed by the number of transactions."
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "| max_msg_num | max_tx_size | transfers | send (s) | relay (s) | transfers/s \
+             | recv submit | recv confirm | ack submit | ack confirm |"
+        )?;
+        writeln!(
+            f,
+            "|------------:|------------:|----------:|---------:|----------:|------------:\
+             |------------:|-------------:|-----------:|------------:|"
+        )?;
+
+        for round in self.rounds.iter() {
+            writeln!(
+                f,
+                "| {} | {} | {} | {:.1} | {:.1} | {:.1} | {} | {} | {} | {} |",
+                round.params.max_msg_num.to_usize(),
+                round.params.max_tx_size.to_usize(),
+                round.transfers,
+                round.send_duration.as_secs_f64(),
+                round.relay_duration.as_secs_f64(),
+                round.throughput(),
+                format_mean(&round.recv_submitted),
+                format_mean(&round.recv_confirmed),
+                format_mean(&round.ack_submitted),
+                format_mean(&round.ack_confirmed),
+            )?;
+        }
+
+        Ok(())
+    }
+}
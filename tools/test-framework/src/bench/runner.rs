@@ -0,0 +1,213 @@
+/*!
+   Running the rounds of a relaying benchmark.
+*/
+
+use core::time::Duration;
+use eyre::eyre;
+use ibc_relayer::chain::handle::{ChainHandle, CountingAndCachingChainHandle};
+use std::fs;
+use std::time::Instant;
+use tracing::info;
+
+use super::config::{BatchParams, BenchmarkConfig};
+use super::latency::LatencySnapshot;
+use super::load::send_load;
+use super::report::{BenchmarkReport, RoundResult};
+use crate::bootstrap::binary::chain::{add_keys_to_chain_handle, new_registry};
+use crate::chain::tagged::TaggedChainDriverExt;
+use crate::error::{handle_generic_error, Error};
+use crate::ibc::denom::derive_ibc_denom;
+use crate::relayer::driver::RelayerDriver;
+use crate::types::binary::chains::{ConnectedChains, DropChainHandle};
+use crate::types::binary::channel::ConnectedChannel;
+use crate::types::single::node::{FullNode, TaggedFullNodeExt};
+use crate::types::wallet::{TaggedTestWalletsExt, TaggedWallet};
+use crate::util::retry::assert_eventually_succeed;
+
+const BALANCE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/**
+   Run one round of the benchmark for each of the configured
+   [`BatchParams`], sending transfers from chain A to chain B over
+   the given channel.
+
+   For each round, the relayer is restarted with fresh chain handles
+   using the round's `max_msg_num` and `max_tx_size`, as the batching
+   parameters are read by the chain runtimes when they are spawned.
+   The supervisor of `relayer` itself is not used, and should not be
+   running during the benchmark.
+
+   The report is also written to
+   [`report_path`](BenchmarkConfig::report_path) if it is set.
+*/
+pub fn run_benchmark<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relayer: &RelayerDriver,
+    chains: &ConnectedChains<ChainA, ChainB>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    config: &BenchmarkConfig,
+) -> Result<BenchmarkReport, Error> {
+    let mut report = BenchmarkReport::new(config.profile, config.msgs_per_tx);
+
+    for params in config.batch_params.iter() {
+        info!("starting benchmark round with {}", params);
+
+        let (round_relayer, _drop_handles) = relayer_with_batch_params(
+            relayer,
+            &[chains.node_a.value(), chains.node_b.value()],
+            params,
+        )?;
+
+        let result =
+            round_relayer.with_supervisor(|| run_round(chains, channel, config, params))?;
+
+        info!(
+            "finished benchmark round with {}: {} transfers relayed in {:.1}s",
+            params,
+            result.transfers,
+            result.relay_duration.as_secs_f64()
+        );
+
+        report.rounds.push(result);
+    }
+
+    if let Some(report_path) = &config.report_path {
+        fs::write(report_path, report.to_string())?;
+
+        info!("written benchmark report to {}", report_path.display());
+    }
+
+    Ok(report)
+}
+
+/**
+   Create a [`RelayerDriver`] with a new registry, whose chain handles
+   are spawned with the given batching parameters.
+
+   The returned chain handles are stopped when dropped.
+*/
+pub fn relayer_with_batch_params(
+    relayer: &RelayerDriver,
+    nodes: &[&FullNode],
+    params: &BatchParams,
+) -> Result<
+    (
+        RelayerDriver,
+        Vec<DropChainHandle<CountingAndCachingChainHandle>>,
+    ),
+    Error,
+> {
+    let mut config = relayer.config.clone();
+
+    for chain_config in config.chains.iter_mut() {
+        chain_config.max_msg_num = params.max_msg_num;
+        chain_config.max_tx_size = params.max_tx_size;
+    }
+
+    let registry = new_registry(config.clone());
+
+    let mut drop_handles = Vec::new();
+
+    for node in nodes {
+        let handle = registry
+            .get_or_spawn(&node.chain_driver.chain_id)
+            .map_err(handle_generic_error)?;
+
+        add_keys_to_chain_handle(&handle, &node.wallets)?;
+
+        drop_handles.push(DropChainHandle(handle));
+    }
+
+    let round_relayer = RelayerDriver {
+        config_path: relayer.config_path.clone(),
+        config,
+        registry,
+        hang_on_fail: relayer.hang_on_fail,
+    };
+
+    Ok((round_relayer, drop_handles))
+}
+
+fn run_round<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ConnectedChains<ChainA, ChainB>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    config: &BenchmarkConfig,
+    params: &BatchParams,
+) -> Result<RoundResult, Error> {
+    let chain_driver_a = chains.node_a.chain_driver();
+    let chain_driver_b = chains.node_b.chain_driver();
+
+    let wallets_a = chains.node_a.wallets();
+    let wallets_b = chains.node_b.wallets();
+
+    let sender = wallets_a.user1();
+    let recipient = wallets_b.user1();
+
+    let denom_a = chains.node_a.denom();
+
+    let denom_b = derive_ibc_denom(
+        &channel.port_b.as_ref(),
+        &channel.channel_id_b.as_ref(),
+        &denom_a,
+    )?;
+
+    let balance_b = chain_driver_b.query_balance(&recipient.address(), &denom_b.as_ref())?;
+
+    let latencies_before = LatencySnapshot::gather();
+
+    let start = Instant::now();
+
+    let transfers = send_load(
+        &chain_driver_a,
+        channel,
+        &sender,
+        &recipient.address(),
+        &denom_a,
+        config.amount,
+        config.msgs_per_tx,
+        &config.profile,
+    )?;
+
+    let send_duration = start.elapsed();
+
+    let target_balance = balance_b + transfers * config.amount;
+
+    let attempts = (config.relay_timeout.as_millis() / BALANCE_POLL_INTERVAL.as_millis())
+        .min(u16::MAX as u128) as u16;
+
+    assert_eventually_succeed(
+        &format!("all {} transfers to be relayed", transfers),
+        attempts,
+        BALANCE_POLL_INTERVAL,
+        || {
+            let balance = chain_driver_b.query_balance(&recipient.address(), &denom_b.as_ref())?;
+
+            if balance >= target_balance {
+                Ok(())
+            } else {
+                Err(Error::generic(eyre!(
+                    "{} out of {} transfers relayed",
+                    balance.saturating_sub(balance_b) / config.amount.max(1),
+                    transfers
+                )))
+            }
+        },
+    )?;
+
+    let relay_duration = start.elapsed();
+
+    let latencies = LatencySnapshot::gather().since(&latencies_before);
+
+    let chain_id_a = chains.node_a.chain_id();
+    let chain_id_b = chains.node_b.chain_id();
+
+    Ok(RoundResult {
+        params: *params,
+        transfers,
+        send_duration,
+        relay_duration,
+        recv_submitted: latencies.submitted(chain_id_b.value()),
+        recv_confirmed: latencies.confirmed(chain_id_b.value()),
+        ack_submitted: latencies.submitted(chain_id_a.value()),
+        ack_confirmed: latencies.confirmed(chain_id_a.value()),
+    })
+}
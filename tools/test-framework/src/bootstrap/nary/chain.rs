@@ -57,6 +57,37 @@ pub fn boostrap_chains_with_any_nodes(
     full_nodes: Vec<FullNode>,
     config_modifier: impl FnOnce(&mut Config),
 ) -> Result<(RelayerDriver, DynamicConnectedChains<impl ChainHandle>), Error> {
+    let (relayer, chain_handles) =
+        bootstrap_relayer_with_nodes(test_config, &full_nodes, config_modifier)?;
+
+    let mut foreign_clients: Vec<Vec<ForeignClient<_, _>>> = Vec::new();
+
+    for handle_a in chain_handles.iter() {
+        let mut foreign_clients_b = Vec::new();
+
+        for handle_b in chain_handles.iter() {
+            let foreign_client = bootstrap_foreign_client(handle_a, handle_b, Default::default())?;
+
+            foreign_clients_b.push(foreign_client);
+        }
+
+        foreign_clients.push(foreign_clients_b);
+    }
+
+    let connected_chains = DynamicConnectedChains::new(chain_handles, full_nodes, foreign_clients);
+
+    Ok((relayer, connected_chains))
+}
+
+/**
+   Write the relayer configuration for the given full nodes, and spawn
+   a chain handle for each of them, without creating any foreign client.
+*/
+pub fn bootstrap_relayer_with_nodes(
+    test_config: &TestConfig,
+    full_nodes: &[FullNode],
+    config_modifier: impl FnOnce(&mut Config),
+) -> Result<(RelayerDriver, Vec<impl ChainHandle>), Error> {
     let mut config = Config::default();
 
     for node in full_nodes.iter() {
@@ -78,20 +109,6 @@ pub fn boostrap_chains_with_any_nodes(
         chain_handles.push(handle);
     }
 
-    let mut foreign_clients: Vec<Vec<ForeignClient<_, _>>> = Vec::new();
-
-    for handle_a in chain_handles.iter() {
-        let mut foreign_clients_b = Vec::new();
-
-        for handle_b in chain_handles.iter() {
-            let foreign_client = bootstrap_foreign_client(handle_a, handle_b, Default::default())?;
-
-            foreign_clients_b.push(foreign_client);
-        }
-
-        foreign_clients.push(foreign_clients_b);
-    }
-
     let relayer = RelayerDriver {
         config_path,
         config,
@@ -99,9 +116,7 @@ pub fn boostrap_chains_with_any_nodes(
         hang_on_fail: test_config.hang_on_fail,
     };
 
-    let connected_chains = DynamicConnectedChains::new(chain_handles, full_nodes, foreign_clients);
-
-    Ok((relayer, connected_chains))
+    Ok((relayer, chain_handles))
 }
 
 fn spawn_chain_handle<Handle: ChainHandle>(
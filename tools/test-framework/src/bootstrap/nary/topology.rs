@@ -0,0 +1,121 @@
+/*!
+   Functions for bootstrapping N-ary chains that are connected according
+   to a [`Topology`].
+*/
+
+use core::time::Duration;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::config::Config;
+use tracing::info;
+
+use crate::bootstrap::binary::chain::{bootstrap_foreign_client_pair, BootstrapClientOptions};
+use crate::bootstrap::binary::channel::{
+    bootstrap_channel_with_connection, BootstrapChannelOptions,
+};
+use crate::bootstrap::binary::connection::{bootstrap_connection, BootstrapConnectionOptions};
+use crate::bootstrap::nary::chain::bootstrap_relayer_with_nodes;
+use crate::error::Error;
+use crate::relayer::driver::RelayerDriver;
+use crate::types::config::TestConfig;
+use crate::types::nary::topology::{ConnectedEdge, ConnectedTopology, Edge, Topology};
+use crate::types::single::node::FullNode;
+use crate::types::tagged::*;
+
+/**
+   Bootstrap a fixed number of chains specified by `SIZE`, and connect
+   them according to the given [`Topology`].
+
+   Only the chains that share an edge get foreign clients for each other,
+   together with one connection and the channels specified on the edge.
+*/
+pub fn bootstrap_topology_with_nodes<const SIZE: usize>(
+    test_config: &TestConfig,
+    full_nodes: [FullNode; SIZE],
+    topology: Topology,
+    connection_delay: Duration,
+    config_modifier: impl FnOnce(&mut Config),
+) -> Result<(RelayerDriver, ConnectedTopology<impl ChainHandle, SIZE>), Error> {
+    topology.validate(SIZE)?;
+
+    let (relayer, chain_handles) =
+        bootstrap_relayer_with_nodes(test_config, &full_nodes, config_modifier)?;
+
+    let edges = topology
+        .edges()
+        .iter()
+        .map(|edge| {
+            bootstrap_edge(
+                &chain_handles,
+                edge,
+                connection_delay,
+                test_config.bootstrap_with_random_ids,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let connected_topology =
+        ConnectedTopology::new(topology, chain_handles, full_nodes.into(), edges)?;
+
+    Ok((relayer, connected_topology))
+}
+
+/**
+   Bootstrap the foreign clients, the connection and the channels
+   of a single [`Edge`] between two of the given chains.
+*/
+pub fn bootstrap_edge<Handle: ChainHandle>(
+    chain_handles: &[Handle],
+    edge: &Edge,
+    connection_delay: Duration,
+    bootstrap_with_random_ids: bool,
+) -> Result<ConnectedEdge<Handle>, Error> {
+    let chain_a = &chain_handles[edge.chain_a];
+    let chain_b = &chain_handles[edge.chain_b];
+
+    let client_options =
+        BootstrapClientOptions::default().bootstrap_with_random_ids(bootstrap_with_random_ids);
+
+    let foreign_clients = bootstrap_foreign_client_pair(chain_a, chain_b, client_options)?;
+
+    let connection_options = BootstrapConnectionOptions::default()
+        .connection_delay(connection_delay)
+        .bootstrap_with_random_ids(bootstrap_with_random_ids);
+
+    let connection = bootstrap_connection(&foreign_clients, connection_options)?;
+
+    let mut channels = Vec::new();
+
+    for spec in edge.channels.iter() {
+        let channel_options = BootstrapChannelOptions::default()
+            .order(spec.order)
+            .bootstrap_with_random_ids(bootstrap_with_random_ids);
+
+        let channel = bootstrap_channel_with_connection(
+            chain_a,
+            chain_b,
+            connection.clone(),
+            &DualTagged::new(&spec.port_a),
+            &DualTagged::new(&spec.port_b),
+            channel_options,
+        )?;
+
+        channels.push(channel);
+    }
+
+    info!(
+        "bootstrapped edge {}-{} between chains {} and {} with {} channel(s)",
+        edge.chain_a,
+        edge.chain_b,
+        chain_a.id(),
+        chain_b.id(),
+        channels.len(),
+    );
+
+    Ok(ConnectedEdge {
+        chain_a: edge.chain_a,
+        chain_b: edge.chain_b,
+        foreign_clients,
+        connection,
+        channels,
+    })
+}
@@ -0,0 +1,173 @@
+/*!
+   Constructs for running test cases with more than two chains that are
+   connected according to a declarative [`Topology`], with the clients,
+   connections and channels of each edge fully bootstrapped.
+*/
+
+use ibc_relayer::chain::handle::ChainHandle;
+use tracing::info;
+
+use crate::bootstrap::nary::topology::bootstrap_topology_with_nodes;
+use crate::error::Error;
+use crate::framework::base::{HasOverrides, TestConfigOverride};
+use crate::framework::binary::chain::RelayerConfigOverride;
+use crate::framework::binary::connection::ConnectionDelayOverride;
+use crate::framework::binary::node::{NodeConfigOverride, NodeGenesisOverride};
+use crate::framework::nary::node::{run_nary_node_test, NaryNodeTest};
+use crate::framework::supervisor::{RunWithSupervisor, SupervisorOverride};
+use crate::relayer::driver::RelayerDriver;
+use crate::types::binary::chains::DropChainHandle;
+use crate::types::config::TestConfig;
+use crate::types::env::write_env;
+use crate::types::nary::topology::{ConnectedTopology, Topology};
+use crate::types::single::node::FullNode;
+use crate::util::suspend::hang_on_error;
+
+/**
+   Runs a test case that implements [`TopologyTest`] with a `SIZE` number of
+   chains, connected according to the [`Topology`] returned by
+   [`TopologyOverride`].
+
+   Compared to [`run_nary_channel_test`](crate::framework::nary::channel::run_nary_channel_test),
+   only the chains that share an edge in the topology are connected,
+   which makes it practical to test multi-hop scenarios with many chains.
+*/
+pub fn run_topology_test<Test, Overrides, const SIZE: usize>(test: &Test) -> Result<(), Error>
+where
+    Test: TopologyTest<SIZE>,
+    Test: HasOverrides<Overrides = Overrides>,
+    Overrides: TestConfigOverride
+        + NodeConfigOverride
+        + NodeGenesisOverride
+        + RelayerConfigOverride
+        + SupervisorOverride
+        + ConnectionDelayOverride
+        + TopologyOverride<SIZE>,
+{
+    run_nary_node_test(&RunTopologyTest::new(&RunWithSupervisor::new(test)))
+}
+
+/**
+    This trait is implemented for test cases that need to have more than
+    two chains running, connected according to a [`Topology`].
+*/
+pub trait TopologyTest<const SIZE: usize> {
+    /// Test runner
+    fn run<Handle: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedTopology<Handle, SIZE>,
+    ) -> Result<(), Error>;
+}
+
+/**
+    An internal trait that can be implemented by test cases to specify
+    the [`Topology`] of the chains.
+
+    This is called by [`RunTopologyTest`] before bootstrapping the
+    chains.
+
+    Note that this trait is not automatically implemented for test cases
+    via [`TestOverrides`](crate::framework::overrides::TestOverrides).
+    So each topology test must implement this trait manually.
+
+    It is possible to implement this with an empty body, in which case
+    the chains are connected in a [`line`](Topology::line) with one
+    unordered transfer channel between each pair of neighbors.
+*/
+pub trait TopologyOverride<const SIZE: usize> {
+    fn topology(&self) -> Topology {
+        Topology::line(SIZE)
+    }
+}
+
+/**
+    A wrapper type that lifts a test case that implements [`TopologyTest`]
+    into a test case the implements [`NaryNodeTest`].
+*/
+pub struct RunTopologyTest<'a, Test, const SIZE: usize> {
+    /// Inner test
+    pub test: &'a Test,
+}
+
+impl<'a, Test, const SIZE: usize> RunTopologyTest<'a, Test, SIZE>
+where
+    Test: TopologyTest<SIZE>,
+{
+    pub fn new(test: &'a Test) -> Self {
+        Self { test }
+    }
+}
+
+impl<'a, Test, Overrides, const SIZE: usize> NaryNodeTest<SIZE> for RunTopologyTest<'a, Test, SIZE>
+where
+    Test: TopologyTest<SIZE>,
+    Test: HasOverrides<Overrides = Overrides>,
+    Overrides: RelayerConfigOverride + ConnectionDelayOverride + TopologyOverride<SIZE>,
+{
+    fn run(&self, config: &TestConfig, nodes: [FullNode; SIZE]) -> Result<(), Error> {
+        let overrides = self.test.get_overrides();
+
+        let (relayer, chains) = bootstrap_topology_with_nodes(
+            config,
+            nodes,
+            overrides.topology(),
+            overrides.connection_delay(),
+            |config| {
+                overrides.modify_relayer_config(config);
+            },
+        )?;
+
+        let env_path = config.chain_store_dir.join("nary-topology.env");
+
+        write_env(&env_path, &(&relayer, &chains))?;
+
+        info!("written topology environment to {}", env_path.display());
+
+        let _drop_handles = chains
+            .chain_handles()
+            .iter()
+            .map(|handle| DropChainHandle(handle.clone()))
+            .collect::<Vec<_>>();
+
+        self.test.run(config, relayer, chains)?;
+
+        Ok(())
+    }
+}
+
+impl<'a, Test, Overrides, const SIZE: usize> TopologyTest<SIZE> for RunWithSupervisor<'a, Test>
+where
+    Test: TopologyTest<SIZE>,
+    Test: HasOverrides<Overrides = Overrides>,
+    Overrides: SupervisorOverride,
+{
+    fn run<Handle: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedTopology<Handle, SIZE>,
+    ) -> Result<(), Error> {
+        if self.get_overrides().should_spawn_supervisor() {
+            relayer
+                .clone()
+                .with_supervisor(|| self.test.run(config, relayer, chains))
+        } else {
+            hang_on_error(config.hang_on_fail, || {
+                self.test.run(config, relayer, chains)
+            })
+        }
+    }
+}
+
+impl<'a, Test, Overrides, const SIZE: usize> HasOverrides for RunTopologyTest<'a, Test, SIZE>
+where
+    Test: HasOverrides<Overrides = Overrides>,
+{
+    type Overrides = Overrides;
+
+    fn get_overrides(&self) -> &Self::Overrides {
+        self.test.get_overrides()
+    }
+}
@@ -5,9 +5,11 @@
 use core::fmt::{self, Display};
 use eyre::Report as Error;
 use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_relayer::chain::handle::ChainHandle;
 use sha2::{Digest, Sha256};
 use subtle_encoding::hex;
 
+use crate::types::binary::channel::ConnectedChannel;
 use crate::types::id::{TaggedChannelIdRef, TaggedPortIdRef};
 use crate::types::tagged::*;
 
@@ -58,50 +60,114 @@ pub fn derive_ibc_denom<ChainA, ChainB>(
     channel_id: &TaggedChannelIdRef<ChainB, ChainA>,
     denom: &TaggedDenomRef<ChainA>,
 ) -> Result<TaggedDenom<ChainB>, Error> {
-    fn derive_denom(
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        denom: &str,
-    ) -> Result<String, Error> {
-        let transfer_path = format!("{}/{}/{}", port_id, channel_id, denom);
-        derive_denom_with_path(&transfer_path)
-    }
+    Ok(MonoTagged::new(prepend_hop(
+        port_id.value(),
+        channel_id.value(),
+        denom.value(),
+    )?))
+}
 
-    /// Derive the transferred token denomination using
-    /// <https://github.com/cosmos/ibc-go/blob/main/docs/architecture/adr-001-coin-source-tracing.md>
-    fn derive_denom_with_path(transfer_path: &str) -> Result<String, Error> {
-        let mut hasher = Sha256::new();
-        hasher.update(transfer_path.as_bytes());
+/**
+   Derives the denom on `ChainB` after a token with the given denom on
+   `ChainA` is transferred over the given channel.
+
+   Unlike [`derive_ibc_denom`], this follows the unwinding rule of ICS-20:
+   if the token was previously received by `ChainA` over the same channel,
+   the last hop is removed from its trace instead of a new hop being added.
+   This makes it possible to follow a token as it travels back towards
+   its source chain.
+*/
+pub fn derive_transferred_denom<ChainA: ChainHandle, ChainB: ChainHandle>(
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    denom: &TaggedDenomRef<ChainA>,
+) -> Result<TaggedDenom<ChainB>, Error> {
+    let denom = transfer_denom(
+        channel.port_a.value(),
+        channel.channel_id_a.value(),
+        channel.port_b.value(),
+        channel.channel_id_b.value(),
+        denom.value(),
+    )?;
+
+    Ok(MonoTagged::new(denom))
+}
 
-        let denom_bytes = hasher.finalize();
-        let denom_hex = String::from_utf8(hex::encode_upper(denom_bytes))?;
+/**
+   Untagged version of [`derive_transferred_denom`].
 
-        Ok(format!("ibc/{}", denom_hex))
+   Derives the denom that is received on the destination chain when a
+   token with the given denom is sent from `source_port`/`source_channel`
+   to `destination_port`/`destination_channel`.
+*/
+pub fn transfer_denom(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    destination_port: &PortId,
+    destination_channel: &ChannelId,
+    denom: &Denom,
+) -> Result<Denom, Error> {
+    match denom {
+        Denom::Ibc {
+            path,
+            denom: base_denom,
+            ..
+        } => {
+            let source_prefix = format!("{}/{}", source_port, source_channel);
+
+            if path == &source_prefix {
+                Ok(Denom::Base(base_denom.clone()))
+            } else if let Some(rest) = path.strip_prefix(&format!("{}/", source_prefix)) {
+                let hashed = derive_denom_with_path(&format!("{}/{}", rest, base_denom))?;
+
+                Ok(Denom::Ibc {
+                    path: rest.to_string(),
+                    denom: base_denom.clone(),
+                    hashed,
+                })
+            } else {
+                prepend_hop(destination_port, destination_channel, denom)
+            }
+        }
+        Denom::Base(_) => prepend_hop(destination_port, destination_channel, denom),
     }
+}
 
-    match denom.value() {
+fn prepend_hop(port_id: &PortId, channel_id: &ChannelId, denom: &Denom) -> Result<Denom, Error> {
+    match denom {
         Denom::Base(denom) => {
-            let hashed = derive_denom(port_id.value(), channel_id.value(), denom)?;
+            let hashed = derive_denom_with_path(&format!("{}/{}/{}", port_id, channel_id, denom))?;
 
-            Ok(MonoTagged::new(Denom::Ibc {
+            Ok(Denom::Ibc {
                 path: format!("{}/{}", port_id, channel_id),
                 denom: denom.clone(),
                 hashed,
-            }))
+            })
         }
         Denom::Ibc { path, denom, .. } => {
             let new_path = format!("{}/{}/{}", port_id, channel_id, path);
             let hashed = derive_denom_with_path(&format!("{}/{}", new_path, denom))?;
 
-            Ok(MonoTagged::new(Denom::Ibc {
+            Ok(Denom::Ibc {
                 path: new_path,
                 denom: denom.clone(),
                 hashed,
-            }))
+            })
         }
     }
 }
 
+/// Derive the transferred token denomination using
+/// <https://github.com/cosmos/ibc-go/blob/main/docs/architecture/adr-001-coin-source-tracing.md>
+fn derive_denom_with_path(transfer_path: &str) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    hasher.update(transfer_path.as_bytes());
+
+    let denom_bytes = hasher.finalize();
+    let denom_hex = String::from_utf8(hex::encode_upper(denom_bytes))?;
+
+    Ok(format!("ibc/{}", denom_hex))
+}
+
 impl Denom {
     pub fn base(denom: &str) -> Self {
         Denom::Base(denom.to_string())
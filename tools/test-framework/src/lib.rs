@@ -6,6 +6,7 @@
 
 extern crate alloc;
 
+pub mod bench;
 pub mod bootstrap;
 pub mod chain;
 pub mod error;
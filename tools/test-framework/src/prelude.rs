@@ -43,6 +43,9 @@ pub use crate::framework::nary::connection::{
     run_nary_connection_test, NaryConnectionTest, RunNaryConnectionTest,
 };
 pub use crate::framework::nary::node::{run_nary_node_test, NaryNodeTest, RunNaryNodeTest};
+pub use crate::framework::nary::topology::{
+    run_topology_test, RunTopologyTest, TopologyOverride, TopologyTest,
+};
 pub use crate::framework::overrides::TestOverrides;
 pub use crate::framework::supervisor::RunWithSupervisor;
 pub use crate::ibc::denom::Denom;
@@ -59,6 +62,7 @@ pub use crate::types::id::*;
 pub use crate::types::nary::chains::NaryConnectedChains;
 pub use crate::types::nary::channel::ConnectedChannels as NaryConnectedChannels;
 pub use crate::types::nary::connection::ConnectedConnections as NaryConnectedConnections;
+pub use crate::types::nary::topology::{ChannelSpec, ConnectedTopology, Topology};
 pub use crate::types::single::node::{FullNode, TaggedFullNodeExt};
 pub use crate::types::tagged::{DualTagged, MonoTagged};
 pub use crate::types::wallet::{
@@ -0,0 +1,663 @@
+/*!
+   Declarative descriptions of how N-ary chains are connected to each other.
+
+   Instead of connecting every chain to every other chain, as is done
+   by [`NaryConnectedChains`](crate::types::nary::chains::NaryConnectedChains),
+   a [`Topology`] describes only the edges that should exist between the
+   chains, together with the channels that should be opened on each edge.
+   This allows multi-hop scenarios such as a line of chains, or a hub chain
+   connected to a number of zones, to be set up without paying for the
+   `SIZE`x`SIZE` clients, connections and channels.
+*/
+
+use eyre::eyre;
+use ibc::core::ics04_channel::channel::Order;
+use ibc::core::ics24_host::identifier::PortId;
+use ibc_relayer::chain::handle::ChainHandle;
+use std::collections::VecDeque;
+
+use crate::error::Error;
+use crate::ibc::denom::{transfer_denom, Denom};
+use crate::types::binary::channel::ConnectedChannel;
+use crate::types::binary::connection::ConnectedConnection;
+use crate::types::binary::foreign_client::ForeignClientPair;
+use crate::types::env::{prefix_writer, EnvWriter, ExportEnv};
+use crate::types::nary::aliases::NthChainHandle;
+use crate::types::nary::chains::NthFullNode;
+use crate::types::nary::channel::NthConnectedChannel;
+use crate::types::single::node::FullNode;
+use crate::types::tagged::*;
+use crate::util::array::try_into_array;
+
+/**
+   The specification of a channel to be opened on an [`Edge`].
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelSpec {
+    /**
+       The port ID on the first chain of the edge.
+    */
+    pub port_a: PortId,
+
+    /**
+       The port ID on the second chain of the edge.
+    */
+    pub port_b: PortId,
+
+    /**
+       The ordering of the channel.
+    */
+    pub order: Order,
+}
+
+/**
+   An edge of a [`Topology`], connecting the chains at position
+   `chain_a` and `chain_b` with a single connection, on which
+   each of the specified channels is opened.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub chain_a: usize,
+    pub chain_b: usize,
+    pub channels: Vec<ChannelSpec>,
+}
+
+/**
+   A declarative description of which chains out of `size` chains are
+   connected to each other.
+
+   The common shapes can be built with [`Topology::line`],
+   [`Topology::star`] and [`Topology::ring`], and then customized with
+   [`Topology::edge`] and [`Topology::with_channels`]:
+
+   ```rust
+   # use ibc_test_framework::types::nary::topology::{ChannelSpec, Topology};
+   # use ibc::core::ics04_channel::channel::Order;
+   let topology = Topology::line(3)
+       .with_channels(vec![ChannelSpec::transfer(), ChannelSpec::transfer()])
+       .edge(1, 2, vec![ChannelSpec::transfer().order(Order::Ordered)]);
+   ```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Topology {
+    size: usize,
+    edges: Vec<Edge>,
+}
+
+/**
+   An [`Edge`] of a [`Topology`] with the foreign clients, the
+   connection and the channels fully bootstrapped.
+*/
+#[derive(Clone)]
+pub struct ConnectedEdge<Handle: ChainHandle> {
+    pub chain_a: usize,
+    pub chain_b: usize,
+    pub foreign_clients: ForeignClientPair<Handle, Handle>,
+    pub connection: ConnectedConnection<Handle, Handle>,
+    pub channels: Vec<ConnectedChannel<Handle, Handle>>,
+}
+
+/**
+   A fixed-size number of chains as specified by `SIZE`, connected
+   according to a [`Topology`].
+*/
+#[derive(Clone)]
+pub struct ConnectedTopology<Handle: ChainHandle, const SIZE: usize> {
+    topology: Topology,
+    chain_handles: [Handle; SIZE],
+    full_nodes: [FullNode; SIZE],
+    edges: Vec<ConnectedEdge<Handle>>,
+}
+
+impl ChannelSpec {
+    pub fn new(port_a: PortId, port_b: PortId, order: Order) -> Self {
+        Self {
+            port_a,
+            port_b,
+            order,
+        }
+    }
+
+    /**
+       An unordered channel between the `"transfer"` ports on both chains.
+    */
+    pub fn transfer() -> Self {
+        Self::new(PortId::transfer(), PortId::transfer(), Order::Unordered)
+    }
+
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /**
+       Switch the ports of the first and the second chain.
+    */
+    pub fn flip(self) -> Self {
+        Self {
+            port_a: self.port_b,
+            port_b: self.port_a,
+            order: self.order,
+        }
+    }
+}
+
+impl Default for ChannelSpec {
+    fn default() -> Self {
+        Self::transfer()
+    }
+}
+
+impl Topology {
+    /**
+       A topology of `size` chains without any edge.
+    */
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            edges: Vec::new(),
+        }
+    }
+
+    /**
+       Chains connected one after another, i.e. `0-1, 1-2, ..., (n-2)-(n-1)`.
+    */
+    pub fn line(size: usize) -> Self {
+        (1..size).fold(Self::new(size), |topology, i| {
+            topology.edge(i - 1, i, vec![ChannelSpec::transfer()])
+        })
+    }
+
+    /**
+       The chain at position 0 acting as a hub, connected to every other chain.
+    */
+    pub fn star(size: usize) -> Self {
+        (1..size).fold(Self::new(size), |topology, i| {
+            topology.edge(0, i, vec![ChannelSpec::transfer()])
+        })
+    }
+
+    /**
+       A [`line`](Topology::line) with the last chain connected back to the
+       first chain.
+    */
+    pub fn ring(size: usize) -> Self {
+        let topology = Self::line(size);
+
+        if size > 2 {
+            topology.edge(size - 1, 0, vec![ChannelSpec::transfer()])
+        } else {
+            topology
+        }
+    }
+
+    /**
+       Every chain connected to every other chain. Unlike the N-ary channel
+       tests, chains are not connected to themselves.
+    */
+    pub fn full(size: usize) -> Self {
+        let mut topology = Self::new(size);
+
+        for i in 0..size {
+            for j in i + 1..size {
+                topology = topology.edge(i, j, vec![ChannelSpec::transfer()]);
+            }
+        }
+
+        topology
+    }
+
+    /**
+       Add an edge between the chains at position `chain_a` and `chain_b`,
+       with the given channels opened on it.
+
+       If there is already an edge between the two chains, its channels
+       are replaced.
+    */
+    pub fn edge(mut self, chain_a: usize, chain_b: usize, channels: Vec<ChannelSpec>) -> Self {
+        match self.edge_index(chain_a, chain_b) {
+            Some((index, flipped)) => {
+                self.edges[index].channels = if flipped {
+                    channels.into_iter().map(ChannelSpec::flip).collect()
+                } else {
+                    channels
+                };
+            }
+            None => {
+                self.edges.push(Edge {
+                    chain_a,
+                    chain_b,
+                    channels,
+                });
+            }
+        }
+
+        self
+    }
+
+    /**
+       Replace the channels opened on every edge with the given channels.
+    */
+    pub fn with_channels(mut self, channels: Vec<ChannelSpec>) -> Self {
+        for edge in self.edges.iter_mut() {
+            edge.channels = channels.clone();
+        }
+
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
+    /**
+       Get the edge between the chains at position `chain_a` and `chain_b`,
+       regardless of the direction in which it was declared.
+    */
+    pub fn edge_between(&self, chain_a: usize, chain_b: usize) -> Option<&Edge> {
+        self.edge_index(chain_a, chain_b)
+            .map(|(index, _)| &self.edges[index])
+    }
+
+    /**
+       The positions of the chains that have an edge with the chain at
+       position `chain`, in the order the edges were declared.
+    */
+    pub fn neighbors(&self, chain: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter_map(|edge| {
+                if edge.chain_a == chain {
+                    Some(edge.chain_b)
+                } else if edge.chain_b == chain {
+                    Some(edge.chain_a)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /**
+       Find a path with the fewest hops from the chain at position `from`
+       to the chain at position `to`. The returned path starts with `from`
+       and ends with `to`.
+    */
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if from >= self.size || to >= self.size {
+            return None;
+        }
+
+        let mut previous: Vec<Option<usize>> = vec![None; self.size];
+        let mut visited = vec![false; self.size];
+        let mut queue = VecDeque::new();
+
+        visited[from] = true;
+        queue.push_back(from);
+
+        while let Some(chain) = queue.pop_front() {
+            if chain == to {
+                let mut path = vec![to];
+                let mut current = to;
+
+                while let Some(prev) = previous[current] {
+                    path.push(prev);
+                    current = prev;
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            for neighbor in self.neighbors(chain) {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    previous[neighbor] = Some(chain);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    /**
+       Check that the topology can be bootstrapped with `size` chains.
+
+       Every edge must connect two different chains within `size`,
+       each pair of chains can be connected by at most one edge, and
+       each edge must have at least one channel.
+    */
+    pub fn validate(&self, size: usize) -> Result<(), Error> {
+        if self.size != size {
+            return Err(Error::generic(eyre!(
+                "topology is declared for {} chains, but {} chains are bootstrapped",
+                self.size,
+                size
+            )));
+        }
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            if edge.chain_a >= size || edge.chain_b >= size {
+                return Err(Error::generic(eyre!(
+                    "edge {}-{} is beyond the number of chains {}",
+                    edge.chain_a,
+                    edge.chain_b,
+                    size
+                )));
+            }
+
+            if edge.chain_a == edge.chain_b {
+                return Err(Error::generic(eyre!(
+                    "edge {}-{} connects a chain to itself",
+                    edge.chain_a,
+                    edge.chain_b
+                )));
+            }
+
+            if edge.channels.is_empty() {
+                return Err(Error::generic(eyre!(
+                    "edge {}-{} has no channel",
+                    edge.chain_a,
+                    edge.chain_b
+                )));
+            }
+
+            let duplicate = self.edges[..i]
+                .iter()
+                .any(|other| Self::same_chains(other, edge.chain_a, edge.chain_b).is_some());
+
+            if duplicate {
+                return Err(Error::generic(eyre!(
+                    "chains {} and {} are connected by more than one edge",
+                    edge.chain_a,
+                    edge.chain_b
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn edge_index(&self, chain_a: usize, chain_b: usize) -> Option<(usize, bool)> {
+        self.edges.iter().enumerate().find_map(|(index, edge)| {
+            Self::same_chains(edge, chain_a, chain_b).map(|flipped| (index, flipped))
+        })
+    }
+
+    /**
+       Returns `Some(flipped)` if the edge connects `chain_a` and `chain_b`,
+       with `flipped` set if the edge was declared in the other direction.
+    */
+    fn same_chains(edge: &Edge, chain_a: usize, chain_b: usize) -> Option<bool> {
+        if edge.chain_a == chain_a && edge.chain_b == chain_b {
+            Some(false)
+        } else if edge.chain_a == chain_b && edge.chain_b == chain_a {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+impl<Handle: ChainHandle> ConnectedEdge<Handle> {
+    /**
+       Switch the direction of the edge, so that the original chain B
+       becomes the new chain A.
+    */
+    pub fn flip(self) -> Self {
+        Self {
+            chain_a: self.chain_b,
+            chain_b: self.chain_a,
+            foreign_clients: self.foreign_clients.flip(),
+            connection: self.connection.flip(),
+            channels: self
+                .channels
+                .into_iter()
+                .map(ConnectedChannel::flip)
+                .collect(),
+        }
+    }
+}
+
+impl<Handle: ChainHandle, const SIZE: usize> ConnectedTopology<Handle, SIZE> {
+    pub fn new(
+        topology: Topology,
+        chain_handles: Vec<Handle>,
+        full_nodes: Vec<FullNode>,
+        edges: Vec<ConnectedEdge<Handle>>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            topology,
+            chain_handles: try_into_array(chain_handles)?,
+            full_nodes: try_into_array(full_nodes)?,
+            edges,
+        })
+    }
+
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    pub fn chain_handles(&self) -> &[Handle; SIZE] {
+        &self.chain_handles
+    }
+
+    pub fn full_nodes(&self) -> &[FullNode; SIZE] {
+        &self.full_nodes
+    }
+
+    pub fn edges(&self) -> &Vec<ConnectedEdge<Handle>> {
+        &self.edges
+    }
+
+    /**
+       Get the [`ChainHandle`] at position `POS`, which must be less than `SIZE`.
+    */
+    pub fn chain_handle_at<const POS: usize>(&self) -> Result<NthChainHandle<POS, Handle>, Error> {
+        if POS >= SIZE {
+            Err(Error::generic(eyre!(
+                "cannot get chain handle beyond position {}",
+                POS
+            )))
+        } else {
+            Ok(MonoTagged::new(self.chain_handles[POS].clone()))
+        }
+    }
+
+    /**
+       Get the [`FullNode`] at position `POS`, which must be less than `SIZE`.
+    */
+    pub fn full_node_at<const POS: usize>(&self) -> Result<NthFullNode<POS, Handle>, Error> {
+        if POS >= SIZE {
+            Err(Error::generic(eyre!(
+                "cannot get full node beyond position {}",
+                POS
+            )))
+        } else {
+            Ok(MonoTagged::new(self.full_nodes[POS].clone()))
+        }
+    }
+
+    /**
+       Get the edge between the chains at position `chain_a` and `chain_b`,
+       oriented so that chain A of the returned edge is `chain_a`.
+    */
+    pub fn edge_between(
+        &self,
+        chain_a: usize,
+        chain_b: usize,
+    ) -> Result<ConnectedEdge<Handle>, Error> {
+        self.edges
+            .iter()
+            .find_map(|edge| {
+                if edge.chain_a == chain_a && edge.chain_b == chain_b {
+                    Some(edge.clone())
+                } else if edge.chain_a == chain_b && edge.chain_b == chain_a {
+                    Some(edge.clone().flip())
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| {
+                Error::generic(eyre!(
+                    "there is no edge between chains {} and {}",
+                    chain_a,
+                    chain_b
+                ))
+            })
+    }
+
+    /**
+       Get the channel at `index` among the channels opened between
+       the chains at position `chain_a` and `chain_b`.
+    */
+    pub fn channel_between(
+        &self,
+        chain_a: usize,
+        chain_b: usize,
+        index: usize,
+    ) -> Result<ConnectedChannel<Handle, Handle>, Error> {
+        let edge = self.edge_between(chain_a, chain_b)?;
+
+        edge.channels.into_iter().nth(index).ok_or_else(|| {
+            Error::generic(eyre!(
+                "there is no channel {} between chains {} and {}",
+                index,
+                chain_a,
+                chain_b
+            ))
+        })
+    }
+
+    /**
+       Tagged version of [`channel_between`](Self::channel_between), with
+       the chain positions given as const generics.
+    */
+    pub fn channel_at<const CHAIN_A: usize, const CHAIN_B: usize>(
+        &self,
+        index: usize,
+    ) -> Result<NthConnectedChannel<CHAIN_A, CHAIN_B, Handle>, Error> {
+        if CHAIN_A >= SIZE || CHAIN_B >= SIZE {
+            Err(Error::generic(eyre!(
+                "cannot get channel beyond position {}/{}",
+                CHAIN_A,
+                CHAIN_B
+            )))
+        } else {
+            let channel = self.channel_between(CHAIN_A, CHAIN_B, index)?;
+
+            Ok(channel.map_chain(MonoTagged::new, MonoTagged::new))
+        }
+    }
+
+    /**
+       Get the first channel of each hop along `path`, which must be a
+       sequence of chain positions with each consecutive pair connected
+       by an edge.
+    */
+    pub fn channels_along(
+        &self,
+        path: &[usize],
+    ) -> Result<Vec<ConnectedChannel<Handle, Handle>>, Error> {
+        path.windows(2)
+            .map(|hop| self.channel_between(hop[0], hop[1], 0))
+            .collect()
+    }
+
+    /**
+       Follow the denom trace of a token with the given denom on the first
+       chain of `path`, as it is transferred over the first channel of each hop.
+
+       Returns the denom of the token on each chain along the path, starting
+       with `denom` itself. Hops going back towards the source of the token
+       are unwound, so a token that travels back to its origin ends up
+       with its base denom.
+    */
+    pub fn denoms_along(&self, path: &[usize], denom: &Denom) -> Result<Vec<Denom>, Error> {
+        let mut denoms = vec![denom.clone()];
+
+        for channel in self.channels_along(path)? {
+            let current = denoms.last().expect("denoms is never empty");
+
+            let next = transfer_denom(
+                channel.port_a.value(),
+                channel.channel_id_a.value(),
+                channel.port_b.value(),
+                channel.channel_id_b.value(),
+                current,
+            )?;
+
+            denoms.push(next);
+        }
+
+        Ok(denoms)
+    }
+}
+
+impl<Handle: ChainHandle> ExportEnv for ConnectedEdge<Handle> {
+    fn export_env(&self, writer: &mut impl EnvWriter) {
+        writer.write_env(
+            &format!("CLIENT_ID_{}_to_{}", self.chain_b, self.chain_a),
+            &format!("{}", self.foreign_clients.client_id_a()),
+        );
+
+        writer.write_env(
+            &format!("CLIENT_ID_{}_to_{}", self.chain_a, self.chain_b),
+            &format!("{}", self.foreign_clients.client_id_b()),
+        );
+
+        writer.write_env(
+            &format!("CONNECTION_ID_{}_to_{}", self.chain_b, self.chain_a),
+            &format!("{}", self.connection.connection_id_a),
+        );
+
+        writer.write_env(
+            &format!("CONNECTION_ID_{}_to_{}", self.chain_a, self.chain_b),
+            &format!("{}", self.connection.connection_id_b),
+        );
+
+        for (k, channel) in self.channels.iter().enumerate() {
+            writer.write_env(
+                &format!("CHANNEL_ID_{}_to_{}_{}", self.chain_b, self.chain_a, k),
+                &format!("{}", channel.channel_id_a),
+            );
+
+            writer.write_env(
+                &format!("PORT_{}_to_{}_{}", self.chain_b, self.chain_a, k),
+                &format!("{}", channel.port_a),
+            );
+
+            writer.write_env(
+                &format!("CHANNEL_ID_{}_to_{}_{}", self.chain_a, self.chain_b, k),
+                &format!("{}", channel.channel_id_b),
+            );
+
+            writer.write_env(
+                &format!("PORT_{}_to_{}_{}", self.chain_a, self.chain_b, k),
+                &format!("{}", channel.port_b),
+            );
+        }
+    }
+}
+
+impl<Handle: ChainHandle, const SIZE: usize> ExportEnv for ConnectedTopology<Handle, SIZE> {
+    fn export_env(&self, writer: &mut impl EnvWriter) {
+        for (i, node) in self.full_nodes.iter().enumerate() {
+            writer.write_env(
+                &format!("CHAIN_ID_{}", i),
+                &format!("{}", node.chain_driver.chain_id),
+            );
+
+            node.export_env(&mut prefix_writer(&format!("NODE_{}", i), writer));
+        }
+
+        for edge in self.edges.iter() {
+            edge.export_env(writer);
+        }
+    }
+}
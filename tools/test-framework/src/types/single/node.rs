@@ -149,6 +149,8 @@ impl FullNode {
             gas_price: config::GasPrice::new(0.001, "stake".to_string()),
             packet_filter: Default::default(),
             address_type: Default::default(),
+            wallet_refill: None,
+            dry_run: false,
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         })